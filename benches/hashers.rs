@@ -0,0 +1,57 @@
+/*
+* @author Bodo (Hugo) Barwich
+* @version 2026-10-18
+* @package Blockchain Exercise
+* @subpackage Hasher Benchmarks
+
+* This Benchmark compares the Hash Algorithms which a Chain can compute its Block Hashes with
+*
+*---------------------------------
+* Requirements:
+* - The Rust Crate "criterion" must be installed
+*/
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+
+use blockchain_api::consensus::hasher::HASH_ALGORITHMS;
+use blockchain_api::model::blockchain::Block;
+use blockchain_api::model::transaction::Transaction;
+
+/// Build a Block with `count` `Transaction`s like a Miner hashes it.
+fn build_block(count: usize) -> Block {
+    let transactions = (0..count)
+        .map(|i| {
+            Transaction::from_data(
+                format!("sender{}", i),
+                String::from("16Jswqk47s9PUcyCc88MMVwzgvHPvtEpf"),
+                1.5,
+            )
+        })
+        .collect();
+
+    Block::build_block(2, 0, "0", Some(transactions))
+}
+
+fn bench_block_hashes(c: &mut Criterion) {
+    let mut group = c.benchmark_group("block_hash");
+
+    for count in [1, 100] {
+        let block = build_block(count);
+        let block_json = block.to_json().unwrap();
+
+        group.throughput(Throughput::Bytes(block_json.len() as u64));
+
+        for algorithm in HASH_ALGORITHMS {
+            group.bench_with_input(
+                BenchmarkId::new(algorithm.get_name(), count),
+                &block,
+                |b, block| b.iter(|| block.to_hash_with(algorithm)),
+            );
+        }
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_block_hashes);
+criterion_main!(benches);
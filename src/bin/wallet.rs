@@ -0,0 +1,385 @@
+/*
+* @author Bodo (Hugo) Barwich
+* @version 2026-10-18
+* @package Blockchain Exercise
+* @subpackage Blockchain Wallet
+
+* This Module runs the `blockchain-wallet` Command Line Client which keeps
+* the Key Pairs in an encrypted Keystore and signs `Transaction`s locally
+*
+*---------------------------------
+* Requirements:
+* - The Rust Crate "blockchain_api" must be installed
+*/
+
+use actix_web::client::Client;
+use clap::{Parser, Subcommand};
+use std::io::{self, BufRead, Error, ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use blockchain_api::model::index::AddressHistory;
+use blockchain_api::model::transaction::Transaction;
+use blockchain_api::wallet::hd::{HdWallet, DEFAULT_GAP_LIMIT};
+use blockchain_api::wallet::{Keystore, WalletError};
+use blockchain_api::ResponseData;
+
+const PASSWORD_VARIABLE: &str = "BLOCKCHAIN_WALLET_PASSWORD";
+
+//==============================================================================
+// Structure WalletCli Declaration
+
+/// Command Line of the Wallet
+#[derive(Debug, Parser)]
+#[command(
+    name = "blockchain-wallet",
+    version,
+    about = "Wallet for the Blockchain API Node"
+)]
+struct WalletCli {
+    /// Keystore File with the encrypted Key Pairs
+    #[arg(long, global = true, default_value = "wallet.json")]
+    keystore: PathBuf,
+    /// Base URL of the Blockchain Node
+    #[arg(long, global = true, default_value = "http://127.0.0.1:3100/")]
+    node: String,
+    #[command(subcommand)]
+    command: WalletCommand,
+}
+
+#[derive(Debug, Subcommand)]
+enum WalletCommand {
+    /// Generate a new Key Pair
+    New {
+        /// Name to refer to the Key Pair
+        name: String,
+    },
+    /// List the Key Pairs and their Addresses
+    List,
+    /// Show the Balance of a Key Pair or an Address
+    Balance {
+        /// Name or Address of the Key Pair
+        key: String,
+    },
+    /// Manage Mnemonic Seeds which derive any number of Key Pairs
+    Seed {
+        #[command(subcommand)]
+        command: SeedCommand,
+    },
+    /// Sign a Transaction and submit it to the Node
+    Send {
+        /// Name or Address of the sending Key Pair or `<seed>/<index>` for a derived Key Pair
+        #[arg(long)]
+        from: String,
+        /// Address of the Receiver
+        #[arg(long)]
+        to: String,
+        #[arg(long)]
+        amount: f64,
+        /// Number to tell apart identical Transactions (defaults to the current time)
+        #[arg(long)]
+        nonce: Option<u64>,
+        /// Only print the signed Transaction
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+enum SeedCommand {
+    /// Generate a new Mnemonic and store it
+    New {
+        name: String,
+        /// Number of Words of the Mnemonic (12 or 24)
+        #[arg(long, default_value_t = 24)]
+        words: usize,
+    },
+    /// Store an existing Mnemonic read from the Standard Input
+    Restore { name: String },
+    /// Show the Address derived at an Index
+    Derive {
+        name: String,
+        #[arg(long, default_value_t = 0)]
+        index: u32,
+    },
+    /// Find the used Addresses of the Seed on the Node
+    Discover {
+        name: String,
+        /// Number of consecutive unused Addresses after which the Discovery stops
+        #[arg(long, default_value_t = DEFAULT_GAP_LIMIT)]
+        gap_limit: u32,
+    },
+}
+
+//==============================================================================
+// Executing Section
+
+fn main() -> Result<(), Error> {
+    let cli = WalletCli::parse();
+
+    match cli.command {
+        WalletCommand::New { name } => {
+            let mut keystore = Keystore::load(&cli.keystore).map_err(wallet_error)?;
+            let password = read_password(true)?;
+            let entry = keystore
+                .generate_key(&name, &password)
+                .map_err(wallet_error)?;
+
+            println!("Key '{}': Address {}", entry.name, entry.address);
+
+            keystore.save(&cli.keystore).map_err(wallet_error)
+        }
+        WalletCommand::List => {
+            let keystore = Keystore::load(&cli.keystore).map_err(wallet_error)?;
+
+            for entry in keystore.keys.iter() {
+                println!("{}\t{}", entry.name, entry.address);
+            }
+
+            Ok(())
+        }
+        WalletCommand::Balance { key } => {
+            let keystore = Keystore::load(&cli.keystore).map_err(wallet_error)?;
+            let address = match keystore.get_key(&key) {
+                Some(entry) => entry.address.clone(),
+                None => key,
+            };
+            let url = format!("{}addresses/{}/transactions?limit=0", cli.node, address);
+            let history: AddressHistory =
+                actix_web::rt::System::new("blockchain-wallet").block_on(get_json(url))?;
+
+            println!(
+                "Address {}: Balance {} (received: {}; sent: {})",
+                history.address, history.balance, history.total_received, history.total_sent
+            );
+
+            Ok(())
+        }
+        WalletCommand::Seed { command } => run_seed_command(&cli.keystore, &cli.node, command),
+        WalletCommand::Send {
+            from,
+            to,
+            amount,
+            nonce,
+            dry_run,
+        } => {
+            let keystore = Keystore::load(&cli.keystore).map_err(wallet_error)?;
+            let password = read_password(false)?;
+            let signing_key = match from.split_once('/') {
+                Some((seed, index)) => {
+                    let index = index.parse::<u32>().map_err(|_| {
+                        Error::new(
+                            ErrorKind::InvalidInput,
+                            format!("Seed '{}': Index '{}' is invalid", seed, index),
+                        )
+                    })?;
+
+                    keystore
+                        .unlock_seed(seed, &password)
+                        .map_err(wallet_error)?
+                        .derive_key(index)
+                }
+                None => keystore.unlock(&from, &password).map_err(wallet_error)?,
+            };
+            let nonce = nonce.unwrap_or_else(|| {
+                SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .map(|d| d.as_millis() as u64)
+                    .unwrap_or(1)
+            });
+            let mut transaction =
+                Transaction::from_key(&signing_key.verifying_key(), to, amount, nonce);
+
+            transaction.sign(&signing_key).map_err(|e| {
+                Error::new(
+                    ErrorKind::InvalidInput,
+                    format!("Transaction: Transaction cannot be encoded! {}", e),
+                )
+            })?;
+
+            if !transaction.is_valid() {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    "Transaction: Transaction is invalid",
+                ));
+            }
+
+            if dry_run {
+                println!("{}", transaction.to_json().map_err(Error::other)?);
+
+                return Ok(());
+            }
+
+            let url = format!("{}add_transaction", cli.node);
+            let response: ResponseData = actix_web::rt::System::new("blockchain-wallet")
+                .block_on(post_json(url, transaction))?;
+
+            println!("{}", response.description);
+
+            Ok(())
+        }
+    }
+}
+
+fn run_seed_command(keystore_file: &Path, node: &str, command: SeedCommand) -> Result<(), Error> {
+    let mut keystore = Keystore::load(keystore_file).map_err(wallet_error)?;
+
+    match command {
+        SeedCommand::New { name, words } => {
+            let mnemonic = HdWallet::generate_mnemonic(words).map_err(wallet_error)?;
+            let password = read_password(true)?;
+
+            keystore
+                .add_seed(&name, &mnemonic, &password)
+                .map_err(wallet_error)?;
+            keystore.save(keystore_file).map_err(wallet_error)?;
+
+            println!(
+                "Seed '{}': Write down the Mnemonic to restore the Keys:",
+                name
+            );
+            println!("{}", mnemonic);
+
+            Ok(())
+        }
+        SeedCommand::Restore { name } => {
+            let mnemonic = prompt("Mnemonic: ")?;
+            let password = read_password(true)?;
+
+            keystore
+                .add_seed(&name, mnemonic.trim(), &password)
+                .map_err(wallet_error)?;
+            keystore.save(keystore_file).map_err(wallet_error)?;
+
+            println!("Seed '{}': Mnemonic restored", name);
+
+            Ok(())
+        }
+        SeedCommand::Derive { name, index } => {
+            let password = read_password(false)?;
+            let wallet = keystore
+                .unlock_seed(&name, &password)
+                .map_err(wallet_error)?;
+
+            println!("{}/{}\t{}", name, index, wallet.derive_address(index));
+
+            Ok(())
+        }
+        SeedCommand::Discover { name, gap_limit } => {
+            let password = read_password(false)?;
+            let wallet = keystore
+                .unlock_seed(&name, &password)
+                .map_err(wallet_error)?;
+            let mut system = actix_web::rt::System::new("blockchain-wallet");
+            let addresses = wallet.discover(gap_limit, |address| {
+                let url = format!("{}addresses/{}/transactions?limit=0", node, address);
+                let history: AddressHistory = system.block_on(get_json(url))?;
+
+                Ok::<bool, Error>(history.total_count > 0)
+            })?;
+
+            for a in addresses.iter() {
+                println!(
+                    "{}/{}\t{}\t{}",
+                    name,
+                    a.index,
+                    a.address,
+                    if a.used { "used" } else { "unused" }
+                );
+            }
+
+            Ok(())
+        }
+    }
+}
+
+//==============================================================================
+// Auxiliary Functions
+
+/// Read the Keystore Password from the Environment or from the Standard Input
+fn read_password(confirm: bool) -> Result<String, Error> {
+    if let Ok(password) = std::env::var(PASSWORD_VARIABLE) {
+        return Ok(password);
+    }
+
+    let password = prompt("Keystore Password: ")?;
+
+    if confirm && prompt("Repeat Password: ")? != password {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            "Keystore: Passwords do not match",
+        ));
+    }
+
+    if password.is_empty() {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            "Keystore: Password must not be empty",
+        ));
+    }
+
+    Ok(password)
+}
+
+fn prompt(text: &str) -> Result<String, Error> {
+    let mut line = String::new();
+
+    eprint!("{}", text);
+    io::stderr().flush()?;
+    io::stdin().lock().read_line(&mut line)?;
+
+    Ok(line.trim_end_matches(['\r', '\n']).to_owned())
+}
+
+async fn get_json<T: serde::de::DeserializeOwned>(url: String) -> Result<T, Error> {
+    let client = Client::builder().timeout(Duration::from_secs(30)).finish();
+    let mut response =
+        client.get(url.as_str()).send().await.map_err(|e| {
+            Error::other(format!("Node '{}': Request failed! Message: {:?}", url, e))
+        })?;
+
+    if !response.status().is_success() {
+        return Err(Error::other(format!(
+            "Node '{}': Request failed with Status {}",
+            url,
+            response.status()
+        )));
+    }
+
+    response
+        .json::<T>()
+        .await
+        .map_err(|e| Error::other(format!("Node '{}': Response is invalid: {:?}", url, e)))
+}
+
+async fn post_json<T: serde::de::DeserializeOwned>(
+    url: String,
+    transaction: Transaction,
+) -> Result<T, Error> {
+    let client = Client::builder().timeout(Duration::from_secs(30)).finish();
+    let mut response = client
+        .post(url.as_str())
+        .send_json(&transaction)
+        .await
+        .map_err(|e| Error::other(format!("Node '{}': Request failed! Message: {:?}", url, e)))?;
+
+    if !response.status().is_success() {
+        let body = response.body().await.unwrap_or_default();
+
+        return Err(Error::other(format!(
+            "Node '{}': Transaction was rejected with Status {}: {}",
+            url,
+            response.status(),
+            String::from_utf8_lossy(&body)
+        )));
+    }
+
+    response
+        .json::<T>()
+        .await
+        .map_err(|e| Error::other(format!("Node '{}': Response is invalid: {:?}", url, e)))
+}
+
+fn wallet_error(e: WalletError) -> Error {
+    Error::other(e.report)
+}
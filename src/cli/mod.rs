@@ -0,0 +1,578 @@
+/*
+* @author Bodo (Hugo) Barwich
+* @version 2026-10-18
+* @package Blockchain Exercise
+* @subpackage Blockchain Command Line
+
+* This Module defines the Subcommands of the `actix-blockchain` Binary.
+* All Subcommands except `serve` work directly on the Data Directory
+* without launching the Node.
+*
+*---------------------------------
+* Requirements:
+* - The Rust Crate "clap" must be installed
+*/
+
+use actix_web::web;
+use clap::{Parser, Subcommand};
+use std::fs;
+use std::io::{Error, ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::Duration;
+
+use crate::config::{AppConfig, ConfigArgs, ConfigError, LayeredConfig};
+use crate::model::blockchain::{Block, Blockchain};
+use crate::model::transaction::{MutexTransactionList, Transaction};
+use crate::model::wire;
+use crate::storage::ChainStore;
+
+//==============================================================================
+// Structure Cli Declaration
+
+/// Command Line of the Blockchain Node
+#[derive(Debug, Parser)]
+#[command(name = "actix-blockchain", version, about = "Blockchain API Node")]
+pub struct Cli {
+    #[command(flatten)]
+    pub config: ConfigArgs,
+    /// Refuse to run if the Configuration has any Problems
+    #[arg(long, global = true)]
+    pub strict: bool,
+    /// Report the Problems of the Configuration and exit
+    #[arg(long)]
+    pub check_config: bool,
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+/// Subcommands of the Blockchain Node. Without a Subcommand the Node is served.
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Launch the HTTP Server
+    Serve,
+    /// Create the Data Directory and mine the Genesis Block
+    Init,
+    /// Write the stored Chain as JSON to a File or to the Standard Output
+    Export {
+        /// File to write the Chain to
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+        /// Write the compact binary Format instead of JSON
+        #[arg(long)]
+        binary: bool,
+    },
+    /// Read a Chain as JSON or in the binary Format from a File and store it
+    Import {
+        /// File to read the Chain from
+        input: PathBuf,
+        /// Replace the stored Chain even if it is longer
+        #[arg(long)]
+        force: bool,
+    },
+    /// Validate the stored Chain
+    Verify,
+    /// Mine Blocks and store them
+    Mine {
+        /// Number of Blocks to mine
+        #[arg(long, default_value_t = 1)]
+        blocks: u64,
+    },
+    /// Show stored Data
+    Inspect {
+        #[command(subcommand)]
+        target: InspectTarget,
+    },
+}
+
+/// Data which can be inspected
+#[derive(Debug, Subcommand)]
+pub enum InspectTarget {
+    /// Show the Block with the given Index
+    Block { index: u64 },
+}
+
+//==============================================================================
+// Command Line Execution
+
+/// Run the Subcommand given on the Command Line
+pub fn run(cli: Cli) -> Result<(), Error> {
+    let layered = AppConfig::load(&cli.config);
+
+    check_config(&layered, cli.strict, cli.check_config)?;
+
+    if cli.check_config {
+        return Ok(());
+    }
+
+    let config = &layered.config;
+
+    match cli.command.unwrap_or(Command::Serve) {
+        Command::Serve => {
+            actix_web::rt::System::new("blockchain-api").block_on(crate::serve(layered))
+        }
+        Command::Init => init_chain(config),
+        Command::Export { output, binary } => export_chain(config, output, binary),
+        Command::Import { input, force } => import_chain(config, &input, force),
+        Command::Verify => verify_chain(config),
+        Command::Mine { blocks } => mine_chain(config, blocks),
+        Command::Inspect {
+            target: InspectTarget::Block { index },
+        } => inspect_block(config, index),
+    }
+}
+
+/// Report the Problems of the Configuration.
+///
+/// # Returns:
+/// - an Error if the Configuration is invalid and it must not be used
+///
+fn check_config(layered: &LayeredConfig, strict: bool, check_only: bool) -> Result<(), Error> {
+    if check_only {
+        println!("{}", layered.describe());
+    }
+
+    match layered.validate() {
+        Ok(()) => {
+            if check_only {
+                println!("Config: Configuration is valid");
+            }
+
+            Ok(())
+        }
+        Err(errors) => {
+            for e in errors.iter() {
+                eprintln!("Config: {}", e);
+            }
+
+            if check_only || strict {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    format!(
+                        "Config: Configuration is invalid ({} Problems)",
+                        errors.len()
+                    ),
+                ));
+            }
+
+            eprintln!("Config: Running anyway. Use '--strict' to refuse invalid Configurations");
+
+            Ok(())
+        }
+    }
+}
+
+//==============================================================================
+// Subcommands
+
+pub fn init_chain(config: &AppConfig) -> Result<(), Error> {
+    let store = open_store(config)?;
+
+    if store.load()?.is_some() {
+        return Err(Error::new(
+            ErrorKind::AlreadyExists,
+            format!(
+                "Blockchain: Chain is already stored in {:?}",
+                store.get_chain_file()
+            ),
+        ));
+    }
+
+    let mut blockchain = Blockchain::with_ledger_mode(config.ledger_mode);
+
+    blockchain.difficulty = config.difficulty;
+    blockchain.target_bits = config.get_target_bits();
+    blockchain.mining_reward = config.mining_reward;
+    blockchain.consensus = config.consensus;
+    blockchain.hash_algorithm = config.hash_algorithm;
+    blockchain.authority = config.get_authority_params();
+
+    mine_blocks(&mut blockchain, config, &store, 1)?;
+
+    println!(
+        "Blockchain: Data Directory {:?} initialised",
+        store.get_directory()
+    );
+
+    Ok(())
+}
+
+pub fn export_chain(
+    config: &AppConfig,
+    output: Option<PathBuf>,
+    binary: bool,
+) -> Result<(), Error> {
+    let blockchain = load_chain(&open_store(config)?)?;
+    let chain_data = if binary {
+        wire::encode(&blockchain.chain).map_err(|e| Error::other(e.report))?
+    } else {
+        serde_json::to_vec_pretty(&blockchain.chain).map_err(Error::other)?
+    };
+
+    match output {
+        Some(file) => {
+            fs::write(&file, chain_data)?;
+
+            eprintln!(
+                "Blockchain: {} Blocks exported to {:?}",
+                blockchain.chain.len(),
+                file
+            );
+        }
+        None if binary => std::io::stdout().write_all(&chain_data)?,
+        None => println!("{}", String::from_utf8_lossy(&chain_data)),
+    }
+
+    Ok(())
+}
+
+pub fn import_chain(config: &AppConfig, input: &Path, force: bool) -> Result<(), Error> {
+    let store = open_store(config)?;
+    let chain_data = fs::read(input)?;
+    // Binary Exports are recognised by their Header
+    let chain: Vec<Block> = if wire::is_wire_data(&chain_data) {
+        wire::decode(&chain_data).map_err(|e| {
+            Error::new(
+                ErrorKind::InvalidData,
+                format!(
+                    "Chain File {:?}: parse file failed with Error: '{}'",
+                    input, e.report
+                ),
+            )
+        })?
+    } else {
+        serde_json::from_slice(&chain_data).map_err(|e| {
+            Error::new(
+                ErrorKind::InvalidData,
+                format!(
+                    "Chain File {:?}: parse file failed with Error: '{:?}'",
+                    input, e
+                ),
+            )
+        })?
+    };
+    if chain.is_empty() {
+        return Err(Error::new(
+            ErrorKind::InvalidData,
+            format!("Chain File {:?}: Chain has no Blocks", input),
+        ));
+    }
+
+    let stored = store.load()?;
+    let (difficulty, target_bits, ledger_mode, mining_reward, consensus, hash_algorithm, authority) =
+        match stored.as_ref() {
+            Some(b) => (
+                b.difficulty,
+                b.target_bits,
+                b.ledger_mode,
+                b.mining_reward,
+                b.consensus,
+                b.hash_algorithm,
+                b.authority.clone(),
+            ),
+            None => (
+                config.difficulty,
+                config.get_target_bits(),
+                config.ledger_mode,
+                config.mining_reward,
+                config.consensus,
+                config.hash_algorithm,
+                config.get_authority_params(),
+            ),
+        };
+    let mut blockchain = Blockchain::with_ledger_mode(ledger_mode);
+
+    blockchain.difficulty = difficulty;
+    blockchain.target_bits = target_bits;
+    blockchain.mining_reward = mining_reward;
+    blockchain.consensus = consensus;
+    blockchain.hash_algorithm = hash_algorithm;
+    blockchain.authority = authority;
+
+    if let Some(b) = blockchain
+        .find_invalid_block(&chain)
+        .or_else(|| find_invalid_transaction(&chain))
+    {
+        return Err(Error::new(
+            ErrorKind::InvalidData,
+            format!("Chain File {:?}: Block {} is invalid", input, b.index),
+        ));
+    }
+
+    match stored {
+        Some(mut b) if !force => {
+            if b.replace_chain(chain).is_none() {
+                println!("Blockchain: Chain was kept because it is the longest valid Chain");
+
+                return Ok(());
+            }
+
+            blockchain = b;
+        }
+        _ => {
+            blockchain.chain = chain;
+            blockchain
+                .rebuild_index()
+                .map_err(|e| Error::new(ErrorKind::InvalidData, e.report))?;
+        }
+    }
+
+    store.save(&blockchain)?;
+
+    println!(
+        "Blockchain: {} Blocks imported from {:?}",
+        blockchain.chain.len(),
+        input
+    );
+
+    Ok(())
+}
+
+pub fn verify_chain(config: &AppConfig) -> Result<(), Error> {
+    let store = open_store(config)?;
+    let blockchain = load_chain(&store)?;
+
+    match blockchain
+        .find_invalid_block(&blockchain.chain)
+        .or_else(|| find_invalid_transaction(&blockchain.chain))
+    {
+        Some(b) => Err(Error::new(
+            ErrorKind::InvalidData,
+            format!(
+                "Blockchain: Block {} in {:?} is invalid",
+                b.index,
+                store.get_chain_file()
+            ),
+        )),
+        None => {
+            println!(
+                "Blockchain: {} Blocks verified (Target: {:08x}; Chainwork: {:x})",
+                blockchain.chain.len(),
+                blockchain.get_target_bits(),
+                blockchain.get_chainwork()
+            );
+
+            Ok(())
+        }
+    }
+}
+
+pub fn mine_chain(config: &AppConfig, blocks: u64) -> Result<(), Error> {
+    let store = open_store(config)?;
+    // The stored Chain keeps its Difficulty
+    let mut blockchain = load_chain(&store)?;
+
+    mine_blocks(&mut blockchain, config, &store, blocks)
+}
+
+pub fn inspect_block(config: &AppConfig, index: u64) -> Result<(), Error> {
+    let blockchain = load_chain(&open_store(config)?)?;
+
+    match blockchain.get_block(index) {
+        Some(b) => {
+            println!("{}", serde_json::to_string_pretty(b).map_err(Error::other)?);
+
+            Ok(())
+        }
+        None => Err(Error::new(
+            ErrorKind::NotFound,
+            format!("Block {}: Block does not exist", index),
+        )),
+    }
+}
+
+//==============================================================================
+// Auxiliary Functions
+
+fn open_store(config: &AppConfig) -> Result<ChainStore, Error> {
+    ChainStore::from_directory(&config.data_directory).ok_or_else(|| {
+        Error::new(
+            ErrorKind::InvalidInput,
+            "Config: No Data Directory configured. Use '--data-dir'",
+        )
+    })
+}
+
+fn load_chain(store: &ChainStore) -> Result<Blockchain, Error> {
+    store.load()?.ok_or_else(|| {
+        Error::new(
+            ErrorKind::NotFound,
+            format!(
+                "Blockchain: No Chain stored in {:?}. Use 'init' first",
+                store.get_directory()
+            ),
+        )
+    })
+}
+
+/// Find the first Block with a `Transaction` which is not valid on its own
+fn find_invalid_transaction(chain: &[Block]) -> Option<&Block> {
+    chain
+        .iter()
+        .find(|b| !b.transactions.iter().all(|t| t.is_valid_in_chain()))
+}
+
+/// Mine Blocks and store the Chain after each Block.
+///
+/// Like the Node the Mining Reward for the configured Reward Address is granted with
+/// the next Block. It is stored with the waiting `Transaction`s until the next Block
+/// is mined.
+fn mine_blocks(
+    blockchain: &mut Blockchain,
+    config: &AppConfig,
+    store: &ChainStore,
+    blocks: u64,
+) -> Result<(), Error> {
+    if config.reward_address.is_empty() {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            format!("Config: {}", ConfigError::MissingRewardAddress),
+        ));
+    }
+
+    let pending: Vec<Transaction> = store
+        .load_pending()?
+        .into_iter()
+        .filter(|t| {
+            t.to_hash()
+                .is_ok_and(|txid| blockchain.get_transaction(&txid).is_none())
+        })
+        .collect();
+    let transactions = web::Data::new(MutexTransactionList::from_vec(pending));
+
+    blockchain.set_validator_key(config.load_validator_key()?);
+    blockchain.set_reward_address(&config.reward_address);
+
+    for _ in 0..blocks {
+        // Proof of Authority and Proof of Stake Validators wait for their Turn
+        let proof = loop {
+            match blockchain.seal_block(&transactions) {
+                Ok(p) => break p,
+                Err(e) if e.status == "waiting" => thread::sleep(Duration::from_secs(1)),
+                Err(e) => return Err(Error::other(e.report)),
+            }
+        };
+
+        store.save(blockchain)?;
+
+        for reward in blockchain.build_rewards(&config.reward_address, blockchain.mining_reward) {
+            transactions
+                .add_transaction(reward)
+                .map_err(|e| Error::other(format!("Mining: Reward Grant failed! {:?}", e)))?;
+        }
+
+        let pending = transactions.into_vec();
+
+        store.save_pending(&pending)?;
+        transactions.restore(pending);
+
+        println!(
+            "Block (Index: {}; Proof: {}): New Block mined",
+            blockchain.get_last_block_index(),
+            proof
+        );
+    }
+
+    Ok(())
+}
+
+//==============================================================================
+// Unit Tests
+
+#[test]
+fn cli_subcommands_parse() {
+    let cli = Cli::parse_from([
+        "actix-blockchain",
+        "mine",
+        "--blocks",
+        "3",
+        "--data-dir",
+        "data",
+    ]);
+
+    assert!(matches!(cli.command, Some(Command::Mine { blocks: 3 })));
+    assert_eq!(cli.config.data_directory.as_deref(), Some("data"));
+
+    let cli = Cli::parse_from(["actix-blockchain", "inspect", "block", "2"]);
+
+    assert!(matches!(
+        cli.command,
+        Some(Command::Inspect {
+            target: InspectTarget::Block { index: 2 }
+        })
+    ));
+
+    let cli = Cli::parse_from(["actix-blockchain", "--port", "3200"]);
+
+    assert!(cli.command.is_none());
+    assert_eq!(cli.config.port, Some(3200));
+
+    // Invalid numeric Flags are refused by the Parser
+    assert!(Cli::try_parse_from(["actix-blockchain", "--port", "99999"]).is_err());
+    assert!(Cli::try_parse_from(["actix-blockchain", "--difficulty", "many"]).is_err());
+}
+
+#[test]
+fn cli_offline_chain() {
+    let directory = std::env::temp_dir().join(format!("blockchain-cli-{}", std::process::id()));
+    let import_directory = directory.join("import");
+    let export_file = directory.join("export.json");
+    let binary_file = directory.join("export.bin");
+    let mut config = AppConfig::new();
+
+    config.difficulty = 1;
+    config.reward_address = String::from("16Jswqk47s9PUcyCc88MMVwzgvHPvtEpf");
+    config.data_directory = directory.join("data").display().to_string();
+
+    init_chain(&config).unwrap();
+    assert_eq!(
+        init_chain(&config).unwrap_err().kind(),
+        ErrorKind::AlreadyExists
+    );
+
+    mine_chain(&config, 2).unwrap();
+    verify_chain(&config).unwrap();
+    export_chain(&config, Some(export_file.clone()), false).unwrap();
+    export_chain(&config, Some(binary_file.clone()), true).unwrap();
+
+    let mut import_config = config.clone();
+
+    import_config.data_directory = import_directory.display().to_string();
+
+    import_chain(&import_config, &export_file, false).unwrap();
+    verify_chain(&import_config).unwrap();
+
+    // The binary Export holds the same Chain
+    import_chain(&import_config, &binary_file, true).unwrap();
+    assert!(fs::metadata(&binary_file).unwrap().len() < fs::metadata(&export_file).unwrap().len());
+
+    inspect_block(&import_config, 3).unwrap();
+    assert_eq!(
+        inspect_block(&import_config, 4).unwrap_err().kind(),
+        ErrorKind::NotFound
+    );
+
+    // Chains without any Block are refused
+    let empty_file = directory.join("empty.json");
+
+    fs::write(&empty_file, "[]").unwrap();
+    assert_eq!(
+        import_chain(&import_config, &empty_file, true)
+            .unwrap_err()
+            .kind(),
+        ErrorKind::InvalidData
+    );
+
+    let imported = ChainStore::new(&import_directory).load().unwrap().unwrap();
+    let pending = ChainStore::new(Path::new(&config.data_directory))
+        .load_pending()
+        .unwrap();
+
+    fs::remove_dir_all(&directory).unwrap();
+
+    // The Reward for the last Block waits for the next Block like on the Node
+    assert_eq!(imported.chain.len(), 3);
+    assert_eq!(imported.get_balance(&config.reward_address), 20f64);
+    assert_eq!(pending.len(), 1);
+    assert_eq!(pending[0].nonce, 4);
+}
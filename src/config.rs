@@ -5,35 +5,249 @@
 * @subpackage Configuration Loader
 
 * This Module defines functions to load the application configuration
+* from the layers: built-in defaults, configuration file, environment and command line
 *
 *---------------------------------
 * Requirements:
+* - The Rust Crate "serde_yaml" must be installed
+* - The Rust Crate "toml" must be installed
+* - The Rust Crate "clap" must be installed
 */
 
 extern crate serde;
 extern crate serde_yaml;
 
+use clap::Args;
+use ed25519_dalek::SigningKey;
 use serde_derive::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
 use std::ffi::OsStr;
-//use std::fmt;
+use std::fmt;
 use std::fs;
 use std::io::{Error, ErrorKind};
 use std::path::{Path, PathBuf};
 
-const CONFIG_FILE: &'static str = ".env";
+use crate::consensus::seal;
+use crate::consensus::{pow, AuthorityParams, ConsensusMode, HashAlgorithm};
+use crate::model::address::Address;
+use crate::model::blockchain::LedgerMode;
+use crate::wallet::{Keystore, WalletError};
+
+const CONFIG_FILE: &str = ".env";
+const ENV_PREFIX: &str = "BLOCKCHAIN_";
+/// `BLOCKCHAIN_*` Environment Variables which are not Configuration Fields
+const ENV_SETTINGS: &[&str] = &["BLOCKCHAIN_CONFIG", VALIDATOR_PASSWORD_VARIABLE];
+/// Environment Variable with the Password of the Validator Key in the Keystore
+const VALIDATOR_PASSWORD_VARIABLE: &str = "BLOCKCHAIN_VALIDATOR_PASSWORD";
+
+/// Kinds of values of the Configuration Fields
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum FieldKind {
+    Text,
+    Integer,
+    Decimal,
+}
+
+/// All Configuration Fields with their kind of value
+const CONFIG_FIELDS: &[(&str, FieldKind)] = &[
+    ("component", FieldKind::Text),
+    ("project", FieldKind::Text),
+    ("web_root", FieldKind::Text),
+    ("main_directory", FieldKind::Text),
+    ("config_file", FieldKind::Text),
+    ("host", FieldKind::Text),
+    ("port", FieldKind::Integer),
+    ("miner_count", FieldKind::Integer),
+    ("difficulty", FieldKind::Integer),
+    ("target_bits", FieldKind::Text),
+    ("mining_reward", FieldKind::Decimal),
+    ("reward_address", FieldKind::Text),
+    ("data_directory", FieldKind::Text),
+    ("ledger_mode", FieldKind::Text),
+    ("consensus", FieldKind::Text),
+    ("hash_algorithm", FieldKind::Text),
+    ("validators", FieldKind::Text),
+    ("validator_key", FieldKind::Text),
+    ("validator_keystore", FieldKind::Text),
+    ("block_interval", FieldKind::Integer),
+    ("admin_token", FieldKind::Text),
+    ("peers", FieldKind::Text),
+    ("sync_interval", FieldKind::Integer),
+];
 
 //==============================================================================
 // Structure AppConfig Declaration
 
 /// Structure for the Application Configuration
 #[derive(Debug, Serialize, Deserialize)]
+#[serde(default)]
 pub struct AppConfig {
     pub component: String,
     pub project: String,
     pub web_root: String,
     pub main_directory: String,
     pub config_file: String,
+    pub host: String,
+    pub port: u16,
     pub miner_count: u16,
+    pub difficulty: usize,
+    /// Hex encoded compact Proof of Work Target which overrides the `difficulty`
+    pub target_bits: String,
+    pub mining_reward: f64,
+    pub reward_address: String,
+    pub data_directory: String,
+    /// Ledger Model of newly created Chains
+    pub ledger_mode: LedgerMode,
+    /// Consensus Engine of newly created Chains
+    pub consensus: ConsensusMode,
+    /// Hash Algorithm of newly created Chains
+    pub hash_algorithm: HashAlgorithm,
+    /// Comma separated hex encoded Public Keys of the initial Proof of Authority or Proof of Stake
+    /// Validators
+    pub validators: String,
+    /// Name or Address of the Key in the `validator_keystore` which this Node seals Blocks
+    /// with as Validator
+    pub validator_key: String,
+    /// Keystore File of the Wallet with the encrypted Validator Key
+    pub validator_keystore: String,
+    /// Length of a Proof of Authority Slot in seconds
+    pub block_interval: u32,
+    /// Bearer Token for the administrative Endpoints. Without a Token they only answer
+    /// Clients on the same Host.
+    pub admin_token: String,
+    /// Comma separated Base URLs of the Nodes whose longer valid Chains are adopted
+    pub peers: String,
+    /// Seconds between the Polls of the Peer Nodes
+    pub sync_interval: u32,
+}
+
+/// Layer a Configuration value was taken from
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConfigSource {
+    Default,
+    File(PathBuf),
+    Environment(String),
+    CommandLine(String),
+}
+
+/// Structure for the effective Configuration with the Source of each Field
+#[derive(Debug, Clone)]
+pub struct LayeredConfig {
+    pub config: AppConfig,
+    pub sources: BTreeMap<String, ConfigSource>,
+    /// Problems found while reading the Layers
+    pub errors: Vec<ConfigError>,
+}
+
+/// Problems found in the Configuration
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConfigError {
+    /// The Configuration File could not be read or parsed
+    UnreadableFile { file: PathBuf, message: String },
+    /// A Layer sets a Field which does not exist
+    UnknownField { source: ConfigSource, field: String },
+    /// A Value does not fit the Field it is given for
+    InvalidValue {
+        source: ConfigSource,
+        field: String,
+        value: String,
+    },
+    /// At least one Mining Worker is required
+    ZeroMiners,
+    /// The Server cannot listen on Port 0
+    ZeroPort,
+    /// The Difficulty must be between 1 and 64 leading zeros
+    InvalidDifficulty(usize),
+    /// The Mining Reward must be a finite Amount which is not negative
+    InvalidMiningReward(f64),
+    /// The Web Root must start and end with `/`
+    InvalidWebRoot(String),
+    /// The Target Bits must be a hex encoded positive compact Target
+    InvalidTargetBits(String),
+    /// The Mining Rewards need an Address to be sent to
+    MissingRewardAddress,
+    /// The Mining Rewards must be sent to a valid Address
+    InvalidRewardAddress { address: String, message: String },
+    /// The Blockchain cannot be stored in the Data Directory
+    UnwritableDataDirectory { directory: String, message: String },
+    /// A Validator Key is not a hex encoded Ed25519 Key
+    InvalidValidatorKey { field: String, key: String },
+    /// The Keystore does not contain the Validator Key
+    MissingValidatorKey { keystore: String, key: String },
+    /// Proof of Authority and Proof of Stake Chains need at least 1 Validator
+    NoValidators,
+    /// Proof of Authority Slots must last at least 1 second
+    ZeroBlockInterval,
+    /// Stakes are only tracked in Account Mode
+    StakingNeedsAccountMode,
+}
+
+/// Command Line Flags which override the Configuration
+#[derive(Debug, Clone, Default, Args)]
+pub struct ConfigArgs {
+    /// Configuration File in YAML or TOML format
+    #[arg(long, global = true, value_name = "FILE")]
+    pub config: Option<PathBuf>,
+    /// Name of this Node
+    #[arg(long, global = true)]
+    pub component: Option<String>,
+    /// Path under which the API is served
+    #[arg(long, global = true)]
+    pub web_root: Option<String>,
+    /// Host Address to bind to
+    #[arg(long, global = true)]
+    pub host: Option<String>,
+    /// Port to listen on
+    #[arg(long, global = true)]
+    pub port: Option<u16>,
+    /// Number of Mining Worker Threads
+    #[arg(long, global = true)]
+    pub miner_count: Option<u16>,
+    /// Number of leading zeros of a valid Block Hash
+    #[arg(long, global = true)]
+    pub difficulty: Option<usize>,
+    /// Compact Target like "1f00ffff" which a Block Hash must not exceed
+    #[arg(long, global = true, value_name = "BITS")]
+    pub target_bits: Option<String>,
+    /// Amount granted for each mined Block
+    #[arg(long, global = true)]
+    pub mining_reward: Option<f64>,
+    /// Address which receives the Mining Rewards
+    #[arg(long, global = true)]
+    pub reward_address: Option<String>,
+    /// Directory to store the Blockchain in
+    #[arg(long = "data-dir", global = true, value_name = "DIRECTORY")]
+    pub data_directory: Option<String>,
+    /// Ledger Model of a new Chain ("account" or "utxo")
+    #[arg(long, global = true)]
+    pub ledger_mode: Option<String>,
+    /// Consensus Engine of a new Chain ("proof_of_work", "instant", "proof_of_authority" or
+    /// "proof_of_stake")
+    #[arg(long, global = true)]
+    pub consensus: Option<String>,
+    /// Block Hash Algorithm of a new Chain ("sha256", "double_sha256", "sha3_256", "blake3"
+    /// or "argon2id")
+    #[arg(long, global = true)]
+    pub hash_algorithm: Option<String>,
+    /// Comma separated Public Keys of the initial Validators of a new Chain
+    #[arg(long, global = true)]
+    pub validators: Option<String>,
+    /// Name or Address of the Keystore Key to seal Proof of Authority Blocks with
+    #[arg(long, global = true, value_name = "KEY")]
+    pub validator_key: Option<String>,
+    /// Keystore File with the encrypted Validator Key
+    #[arg(long, global = true, value_name = "FILE")]
+    pub validator_keystore: Option<String>,
+    /// Length of a Proof of Authority Slot in seconds
+    #[arg(long, global = true)]
+    pub block_interval: Option<u32>,
+    /// Comma separated Base URLs of the Nodes to adopt longer valid Chains from
+    #[arg(long, global = true, value_name = "URLS")]
+    pub peers: Option<String>,
+    /// Seconds between the Polls of the Peer Nodes
+    #[arg(long, global = true)]
+    pub sync_interval: Option<u32>,
 }
 
 //==============================================================================
@@ -61,7 +275,24 @@ impl AppConfig {
             web_root: String::from("/"),
             main_directory: String::new(),
             config_file: String::new(),
+            host: String::from("127.0.0.1"),
+            port: 3100,
             miner_count: 2,
+            difficulty: 4,
+            target_bits: String::new(),
+            mining_reward: 10.0,
+            reward_address: String::new(),
+            data_directory: String::new(),
+            ledger_mode: LedgerMode::Account,
+            consensus: ConsensusMode::ProofOfWork,
+            hash_algorithm: HashAlgorithm::Sha256,
+            validators: String::new(),
+            validator_key: String::new(),
+            validator_keystore: String::from("wallet.json"),
+            block_interval: 5,
+            admin_token: String::new(),
+            peers: String::new(),
+            sync_interval: 30,
         }
     }
 
@@ -72,10 +303,27 @@ project: 'Actix Blockchain'
 web_root: '/'
 main_directory: ''
 config_file: ''
+host: '127.0.0.1'
+port: 3100
 miner_count: 2
+difficulty: 4
+target_bits: ''
+mining_reward: 10.0
+reward_address: ''
+data_directory: ''
+ledger_mode: 'account'
+consensus: 'proof_of_work'
+hash_algorithm: 'sha256'
+validators: ''
+validator_key: ''
+validator_keystore: 'wallet.json'
+block_interval: 5
+admin_token: ''
+peers: ''
+sync_interval: 30
 ";
         // Deserialize it back to a Rust type.
-        let config: AppConfig = match serde_yaml::from_str(&config_yaml) {
+        let config: AppConfig = match serde_yaml::from_str(config_yaml) {
             Ok(cfg) => cfg,
             Err(e) => {
                 eprintln!("Default Config could not be parsed: {:?}", e);
@@ -89,7 +337,7 @@ miner_count: 2
     pub fn from_file() -> AppConfig {
         let mut config: Option<AppConfig> = None;
 
-        match try_find_file(Path::new(&*CONFIG_FILE)) {
+        match try_find_file(Path::new(CONFIG_FILE)) {
             Ok(file) => {
                 config = match try_config_from_path(&file) {
                     Ok(cfg) => Some(cfg),
@@ -112,9 +360,534 @@ miner_count: 2
             config = Some(AppConfig::from_yaml());
         }
 
-        match config {
-            Some(cfg) => cfg,
-            None => AppConfig::new(),
+        config.unwrap_or_default()
+    }
+
+    /// Load the effective Configuration from all Layers.
+    ///
+    /// The Layers are applied in this order, each overriding the previous ones:
+    /// - built-in defaults
+    /// - the Configuration File given by `--config` or `BLOCKCHAIN_CONFIG`
+    ///   (otherwise a `.env` file in the working directory or one of its parents)
+    /// - `BLOCKCHAIN_*` Environment Variables (e.g. `BLOCKCHAIN_PORT`)
+    /// - Command Line Flags (e.g. `--port`)
+    pub fn load(args: &ConfigArgs) -> LayeredConfig {
+        Self::load_layers(args, std::env::vars())
+    }
+
+    /// Load the effective Configuration with the given Environment Variables.
+    pub fn load_layers(
+        args: &ConfigArgs,
+        environment: impl IntoIterator<Item = (String, String)>,
+    ) -> LayeredConfig {
+        let environment: BTreeMap<String, String> = environment.into_iter().collect();
+        let mut values = match serde_json::to_value(AppConfig::new()) {
+            Ok(Value::Object(m)) => m,
+            _ => Map::new(),
+        };
+        let mut sources: BTreeMap<String, ConfigSource> = CONFIG_FIELDS
+            .iter()
+            .map(|(name, _)| (name.to_string(), ConfigSource::Default))
+            .collect();
+        let mut errors = Vec::<ConfigError>::new();
+
+        //------------------------
+        // Configuration File
+
+        let config_file = match args.config.as_ref() {
+            Some(f) => Some(f.clone()),
+            None => match environment.get("BLOCKCHAIN_CONFIG") {
+                Some(f) => Some(PathBuf::from(f)),
+                None => try_find_file(Path::new(CONFIG_FILE)).ok(),
+            },
+        };
+
+        if let Some(file) = config_file {
+            match try_values_from_path(&file) {
+                Ok(file_values) => {
+                    for (name, value) in file_values {
+                        let source = ConfigSource::File(file.clone());
+
+                        if field_kind(&name).is_none() {
+                            errors.push(ConfigError::UnknownField {
+                                source,
+                                field: name,
+                            });
+                        } else if is_value_valid(&name, &value) {
+                            sources.insert(name.clone(), source);
+                            values.insert(name, value);
+                        } else {
+                            errors.push(ConfigError::InvalidValue {
+                                source,
+                                field: name,
+                                value: value.to_string(),
+                            });
+                        }
+                    }
+
+                    values.insert(
+                        String::from("config_file"),
+                        Value::String(file.display().to_string()),
+                    );
+                    sources.insert(String::from("config_file"), ConfigSource::File(file));
+                }
+                Err(e) => errors.push(ConfigError::UnreadableFile {
+                    file,
+                    message: e.to_string(),
+                }),
+            }
+        }
+
+        //------------------------
+        // Environment Variables
+
+        // Legacy Variables are overridden by the `BLOCKCHAIN_*` Variables
+        for (var, name) in [("COMPONENT", "component"), ("PORT", "port")] {
+            if let Some(value) = environment.get(var) {
+                apply_text_value(
+                    &mut values,
+                    &mut sources,
+                    &mut errors,
+                    name,
+                    value,
+                    ConfigSource::Environment(var.to_owned()),
+                );
+            }
+        }
+
+        for var in environment.keys() {
+            if let Some(name) = var.strip_prefix(ENV_PREFIX) {
+                let name = name.to_lowercase();
+
+                if field_kind(&name).is_none() && !ENV_SETTINGS.contains(&var.as_str()) {
+                    errors.push(ConfigError::UnknownField {
+                        source: ConfigSource::Environment(var.clone()),
+                        field: name,
+                    });
+                }
+            }
+        }
+
+        for (name, _) in CONFIG_FIELDS.iter() {
+            let var = format!("{}{}", ENV_PREFIX, name.to_uppercase());
+
+            if let Some(value) = environment.get(&var) {
+                apply_text_value(
+                    &mut values,
+                    &mut sources,
+                    &mut errors,
+                    name,
+                    value,
+                    ConfigSource::Environment(var.clone()),
+                );
+            }
+        }
+
+        //------------------------
+        // Command Line Flags
+
+        let flags = [
+            ("component", &args.component),
+            ("web_root", &args.web_root),
+            ("host", &args.host),
+            ("target_bits", &args.target_bits),
+            ("reward_address", &args.reward_address),
+            ("data_directory", &args.data_directory),
+            ("ledger_mode", &args.ledger_mode),
+            ("consensus", &args.consensus),
+            ("hash_algorithm", &args.hash_algorithm),
+            ("validators", &args.validators),
+            ("validator_key", &args.validator_key),
+            ("validator_keystore", &args.validator_keystore),
+            ("peers", &args.peers),
+        ];
+
+        for (name, flag) in flags {
+            if let Some(value) = flag {
+                apply_text_value(
+                    &mut values,
+                    &mut sources,
+                    &mut errors,
+                    name,
+                    value,
+                    ConfigSource::CommandLine(flag_name(name)),
+                );
+            }
+        }
+
+        // Numeric Flags are parsed by the Command Line Parser already
+        let numeric_flags = [
+            ("port", args.port.map(Value::from)),
+            ("miner_count", args.miner_count.map(Value::from)),
+            ("difficulty", args.difficulty.map(Value::from)),
+            ("mining_reward", args.mining_reward.map(Value::from)),
+            ("block_interval", args.block_interval.map(Value::from)),
+            ("sync_interval", args.sync_interval.map(Value::from)),
+        ];
+
+        for (name, flag) in numeric_flags {
+            if let Some(value) = flag {
+                let source = ConfigSource::CommandLine(flag_name(name));
+
+                if is_value_valid(name, &value) {
+                    values.insert(name.to_owned(), value);
+                    sources.insert(name.to_owned(), source);
+                } else {
+                    errors.push(ConfigError::InvalidValue {
+                        source,
+                        field: name.to_owned(),
+                        value: value.to_string(),
+                    });
+                }
+            }
+        }
+
+        let config = match serde_json::from_value::<AppConfig>(Value::Object(values)) {
+            Ok(cfg) => cfg,
+            Err(e) => {
+                // Each Value was checked on its own so this is not expected
+                eprintln!("Config: Configuration is invalid: {:?}", e);
+                eprintln!("Falling back to default configuration ...");
+
+                sources
+                    .values_mut()
+                    .for_each(|source| *source = ConfigSource::Default);
+
+                AppConfig::new()
+            }
+        };
+
+        LayeredConfig {
+            config,
+            sources,
+            errors,
+        }
+    }
+
+    /// Check the Configuration for Settings the Node cannot run with.
+    ///
+    /// Nothing is written to the File System. The Data Directory only needs to be creatable.
+    pub fn validate(&self) -> Result<(), Vec<ConfigError>> {
+        let mut errors = Vec::<ConfigError>::new();
+
+        if self.miner_count == 0 {
+            errors.push(ConfigError::ZeroMiners);
+        }
+
+        if self.port == 0 {
+            errors.push(ConfigError::ZeroPort);
+        }
+
+        if self.difficulty == 0 || self.difficulty > 64 {
+            errors.push(ConfigError::InvalidDifficulty(self.difficulty));
+        }
+
+        if !self.mining_reward.is_finite() || self.mining_reward < 0f64 {
+            errors.push(ConfigError::InvalidMiningReward(self.mining_reward));
+        }
+
+        if !self.web_root.starts_with('/') || !self.web_root.ends_with('/') {
+            errors.push(ConfigError::InvalidWebRoot(self.web_root.clone()));
+        }
+
+        if self.reward_address.is_empty() {
+            errors.push(ConfigError::MissingRewardAddress);
+        } else if let Err(e) = Address::parse(&self.reward_address) {
+            errors.push(ConfigError::InvalidRewardAddress {
+                address: self.reward_address.clone(),
+                message: e.to_string(),
+            });
+        }
+
+        for validator in self.get_authority_params().validators {
+            if seal::decode_public_key(&validator).is_none() {
+                errors.push(ConfigError::InvalidValidatorKey {
+                    field: String::from("validators"),
+                    key: validator,
+                });
+            }
+        }
+
+        if !self.target_bits.trim().is_empty()
+            && self
+                .get_target_bits()
+                .and_then(pow::bits_to_target)
+                .is_none()
+        {
+            errors.push(ConfigError::InvalidTargetBits(self.target_bits.clone()));
+        }
+
+        if !self.validator_key.is_empty() {
+            let keystore = Keystore::load(Path::new(&self.validator_keystore));
+
+            if !matches!(keystore, Ok(k) if k.get_key(&self.validator_key).is_some()) {
+                errors.push(ConfigError::MissingValidatorKey {
+                    keystore: self.validator_keystore.clone(),
+                    key: self.validator_key.clone(),
+                });
+            }
+        }
+
+        if self.consensus == ConsensusMode::ProofOfAuthority
+            || self.consensus == ConsensusMode::ProofOfStake
+        {
+            // Proof of Stake Chains start with the Validators before anything is staked
+            if self.get_authority_params().is_empty() {
+                errors.push(ConfigError::NoValidators);
+            }
+
+            if self.block_interval == 0 {
+                errors.push(ConfigError::ZeroBlockInterval);
+            }
+        }
+
+        if self.consensus == ConsensusMode::ProofOfStake && self.ledger_mode != LedgerMode::Account
+        {
+            errors.push(ConfigError::StakingNeedsAccountMode);
+        }
+
+        if !self.data_directory.is_empty() {
+            if let Err(e) = check_directory(Path::new(&self.data_directory)) {
+                errors.push(ConfigError::UnwritableDataDirectory {
+                    directory: self.data_directory.clone(),
+                    message: e.to_string(),
+                });
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /*----------------------------------------------------------------------------
+     * Consultation Methods
+     */
+
+    /// Address the HTTP Server binds to
+    pub fn get_bind_address(&self) -> String {
+        format!("{}:{}", self.host, self.port)
+    }
+
+    /// Compact Proof of Work Target or `None` to derive it from the `difficulty`
+    pub fn get_target_bits(&self) -> Option<u32> {
+        let target_bits = self.target_bits.trim();
+
+        if target_bits.is_empty() {
+            return None;
+        }
+
+        u32::from_str_radix(target_bits.trim_start_matches("0x"), 16).ok()
+    }
+
+    /// Proof of Authority Parameters of newly created Chains
+    pub fn get_authority_params(&self) -> AuthorityParams {
+        AuthorityParams {
+            validators: self
+                .validators
+                .split(',')
+                .map(|v| v.trim().to_owned())
+                .filter(|v| !v.is_empty())
+                .collect(),
+            block_interval: self.block_interval,
+        }
+    }
+
+    /// Base URLs of the Peer Nodes
+    pub fn get_peers(&self) -> Vec<String> {
+        self.peers
+            .split(',')
+            .map(|p| p.trim().to_owned())
+            .filter(|p| !p.is_empty())
+            .collect()
+    }
+
+    /// Decrypt the Key which this Node seals Proof of Authority Blocks with.
+    ///
+    /// # Returns:
+    /// - `None` if no Validator Key is configured
+    /// - an Error if the Keystore cannot be read or the Password does not match
+    ///
+    pub fn unlock_validator_key(&self, password: &str) -> Result<Option<SigningKey>, WalletError> {
+        if self.validator_key.is_empty() {
+            return Ok(None);
+        }
+
+        Keystore::load(Path::new(&self.validator_keystore))?
+            .unlock(&self.validator_key, password)
+            .map(Some)
+    }
+
+    /// Decrypt the Validator Key with the Password from the Environment like the Wallet.
+    pub fn load_validator_key(&self) -> Result<Option<SigningKey>, Error> {
+        if self.validator_key.is_empty() {
+            return Ok(None);
+        }
+
+        let password = std::env::var(VALIDATOR_PASSWORD_VARIABLE).map_err(|_| {
+            Error::new(
+                ErrorKind::InvalidInput,
+                format!(
+                    "Config: The Password of the Validator Key is required in '{}'",
+                    VALIDATOR_PASSWORD_VARIABLE
+                ),
+            )
+        })?;
+
+        self.unlock_validator_key(&password).map_err(|e| {
+            Error::new(
+                ErrorKind::InvalidInput,
+                format!("Config: Validator Key could not be unlocked! {}", e.report),
+            )
+        })
+    }
+}
+
+impl LayeredConfig {
+    /*----------------------------------------------------------------------------
+     * Consultation Methods
+     */
+
+    /// Check the Configuration together with the Problems found while loading it
+    pub fn validate(&self) -> Result<(), Vec<ConfigError>> {
+        let mut errors = self.errors.clone();
+
+        if let Err(e) = self.config.validate() {
+            errors.extend(e);
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Describe the effective Configuration with the Source of each Field
+    pub fn describe(&self) -> String {
+        let values = match serde_json::to_value(&self.config) {
+            Ok(Value::Object(m)) => m,
+            _ => Map::new(),
+        };
+        let mut description = String::from("Effective Configuration:\n");
+
+        for (name, _) in CONFIG_FIELDS.iter() {
+            let value = match values.get(*name).cloned() {
+                // The Admin Token must not be printed
+                Some(Value::String(k)) if *name == "admin_token" && !k.is_empty() => {
+                    Value::String(String::from("***"))
+                }
+                Some(v) => v,
+                None => Value::Null,
+            };
+            let source = self
+                .sources
+                .get(*name)
+                .cloned()
+                .unwrap_or(ConfigSource::Default);
+
+            description.push_str(&format!("  {} = {} ({})\n", name, value, source));
+        }
+
+        description
+    }
+}
+
+impl fmt::Display for ConfigSource {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ConfigSource::Default => write!(f, "default"),
+            ConfigSource::File(p) => write!(f, "file {}", p.display()),
+            ConfigSource::Environment(v) => write!(f, "env {}", v),
+            ConfigSource::CommandLine(a) => write!(f, "flag {}", a),
+        }
+    }
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ConfigError::UnreadableFile { file, message } => write!(
+                f,
+                "Config File {}: File could not be read: {}",
+                file.display(),
+                message
+            ),
+            ConfigError::UnknownField { source, field } => {
+                write!(f, "Field '{}' ({}): Field does not exist", field, source)
+            }
+            ConfigError::InvalidValue {
+                source,
+                field,
+                value,
+            } => write!(
+                f,
+                "Field '{}' ({}): Value '{}' is invalid for this Field",
+                field, source, value
+            ),
+            ConfigError::ZeroMiners => write!(
+                f,
+                "Field 'miner_count': At least 1 Mining Worker is required"
+            ),
+            ConfigError::ZeroPort => write!(f, "Field 'port': Port 0 cannot be listened on"),
+            ConfigError::InvalidDifficulty(difficulty) => write!(
+                f,
+                "Field 'difficulty': Value '{}' must be between 1 and 64",
+                difficulty
+            ),
+            ConfigError::InvalidMiningReward(reward) => write!(
+                f,
+                "Field 'mining_reward': Value '{}' must be a finite Amount of at least 0",
+                reward
+            ),
+            ConfigError::InvalidWebRoot(web_root) => write!(
+                f,
+                "Field 'web_root': Value '{}' must start and end with '/'",
+                web_root
+            ),
+            ConfigError::InvalidTargetBits(target_bits) => write!(
+                f,
+                "Field 'target_bits': Value '{}' is no valid compact Target",
+                target_bits
+            ),
+            ConfigError::MissingRewardAddress => write!(
+                f,
+                "Field 'reward_address': An Address for the Mining Rewards is required"
+            ),
+            ConfigError::InvalidRewardAddress { address, message } => write!(
+                f,
+                "Field 'reward_address': Value '{}' is invalid: {}",
+                address, message
+            ),
+            ConfigError::UnwritableDataDirectory { directory, message } => write!(
+                f,
+                "Field 'data_directory': Directory '{}' is not writable: {}",
+                directory, message
+            ),
+            ConfigError::InvalidValidatorKey { field, key } => write!(
+                f,
+                "Field '{}': Key '{}' is not a hex encoded Ed25519 Key",
+                field, key
+            ),
+            ConfigError::MissingValidatorKey { keystore, key } => write!(
+                f,
+                "Field 'validator_key': Keystore '{}' has no Key '{}'",
+                keystore, key
+            ),
+            ConfigError::NoValidators => write!(
+                f,
+                "Field 'validators': Proof of Authority and Proof of Stake need at least 1 Validator"
+            ),
+            ConfigError::ZeroBlockInterval => write!(
+                f,
+                "Field 'block_interval': Slots must last at least 1 second"
+            ),
+            ConfigError::StakingNeedsAccountMode => write!(
+                f,
+                "Field 'consensus': Proof of Stake needs the Ledger Mode 'account'"
+            ),
         }
     }
 }
@@ -131,7 +904,24 @@ impl Clone for AppConfig {
             web_root: self.web_root.clone(),
             main_directory: self.main_directory.clone(),
             config_file: self.config_file.clone(),
+            host: self.host.clone(),
+            port: self.port,
             miner_count: self.miner_count,
+            difficulty: self.difficulty,
+            target_bits: self.target_bits.clone(),
+            mining_reward: self.mining_reward,
+            reward_address: self.reward_address.clone(),
+            data_directory: self.data_directory.clone(),
+            ledger_mode: self.ledger_mode,
+            consensus: self.consensus,
+            hash_algorithm: self.hash_algorithm,
+            validators: self.validators.clone(),
+            validator_key: self.validator_key.clone(),
+            validator_keystore: self.validator_keystore.clone(),
+            block_interval: self.block_interval,
+            admin_token: self.admin_token.clone(),
+            peers: self.peers.clone(),
+            sync_interval: self.sync_interval,
         }
     }
 }
@@ -197,17 +987,138 @@ fn try_config_from_path(file: &Path) -> Result<AppConfig, Error> {
         )
     })?;
     let config: AppConfig = serde_yaml::from_str(&config_yaml).map_err(|e| {
+        Error::other(format!(
+            "Config File {:?}: parse file failed with Error: '{:?}'",
+            file.file_name(),
+            e
+        ))
+    })?;
+
+    Ok(config)
+}
+
+/// Read the Fields of a YAML or TOML Configuration File
+fn try_values_from_path(file: &Path) -> Result<Map<String, Value>, Error> {
+    let config_text = fs::read_to_string(file).map_err(|e| {
         Error::new(
-            ErrorKind::Other,
+            ErrorKind::NotFound,
             format!(
-                "Config File {:?}: parse file failed with Error: '{:?}'",
-                file.file_name(),
-                e
+                "Config File {:?}: read file failed with Error: '{:?}'",
+                file, e
             ),
         )
     })?;
+    let is_toml = file.extension() == Some(OsStr::new("toml"));
+    let values: Result<Value, String> = if is_toml {
+        toml::from_str(&config_text).map_err(|e| e.to_string())
+    } else {
+        serde_yaml::from_str(&config_text).map_err(|e| e.to_string())
+    };
 
-    Ok(config)
+    match values {
+        Ok(Value::Object(m)) => Ok(m),
+        // An empty File does not override any Field
+        Ok(Value::Null) => Ok(Map::new()),
+        Ok(v) => Err(Error::other(format!(
+            "Config File {:?}: Mapping of Fields expected but found: '{}'",
+            file.file_name(),
+            v
+        ))),
+        Err(e) => Err(Error::other(format!(
+            "Config File {:?}: parse file failed with Error: '{}'",
+            file.file_name(),
+            e
+        ))),
+    }
+}
+
+fn field_kind(name: &str) -> Option<FieldKind> {
+    CONFIG_FIELDS
+        .iter()
+        .find(|(field, _)| *field == name)
+        .map(|(_, kind)| *kind)
+}
+
+/// Check whether a Value fits the type and range of the Field
+fn is_value_valid(name: &str, value: &Value) -> bool {
+    let mut values = match serde_json::to_value(AppConfig::new()) {
+        Ok(Value::Object(m)) => m,
+        _ => return false,
+    };
+
+    values.insert(name.to_owned(), value.clone());
+
+    serde_json::from_value::<AppConfig>(Value::Object(values)).is_ok()
+}
+
+/// Command Line Flag of a Configuration Field
+fn flag_name(name: &str) -> String {
+    match name {
+        "data_directory" => String::from("--data-dir"),
+        _ => format!("--{}", name.replace('_', "-")),
+    }
+}
+
+/// Convert a textual Value from the Environment or the Command Line to the kind
+/// of the Field. Invalid Values are reported and ignored.
+fn apply_text_value(
+    values: &mut Map<String, Value>,
+    sources: &mut BTreeMap<String, ConfigSource>,
+    errors: &mut Vec<ConfigError>,
+    name: &str,
+    text: &str,
+    source: ConfigSource,
+) {
+    let value = match field_kind(name) {
+        Some(FieldKind::Text) => Some(Value::String(text.to_owned())),
+        Some(FieldKind::Integer) => text.trim().parse::<u64>().ok().map(Value::from),
+        Some(FieldKind::Decimal) => text.trim().parse::<f64>().ok().map(Value::from),
+        None => None,
+    };
+
+    match value {
+        Some(v) if is_value_valid(name, &v) => {
+            values.insert(name.to_owned(), v);
+            sources.insert(name.to_owned(), source);
+        }
+        _ => errors.push(ConfigError::InvalidValue {
+            source,
+            field: name.to_owned(),
+            value: text.to_owned(),
+        }),
+    }
+}
+
+/// Check that the Directory or its nearest existing Parent is a writable Directory
+/// without changing anything on the File System
+fn check_directory(directory: &Path) -> Result<(), Error> {
+    let mut current = directory;
+
+    loop {
+        match fs::metadata(current) {
+            Ok(m) if !m.is_dir() => {
+                return Err(Error::other(format!(
+                    "'{}' is not a Directory",
+                    current.display()
+                )))
+            }
+            Ok(m) if m.permissions().readonly() => {
+                return Err(Error::new(
+                    ErrorKind::PermissionDenied,
+                    format!("'{}' is read-only", current.display()),
+                ))
+            }
+            Ok(_) => return Ok(()),
+            Err(e) if e.kind() == ErrorKind::NotFound => {
+                current = match current.parent() {
+                    Some(p) if p.as_os_str().is_empty() => Path::new("."),
+                    Some(p) => p,
+                    None => return Err(e),
+                };
+            }
+            Err(e) => return Err(e),
+        }
+    }
 }
 
 #[allow(dead_code)]
@@ -226,8 +1137,226 @@ fn find_path_parent(current: &Path, name: &str) -> Option<PathBuf> {
         odir = d.parent();
     }
 
-    match odir {
-        Some(d) => Some(PathBuf::from(d)),
-        None => None,
-    }
+    odir.map(PathBuf::from)
+}
+
+//==============================================================================
+// Unit Tests
+
+#[test]
+fn config_layer_precedence() {
+    let file = std::env::temp_dir().join(format!("blockchain-config-{}.toml", std::process::id()));
+
+    fs::write(
+        &file,
+        "component = \"file-node\"\nport = 3200\ndifficulty = 2\nminer_count = -1\nunknown_field = 1\n",
+    )
+    .unwrap();
+
+    let args = ConfigArgs {
+        config: Some(file.clone()),
+        host: Some(String::from("0.0.0.0")),
+        difficulty: Some(3),
+        ..Default::default()
+    };
+    let environment = vec![
+        (String::from("PORT"), String::from("3300")),
+        (String::from("BLOCKCHAIN_PORT"), String::from("3400")),
+        (String::from("BLOCKCHAIN_DIFFICULTY"), String::from("5")),
+        (
+            String::from("BLOCKCHAIN_MINING_REWARD"),
+            String::from("no-number"),
+        ),
+    ];
+
+    let layered = AppConfig::load_layers(&args, environment);
+
+    fs::remove_file(&file).unwrap();
+
+    assert_eq!(layered.config.component.as_str(), "file-node");
+    assert_eq!(layered.config.port, 3400);
+    assert_eq!(layered.config.miner_count, 2);
+    assert_eq!(layered.config.difficulty, 3);
+    assert_eq!(layered.config.mining_reward, 10f64);
+    assert_eq!(layered.config.get_bind_address().as_str(), "0.0.0.0:3400");
+    assert_eq!(
+        layered.sources["component"],
+        ConfigSource::File(file.clone())
+    );
+    assert_eq!(
+        layered.sources["port"],
+        ConfigSource::Environment(String::from("BLOCKCHAIN_PORT"))
+    );
+    assert_eq!(
+        layered.sources["difficulty"],
+        ConfigSource::CommandLine(String::from("--difficulty"))
+    );
+    assert_eq!(layered.sources["mining_reward"], ConfigSource::Default);
+    assert_eq!(
+        layered.errors,
+        vec![
+            ConfigError::InvalidValue {
+                source: ConfigSource::File(file.clone()),
+                field: String::from("miner_count"),
+                value: String::from("-1"),
+            },
+            ConfigError::UnknownField {
+                source: ConfigSource::File(file),
+                field: String::from("unknown_field"),
+            },
+            ConfigError::InvalidValue {
+                source: ConfigSource::Environment(String::from("BLOCKCHAIN_MINING_REWARD")),
+                field: String::from("mining_reward"),
+                value: String::from("no-number"),
+            },
+        ]
+    );
+    assert!(layered.validate().is_err());
+}
+
+#[test]
+fn config_validation_errors() {
+    let blocking_file =
+        std::env::temp_dir().join(format!("blockchain-blocking-{}", std::process::id()));
+
+    fs::write(&blocking_file, "not a directory").unwrap();
+
+    let mut config = AppConfig::new();
+
+    assert_eq!(
+        config.validate(),
+        Err(vec![ConfigError::MissingRewardAddress])
+    );
+
+    config.reward_address = String::from("16Jswqk47s9PUcyCc88MMVwzgvHPvtEpf");
+
+    assert_eq!(config.validate(), Ok(()));
+
+    config.miner_count = 0;
+    config.web_root = String::from("/api");
+    config.data_directory = blocking_file.join("data").display().to_string();
+
+    let errors = config.validate().unwrap_err();
+
+    fs::remove_file(&blocking_file).unwrap();
+
+    assert_eq!(errors.len(), 3);
+    assert_eq!(errors[0], ConfigError::ZeroMiners);
+    assert_eq!(errors[1], ConfigError::InvalidWebRoot(String::from("/api")));
+    assert!(matches!(
+        errors[2],
+        ConfigError::UnwritableDataDirectory { .. }
+    ));
+
+    //-------------------------------------
+    // Validation does not create the Data Directory
+
+    let mut config = AppConfig::new();
+    let data_directory =
+        std::env::temp_dir().join(format!("blockchain-unchecked-{}", std::process::id()));
+
+    config.reward_address = String::from("16Jswqk47s9PUcyCc88MMVwzgvHPvtEpf");
+    config.data_directory = data_directory.join("data").display().to_string();
+
+    assert_eq!(config.validate(), Ok(()));
+    assert!(!data_directory.exists());
+
+    //-------------------------------------
+    // Numeric Settings must be in their Range
+
+    config.port = 0;
+    config.difficulty = 65;
+    config.mining_reward = -1.0;
+
+    assert_eq!(
+        config.validate(),
+        Err(vec![
+            ConfigError::ZeroPort,
+            ConfigError::InvalidDifficulty(65),
+            ConfigError::InvalidMiningReward(-1.0),
+        ])
+    );
+
+    config.port = 3100;
+    config.difficulty = 0;
+    config.mining_reward = f64::NAN;
+
+    let errors = config.validate().unwrap_err();
+
+    assert_eq!(errors[0], ConfigError::InvalidDifficulty(0));
+    assert!(matches!(errors[1], ConfigError::InvalidMiningReward(r) if r.is_nan()));
+
+    //-------------------------------------
+    // Compact Targets must be positive
+
+    let mut config = AppConfig::new();
+
+    config.reward_address = String::from("16Jswqk47s9PUcyCc88MMVwzgvHPvtEpf");
+
+    config.target_bits = String::from("1d80ffff");
+
+    assert_eq!(
+        config.validate(),
+        Err(vec![ConfigError::InvalidTargetBits(String::from(
+            "1d80ffff"
+        ))])
+    );
+
+    config.target_bits = String::from("0x1d00ffff");
+
+    assert_eq!(config.get_target_bits(), Some(0x1d00_ffff));
+    assert_eq!(config.validate(), Ok(()));
+}
+
+#[test]
+fn config_validator_keystore() {
+    use crate::wallet::KdfParams;
+
+    let keystore_file =
+        std::env::temp_dir().join(format!("blockchain-validator-{}.json", std::process::id()));
+    let signing_key = SigningKey::from_bytes(&[9u8; 32]);
+    let mut keystore = Keystore::with_kdf(KdfParams {
+        memory_cost: 64,
+        time_cost: 1,
+        parallelism: 1,
+    });
+
+    keystore
+        .add_key("validator", &signing_key, "secret")
+        .unwrap();
+    keystore.save(&keystore_file).unwrap();
+
+    let mut config = AppConfig::new();
+
+    config.reward_address = String::from("16Jswqk47s9PUcyCc88MMVwzgvHPvtEpf");
+    config.validator_keystore = keystore_file.display().to_string();
+
+    assert_eq!(config.unlock_validator_key("secret"), Ok(None));
+
+    config.validator_key = String::from("validator");
+
+    assert_eq!(config.validate(), Ok(()));
+    assert_eq!(
+        config
+            .unlock_validator_key("secret")
+            .unwrap()
+            .map(|k| k.to_bytes()),
+        Some(signing_key.to_bytes())
+    );
+    assert!(config.unlock_validator_key("wrong").is_err());
+
+    //-------------------------------------
+    // The Key must be in the Keystore
+
+    config.validator_key = String::from("unknown");
+
+    assert_eq!(
+        config.validate(),
+        Err(vec![ConfigError::MissingValidatorKey {
+            keystore: config.validator_keystore.clone(),
+            key: String::from("unknown"),
+        }])
+    );
+
+    fs::remove_file(&keystore_file).unwrap();
 }
@@ -0,0 +1,217 @@
+/*
+* @author Bodo (Hugo) Barwich
+* @version 2026-10-18
+* @package Blockchain Exercise
+* @subpackage Block Hashers
+
+* This Module defines the Hash Algorithms which a Chain computes its Block Hashes with
+*
+*---------------------------------
+* Requirements:
+* - The Rust Crate "sha2" must be installed
+* - The Rust Crate "sha3" must be installed
+* - The Rust Crate "blake3" must be installed
+* - The Rust Crate "argon2" must be installed
+*/
+
+use argon2::{Algorithm, Argon2, Params, Version};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use sha3::Sha3_256;
+use std::fmt::Debug;
+
+use super::ConsensusError;
+
+/// Memory of a single Argon2id Block Hash in KiB
+pub const ARGON2_MEMORY_KIB: u32 = 1024;
+
+/// Argon2id needs a Salt. All Nodes must use the same one.
+const ARGON2_SALT: &[u8] = b"actix-blockchain";
+
+//==============================================================================
+// Trait BlockHasher Declaration
+
+/// Hash Algorithm of a Chain
+///
+/// It is chosen with the Genesis Block and cannot change afterwards.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HashAlgorithm {
+    #[default]
+    Sha256,
+    /// SHA-256 applied twice like in Bitcoin
+    DoubleSha256,
+    #[serde(rename = "sha3_256")]
+    Sha3_256,
+    Blake3,
+    /// Memory-hard Hash which levels the Advantage of Mining Hardware
+    Argon2id,
+}
+
+/// All supported Hash Algorithms
+pub const HASH_ALGORITHMS: [HashAlgorithm; 5] = [
+    HashAlgorithm::Sha256,
+    HashAlgorithm::DoubleSha256,
+    HashAlgorithm::Sha3_256,
+    HashAlgorithm::Blake3,
+    HashAlgorithm::Argon2id,
+];
+
+/// Interface of the Block Hashers
+pub trait BlockHasher: Debug + Send + Sync {
+    fn get_algorithm(&self) -> HashAlgorithm;
+
+    /// Compute the hex encoded 256 bit Hash of the serialized Block.
+    fn hash(&self, data: &[u8]) -> Result<String, ConsensusError>;
+}
+
+//==============================================================================
+// Structure Hasher Declarations
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Sha256Hasher;
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DoubleSha256Hasher;
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Sha3Hasher;
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Blake3Hasher;
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Argon2Hasher;
+
+//==============================================================================
+// Structure HashAlgorithm Implementation
+
+impl HashAlgorithm {
+    /*----------------------------------------------------------------------------
+     * Consultation Methods
+     */
+
+    pub fn get_hasher(self) -> &'static dyn BlockHasher {
+        match self {
+            HashAlgorithm::Sha256 => &Sha256Hasher,
+            HashAlgorithm::DoubleSha256 => &DoubleSha256Hasher,
+            HashAlgorithm::Sha3_256 => &Sha3Hasher,
+            HashAlgorithm::Blake3 => &Blake3Hasher,
+            HashAlgorithm::Argon2id => &Argon2Hasher,
+        }
+    }
+
+    /// Compute the hex encoded Hash of the Data with this Algorithm.
+    pub fn hash(self, data: &[u8]) -> Result<String, ConsensusError> {
+        self.get_hasher().hash(data)
+    }
+
+    /// Name of the Algorithm as used in the Configuration
+    pub fn get_name(self) -> &'static str {
+        match self {
+            HashAlgorithm::Sha256 => "sha256",
+            HashAlgorithm::DoubleSha256 => "double_sha256",
+            HashAlgorithm::Sha3_256 => "sha3_256",
+            HashAlgorithm::Blake3 => "blake3",
+            HashAlgorithm::Argon2id => "argon2id",
+        }
+    }
+}
+
+//==============================================================================
+// Structure Hasher Implementations
+
+impl BlockHasher for Sha256Hasher {
+    fn get_algorithm(&self) -> HashAlgorithm {
+        HashAlgorithm::Sha256
+    }
+
+    fn hash(&self, data: &[u8]) -> Result<String, ConsensusError> {
+        Ok(hex::encode(Sha256::digest(data)))
+    }
+}
+
+impl BlockHasher for DoubleSha256Hasher {
+    fn get_algorithm(&self) -> HashAlgorithm {
+        HashAlgorithm::DoubleSha256
+    }
+
+    fn hash(&self, data: &[u8]) -> Result<String, ConsensusError> {
+        Ok(hex::encode(Sha256::digest(Sha256::digest(data))))
+    }
+}
+
+impl BlockHasher for Sha3Hasher {
+    fn get_algorithm(&self) -> HashAlgorithm {
+        HashAlgorithm::Sha3_256
+    }
+
+    fn hash(&self, data: &[u8]) -> Result<String, ConsensusError> {
+        Ok(hex::encode(Sha3_256::digest(data)))
+    }
+}
+
+impl BlockHasher for Blake3Hasher {
+    fn get_algorithm(&self) -> HashAlgorithm {
+        HashAlgorithm::Blake3
+    }
+
+    fn hash(&self, data: &[u8]) -> Result<String, ConsensusError> {
+        Ok(blake3::hash(data).to_hex().to_string())
+    }
+}
+
+impl BlockHasher for Argon2Hasher {
+    fn get_algorithm(&self) -> HashAlgorithm {
+        HashAlgorithm::Argon2id
+    }
+
+    /// Each Hash fills `ARGON2_MEMORY_KIB` of Memory in a single Pass.
+    fn hash(&self, data: &[u8]) -> Result<String, ConsensusError> {
+        let mut output = [0u8; 32];
+
+        Params::new(ARGON2_MEMORY_KIB, 1, 1, Some(output.len()))
+            .map(|params| Argon2::new(Algorithm::Argon2id, Version::V0x13, params))
+            .and_then(|argon2| argon2.hash_password_into(data, ARGON2_SALT, &mut output))
+            .map_err(|e| ConsensusError::new("failed", &format!("Argon2id: Hash failed! {}", e)))?;
+
+        Ok(hex::encode(output))
+    }
+}
+
+//==============================================================================
+// Unit Tests
+
+#[test]
+fn block_hashers() {
+    // Known Answers for the Input "abc"
+    assert_eq!(
+        HashAlgorithm::Sha256.hash(b"abc").unwrap(),
+        "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+    );
+    assert_eq!(
+        HashAlgorithm::DoubleSha256.hash(b"abc").unwrap(),
+        "4f8b42c22dd3729b519ba6f68d2da7cc5b2d606d05daed5ad5128cc03e6c6358"
+    );
+    assert_eq!(
+        HashAlgorithm::Sha3_256.hash(b"abc").unwrap(),
+        "3a985da74fe225b2045c172d6bd390bd855f086e3e9d525b46bfe24511431532"
+    );
+    assert_eq!(
+        HashAlgorithm::Blake3.hash(b"abc").unwrap(),
+        "6437b3ac38465133ffb63b75273a8db548c558465d79db03fd359c6cd5bd9d85"
+    );
+
+    for algorithm in HASH_ALGORITHMS {
+        let hash = algorithm.hash(b"abc").unwrap();
+
+        assert_eq!(algorithm.get_hasher().get_algorithm(), algorithm);
+        assert_eq!(hash.len(), 64, "{}", algorithm.get_name());
+        assert_eq!(algorithm.hash(b"abc").unwrap(), hash);
+        assert_ne!(algorithm.hash(b"abd").unwrap(), hash);
+        assert_eq!(
+            serde_json::to_string(&algorithm).unwrap(),
+            format!("\"{}\"", algorithm.get_name())
+        );
+    }
+}
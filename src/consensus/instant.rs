@@ -0,0 +1,53 @@
+/*
+* @author Bodo (Hugo) Barwich
+* @version 2026-10-18
+* @package Blockchain Exercise
+* @subpackage Instant Seal Engine
+
+* This Module seals Blocks at once without any Proof.
+* It is only meant for Test Networks.
+*
+*---------------------------------
+* Requirements:
+*/
+
+use super::{ConsensusEngine, ConsensusError, ConsensusMode, SealContext, SealProgress};
+use crate::model::blockchain::Block;
+
+//==============================================================================
+// Structure InstantSeal Declaration
+
+/// Structure for sealing Blocks without any Proof
+#[derive(Debug, Clone, Copy, Default)]
+pub struct InstantSeal;
+
+//==============================================================================
+// Structure InstantSeal Implementation
+
+impl ConsensusEngine for InstantSeal {
+    fn get_mode(&self) -> ConsensusMode {
+        ConsensusMode::Instant
+    }
+
+    fn seal(
+        &self,
+        _context: &SealContext,
+        block: &mut Block,
+        _on_tick: &mut dyn FnMut(&mut Block, &SealProgress),
+    ) -> Result<(), ConsensusError> {
+        block.proof = 0;
+
+        Ok(())
+    }
+
+    /// Only Blocks without Proof and Target as this Engine seals them are accepted.
+    fn verify_seal(&self, _context: &SealContext, block: &Block) -> bool {
+        block.proof == 0 && block.bits == 0
+    }
+
+    /// Without any Proof anyone can forge a longer Chain, so a competing Chain
+    /// never replaces the local Chain.
+    fn choose_fork(&self, _local: &[Block], _candidate: &[Block]) -> bool {
+        false
+    }
+}
@@ -0,0 +1,143 @@
+/*
+* @author Bodo (Hugo) Barwich
+* @version 2026-10-18
+* @package Blockchain Exercise
+* @subpackage Consensus Engines
+
+* This Module defines the Interface of the Consensus Engines which seal new Blocks,
+* verify the Seals of received Blocks and choose between competing Chains
+*
+*---------------------------------
+* Requirements:
+*/
+
+pub mod hasher;
+pub mod instant;
+pub mod poa;
+pub mod pos;
+pub mod pow;
+pub mod seal;
+
+use serde::{Deserialize, Serialize};
+use std::fmt::Debug;
+
+use crate::model::blockchain::Block;
+use crate::model::stake::StakeLedger;
+
+pub use hasher::HashAlgorithm;
+pub use instant::InstantSeal;
+pub use poa::{AuthorityParams, ProofOfAuthority, ValidatorSet};
+pub use pos::ProofOfStake;
+pub use pow::ProofOfWork;
+
+//==============================================================================
+// Trait ConsensusEngine Declaration
+
+/// Consensus Engine of a Chain
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConsensusMode {
+    /// Blocks are sealed with a Nonce whose Block Hash meets the Difficulty
+    #[default]
+    ProofOfWork,
+    /// Blocks are sealed at once. Only meant for Test Networks.
+    Instant,
+    /// Blocks are signed by the Validators in turns
+    ProofOfAuthority,
+    /// Blocks are signed by Validators drawn by their Stakes
+    ProofOfStake,
+}
+
+/// Structure for the Errors of Blocks which cannot be sealed
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConsensusError {
+    pub status: String,
+    pub report: String,
+}
+
+/// Structure for the Chain Data which an Engine needs to seal or verify a Block
+#[derive(Debug, Clone)]
+pub struct SealContext<'a> {
+    /// Blocks before the sealed Block
+    pub ancestors: &'a [Block],
+    /// Validator Set after the `ancestors` of a Proof of Authority Chain
+    pub validator_set: &'a ValidatorSet,
+    /// Stakes after the `ancestors` of a Proof of Stake Chain
+    pub stakes: &'a StakeLedger,
+    /// Hash of the verified Block if the Caller computed it already
+    pub block_hash: Option<&'a str>,
+    /// Compact Proof of Work Target of the Chain
+    pub bits: u32,
+    /// Hash Algorithm of the Chain
+    pub hash_algorithm: HashAlgorithm,
+}
+
+/// Structure for the Progress of a running Seal
+#[derive(Debug, Clone, Default)]
+pub struct SealProgress {
+    pub attempts: u64,
+    /// Lowest Block Hash found so far
+    pub best_hash: String,
+}
+
+/// Interface of the Consensus Engines
+///
+/// The `Blockchain` selects the `Transaction`s and maintains the Ledger. The Engine
+/// only decides how a Block is sealed and which Chain is adopted.
+/// The Chain State which an Engine needs is kept up to date by the `Blockchain` and
+/// passed with the `SealContext`, so the Engines do not replay the Chain and new
+/// State does not change the Interface.
+pub trait ConsensusEngine: Debug + Send + Sync {
+    fn get_mode(&self) -> ConsensusMode;
+
+    /// Seal a Block whose `Transaction`s are already selected.
+    ///
+    /// `on_tick` is called once per second while the Seal runs. It receives the
+    /// Progress and can add `Transaction`s to the Block.
+    /// An Engine which may not seal the Block now returns an Error.
+    fn seal(
+        &self,
+        context: &SealContext,
+        block: &mut Block,
+        on_tick: &mut dyn FnMut(&mut Block, &SealProgress),
+    ) -> Result<(), ConsensusError>;
+
+    /// Check the Seal of a Block which follows the `ancestors` of the Context.
+    fn verify_seal(&self, context: &SealContext, block: &Block) -> bool;
+
+    /// Decide whether a valid competing Chain replaces the local Chain.
+    ///
+    /// By default the longer Chain wins.
+    fn choose_fork(&self, local: &[Block], candidate: &[Block]) -> bool {
+        candidate.len() > local.len()
+    }
+}
+
+//==============================================================================
+// Structure ConsensusError Implementation
+
+impl ConsensusError {
+    /*----------------------------------------------------------------------------
+     * Constructors
+     */
+
+    pub fn new(status: &str, report: &str) -> Self {
+        Self {
+            status: status.to_owned(),
+            report: report.to_owned(),
+        }
+    }
+}
+
+//==============================================================================
+// Auxiliary Functions
+
+/// Create the Engine of a Consensus Mode which verifies the Blocks of a Chain.
+pub fn build_engine(mode: ConsensusMode, authority: &AuthorityParams) -> Box<dyn ConsensusEngine> {
+    match mode {
+        ConsensusMode::ProofOfWork => Box::new(ProofOfWork),
+        ConsensusMode::Instant => Box::new(InstantSeal),
+        ConsensusMode::ProofOfAuthority => Box::new(ProofOfAuthority::new(authority)),
+        ConsensusMode::ProofOfStake => Box::new(ProofOfStake::new(authority)),
+    }
+}
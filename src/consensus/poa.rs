@@ -0,0 +1,485 @@
+/*
+* @author Bodo (Hugo) Barwich
+* @version 2026-10-18
+* @package Blockchain Exercise
+* @subpackage Proof of Authority Engine
+
+* This Module seals Blocks by the Signature of a Validator.
+* The Validators take turns in Slots of a fixed Interval and the Validator Set
+* is changed by the Votes which the Validators cast in their Blocks.
+*
+*---------------------------------
+* Requirements:
+* - The Rust Crate "ed25519-dalek" must be installed
+*/
+
+use ed25519_dalek::SigningKey;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::time::SystemTime;
+
+use super::seal::{self, decode_public_key};
+use super::{ConsensusEngine, ConsensusError, ConsensusMode, SealContext, SealProgress};
+use crate::model::blockchain::Block;
+
+//==============================================================================
+// Structure ProofOfAuthority Declaration
+
+/// Structure for the Parameters of a Proof of Authority Chain
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AuthorityParams {
+    /// Hex encoded Ed25519 Public Keys of the initial Validators
+    pub validators: Vec<String>,
+    /// Length of a Slot in seconds
+    pub block_interval: u32,
+}
+
+/// Structure for a Vote to add or remove a Validator
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ValidatorVote {
+    /// Hex encoded Ed25519 Public Key of the Validator
+    pub validator: String,
+    pub add: bool,
+}
+
+/// Structure for the Votes cast for a proposed Change of the Validator Set
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VoteTally {
+    pub validator: String,
+    pub add: bool,
+    pub votes: usize,
+}
+
+/// Structure for the Validators of a Chain and the Votes which did not pass yet
+///
+/// A Change takes effect as soon as more than half of the Validators voted for it.
+#[derive(Debug, Clone, Default)]
+pub struct ValidatorSet {
+    /// Validators in the Order of their Turns
+    validators: Vec<String>,
+    /// Latest Vote of each Validator for each proposed Validator
+    votes: BTreeMap<String, BTreeMap<String, bool>>,
+}
+
+/// Structure for the Proof of Authority (PoA) Consensus Protocol
+#[derive(Debug, Clone)]
+pub struct ProofOfAuthority {
+    params: AuthorityParams,
+    /// Key of this Node if it is a Validator
+    signing_key: Option<SigningKey>,
+    /// Changes of the Validator Set this Node votes for
+    proposals: Vec<ValidatorVote>,
+}
+
+//==============================================================================
+// Structure AuthorityParams Implementation
+
+impl Default for AuthorityParams {
+    /*----------------------------------------------------------------------------
+     * Default Constructor
+     */
+
+    fn default() -> Self {
+        Self {
+            validators: Vec::new(),
+            block_interval: 5,
+        }
+    }
+}
+
+impl AuthorityParams {
+    /*----------------------------------------------------------------------------
+     * Consultation Methods
+     */
+
+    pub fn is_empty(&self) -> bool {
+        self.validators.is_empty()
+    }
+
+    /// Slot of a Block Timestamp
+    pub fn get_slot(&self, timestamp: u32) -> u64 {
+        timestamp as u64 / self.block_interval.max(1) as u64
+    }
+}
+
+//==============================================================================
+// Structure ValidatorSet Implementation
+
+impl ValidatorSet {
+    /*----------------------------------------------------------------------------
+     * Constructors
+     */
+
+    pub fn new(validators: &[String]) -> Self {
+        let mut set = Self::default();
+
+        for validator in validators {
+            if !set.validators.contains(validator) {
+                set.validators.push(validator.clone());
+            }
+        }
+
+        set
+    }
+
+    /// Replay the Votes of a Chain on the initial Validators.
+    pub fn from_chain(validators: &[String], chain: &[Block]) -> Self {
+        let mut set = Self::new(validators);
+
+        chain.iter().for_each(|b| set.apply_block(b));
+
+        set
+    }
+
+    /*----------------------------------------------------------------------------
+     * Administration Methods
+     */
+
+    /// Count the Vote of the Validator which sealed the Block.
+    pub fn apply_block(&mut self, block: &Block) {
+        if let Some(seal) = block.seal.as_ref() {
+            if let Some(vote) = seal.vote.as_ref() {
+                self.cast_vote(&seal.signer, vote);
+            }
+        }
+    }
+
+    fn cast_vote(&mut self, voter: &str, vote: &ValidatorVote) {
+        if !self.is_validator(voter) || !self.is_vote_effective(vote) {
+            return;
+        }
+
+        let ballots = self.votes.entry(vote.validator.clone()).or_default();
+
+        ballots.insert(voter.to_owned(), vote.add);
+
+        let count = ballots.values().filter(|add| **add == vote.add).count();
+
+        if count > self.validators.len() / 2 {
+            self.votes.remove(&vote.validator);
+
+            if vote.add {
+                self.validators.push(vote.validator.clone());
+            } else {
+                self.validators.retain(|v| *v != vote.validator);
+
+                // The Votes of a removed Validator do not count anymore
+                self.votes.values_mut().for_each(|b| {
+                    b.remove(&vote.validator);
+                });
+            }
+        }
+    }
+
+    /*----------------------------------------------------------------------------
+     * Consultation Methods
+     */
+
+    pub fn get_validators(&self) -> &[String] {
+        &self.validators
+    }
+
+    pub fn is_validator(&self, validator: &str) -> bool {
+        self.validators.iter().any(|v| v == validator)
+    }
+
+    /// Validator whose Turn a Slot is
+    pub fn get_scheduled(&self, slot: u64) -> Option<&String> {
+        match self.validators.len() {
+            0 => None,
+            count => self.validators.get((slot % count as u64) as usize),
+        }
+    }
+
+    /// Check whether a Vote would change the Validator Set.
+    ///
+    /// The last Validator cannot be removed.
+    pub fn is_vote_effective(&self, vote: &ValidatorVote) -> bool {
+        match vote.add {
+            true => !self.is_validator(&vote.validator),
+            false => self.is_validator(&vote.validator) && self.validators.len() > 1,
+        }
+    }
+
+    pub fn has_voted(&self, voter: &str, vote: &ValidatorVote) -> bool {
+        self.votes
+            .get(&vote.validator)
+            .and_then(|b| b.get(voter))
+            .is_some_and(|add| *add == vote.add)
+    }
+
+    /// Votes for the Changes which did not pass yet
+    pub fn get_tallies(&self) -> Vec<VoteTally> {
+        self.votes
+            .iter()
+            .flat_map(|(validator, ballots)| {
+                [true, false].into_iter().filter_map(move |add| {
+                    let votes = ballots.values().filter(|a| **a == add).count();
+
+                    (votes > 0).then(|| VoteTally {
+                        validator: validator.clone(),
+                        add,
+                        votes,
+                    })
+                })
+            })
+            .collect()
+    }
+}
+
+//==============================================================================
+// Structure ProofOfAuthority Implementation
+
+impl ProofOfAuthority {
+    /*----------------------------------------------------------------------------
+     * Constructors
+     */
+
+    /// Create an Engine which only verifies Seals.
+    pub fn new(params: &AuthorityParams) -> Self {
+        Self {
+            params: params.clone(),
+            signing_key: None,
+            proposals: Vec::new(),
+        }
+    }
+
+    /// Create an Engine which seals Blocks with the Key of a Validator and votes
+    /// for the proposed Changes of the Validator Set.
+    pub fn with_key(
+        params: &AuthorityParams,
+        signing_key: &SigningKey,
+        proposals: &[ValidatorVote],
+    ) -> Self {
+        Self {
+            params: params.clone(),
+            signing_key: Some(signing_key.clone()),
+            proposals: proposals.to_vec(),
+        }
+    }
+}
+
+impl ConsensusEngine for ProofOfAuthority {
+    fn get_mode(&self) -> ConsensusMode {
+        ConsensusMode::ProofOfAuthority
+    }
+
+    /// Sign the Block if its Slot is the Turn of this Validator.
+    ///
+    /// The first Change which this Validator has not voted for yet is added as Vote.
+    fn seal(
+        &self,
+        context: &SealContext,
+        block: &mut Block,
+        _on_tick: &mut dyn FnMut(&mut Block, &SealProgress),
+    ) -> Result<(), ConsensusError> {
+        let signing_key = self.signing_key.as_ref().ok_or_else(|| {
+            ConsensusError::new("unauthorized", "Proof of Authority: No Validator Key")
+        })?;
+        let signer = hex::encode(signing_key.verifying_key().as_bytes());
+        let set = context.validator_set;
+        let slot = self.params.get_slot(block.timestamp);
+
+        if !set.is_validator(&signer) {
+            return Err(ConsensusError::new(
+                "unauthorized",
+                &format!("Proof of Authority: Key '{}' is not a Validator", signer),
+            ));
+        }
+
+        if context
+            .ancestors
+            .last()
+            .is_some_and(|b| self.params.get_slot(b.timestamp) >= slot)
+        {
+            return Err(ConsensusError::new(
+                "waiting",
+                &format!("Proof of Authority: Slot {} is already sealed", slot),
+            ));
+        }
+
+        if set.get_scheduled(slot) != Some(&signer) {
+            return Err(ConsensusError::new(
+                "waiting",
+                &format!(
+                    "Proof of Authority: Slot {} is not the Turn of this Node",
+                    slot
+                ),
+            ));
+        }
+
+        let vote = self
+            .proposals
+            .iter()
+            .find(|v| set.is_vote_effective(v) && !set.has_voted(&signer, v))
+            .cloned();
+
+        block.proof = 0;
+        seal::sign_block(block, signing_key, vote);
+
+        Ok(())
+    }
+
+    /// Check that the Block is signed by the Validator scheduled for its Slot
+    /// and that its Slot follows the Slot of its Predecessor.
+    fn verify_seal(&self, context: &SealContext, block: &Block) -> bool {
+        let seal = match block.seal.as_ref() {
+            Some(s) if block.proof == 0 => s,
+            _ => return false,
+        };
+        let set = context.validator_set;
+        let slot = self.params.get_slot(block.timestamp);
+        let now = match SystemTime::now().duration_since(SystemTime::UNIX_EPOCH) {
+            Ok(n) => n.as_secs(),
+            Err(_) => 0,
+        };
+
+        if context
+            .ancestors
+            .last()
+            .is_some_and(|b| self.params.get_slot(b.timestamp) >= slot)
+            || block.timestamp as u64 > now + self.params.block_interval as u64
+        {
+            return false;
+        }
+
+        if seal
+            .vote
+            .as_ref()
+            .is_some_and(|v| decode_public_key(&v.validator).is_none())
+        {
+            return false;
+        }
+
+        set.get_scheduled(slot) == Some(&seal.signer) && seal::is_block_signed(block)
+    }
+}
+
+//==============================================================================
+// Unit Tests
+
+#[test]
+fn authority_schedule_and_votes() {
+    let keys: Vec<SigningKey> = (1..=3).map(|i| SigningKey::from_bytes(&[i; 32])).collect();
+    let validators: Vec<String> = keys
+        .iter()
+        .map(|k| hex::encode(k.verifying_key().as_bytes()))
+        .collect();
+    let params = AuthorityParams {
+        validators: validators[..2].to_vec(),
+        block_interval: 10,
+    };
+    let proposal = ValidatorVote {
+        validator: validators[2].clone(),
+        add: true,
+    };
+    let engines: Vec<ProofOfAuthority> = keys
+        .iter()
+        .map(|k| ProofOfAuthority::with_key(&params, k, std::slice::from_ref(&proposal)))
+        .collect();
+    let verifier = ProofOfAuthority::new(&params);
+    let stakes = crate::model::stake::StakeLedger::new();
+    let seal = |engine: &ProofOfAuthority, ancestors: &[Block], block: &mut Block| {
+        let validator_set = ValidatorSet::from_chain(&params.validators, ancestors);
+        let context = SealContext {
+            ancestors,
+            validator_set: &validator_set,
+            stakes: &stakes,
+            block_hash: None,
+            bits: 0,
+            hash_algorithm: Default::default(),
+        };
+
+        engine.seal(&context, block, &mut |_, _| {})
+    };
+    let verify = |ancestors: &[Block], block: &Block| {
+        let validator_set = ValidatorSet::from_chain(&params.validators, ancestors);
+        let context = SealContext {
+            ancestors,
+            validator_set: &validator_set,
+            stakes: &stakes,
+            block_hash: None,
+            bits: 0,
+            hash_algorithm: Default::default(),
+        };
+
+        verifier.verify_seal(&context, block)
+    };
+
+    //-------------------------------------
+    // Slot 100 is the Turn of the first Validator
+
+    let mut first = Block::build_block(1, 0, "0", None);
+
+    first.timestamp = 1000;
+
+    assert_eq!(
+        seal(&engines[1], &[], &mut first).map_err(|e| e.status),
+        Err(String::from("waiting"))
+    );
+    assert_eq!(
+        seal(&engines[2], &[], &mut first).map_err(|e| e.status),
+        Err(String::from("unauthorized"))
+    );
+    assert!(seal(&engines[0], &[], &mut first).is_ok());
+    assert!(verify(&[], &first));
+    assert_eq!(
+        first.seal.as_ref().and_then(|s| s.vote.clone()),
+        Some(proposal.clone())
+    );
+
+    let mut tampered = first.clone();
+
+    tampered.previous_hash = String::from("1");
+
+    assert!(!verify(&[], &tampered));
+
+    //-------------------------------------
+    // The second Vote adds the third Validator
+
+    let chain = vec![first.clone()];
+    let set = ValidatorSet::from_chain(&params.validators, &chain);
+
+    assert_eq!(set.get_validators(), &validators[..2]);
+    assert_eq!(
+        set.get_tallies(),
+        vec![VoteTally {
+            validator: validators[2].clone(),
+            add: true,
+            votes: 1,
+        }]
+    );
+
+    let mut second = Block::build_block(2, 0, &first.to_hash().unwrap(), None);
+
+    second.timestamp = 1005;
+
+    assert_eq!(
+        seal(&engines[0], &chain, &mut second).map_err(|e| e.status),
+        Err(String::from("waiting"))
+    );
+
+    second.timestamp = 1010;
+
+    assert!(seal(&engines[1], &chain, &mut second).is_ok());
+    assert!(verify(&chain, &second));
+
+    let chain = vec![first, second.clone()];
+    let set = ValidatorSet::from_chain(&params.validators, &chain);
+
+    assert_eq!(set.get_validators(), &validators[..]);
+    assert!(set.get_tallies().is_empty());
+
+    //-------------------------------------
+    // Slot 104 is the Turn of the new Validator
+
+    let mut third = Block::build_block(3, 0, &second.to_hash().unwrap(), None);
+
+    third.timestamp = 1040;
+
+    assert!(seal(&engines[2], &chain, &mut third).is_ok());
+    assert!(third.seal.as_ref().is_some_and(|s| s.vote.is_none()));
+    assert!(verify(&chain, &third));
+    assert!(!verify(&chain[..1], &third));
+}
@@ -0,0 +1,415 @@
+/*
+* @author Bodo (Hugo) Barwich
+* @version 2026-10-18
+* @package Blockchain Exercise
+* @subpackage Proof of Stake Engine
+
+* This Module seals Blocks by the Signature of a Validator which is drawn for each
+* Block Index weighted by the Stakes. The Draw is derived from the Hash of the
+* previous Block.
+*
+*---------------------------------
+* Requirements:
+* - The Rust Crate "ed25519-dalek" must be installed
+* - The Rust Crate "sha2" must be installed
+*/
+
+use ed25519_dalek::SigningKey;
+use sha2::{Digest, Sha256};
+use std::time::SystemTime;
+
+use super::seal::{self, DoubleSignEvidence};
+use super::{
+    AuthorityParams, ConsensusEngine, ConsensusError, ConsensusMode, SealContext, SealProgress,
+};
+use crate::model::blockchain::Block;
+use crate::model::stake::StakeLedger;
+
+//==============================================================================
+// Structure ProofOfStake Declaration
+
+/// Structure for the Proof of Stake (PoS) Consensus Protocol
+///
+/// While nobody has staked yet the initial Validators are drawn with equal Weights.
+/// A Block must be at least `block_interval` seconds younger than its Predecessor.
+/// If the drawn Validator does not seal the Block another Validator is drawn
+/// after each further `block_interval` seconds.
+#[derive(Debug, Clone)]
+pub struct ProofOfStake {
+    params: AuthorityParams,
+    /// Key of this Node if it is a Validator
+    signing_key: Option<SigningKey>,
+    /// Double Signatures this Node reports in the Blocks it seals
+    evidence: Vec<DoubleSignEvidence>,
+}
+
+//==============================================================================
+// Structure ProofOfStake Implementation
+
+impl ProofOfStake {
+    /*----------------------------------------------------------------------------
+     * Constructors
+     */
+
+    /// Create an Engine which only verifies Seals.
+    pub fn new(params: &AuthorityParams) -> Self {
+        Self {
+            params: params.clone(),
+            signing_key: None,
+            evidence: Vec::new(),
+        }
+    }
+
+    /// Create an Engine which seals Blocks with the Key of a Validator and
+    /// reports the Double Signatures of other Validators.
+    pub fn with_key(
+        params: &AuthorityParams,
+        signing_key: &SigningKey,
+        evidence: &[DoubleSignEvidence],
+    ) -> Self {
+        Self {
+            params: params.clone(),
+            signing_key: Some(signing_key.clone()),
+            evidence: evidence.to_vec(),
+        }
+    }
+
+    /*----------------------------------------------------------------------------
+     * Consultation Methods
+     */
+
+    /// Weights of the Validators with the Stakes of the Ledger
+    pub fn get_weights(&self, ledger: &StakeLedger) -> Vec<(String, u64)> {
+        let weights = ledger.get_weights();
+
+        if !weights.is_empty() {
+            return weights;
+        }
+
+        let mut validators = self.params.validators.clone();
+
+        validators.sort();
+        validators.dedup();
+
+        validators.into_iter().map(|v| (v, 1)).collect()
+    }
+
+    /// Number of Draws which passed before a Block with the Timestamp.
+    ///
+    /// The Genesis Block is drawn per Interval since the Epoch.
+    fn get_round(&self, parent: Option<&Block>, timestamp: u32) -> Option<u64> {
+        let interval = self.params.block_interval.max(1);
+
+        match parent {
+            Some(p) => timestamp
+                .checked_sub(p.timestamp)
+                .and_then(|elapsed| (elapsed / interval).checked_sub(1))
+                .map(|r| r as u64),
+            None => Some(self.params.get_slot(timestamp)),
+        }
+    }
+
+    /// Draw the Validator of a Block from the Stakes of its Ancestors.
+    ///
+    /// The Draw is seeded with the `previous_hash` of the Block which links it to its Parent.
+    fn get_scheduled(
+        &self,
+        context: &SealContext,
+        block: &Block,
+    ) -> Result<String, ConsensusError> {
+        let ancestors = context.ancestors;
+        let parent = ancestors.last();
+        let round = self.get_round(parent, block.timestamp).ok_or_else(|| {
+            ConsensusError::new("waiting", "Proof of Stake: Block Interval has not passed")
+        })?;
+
+        select_validator(
+            &self.get_weights(context.stakes),
+            &block.previous_hash,
+            ancestors.len() as u64 + 1,
+            round,
+        )
+        .ok_or_else(|| ConsensusError::new("unauthorized", "Proof of Stake: No Validators"))
+    }
+}
+
+impl ConsensusEngine for ProofOfStake {
+    fn get_mode(&self) -> ConsensusMode {
+        ConsensusMode::ProofOfStake
+    }
+
+    /// Sign the Block if this Validator is drawn for it.
+    ///
+    /// The Double Signatures which were not punished yet are added as Evidence.
+    fn seal(
+        &self,
+        context: &SealContext,
+        block: &mut Block,
+        _on_tick: &mut dyn FnMut(&mut Block, &SealProgress),
+    ) -> Result<(), ConsensusError> {
+        let signing_key = self.signing_key.as_ref().ok_or_else(|| {
+            ConsensusError::new("unauthorized", "Proof of Stake: No Validator Key")
+        })?;
+        let signer = hex::encode(signing_key.verifying_key().as_bytes());
+        let scheduled = self.get_scheduled(context, block)?;
+
+        if scheduled != signer {
+            return Err(ConsensusError::new(
+                "waiting",
+                &format!(
+                    "Proof of Stake: Block {} is drawn for Validator '{}'",
+                    block.index, scheduled
+                ),
+            ));
+        }
+
+        let mut ledger = context.stakes.clone();
+
+        block.evidence.clear();
+
+        if ledger.apply_block(block).is_ok() {
+            for evidence in self.evidence.iter() {
+                if ledger.apply_evidence(evidence).is_ok() {
+                    block.evidence.push(evidence.clone());
+                }
+            }
+        }
+
+        block.proof = 0;
+        seal::sign_block(block, signing_key, None);
+
+        Ok(())
+    }
+
+    /// Check that the Block is signed by the Validator drawn for it.
+    fn verify_seal(&self, context: &SealContext, block: &Block) -> bool {
+        let seal = match block.seal.as_ref() {
+            Some(s) if block.proof == 0 && s.vote.is_none() => s,
+            _ => return false,
+        };
+        let now = match SystemTime::now().duration_since(SystemTime::UNIX_EPOCH) {
+            Ok(n) => n.as_secs(),
+            Err(_) => 0,
+        };
+
+        if block.timestamp as u64 > now + self.params.block_interval as u64 {
+            return false;
+        }
+
+        match self.get_scheduled(context, block) {
+            Ok(scheduled) => scheduled == seal.signer && seal::is_block_signed(block),
+            Err(_) => false,
+        }
+    }
+}
+
+//==============================================================================
+// Auxiliary Functions
+
+/// Draw a Validator with a Probability proportional to its Weight.
+///
+/// The Draw is the SHA-256 Hash of the previous Block Hash, the Block Index and
+/// the Round. So all Nodes draw the same Validator.
+pub fn select_validator(
+    weights: &[(String, u64)],
+    previous_hash: &str,
+    block_index: u64,
+    round: u64,
+) -> Option<String> {
+    let total: u128 = weights.iter().map(|(_, w)| *w as u128).sum();
+
+    if total == 0 {
+        return None;
+    }
+
+    let seed = Sha256::digest(format!("{}:{}:{}", previous_hash, block_index, round));
+    let mut draw = u128::from_be_bytes(seed[..16].try_into().ok()?) % total;
+
+    for (validator, weight) in weights {
+        if draw < *weight as u128 {
+            return Some(validator.clone());
+        }
+
+        draw -= *weight as u128;
+    }
+
+    None
+}
+
+//==============================================================================
+// Unit Tests
+
+#[test]
+fn stake_weighted_draws_and_slashing() {
+    use crate::model::stake::StakeAction;
+    use crate::model::transaction::{Transaction, COINBASE_SENDER};
+
+    //-------------------------------------
+    // Draws are deterministic and follow the Weights
+
+    let weights = vec![(String::from("a"), 1), (String::from("b"), 3)];
+    let draws: Vec<String> = (0..400)
+        .filter_map(|round| select_validator(&weights, "hash", 2, round))
+        .collect();
+
+    assert_eq!(draws.len(), 400);
+    assert_eq!(
+        select_validator(&weights, "hash", 2, 7),
+        Some(draws[7].clone())
+    );
+    assert!(
+        draws.iter().filter(|v| *v == "b").count() > 2 * draws.iter().filter(|v| *v == "a").count()
+    );
+    assert_eq!(select_validator(&[], "hash", 2, 0), None);
+
+    //-------------------------------------
+    // The first Block funds and stakes 2 Validators
+
+    let keys: Vec<SigningKey> = (1..=3).map(|i| SigningKey::from_bytes(&[i; 32])).collect();
+    let validators: Vec<String> = keys
+        .iter()
+        .map(|k| hex::encode(k.verifying_key().as_bytes()))
+        .collect();
+    let params = AuthorityParams {
+        validators: validators.clone(),
+        block_interval: 10,
+    };
+    let verifier = ProofOfStake::new(&params);
+    let validator_set = super::ValidatorSet::new(&params.validators);
+    let seal = |engine: &ProofOfStake, ancestors: &[Block], block: &mut Block| {
+        let stakes = StakeLedger::from_chain(ancestors).unwrap();
+        let context = SealContext {
+            ancestors,
+            validator_set: &validator_set,
+            stakes: &stakes,
+            block_hash: None,
+            bits: 0,
+            hash_algorithm: Default::default(),
+        };
+
+        engine.seal(&context, block, &mut |_, _| {})
+    };
+    let verify = |ancestors: &[Block], block: &Block| {
+        let stakes = StakeLedger::from_chain(ancestors).unwrap();
+        let context = SealContext {
+            ancestors,
+            validator_set: &validator_set,
+            stakes: &stakes,
+            block_hash: None,
+            bits: 0,
+            hash_algorithm: Default::default(),
+        };
+
+        verifier.verify_seal(&context, block)
+    };
+    let mut transactions = Vec::new();
+
+    for key in keys[..2].iter() {
+        let mut stake = Transaction::from_stake(&key.verifying_key(), StakeAction::Stake, 5.0, 1);
+
+        stake.sign(key).unwrap();
+        transactions.push(Transaction::from_data(
+            String::from(COINBASE_SENDER),
+            stake.sender.clone(),
+            10.0,
+        ));
+        transactions.push(stake);
+    }
+
+    let mut first = Block::build_block(1, 0, "0", Some(transactions));
+
+    first.timestamp = 1000;
+
+    // Without Stakes any of the initial Validators can be drawn
+    assert_eq!(verifier.get_weights(&StakeLedger::new()).len(), 3);
+    assert!(keys
+        .iter()
+        .any(|k| seal(&ProofOfStake::with_key(&params, k, &[]), &[], &mut first).is_ok()));
+    assert!(verify(&[], &first));
+
+    let mut tampered = first.clone();
+
+    tampered.previous_hash = String::from("1");
+
+    assert!(!verify(&[], &tampered));
+
+    //-------------------------------------
+    // Only staked Validators are drawn once the Interval passed
+
+    let chain = vec![first.clone()];
+    let mut second = Block::build_block(2, 0, &first.to_hash().unwrap(), None);
+
+    second.timestamp = 1005;
+
+    assert_eq!(
+        seal(
+            &ProofOfStake::with_key(&params, &keys[0], &[]),
+            &chain,
+            &mut second
+        )
+        .map_err(|e| e.status),
+        Err(String::from("waiting"))
+    );
+
+    second.timestamp = 1010;
+
+    assert_eq!(
+        seal(
+            &ProofOfStake::with_key(&params, &keys[2], &[]),
+            &chain,
+            &mut second
+        )
+        .map_err(|e| e.status),
+        Err(String::from("waiting"))
+    );
+
+    let signer = (0..2)
+        .find(|&i| {
+            seal(
+                &ProofOfStake::with_key(&params, &keys[i], &[]),
+                &chain,
+                &mut second,
+            )
+            .is_ok()
+        })
+        .unwrap();
+
+    assert!(verify(&chain, &second));
+
+    //-------------------------------------
+    // The other Validator punishes a second Block of the same Signer
+
+    let mut conflicting = second.clone();
+
+    conflicting.timestamp = 1011;
+    seal::sign_block(&mut conflicting, &keys[signer], None);
+
+    let evidence = DoubleSignEvidence::from_blocks(&second, &conflicting).unwrap();
+    let other = 1 - signer;
+    let chain = vec![first, second.clone()];
+    let mut third = Block::build_block(3, 0, &second.to_hash().unwrap(), None);
+    let reporter = ProofOfStake::with_key(&params, &keys[other], std::slice::from_ref(&evidence));
+
+    // Wait for the first Round which draws the other Validator
+    third.timestamp = (1..=100)
+        .map(|round| 1010 + 10 * round)
+        .find(|&ts| {
+            third.timestamp = ts;
+
+            seal(&reporter, &chain, &mut third).is_ok()
+        })
+        .unwrap();
+
+    assert_eq!(third.evidence, vec![evidence]);
+    assert!(verify(&chain, &third));
+
+    let chain = vec![chain[0].clone(), chain[1].clone(), third];
+    let ledger = StakeLedger::from_chain(&chain).unwrap();
+
+    assert_eq!(ledger.get_stake(&validators[signer]), 0.0);
+    assert_eq!(ledger.get_stake(&validators[other]), 5.0);
+    assert_eq!(
+        reporter.get_weights(&ledger),
+        vec![(validators[other].clone(), 5_000_000)]
+    );
+}
@@ -0,0 +1,265 @@
+/*
+* @author Bodo (Hugo) Barwich
+* @version 2026-10-18
+* @package Blockchain Exercise
+* @subpackage Proof of Work Engine
+
+* This Module seals Blocks with a Nonce whose Block Hash is numerically below
+* the Target of the Chain
+*
+*---------------------------------
+* Requirements:
+* - The Rust Crate "num" must be installed
+*/
+
+use num::{BigUint, One, Zero};
+
+use super::{ConsensusEngine, ConsensusError, ConsensusMode, SealContext, SealProgress};
+use crate::model::blockchain::{self, Block};
+use crate::model::header::BlockHeader;
+
+//==============================================================================
+// Structure ProofOfWork Declaration
+
+/// Structure for the Proof of Work (PoW) Consensus Protocol
+///
+/// Of competing Chains the Chain with the most cumulative Work wins.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ProofOfWork;
+
+//==============================================================================
+// Structure ProofOfWork Implementation
+
+impl ConsensusEngine for ProofOfWork {
+    fn get_mode(&self) -> ConsensusMode {
+        ConsensusMode::ProofOfWork
+    }
+
+    /// Search the Nonce from 0 on. The Timestamp is updated while searching but never
+    /// goes below the Timestamp the Block was built with.
+    fn seal(
+        &self,
+        context: &SealContext,
+        block: &mut Block,
+        on_tick: &mut dyn FnMut(&mut Block, &SealProgress),
+    ) -> Result<(), ConsensusError> {
+        let target = bits_to_target(context.bits).ok_or_else(|| {
+            ConsensusError::new(
+                "invalid",
+                &format!(
+                    "Proof of Work: Target Bits {:08x} are invalid",
+                    context.bits
+                ),
+            )
+        })?;
+        let mut progress = SealProgress::default();
+        let min_timestamp = block.timestamp;
+        let mut last_tick = blockchain::get_timestamp();
+
+        block.bits = context.bits;
+        block.proof = 0;
+
+        let mut header = BlockHeader::from_block(block)?;
+
+        loop {
+            let now = blockchain::get_timestamp();
+
+            if now != last_tick {
+                last_tick = now;
+                block.update_timestamp(Some(now.max(min_timestamp)));
+                progress.attempts = block.proof;
+
+                on_tick(block, &progress);
+
+                // The Callback can add Transactions to the Body
+                header = BlockHeader::from_block(block)?;
+            }
+
+            header.proof = block.proof;
+
+            let block_hash = header.to_hash(context.hash_algorithm)?;
+
+            if is_hash_below(&block_hash, &target) {
+                println!("Hash (Proof: {}): '{}'", block.proof, block_hash);
+
+                break;
+            }
+
+            // The lowest Hash is the closest to the Target
+            if progress.best_hash.is_empty() || block_hash < progress.best_hash {
+                progress.best_hash = block_hash;
+            }
+
+            block.proof += 1;
+        }
+
+        Ok(())
+    }
+
+    /// Check that the Block was mined for a Target at most as high as the Target of
+    /// the Chain and that its Hash is below it.
+    fn verify_seal(&self, context: &SealContext, block: &Block) -> bool {
+        match (bits_to_target(block.bits), bits_to_target(context.bits)) {
+            (Some(target), Some(required)) if target <= required => {
+                let block_hash = match context.block_hash {
+                    Some(h) => Ok(h.to_owned()),
+                    None => block.to_hash_with(context.hash_algorithm),
+                };
+
+                matches!(block_hash, Ok(h) if is_hash_below(&h, &target))
+            }
+            _ => false,
+        }
+    }
+
+    /// Adopt the Chain with more cumulative Work.
+    fn choose_fork(&self, local: &[Block], candidate: &[Block]) -> bool {
+        get_chain_work(candidate) > get_chain_work(local)
+    }
+}
+
+//==============================================================================
+// Auxiliary Functions
+
+/// Check whether a hex encoded Block Hash is numerically at or below the Target
+/// which the compact `bits` encode.
+pub fn is_hash_valid(block_hash: &str, bits: u32) -> bool {
+    match bits_to_target(bits) {
+        Some(target) => is_hash_below(block_hash, &target),
+        None => false,
+    }
+}
+
+/// Compare a hex encoded 256 bit Block Hash numerically with a Target.
+pub fn is_hash_below(block_hash: &str, target: &BigUint) -> bool {
+    match BigUint::parse_bytes(block_hash.as_bytes(), 16) {
+        Some(h) => block_hash.len() == 64 && h <= *target,
+        None => false,
+    }
+}
+
+/// Decode a compact Target like Bitcoin's `nBits`.
+///
+/// The highest Byte is the Length of the Target in Bytes and the lower 3 Bytes
+/// are its most significant Bytes. Negative, zero and Targets above 256 bits
+/// are invalid.
+pub fn bits_to_target(bits: u32) -> Option<BigUint> {
+    let size = bits >> 24;
+    let mantissa = bits & 0x007f_ffff;
+
+    if bits & 0x0080_0000 != 0 || mantissa == 0 {
+        return None;
+    }
+
+    let target = if size <= 3 {
+        BigUint::from(mantissa >> (8 * (3 - size)))
+    } else {
+        BigUint::from(mantissa) << (8 * (size - 3) as usize)
+    };
+
+    if target.is_zero() || target.bits() > 256 {
+        return None;
+    }
+
+    Some(target)
+}
+
+/// Encode a Target in the compact Format.
+///
+/// Only the 3 most significant Bytes are kept. So the encoded Target can be
+/// slightly lower.
+pub fn target_to_bits(target: &BigUint) -> u32 {
+    if target.is_zero() {
+        return 0;
+    }
+
+    let mut size = target.to_bytes_be().len() as u32;
+    let mantissa = if size <= 3 {
+        target << (8 * (3 - size) as usize)
+    } else {
+        target >> (8 * (size - 3) as usize)
+    };
+    let mut mantissa = mantissa.iter_u32_digits().next().unwrap_or(0);
+
+    // The Sign Bit must stay clear
+    if mantissa & 0x0080_0000 != 0 {
+        mantissa >>= 8;
+        size += 1;
+    }
+
+    (size << 24) | mantissa
+}
+
+/// Compact Target which requires `difficulty` leading hex zeros in the Block Hash.
+pub fn difficulty_to_bits(difficulty: usize) -> u32 {
+    let zero_bits = (difficulty * 4).min(256);
+
+    target_to_bits(&((BigUint::one() << (256 - zero_bits)) - BigUint::one()))
+}
+
+/// Expected Number of Hashes to find a Block for the Target: `2^256 / (target + 1)`
+pub fn get_block_work(bits: u32) -> BigUint {
+    match bits_to_target(bits) {
+        Some(target) => (BigUint::one() << 256usize) / (target + BigUint::one()),
+        None => BigUint::zero(),
+    }
+}
+
+/// Cumulative Work of all Blocks of a Chain
+pub fn get_chain_work(chain: &[Block]) -> BigUint {
+    chain.iter().map(|b| get_block_work(b.bits)).sum()
+}
+
+//==============================================================================
+// Unit Tests
+
+#[test]
+fn compact_targets() {
+    // The Genesis Target of Bitcoin
+    let target = bits_to_target(0x1d00_ffff).unwrap();
+
+    assert_eq!(
+        target.to_str_radix(16),
+        "ffff0000000000000000000000000000000000000000000000000000"
+    );
+    assert_eq!(target_to_bits(&target), 0x1d00_ffff);
+    assert_eq!(get_block_work(0x1d00_ffff), BigUint::from(0x1_0001_0001u64));
+
+    // Negative, zero and oversized Targets are invalid
+    assert_eq!(bits_to_target(0x1d80_ffff), None);
+    assert_eq!(bits_to_target(0x1d00_0000), None);
+    assert_eq!(bits_to_target(0x2200_ffff), None);
+    assert_eq!(bits_to_target(0x0100_00ff), None);
+    assert_eq!(target_to_bits(&BigUint::from(0x80u32)), 0x0200_8000);
+
+    //-------------------------------------
+    // Leading Zeros only give Targets in 16x Steps
+
+    assert_eq!(difficulty_to_bits(4), 0x1f00_ffff);
+    assert_eq!(difficulty_to_bits(64), 0);
+    assert_eq!(
+        get_block_work(difficulty_to_bits(3)) / get_block_work(difficulty_to_bits(2)),
+        BigUint::from(16u32)
+    );
+    assert!(is_hash_valid(
+        &format!("0000fffe{}", "f".repeat(56)),
+        0x1f00_ffff
+    ));
+    assert!(!is_hash_valid(
+        &format!("0000ffff{}", "f".repeat(56)),
+        0x1f00_ffff
+    ));
+    assert!(!is_hash_valid(
+        &format!("0001{}", "0".repeat(60)),
+        0x1f00_ffff
+    ));
+    assert!(!is_hash_valid("0000", 0x1f00_ffff));
+    assert!(is_hash_valid(
+        &format!("00008{}", "0".repeat(59)),
+        0x1f00_8000
+    ));
+    assert!(!is_hash_valid(
+        &format!("00008{}", "1".repeat(59)),
+        0x1f00_8000
+    ));
+}
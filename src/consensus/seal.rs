@@ -0,0 +1,222 @@
+/*
+* @author Bodo (Hugo) Barwich
+* @version 2026-10-18
+* @package Blockchain Exercise
+* @subpackage Block Signatures
+
+* This Module signs the Headers of Blocks for the Engines whose Validators seal
+* the Blocks with their Keys and defines the Evidence of Double Signatures
+*
+*---------------------------------
+* Requirements:
+* - The Rust Crate "ed25519-dalek" must be installed
+*/
+
+use ed25519_dalek::{Signature, Signer, SigningKey, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use sha256::digest;
+
+use super::poa::ValidatorVote;
+use crate::model::blockchain::Block;
+use crate::model::canonical;
+
+//==============================================================================
+// Structure BlockSeal Declaration
+
+/// Structure for the Signature of the Validator which sealed a Block
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BlockSeal {
+    /// Hex encoded Ed25519 Public Key of the Validator
+    pub signer: String,
+    /// Hex encoded Signature of the `SealHeader` of the Block
+    pub signature: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vote: Option<ValidatorVote>,
+}
+
+/// Structure for the Fields of a Block which the Validator signs
+///
+/// The Content of the Block is only covered by its Hash so that a Header
+/// can be given as Evidence without the Block.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SealHeader {
+    pub index: u64,
+    pub timestamp: u32,
+    pub proof: u64,
+    pub previous_hash: String,
+    /// Hash of the `Transaction`s and the Evidence of the Block
+    pub content_hash: String,
+    pub signer: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vote: Option<ValidatorVote>,
+}
+
+/// Structure for a `SealHeader` with its Signature
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SignedHeader {
+    #[serde(flatten)]
+    pub header: SealHeader,
+    pub signature: String,
+}
+
+/// Structure for the Proof that a Validator signed two different Blocks at the same Index
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DoubleSignEvidence {
+    pub first: SignedHeader,
+    pub second: SignedHeader,
+}
+
+//==============================================================================
+// Structure SealHeader Implementation
+
+impl SealHeader {
+    /*----------------------------------------------------------------------------
+     * Constructors
+     */
+
+    /// Extract the Header of a sealed Block.
+    pub fn from_block(block: &Block) -> Option<Self> {
+        let seal = block.seal.as_ref()?;
+        let content_json =
+            canonical::to_canonical_json(&(&block.transactions, &block.evidence)).ok()?;
+
+        Some(Self {
+            index: block.index,
+            timestamp: block.timestamp,
+            proof: block.proof,
+            previous_hash: block.previous_hash.clone(),
+            content_hash: digest(content_json),
+            signer: seal.signer.clone(),
+            vote: seal.vote.clone(),
+        })
+    }
+
+    /*----------------------------------------------------------------------------
+     * Consultation Methods
+     */
+
+    /// # Returns:
+    /// - `None` if the Header cannot be encoded
+    ///
+    pub fn to_hash(&self) -> Option<String> {
+        canonical::to_canonical_bytes(self).ok().map(digest)
+    }
+}
+
+//==============================================================================
+// Structure SignedHeader Implementation
+
+impl SignedHeader {
+    /*----------------------------------------------------------------------------
+     * Constructors
+     */
+
+    pub fn from_block(block: &Block) -> Option<Self> {
+        Some(Self {
+            header: SealHeader::from_block(block)?,
+            signature: block.seal.as_ref()?.signature.clone(),
+        })
+    }
+
+    /*----------------------------------------------------------------------------
+     * Consultation Methods
+     */
+
+    pub fn is_valid(&self) -> bool {
+        self.header
+            .to_hash()
+            .is_some_and(|h| is_signature_valid(&self.header.signer, &self.signature, &h))
+    }
+}
+
+//==============================================================================
+// Structure DoubleSignEvidence Implementation
+
+impl DoubleSignEvidence {
+    /*----------------------------------------------------------------------------
+     * Constructors
+     */
+
+    /// Build the Evidence from two Blocks which the same Validator sealed at the same Index.
+    pub fn from_blocks(first: &Block, second: &Block) -> Option<Self> {
+        let evidence = Self {
+            first: SignedHeader::from_block(first)?,
+            second: SignedHeader::from_block(second)?,
+        };
+
+        evidence.is_valid().then_some(evidence)
+    }
+
+    /*----------------------------------------------------------------------------
+     * Consultation Methods
+     */
+
+    /// Check that both Headers are signed by the same Validator at the same Index
+    /// and that they differ.
+    pub fn is_valid(&self) -> bool {
+        self.first.header.index == self.second.header.index
+            && self.first.header.signer == self.second.header.signer
+            && matches!(
+                (self.first.header.to_hash(), self.second.header.to_hash()),
+                (Some(first), Some(second)) if first != second
+            )
+            && self.first.is_valid()
+            && self.second.is_valid()
+    }
+
+    /// Hex encoded Public Key of the Validator which signed twice
+    pub fn get_signer(&self) -> &str {
+        &self.first.header.signer
+    }
+
+    pub fn get_index(&self) -> u64 {
+        self.first.header.index
+    }
+}
+
+//==============================================================================
+// Auxiliary Functions
+
+/// Seal a Block with the Signature of its `SealHeader`.
+pub fn sign_block(block: &mut Block, signing_key: &SigningKey, vote: Option<ValidatorVote>) {
+    block.seal = Some(BlockSeal {
+        signer: hex::encode(signing_key.verifying_key().as_bytes()),
+        signature: String::new(),
+        vote,
+    });
+
+    if let Some(hash) = SealHeader::from_block(block).and_then(|h| h.to_hash()) {
+        let signature = signing_key.sign(hash.as_bytes());
+
+        if let Some(seal) = block.seal.as_mut() {
+            seal.signature = hex::encode(signature.to_bytes());
+        }
+    }
+}
+
+/// Check that the Block is sealed with a valid Signature of its Signer.
+pub fn is_block_signed(block: &Block) -> bool {
+    SignedHeader::from_block(block).is_some_and(|h| h.is_valid())
+}
+
+pub fn decode_public_key(public_key: &str) -> Option<VerifyingKey> {
+    hex::decode(public_key)
+        .ok()
+        .and_then(|k| <[u8; 32]>::try_from(k).ok())
+        .and_then(|k| VerifyingKey::from_bytes(&k).ok())
+}
+
+fn is_signature_valid(public_key: &str, signature: &str, payload: &str) -> bool {
+    let public_key = match decode_public_key(public_key) {
+        Some(k) => k,
+        None => return false,
+    };
+
+    match hex::decode(signature)
+        .ok()
+        .and_then(|s| Signature::from_slice(&s).ok())
+    {
+        Some(s) => public_key.verify_strict(payload.as_bytes(), &s).is_ok(),
+        None => false,
+    }
+}
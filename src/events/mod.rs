@@ -0,0 +1,216 @@
+/*
+* @author Bodo (Hugo) Barwich
+* @version 2026-10-18
+* @package Blockchain Exercise
+* @subpackage Blockchain Event Hub
+
+* This Module defines the Actor that fans out Blockchain Events to all Subscribers
+*
+*---------------------------------
+* Requirements:
+* - The Rust Crate "actix" must be installed
+*/
+
+pub mod sse;
+pub mod websocket;
+
+use actix::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+use crate::model::blockchain::{Block, MiningProgress};
+use crate::model::transaction::Transaction;
+
+//==============================================================================
+// Structure ChainEvent Declaration
+
+/// Topics a Subscriber can subscribe to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Topic {
+    NewBlock,
+    NewPendingTransaction,
+    ChainReorg,
+    MiningProgress,
+}
+
+/// Events published to the `EventHub`
+#[derive(Debug, Clone, Serialize, Deserialize, Message)]
+#[rtype(result = "()")]
+#[serde(tag = "topic", content = "data", rename_all = "camelCase")]
+pub enum ChainEvent {
+    NewBlock(Block),
+    NewPendingTransaction(Box<Transaction>),
+    ChainReorg {
+        fork_index: u64,
+        previous_height: u64,
+        height: u64,
+        orphaned_blocks: Vec<Block>,
+        adopted_blocks: Vec<Block>,
+    },
+    MiningProgress(MiningProgress),
+}
+
+//==============================================================================
+// Hub Messages Declaration
+
+/// Register a new Subscriber without any Topics
+#[derive(Message)]
+#[rtype(result = "usize")]
+pub struct Connect {
+    pub recipient: Recipient<ChainEvent>,
+}
+
+/// Remove a Subscriber
+#[derive(Debug, Message)]
+#[rtype(result = "()")]
+pub struct Disconnect {
+    pub id: usize,
+}
+
+/// Add or remove Topics for a Subscriber
+#[derive(Debug, Message)]
+#[rtype(result = "()")]
+pub struct Subscription {
+    pub id: usize,
+    pub topics: Vec<Topic>,
+    pub subscribe: bool,
+}
+
+//==============================================================================
+// Structure EventHub Declaration
+
+/// Structure for distributing the `ChainEvent`s to the Subscribers
+// Define actor
+#[derive(Default)]
+pub struct EventHub {
+    subscribers: HashMap<usize, Subscriber>,
+    next_id: usize,
+}
+
+struct Subscriber {
+    recipient: Recipient<ChainEvent>,
+    topics: HashSet<Topic>,
+}
+
+//==============================================================================
+// Structure ChainEvent Implementation
+
+impl ChainEvent {
+    /*----------------------------------------------------------------------------
+     * Consultation Methods
+     */
+
+    pub fn get_topic(&self) -> Topic {
+        match self {
+            ChainEvent::NewBlock(_) => Topic::NewBlock,
+            ChainEvent::NewPendingTransaction(_) => Topic::NewPendingTransaction,
+            ChainEvent::ChainReorg { .. } => Topic::ChainReorg,
+            ChainEvent::MiningProgress(_) => Topic::MiningProgress,
+        }
+    }
+}
+
+//==============================================================================
+// Structure EventHub Implementation
+
+impl EventHub {
+    /*----------------------------------------------------------------------------
+     * Constructors
+     */
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /*----------------------------------------------------------------------------
+     * Consultation Methods
+     */
+
+    pub fn get_subscriber_count(&self) -> usize {
+        self.subscribers.len()
+    }
+}
+
+impl Actor for EventHub {
+    type Context = Context<Self>;
+
+    fn started(&mut self, _ctx: &mut Self::Context) {
+        println!("Event Hub Actor is alive");
+    }
+}
+
+impl Handler<Connect> for EventHub {
+    type Result = usize;
+
+    fn handle(&mut self, msg: Connect, _ctx: &mut Self::Context) -> Self::Result {
+        self.next_id += 1;
+
+        self.subscribers.insert(
+            self.next_id,
+            Subscriber {
+                recipient: msg.recipient,
+                topics: HashSet::new(),
+            },
+        );
+
+        self.next_id
+    }
+}
+
+impl Handler<Disconnect> for EventHub {
+    type Result = ();
+
+    fn handle(&mut self, msg: Disconnect, _ctx: &mut Self::Context) {
+        self.subscribers.remove(&msg.id);
+    }
+}
+
+impl Handler<Subscription> for EventHub {
+    type Result = ();
+
+    fn handle(&mut self, msg: Subscription, _ctx: &mut Self::Context) {
+        if let Some(subscriber) = self.subscribers.get_mut(&msg.id) {
+            for topic in msg.topics {
+                if msg.subscribe {
+                    subscriber.topics.insert(topic);
+                } else {
+                    subscriber.topics.remove(&topic);
+                }
+            }
+        }
+    }
+}
+
+/// Fan out a published Event to all Subscribers of its Topic
+impl Handler<ChainEvent> for EventHub {
+    type Result = ();
+
+    fn handle(&mut self, msg: ChainEvent, _ctx: &mut Self::Context) {
+        let topic = msg.get_topic();
+        let mut gone = Vec::<usize>::new();
+
+        for (id, subscriber) in self.subscribers.iter() {
+            if subscriber.topics.contains(&topic)
+                && subscriber.recipient.do_send(msg.clone()).is_err()
+            {
+                gone.push(*id);
+            }
+        }
+
+        // Subscribers which stopped are removed
+        gone.iter().for_each(|id| {
+            self.subscribers.remove(id);
+        });
+    }
+}
+
+//==============================================================================
+// Auxiliary Functions
+
+/// Publish an Event if an `EventHub` is configured
+pub fn publish(hub: Option<&Addr<EventHub>>, event: ChainEvent) {
+    if let Some(h) = hub {
+        h.do_send(event);
+    }
+}
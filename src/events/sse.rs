@@ -0,0 +1,171 @@
+/*
+* @author Bodo (Hugo) Barwich
+* @version 2026-10-18
+* @package Blockchain Exercise
+* @subpackage Blockchain Event Stream
+
+* This Module defines the Server-Sent Events Stream which reports the Progress
+* of the Mining Process
+*
+*---------------------------------
+* Requirements:
+* - The Rust Crate "actix" must be installed
+* - The Rust Crate "futures-channel" must be installed
+*/
+
+use actix::prelude::*;
+use actix_web::{web, Error, HttpResponse};
+use futures_channel::mpsc::{unbounded, UnboundedSender};
+use std::time::Duration;
+
+use super::{ChainEvent, Connect, Disconnect, EventHub, Subscription, Topic};
+
+const KEEP_ALIVE_INTERVAL: Duration = Duration::from_secs(15);
+
+//==============================================================================
+// Structure SseClient Declaration
+
+/// Structure for a Server-Sent Events Client
+///
+/// The Client receives the `miningProgress` Events while a Block is mined and
+/// the `newBlock` Event with the final mined Block.
+// Define actor
+pub struct SseClient {
+    id: usize,
+    hub: Addr<EventHub>,
+    sender: UnboundedSender<Result<web::Bytes, Error>>,
+}
+
+//==============================================================================
+// Structure SseClient Implementation
+
+impl SseClient {
+    /*----------------------------------------------------------------------------
+     * Constructors
+     */
+
+    pub fn new(hub: Addr<EventHub>, sender: UnboundedSender<Result<web::Bytes, Error>>) -> Self {
+        Self { id: 0, hub, sender }
+    }
+
+    /*----------------------------------------------------------------------------
+     * Administration Methods
+     */
+
+    /// Send a Frame to the Client. The Client is stopped when it disconnected.
+    fn send_frame(&mut self, frame: String, ctx: &mut Context<Self>) {
+        if self
+            .sender
+            .unbounded_send(Ok(web::Bytes::from(frame)))
+            .is_err()
+        {
+            ctx.stop();
+        }
+    }
+}
+
+impl Actor for SseClient {
+    type Context = Context<Self>;
+
+    fn started(&mut self, ctx: &mut Self::Context) {
+        self.hub
+            .send(Connect {
+                recipient: ctx.address().recipient(),
+            })
+            .into_actor(self)
+            .then(|res, act, ctx| {
+                match res {
+                    Ok(id) => {
+                        act.id = id;
+                        act.hub.do_send(Subscription {
+                            id,
+                            topics: vec![Topic::MiningProgress, Topic::NewBlock],
+                            subscribe: true,
+                        });
+                    }
+                    Err(e) => {
+                        eprintln!("SSE Client: Hub Registration failed: {:?}", e);
+                        ctx.stop();
+                    }
+                }
+
+                fut::ready(())
+            })
+            .wait(ctx);
+
+        // Comments keep the Connection alive and detect disconnected Clients
+        ctx.run_interval(KEEP_ALIVE_INTERVAL, |act, ctx| {
+            act.send_frame(String::from(": keep-alive\n\n"), ctx);
+        });
+    }
+
+    fn stopping(&mut self, _ctx: &mut Self::Context) -> Running {
+        self.hub.do_send(Disconnect { id: self.id });
+
+        Running::Stop
+    }
+}
+
+/// Forward the Events from the Hub to the Client
+impl Handler<ChainEvent> for SseClient {
+    type Result = ();
+
+    fn handle(&mut self, msg: ChainEvent, ctx: &mut Self::Context) {
+        match to_event_frame(&msg) {
+            Ok(frame) => self.send_frame(frame, ctx),
+            Err(e) => eprintln!("SSE Client: JSON formatting failed: {:?}", e),
+        }
+    }
+}
+
+//==============================================================================
+// Server-Sent Events Handler
+
+/// Handler to open the Server-Sent Events Stream of the Mining Progress
+pub async fn dispatch_mining_events(hub: web::Data<Addr<EventHub>>) -> HttpResponse {
+    let (sender, receiver) = unbounded::<Result<web::Bytes, Error>>();
+
+    // Send the Headers to the Client right away
+    let _ = sender.unbounded_send(Ok(web::Bytes::from_static(b": connected\n\n")));
+
+    SseClient::new(hub.get_ref().clone(), sender).start();
+
+    HttpResponse::Ok()
+        .content_type("text/event-stream")
+        .header("Cache-Control", "no-cache")
+        .streaming(receiver)
+}
+
+//==============================================================================
+// Auxiliary Functions
+
+/// Format an Event as Server-Sent Events Frame with the Topic as Event Name
+pub fn to_event_frame(event: &ChainEvent) -> Result<String, serde_json::Error> {
+    let event_json = serde_json::to_value(event)?;
+    let topic = event_json["topic"].as_str().unwrap_or("message").to_owned();
+
+    Ok(format!(
+        "event: {}\ndata: {}\n\n",
+        topic,
+        serde_json::to_string(&event_json["data"])?
+    ))
+}
+
+//==============================================================================
+// Unit Tests
+
+#[test]
+fn mining_progress_event_frame() {
+    use crate::model::blockchain::MiningProgress;
+
+    let frame = to_event_frame(&ChainEvent::MiningProgress(MiningProgress {
+        block_index: 2,
+        attempts: 1000,
+        ..Default::default()
+    }))
+    .unwrap();
+
+    assert!(frame.starts_with("event: miningProgress\ndata: {"));
+    assert!(frame.contains("\"attempts\":1000"));
+    assert!(frame.ends_with("\n\n"));
+}
@@ -0,0 +1,173 @@
+/*
+* @author Bodo (Hugo) Barwich
+* @version 2026-10-18
+* @package Blockchain Exercise
+* @subpackage Blockchain Event WebSocket
+
+* This Module defines the WebSocket Session Actor which forwards the subscribed
+* Blockchain Events to the Client
+*
+*---------------------------------
+* Requirements:
+* - The Rust Crate "actix" must be installed
+* - The Rust Crate "actix-web-actors" must be installed
+*/
+
+use actix::prelude::*;
+use actix_web::{web, Error, HttpRequest, HttpResponse};
+use actix_web_actors::ws;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use super::{ChainEvent, Connect, Disconnect, EventHub, Subscription, Topic};
+
+//==============================================================================
+// Structure SubscriptionRequest Declaration
+
+/// Structure for the Subscription Requests sent by the Client
+///
+/// ```json
+/// {"action": "subscribe", "topics": ["newBlock", "chainReorg"]}
+/// ```
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SubscriptionRequest {
+    pub action: String,
+    pub topics: Vec<Topic>,
+}
+
+//==============================================================================
+// Structure WsSession Declaration
+
+/// Structure for a WebSocket Client Session
+// Define actor
+pub struct WsSession {
+    id: usize,
+    hub: Addr<EventHub>,
+}
+
+//==============================================================================
+// Structure WsSession Implementation
+
+impl WsSession {
+    /*----------------------------------------------------------------------------
+     * Constructors
+     */
+
+    pub fn new(hub: Addr<EventHub>) -> Self {
+        Self { id: 0, hub }
+    }
+
+    /*----------------------------------------------------------------------------
+     * Administration Methods
+     */
+
+    fn handle_request(&mut self, text: &str, ctx: &mut ws::WebsocketContext<Self>) {
+        match serde_json::from_str::<SubscriptionRequest>(text) {
+            Ok(rq) => {
+                let subscribe = match rq.action.as_str() {
+                    "subscribe" => true,
+                    "unsubscribe" => false,
+                    _ => {
+                        ctx.text(
+                            json!({"status": "error", "report": "Unknown action"}).to_string(),
+                        );
+                        return;
+                    }
+                };
+
+                self.hub.do_send(Subscription {
+                    id: self.id,
+                    topics: rq.topics.clone(),
+                    subscribe,
+                });
+
+                ctx.text(
+                    json!({
+                        "status": if subscribe { "subscribed" } else { "unsubscribed" },
+                        "topics": rq.topics,
+                    })
+                    .to_string(),
+                );
+            }
+            Err(e) => {
+                ctx.text(
+                    json!({"status": "error", "report": format!("Invalid Request: {}", e)})
+                        .to_string(),
+                );
+            }
+        }
+    }
+}
+
+impl Actor for WsSession {
+    type Context = ws::WebsocketContext<Self>;
+
+    fn started(&mut self, ctx: &mut Self::Context) {
+        // Register at the Hub to receive the Events
+        self.hub
+            .send(Connect {
+                recipient: ctx.address().recipient(),
+            })
+            .into_actor(self)
+            .then(|res, act, ctx| {
+                match res {
+                    Ok(id) => act.id = id,
+                    Err(e) => {
+                        eprintln!("WebSocket Session: Hub Registration failed: {:?}", e);
+                        ctx.stop();
+                    }
+                }
+
+                fut::ready(())
+            })
+            .wait(ctx);
+    }
+
+    fn stopping(&mut self, _ctx: &mut Self::Context) -> Running {
+        self.hub.do_send(Disconnect { id: self.id });
+
+        Running::Stop
+    }
+}
+
+/// Forward the Events from the Hub to the Client
+impl Handler<ChainEvent> for WsSession {
+    type Result = ();
+
+    fn handle(&mut self, msg: ChainEvent, ctx: &mut Self::Context) {
+        match serde_json::to_string(&msg) {
+            Ok(j) => ctx.text(j),
+            Err(e) => eprintln!("WebSocket Session: JSON formatting failed: {:?}", e),
+        }
+    }
+}
+
+impl StreamHandler<Result<ws::Message, ws::ProtocolError>> for WsSession {
+    fn handle(&mut self, msg: Result<ws::Message, ws::ProtocolError>, ctx: &mut Self::Context) {
+        match msg {
+            Ok(ws::Message::Ping(msg)) => ctx.pong(&msg),
+            Ok(ws::Message::Text(text)) => self.handle_request(&text, ctx),
+            Ok(ws::Message::Close(reason)) => {
+                ctx.close(reason);
+                ctx.stop();
+            }
+            Ok(_) => (),
+            Err(e) => {
+                eprintln!("WebSocket Session: Protocol Error: {:?}", e);
+                ctx.stop();
+            }
+        }
+    }
+}
+
+//==============================================================================
+// WebSocket Handler
+
+/// Handler to open a WebSocket Session for Event Subscriptions
+pub async fn dispatch_event_subscription(
+    req: HttpRequest,
+    stream: web::Payload,
+    hub: web::Data<Addr<EventHub>>,
+) -> Result<HttpResponse, Error> {
+    ws::start(WsSession::new(hub.get_ref().clone()), &req, stream)
+}
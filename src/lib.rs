@@ -18,25 +18,51 @@
 //#[macro_use]
 extern crate json;
 
+pub mod cli;
 pub mod config;
+pub mod consensus;
+pub mod events;
 pub mod miner;
 pub mod model;
-
-use actix::SyncArbiter;
+pub mod multisig;
+pub mod peers;
+pub mod rpc;
+pub mod script;
+pub mod storage;
+pub mod wallet;
+pub mod webhooks;
+
+use actix::{Actor, Addr, SyncArbiter};
 use actix_web::middleware::Logger;
-use actix_web::{error, web, App, Error, HttpResponse, HttpServer};
+use actix_web::{error, web, App, Error, HttpRequest, HttpResponse, HttpServer};
+use clap::Parser;
 use futures_util::StreamExt;
-use std::env;
 use std::sync::Mutex;
 
 use serde::{Deserialize, Serialize};
 
+use cli::Cli;
 use config::AppConfig;
+use config::{ConfigError, LayeredConfig};
+use consensus::poa::{ValidatorVote, VoteTally};
+use consensus::seal::DoubleSignEvidence;
+use consensus::ConsensusMode;
+use events::{ChainEvent, EventHub};
 use miner::{MinerLink, MiningWorker};
-use model::blockchain::Blockchain;
+use model::asset::{Asset, AssetHolder};
+use model::blockchain::{Block, Blockchain, LedgerError};
+use model::index::{Direction, TransactionStatus};
+use model::stake::Stake;
 use model::transaction::{MutexTransactionList, Transaction};
+use model::utxo::{OutPoint, TxOutput};
+use model::wire::{WireFormat, WireObject};
+use multisig::MultisigDrafts;
+use storage::ChainStore;
+use webhooks::WebhookRegistry;
 
 const MAX_SIZE: usize = 262_144; // max payload size is 256k
+const HISTORY_PAGE_SIZE: usize = 20;
+const HISTORY_MAX_PAGE_SIZE: usize = 100;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ResponseData {
@@ -46,6 +72,65 @@ pub struct ResponseData {
     pub description: String,
 }
 
+/// Structure for the Response to a queued Transaction
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TransactionResponseData {
+    pub txid: String,
+    #[serde(flatten)]
+    pub response: ResponseData,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TransactionStatusData {
+    pub txid: String,
+    #[serde(flatten)]
+    pub status: TransactionStatus,
+}
+
+/// Structure for an unspent Output of an Address
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UnspentOutputData {
+    #[serde(flatten)]
+    pub outpoint: OutPoint,
+    #[serde(flatten)]
+    pub output: TxOutput,
+}
+
+/// Structure for an issued Asset with the Addresses which hold it
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AssetData {
+    #[serde(flatten)]
+    pub asset: Asset,
+    pub holders: Vec<AssetHolder>,
+}
+
+/// Structure for the Validators of a Proof of Authority Chain and the Votes to change them
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ValidatorData {
+    pub validators: Vec<String>,
+    pub block_interval: u32,
+    /// Votes in the Chain for the Changes which did not pass yet
+    pub tallies: Vec<VoteTally>,
+    /// Changes this Node votes for in the Blocks it seals
+    pub proposals: Vec<ValidatorVote>,
+}
+
+/// Structure for the Stakes of a Proof of Stake Chain and the reported Double Signatures
+#[derive(Debug, Serialize, Deserialize)]
+pub struct StakingData {
+    pub stakes: Vec<Stake>,
+    pub total_stake: f64,
+    /// Double Signatures this Node reports in the next Block it seals
+    pub evidence: Vec<DoubleSignEvidence>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AddressHistoryQuery {
+    pub direction: Option<Direction>,
+    pub offset: Option<usize>,
+    pub limit: Option<usize>,
+}
+
 /// Handler to build the Home Page
 pub async fn dispatch_home_page() -> HttpResponse {
     //------------------------
@@ -62,8 +147,14 @@ pub async fn dispatch_home_page() -> HttpResponse {
 }
 
 /// Handler to add a Transaction to the Blockchain
+///
+/// The Transaction must be signed for its Sender Address. In Account Mode the Balance
+/// and the Nonce of the Sender are checked against the Chain and the waiting `Transaction`s.
+/// In UTXO Mode the Inputs are checked against the Chain and the waiting `Transaction`s.
 pub async fn add_transaction(
     transaction_mutex: web::Data<MutexTransactionList>,
+    blockchain_mutex: Option<web::Data<Mutex<Blockchain>>>,
+    event_hub: Option<web::Data<Addr<EventHub>>>,
     mut payload: web::Payload,
 ) -> Result<HttpResponse, Error> {
     // payload is a stream of Bytes objects
@@ -90,18 +181,48 @@ pub async fn add_transaction(
                 return Err(error::ErrorBadRequest("Transaction is invalid"));
             }
 
-            match transaction_mutex.add_transaction(request_transaction) {
+            if let Err(e) = check_ledger(
+                &request_transaction,
+                &transaction_mutex,
+                blockchain_mutex.as_ref(),
+            ) {
+                eprintln!("POST Transaction: {}", e.report);
+
+                return Err(error::ErrorBadRequest(e.report));
+            }
+
+            let txid = match request_transaction.to_hash() {
+                Ok(t) => t,
+                Err(e) => {
+                    eprintln!("POST Transaction: Transaction cannot be encoded! {}", e);
+
+                    return Err(error::ErrorBadRequest("Transaction is invalid"));
+                }
+            };
+
+            match transaction_mutex.add_transaction(request_transaction.clone()) {
                 Ok(_) => {
                     println!("Transactions: {:?}", transaction_mutex);
 
+                    events::publish(
+                        event_hub.as_ref().map(|h| h.get_ref()),
+                        ChainEvent::NewPendingTransaction(Box::new(request_transaction)),
+                    );
+
                     //------------------------
                     // Success Notfication
 
-                    Ok(HttpResponse::Created().json(ResponseData {
-                        title: String::from("Actix Blockchain API - Success"),
-                        statuscode: 201,
-                        page: String::from("Add Transaction"),
-                        description: format!("Transactions: Transaction is queued for next block"),
+                    Ok(HttpResponse::Created().json(TransactionResponseData {
+                        response: ResponseData {
+                            title: String::from("Actix Blockchain API - Success"),
+                            statuscode: 201,
+                            page: String::from("Add Transaction"),
+                            description: format!(
+                                "Transactions: Transaction '{}' is queued for next block",
+                                txid
+                            ),
+                        },
+                        txid,
                     }))
                 }
                 Err(e) => {
@@ -128,6 +249,141 @@ pub async fn add_transaction(
     }
 }
 
+/// Send a Value as JSON or in the binary Format which the `Accept` Header requests.
+///
+/// The Response varies with the `Accept` Header so that Caches keep the Formats apart.
+fn respond_with<T: WireObject>(request: &HttpRequest, value: &T) -> Result<HttpResponse, Error> {
+    let accept = request
+        .headers()
+        .get("accept")
+        .and_then(|h| h.to_str().ok())
+        .unwrap_or("");
+    let format = WireFormat::from_accept(accept).ok_or_else(|| {
+        error::ErrorNotAcceptable(format!(
+            "Wire Format: Accept Header '{}' refuses all Formats",
+            accept
+        ))
+    })?;
+    let body = format
+        .encode(value)
+        .map_err(|e| error::ErrorInternalServerError(format!("Wire Format: {}", e.report)))?;
+
+    Ok(HttpResponse::Ok()
+        .content_type(format.get_mime_type())
+        .header("Vary", "Accept")
+        .body(body))
+}
+
+/// Handler to export all Blocks of the Chain
+pub async fn dispatch_chain(
+    request: HttpRequest,
+    blockchain_mutex: web::Data<Mutex<Blockchain>>,
+) -> Result<HttpResponse, Error> {
+    let chain = match blockchain_mutex.lock() {
+        Ok(guard) => guard.chain.clone(),
+        Err(e) => {
+            return Err(error::ErrorInternalServerError(format!(
+                "Blockchain: Mutex Lock failed! Message: {:?}",
+                e
+            )))
+        }
+    };
+
+    respond_with(&request, &chain)
+}
+
+/// Handler to look up a Block by its Index or its Hash
+pub async fn dispatch_block(
+    request: HttpRequest,
+    blockchain_mutex: web::Data<Mutex<Blockchain>>,
+    path: web::Path<String>,
+) -> Result<HttpResponse, Error> {
+    let block = match blockchain_mutex.lock() {
+        Ok(guard) => match path.parse::<u64>() {
+            Ok(i) => guard.get_block(i).cloned(),
+            Err(_) => guard.get_block_by_hash(&path).cloned(),
+        },
+        Err(e) => {
+            return Err(error::ErrorInternalServerError(format!(
+                "Blockchain: Mutex Lock failed! Message: {:?}",
+                e
+            )))
+        }
+    };
+
+    match block {
+        Some(b) => respond_with(&request, &b),
+        None => Err(error::ErrorNotFound(format!(
+            "Block ({}): Block does not exist",
+            *path
+        ))),
+    }
+}
+
+/// Handler to look up a confirmed Transaction by its Transaction Id
+pub async fn dispatch_transaction(
+    request: HttpRequest,
+    blockchain_mutex: web::Data<Mutex<Blockchain>>,
+    path: web::Path<String>,
+) -> Result<HttpResponse, Error> {
+    let transaction = match blockchain_mutex.lock() {
+        Ok(guard) => guard.get_transaction(&path).cloned(),
+        Err(e) => {
+            return Err(error::ErrorInternalServerError(format!(
+                "Blockchain: Mutex Lock failed! Message: {:?}",
+                e
+            )))
+        }
+    };
+
+    match transaction {
+        Some(t) => respond_with(&request, &t),
+        None => Err(error::ErrorNotFound(format!(
+            "Transaction ({}): Transaction is not confirmed",
+            *path
+        ))),
+    }
+}
+
+/// Handler to look up the Status of a Transaction by its Transaction Id
+pub async fn dispatch_transaction_status(
+    blockchain_mutex: web::Data<Mutex<Blockchain>>,
+    transaction_mutex: web::Data<MutexTransactionList>,
+    path: web::Path<String>,
+) -> Result<HttpResponse, Error> {
+    let txid = path.into_inner();
+    // The Blockchain is locked first so that Transactions which are being mined
+    // are found in the Chain once the Block is complete
+    let blockchain = blockchain_mutex.lock().map_err(|e| {
+        error::ErrorInternalServerError(format!("Blockchain: Mutex Lock failed! Message: {:?}", e))
+    })?;
+    let status = blockchain.get_transaction_status(txid.as_str(), &transaction_mutex);
+
+    Ok(HttpResponse::Ok().json(TransactionStatusData { txid, status }))
+}
+
+/// Handler to list the Transactions of an Address page by page
+pub async fn dispatch_address_transactions(
+    blockchain_mutex: web::Data<Mutex<Blockchain>>,
+    path: web::Path<String>,
+    query: web::Query<AddressHistoryQuery>,
+) -> Result<HttpResponse, Error> {
+    let blockchain = blockchain_mutex.lock().map_err(|e| {
+        error::ErrorInternalServerError(format!("Blockchain: Mutex Lock failed! Message: {:?}", e))
+    })?;
+    let history = blockchain.get_address_history(
+        path.as_str(),
+        query.direction.unwrap_or(Direction::All),
+        query.offset.unwrap_or(0),
+        query
+            .limit
+            .unwrap_or(HISTORY_PAGE_SIZE)
+            .min(HISTORY_MAX_PAGE_SIZE),
+    );
+
+    Ok(HttpResponse::Ok().json(history))
+}
+
 /// This Handler reads the Request and parses it into EmailData object with serde
 pub async fn dispatch_mining_request(link: web::Data<MinerLink>) -> Result<HttpResponse, Error> {
     match miner::mine_block(&link).await {
@@ -145,61 +401,473 @@ pub async fn dispatch_mining_request(link: web::Data<MinerLink>) -> Result<HttpR
     }
 }
 
+/// Handler to replace the local Chain with a longer valid Chain from another Node
+///
+/// Only Administrators may push a Chain. Nodes normally adopt Chains by polling their Peers.
+pub async fn replace_chain(
+    request: HttpRequest,
+    blockchain_mutex: web::Data<Mutex<Blockchain>>,
+    transaction_mutex: web::Data<MutexTransactionList>,
+    app_config: Option<web::Data<AppConfig>>,
+    event_hub: Option<web::Data<Addr<EventHub>>>,
+    store: Option<web::Data<ChainStore>>,
+    chain: web::Json<Vec<Block>>,
+) -> Result<HttpResponse, Error> {
+    check_admin(&request, app_config.as_ref().map(|c| c.get_ref()))?;
+
+    let mut blockchain = blockchain_mutex.lock().map_err(|e| {
+        error::ErrorInternalServerError(format!("Blockchain: Mutex Lock failed! Message: {:?}", e))
+    })?;
+
+    match peers::adopt_chain(
+        &mut blockchain,
+        &transaction_mutex,
+        event_hub.as_ref().map(|h| h.get_ref()),
+        store.as_ref().map(|s| s.get_ref()),
+        chain.into_inner(),
+    ) {
+        Some(r) => Ok(HttpResponse::Ok().json(ResponseData {
+            title: String::from("Actix Blockchain API - Success"),
+            statuscode: 200,
+            page: String::from("Replace Chain"),
+            description: format!(
+                "Blockchain: Chain was replaced from Block {} on (Height: {} -> {}); {} Transactions are pending again",
+                r.fork_index, r.previous_height, r.height, r.requeued_count
+            ),
+        })),
+        None => Ok(HttpResponse::Ok().json(ResponseData {
+            title: String::from("Actix Blockchain API - Success"),
+            statuscode: 200,
+            page: String::from("Replace Chain"),
+            description: String::from(
+                "Blockchain: Chain was kept because it is the longest valid Chain",
+            ),
+        })),
+    }
+}
+
+/// Handler to list the unspent Outputs of an Address in UTXO Mode
+pub async fn dispatch_address_outputs(
+    blockchain_mutex: web::Data<Mutex<Blockchain>>,
+    path: web::Path<String>,
+) -> Result<HttpResponse, Error> {
+    match blockchain_mutex.lock() {
+        Ok(guard) => {
+            let outputs: Vec<UnspentOutputData> = guard
+                .get_unspent(&path)
+                .into_iter()
+                .map(|(outpoint, output)| UnspentOutputData { outpoint, output })
+                .collect();
+
+            Ok(HttpResponse::Ok().json(outputs))
+        }
+        Err(e) => Err(error::ErrorInternalServerError(format!(
+            "Blockchain: Mutex Lock failed! Message: {:?}",
+            e
+        ))),
+    }
+}
+
+/// Handler to list the issued Assets
+pub async fn dispatch_assets(
+    blockchain_mutex: web::Data<Mutex<Blockchain>>,
+) -> Result<HttpResponse, Error> {
+    match blockchain_mutex.lock() {
+        Ok(guard) => {
+            let assets: Vec<Asset> = guard.get_assets().into_iter().cloned().collect();
+
+            Ok(HttpResponse::Ok().json(assets))
+        }
+        Err(e) => Err(error::ErrorInternalServerError(format!(
+            "Blockchain: Mutex Lock failed! Message: {:?}",
+            e
+        ))),
+    }
+}
+
+/// Handler to show an Asset with its Holders
+pub async fn dispatch_asset(
+    blockchain_mutex: web::Data<Mutex<Blockchain>>,
+    path: web::Path<String>,
+) -> Result<HttpResponse, Error> {
+    match blockchain_mutex.lock() {
+        Ok(guard) => match guard.get_asset(&path) {
+            Some(asset) => Ok(HttpResponse::Ok().json(AssetData {
+                asset: asset.clone(),
+                holders: guard.get_asset_holders(&path),
+            })),
+            None => Err(error::ErrorNotFound(format!(
+                "Asset ({}): Asset does not exist",
+                *path
+            ))),
+        },
+        Err(e) => Err(error::ErrorInternalServerError(format!(
+            "Blockchain: Mutex Lock failed! Message: {:?}",
+            e
+        ))),
+    }
+}
+
+/// Handler to list the Validators of a Proof of Authority Chain
+pub async fn dispatch_validators(
+    blockchain_mutex: web::Data<Mutex<Blockchain>>,
+) -> Result<HttpResponse, Error> {
+    match blockchain_mutex.lock() {
+        Ok(guard) => {
+            let validator_set = guard.get_validator_set();
+
+            Ok(HttpResponse::Ok().json(ValidatorData {
+                validators: validator_set.get_validators().to_vec(),
+                block_interval: guard.authority.block_interval,
+                tallies: validator_set.get_tallies(),
+                proposals: guard.get_proposals().to_vec(),
+            }))
+        }
+        Err(e) => Err(error::ErrorInternalServerError(format!(
+            "Blockchain: Mutex Lock failed! Message: {:?}",
+            e
+        ))),
+    }
+}
+
+/// Handler to vote for adding or removing a Validator in the Blocks this Node seals
+pub async fn propose_validator(
+    request: HttpRequest,
+    blockchain_mutex: web::Data<Mutex<Blockchain>>,
+    app_config: Option<web::Data<AppConfig>>,
+    vote: web::Json<ValidatorVote>,
+) -> Result<HttpResponse, Error> {
+    check_admin(&request, app_config.as_ref().map(|c| c.get_ref()))?;
+
+    let mut blockchain = blockchain_mutex.lock().map_err(|e| {
+        error::ErrorInternalServerError(format!("Blockchain: Mutex Lock failed! Message: {:?}", e))
+    })?;
+
+    if blockchain.consensus != ConsensusMode::ProofOfAuthority {
+        return Err(error::ErrorBadRequest(
+            "Proposal: Chain does not use Proof of Authority",
+        ));
+    }
+
+    match blockchain.propose_validator(vote.into_inner()) {
+        Ok(()) => Ok(HttpResponse::Ok().json(blockchain.get_proposals())),
+        Err(e) => Err(error::ErrorBadRequest(e.report)),
+    }
+}
+
+/// Handler to list the Stakes of a Proof of Stake Chain
+pub async fn dispatch_staking(
+    blockchain_mutex: web::Data<Mutex<Blockchain>>,
+) -> Result<HttpResponse, Error> {
+    match blockchain_mutex.lock() {
+        Ok(guard) => Ok(HttpResponse::Ok().json(StakingData {
+            stakes: guard.get_stakes().into_iter().cloned().collect(),
+            total_stake: guard.get_total_stake(),
+            evidence: guard.get_pending_evidence().to_vec(),
+        })),
+        Err(e) => Err(error::ErrorInternalServerError(format!(
+            "Blockchain: Mutex Lock failed! Message: {:?}",
+            e
+        ))),
+    }
+}
+
+/// Handler to report a Validator which signed two different Blocks at the same Height
+///
+/// Only Administrators may report Evidence. Both Headers must carry valid Signatures of
+/// the staked Validator. Its Stake is only burnt once the Block with the Evidence is applied.
+pub async fn report_evidence(
+    request: HttpRequest,
+    blockchain_mutex: web::Data<Mutex<Blockchain>>,
+    app_config: Option<web::Data<AppConfig>>,
+    evidence: web::Json<DoubleSignEvidence>,
+) -> Result<HttpResponse, Error> {
+    check_admin(&request, app_config.as_ref().map(|c| c.get_ref()))?;
+
+    let mut blockchain = blockchain_mutex.lock().map_err(|e| {
+        error::ErrorInternalServerError(format!("Blockchain: Mutex Lock failed! Message: {:?}", e))
+    })?;
+
+    match blockchain.report_evidence(evidence.into_inner()) {
+        Ok(()) => Ok(HttpResponse::Ok().json(blockchain.get_pending_evidence())),
+        Err(e) => Err(error::ErrorBadRequest(e.report)),
+    }
+}
+
+/// Check a new `Transaction` against the Ledger of the Chain.
+///
+/// Without the Chain the Balance and the Nonce of the Sender are unknown so that
+/// no `Transaction` is accepted.
+fn check_ledger(
+    transaction: &Transaction,
+    transaction_mutex: &MutexTransactionList,
+    blockchain_mutex: Option<&web::Data<Mutex<Blockchain>>>,
+) -> Result<(), LedgerError> {
+    match blockchain_mutex {
+        Some(b) => match b.lock() {
+            Ok(guard) => guard.check_transaction(transaction, transaction_mutex),
+            Err(e) => Err(LedgerError::new(
+                "failed",
+                &format!("Blockchain: Mutex Lock failed! Message: {:?}", e),
+            )),
+        },
+        None => Err(LedgerError::new(
+            "failed",
+            "Blockchain: Chain is not available to check the Ledger",
+        )),
+    }
+}
+
+/// Check that a Request may use the administrative Endpoints.
+///
+/// With a configured `admin_token` the Request must send it as Bearer Token.
+/// Otherwise only Clients on the same Host are admitted.
+pub fn check_admin(request: &HttpRequest, app_config: Option<&AppConfig>) -> Result<(), Error> {
+    let admin_token = app_config.map(|c| c.admin_token.as_str()).unwrap_or("");
+
+    if !admin_token.is_empty() {
+        let bearer = request
+            .headers()
+            .get("authorization")
+            .and_then(|h| h.to_str().ok())
+            .and_then(|h| h.strip_prefix("Bearer "))
+            .unwrap_or("");
+
+        return if is_token_equal(bearer.trim(), admin_token) {
+            Ok(())
+        } else {
+            Err(error::ErrorUnauthorized("Admin: Bearer Token is invalid"))
+        };
+    }
+
+    match request.peer_addr() {
+        Some(a) if a.ip().is_loopback() => Ok(()),
+        _ => Err(error::ErrorForbidden(
+            "Admin: Endpoint is only available to local Clients",
+        )),
+    }
+}
+
+/// Compare Tokens in constant Time
+fn is_token_equal(token: &str, expected: &str) -> bool {
+    token.len() == expected.len()
+        && token
+            .bytes()
+            .zip(expected.bytes())
+            .fold(0u8, |difference, (a, b)| difference | (a ^ b))
+            == 0
+}
+
 //==============================================================================
 // Executing Section
 
-#[actix_web::main]
-pub async fn main() -> std::io::Result<()> {
-    std::env::set_var("RUST_LOG", "actix_web=info");
-    env_logger::init();
+pub fn main() -> std::io::Result<()> {
+    cli::run(Cli::parse())
+}
 
-    let config = AppConfig::from_file();
+/// Launch the HTTP Server with the effective Configuration
+pub async fn serve(layered: LayeredConfig) -> std::io::Result<()> {
+    std::env::set_var("RUST_LOG", "actix_web=info");
+    let _ = env_logger::try_init();
 
-    println!("app config: {:?}", config);
+    println!("{}", layered.describe());
 
-    let component_name = match env::var("COMPONENT") {
-        Ok(comp) => comp,
-        Err(_) => "default".to_owned(),
-    };
+    let config = layered.config;
+    let component_name = config.component.clone();
+    let app_host = config.get_bind_address();
 
-    let mut app_host = String::from("127.0.0.1");
-    let app_port = match env::var("PORT") {
-        Ok(p) => p,
-        Err(_) => "3100".to_owned(),
-    };
+    // Without any Mining Worker the Mining Requests would never be answered
+    if config.miner_count == 0 {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!("Config: {}", ConfigError::ZeroMiners),
+        ));
+    }
 
-    app_host.push(':');
-    app_host.push_str(app_port.as_str());
+    // Mining Rewards would be lost without an Address to send them to
+    if config.reward_address.is_empty() {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!("Config: {}", ConfigError::MissingRewardAddress),
+        ));
+    }
 
     println!(
         "Blockchain API '{}': launching at {} ...",
         component_name, app_host
     );
 
-    let blockchain = web::Data::new(Mutex::new(Blockchain::new()));
-    let transactions = web::Data::new(MutexTransactionList::new());
+    let store = ChainStore::from_directory(&config.data_directory);
+    let mut chain = match store.as_ref().map(|s| s.load()) {
+        Some(Ok(Some(c))) => {
+            println!(
+                "Blockchain: {} Blocks loaded from {:?}",
+                c.chain.len(),
+                store.as_ref().map(|s| s.get_chain_file())
+            );
+            c
+        }
+        Some(Err(e)) => {
+            return Err(std::io::Error::new(
+                e.kind(),
+                format!("Blockchain: Chain could not be loaded! Message: {}", e),
+            ));
+        }
+        _ => Blockchain::new(),
+    };
+
+    if chain.chain.is_empty() {
+        chain.difficulty = config.difficulty;
+        chain.target_bits = config.get_target_bits();
+        chain.ledger_mode = config.ledger_mode;
+        chain.mining_reward = config.mining_reward;
+        chain.consensus = config.consensus;
+        chain.hash_algorithm = config.hash_algorithm;
+        chain.authority = config.get_authority_params();
+    } else {
+        if chain.ledger_mode != config.ledger_mode {
+            println!(
+                "Blockchain: Stored Chain keeps its Ledger Mode {:?}",
+                chain.ledger_mode
+            );
+        }
+
+        if chain.consensus != config.consensus {
+            println!(
+                "Blockchain: Stored Chain keeps its Consensus Engine {:?}",
+                chain.consensus
+            );
+        }
+
+        if chain.mining_reward != config.mining_reward {
+            println!(
+                "Blockchain: Stored Chain keeps its Mining Reward {}",
+                chain.mining_reward
+            );
+        }
+
+        if chain.hash_algorithm != config.hash_algorithm {
+            println!(
+                "Blockchain: Stored Chain keeps its Hash Algorithm {:?}",
+                chain.hash_algorithm
+            );
+        }
+
+        if chain.difficulty != config.difficulty || chain.target_bits != config.get_target_bits() {
+            println!(
+                "Blockchain: Stored Chain keeps its Difficulty {} (Target: {:08x})",
+                chain.difficulty,
+                chain.get_target_bits()
+            );
+        }
+    }
+
+    let validator_key = config.load_validator_key()?;
+    let slot_interval = match (chain.consensus, validator_key.as_ref()) {
+        (ConsensusMode::ProofOfAuthority, Some(_)) => Some(chain.authority.block_interval),
+        // Proof of Stake Rounds start with the Timestamp of the last Block
+        (ConsensusMode::ProofOfStake, Some(_)) => Some(1),
+        _ => None,
+    };
+
+    chain.set_validator_key(validator_key);
+    chain.set_reward_address(&config.reward_address);
+
+    // The Handlers check the Transactions against the Ledger Mode of the Chain
+    // and the Miners grant the Mining Reward of the Chain
+    let mut config = config;
+
+    config.ledger_mode = chain.ledger_mode;
+    config.mining_reward = chain.mining_reward;
+
+    // Mining Rewards of Blocks mined offline wait for the next Block
+    let pending = match store.as_ref().map(|s| s.load_pending()) {
+        Some(Ok(p)) => p
+            .into_iter()
+            .filter(|t| {
+                t.to_hash()
+                    .is_ok_and(|txid| chain.get_transaction(&txid).is_none())
+            })
+            .collect(),
+        Some(Err(e)) => {
+            eprintln!(
+                "Blockchain: Pending Transactions could not be loaded! Message: {}",
+                e
+            );
+            Vec::new()
+        }
+        None => Vec::new(),
+    };
+
+    let chain_hash_algorithm = chain.hash_algorithm;
+    let blockchain = web::Data::new(Mutex::new(chain));
+    let transactions = web::Data::new(MutexTransactionList::from_vec(pending));
+    let drafts = web::Data::new(MultisigDrafts::new());
+    let event_hub = EventHub::new().start();
+    let webhook_registry = WebhookRegistry::new(Some(event_hub.clone()))
+        .with_hash_algorithm(chain_hash_algorithm)
+        .start();
 
     //Clone the Blockchain and the Transaction Vector for the Mining Worker
     let worker_blockchain = blockchain.clone();
     let worker_transactions = transactions.clone();
+    let worker_hub = event_hub.clone();
+    let worker_store = store.clone();
+    let worker_config = config.clone();
 
-    //Create 2 Mining Worker Instances
+    //Create the configured number of Mining Worker Instances
     let miner = SyncArbiter::start(config.miner_count as usize, move || {
         // Each Worker needs a copy of the reference to the Blockchain Data and
         // the Transaction Vector
-        MiningWorker::with_data(worker_blockchain.clone(), worker_transactions.clone())
+        let mut worker =
+            MiningWorker::with_data(worker_blockchain.clone(), worker_transactions.clone());
+
+        worker.set_event_hub(worker_hub.clone());
+        worker.set_reward(&worker_config.reward_address, worker_config.mining_reward);
+
+        if let Some(s) = worker_store.as_ref() {
+            worker.set_store(s.clone());
+        }
+
+        worker
     });
     //Create 1 Mining Link Object
     let link = MinerLink::new(miner);
 
+    if let Some(block_interval) = slot_interval {
+        // Validators seal a Block in each of their Slots or Rounds
+        miner::start_slot_timer(link.clone(), block_interval);
+    }
+
+    if !config.get_peers().is_empty() {
+        peers::start_peer_sync(
+            config.get_peers(),
+            config.sync_interval,
+            blockchain.clone(),
+            transactions.clone(),
+            Some(event_hub.clone()),
+            store.clone(),
+        );
+    }
+
     HttpServer::new(move || {
         let app_config = web::Data::new(config.clone());
         let link_data = web::Data::new(link.clone());
+        let hub_data = web::Data::new(event_hub.clone());
+        let registry_data = web::Data::new(webhook_registry.clone());
+        let mut app = App::new();
 
-        App::new()
-            .app_data(blockchain.clone())
+        if let Some(s) = store.as_ref() {
+            // Chains adopted from other Nodes are stored as well
+            app = app.app_data(web::Data::new(s.clone()));
+        }
+
+        app.app_data(blockchain.clone())
             .app_data(transactions.clone())
+            .app_data(drafts.clone())
             .app_data(link_data)
+            .app_data(hub_data)
+            .app_data(registry_data)
             .app_data(web::JsonConfig::default().limit(MAX_SIZE)) // <- limit size of the payload (global configuration)
             .service(
                 web::resource(app_config.web_root.as_str())
@@ -209,10 +877,111 @@ pub async fn main() -> std::io::Result<()> {
                 web::resource(app_config.web_root.as_str().to_owned() + "add_transaction")
                     .route(web::post().to(add_transaction)),
             )
+            .service(
+                web::resource(app_config.web_root.as_str().to_owned() + "chain")
+                    .route(web::get().to(dispatch_chain)),
+            )
+            .service(
+                web::resource(app_config.web_root.as_str().to_owned() + "blocks/{id}")
+                    .route(web::get().to(dispatch_block)),
+            )
+            .service(
+                web::resource(app_config.web_root.as_str().to_owned() + "transactions/{txid}")
+                    .route(web::get().to(dispatch_transaction)),
+            )
+            .service(
+                web::resource(
+                    app_config.web_root.as_str().to_owned() + "transactions/{txid}/status",
+                )
+                .route(web::get().to(dispatch_transaction_status)),
+            )
+            .service(
+                web::resource(
+                    app_config.web_root.as_str().to_owned() + "addresses/{address}/transactions",
+                )
+                .route(web::get().to(dispatch_address_transactions)),
+            )
+            .service(
+                web::resource(
+                    app_config.web_root.as_str().to_owned() + "addresses/{address}/outputs",
+                )
+                .route(web::get().to(dispatch_address_outputs)),
+            )
+            .service(
+                web::resource(app_config.web_root.as_str().to_owned() + "assets")
+                    .route(web::get().to(dispatch_assets)),
+            )
+            .service(
+                web::resource(app_config.web_root.as_str().to_owned() + "assets/{id}")
+                    .route(web::get().to(dispatch_asset)),
+            )
+            .service(
+                web::resource(app_config.web_root.as_str().to_owned() + "validators")
+                    .route(web::get().to(dispatch_validators)),
+            )
+            .service(
+                web::resource(app_config.web_root.as_str().to_owned() + "validators/proposals")
+                    .route(web::post().to(propose_validator)),
+            )
+            .service(
+                web::resource(app_config.web_root.as_str().to_owned() + "staking")
+                    .route(web::get().to(dispatch_staking)),
+            )
+            .service(
+                web::resource(app_config.web_root.as_str().to_owned() + "staking/evidence")
+                    .route(web::post().to(report_evidence)),
+            )
             .service(
                 web::resource(app_config.web_root.as_str().to_owned() + "mine_block")
                     .route(web::get().to(dispatch_mining_request)),
             )
+            .service(
+                web::resource(app_config.web_root.as_str().to_owned() + "rpc")
+                    .route(web::post().to(rpc::dispatch_rpc_request)),
+            )
+            .service(
+                web::resource(app_config.web_root.as_str().to_owned() + "replace_chain")
+                    .route(web::post().to(replace_chain)),
+            )
+            .service(
+                web::resource(app_config.web_root.as_str().to_owned() + "ws")
+                    .route(web::get().to(events::websocket::dispatch_event_subscription)),
+            )
+            .service(
+                web::resource(app_config.web_root.as_str().to_owned() + "mining/events")
+                    .route(web::get().to(events::sse::dispatch_mining_events)),
+            )
+            .service(
+                web::resource(app_config.web_root.as_str().to_owned() + "webhooks")
+                    .route(web::post().to(webhooks::register_webhook))
+                    .route(web::get().to(webhooks::list_webhooks)),
+            )
+            .service(
+                web::resource(app_config.web_root.as_str().to_owned() + "webhooks/{id}")
+                    .route(web::delete().to(webhooks::remove_webhook)),
+            )
+            .service(
+                web::resource(app_config.web_root.as_str().to_owned() + "webhooks/{id}/deliveries")
+                    .route(web::get().to(webhooks::list_deliveries)),
+            )
+            .service(
+                web::resource(app_config.web_root.as_str().to_owned() + "multisig/addresses")
+                    .route(web::post().to(multisig::create_multisig_address)),
+            )
+            .service(
+                web::resource(app_config.web_root.as_str().to_owned() + "multisig/drafts")
+                    .route(web::post().to(multisig::create_draft)),
+            )
+            .service(
+                web::resource(app_config.web_root.as_str().to_owned() + "multisig/drafts/{id}")
+                    .route(web::get().to(multisig::get_draft)),
+            )
+            .service(
+                web::resource(
+                    app_config.web_root.as_str().to_owned() + "multisig/drafts/{id}/signatures",
+                )
+                .route(web::post().to(multisig::add_draft_signature)),
+            )
             /*            .service(
                             web::resource(app_config.web_root.as_str().to_owned() + "ping")
                                 .route(web::get().to(dispatch_ping_request)),
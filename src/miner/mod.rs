@@ -21,8 +21,11 @@ use rand::distributions::{Distribution, Uniform};
 //use tokio::time::{sleep, Duration};
 use std::{thread, time};
 
+use crate::events::{self, ChainEvent, EventHub};
+use crate::model::address;
 use crate::model::blockchain::Blockchain;
 use crate::model::transaction::MutexTransactionList;
+use crate::storage::ChainStore;
 
 //==============================================================================
 // Structure MiningMessage Declaration
@@ -54,6 +57,10 @@ pub struct MiningError {
 pub struct MiningWorker {
     blockchain_mutex: web::Data<Mutex<Blockchain>>,
     transaction_mutex: web::Data<MutexTransactionList>,
+    event_hub: Option<Addr<EventHub>>,
+    store: Option<ChainStore>,
+    reward_address: String,
+    mining_reward: f64,
 }
 
 //==============================================================================
@@ -78,6 +85,10 @@ impl MiningWorker {
         Self {
             blockchain_mutex: web::Data::new(Mutex::new(Blockchain::new())),
             transaction_mutex: web::Data::new(MutexTransactionList::new()),
+            event_hub: None,
+            store: None,
+            reward_address: String::from(address::BURN_ADDRESS),
+            mining_reward: 10f64,
         }
     }
 
@@ -86,8 +97,12 @@ impl MiningWorker {
         transaction_mutex: web::Data<MutexTransactionList>,
     ) -> Self {
         Self {
-            blockchain_mutex: blockchain_mutex,
-            transaction_mutex: transaction_mutex,
+            blockchain_mutex,
+            transaction_mutex,
+            event_hub: None,
+            store: None,
+            reward_address: String::from(address::BURN_ADDRESS),
+            mining_reward: 10f64,
         }
     }
 
@@ -104,6 +119,22 @@ impl MiningWorker {
         self.transaction_mutex = transaction_mutex;
     }
 
+    /// Publish the newly mined Blocks and the Mining Rewards to the `EventHub`
+    pub fn set_event_hub(&mut self, event_hub: Addr<EventHub>) {
+        self.event_hub = Some(event_hub);
+    }
+
+    /// Store the Blockchain after each newly mined Block
+    pub fn set_store(&mut self, store: ChainStore) {
+        self.store = Some(store);
+    }
+
+    /// Configure the Mining Reward granted for each mined Block
+    pub fn set_reward(&mut self, reward_address: &str, mining_reward: f64) {
+        self.reward_address = reward_address.to_owned();
+        self.mining_reward = mining_reward;
+    }
+
     pub fn mine_block(&mut self) -> Result<u64, MiningError> {
         match self.blockchain_mutex.lock() {
             Ok(mut guard) => {
@@ -111,18 +142,41 @@ impl MiningWorker {
 
                 let blockchain = guard.deref_mut();
 
-                let proof = blockchain.proof_of_work(&self.transaction_mutex);
-
-                match self
-                    .transaction_mutex
-                    .add_transaction_from_data("blockchain", "Miner", 10f64)
-                {
-                    Ok(()) => Ok(proof),
-                    Err(e) => Err(MiningError {
-                        status: "failed".to_owned(),
-                        report: format!("Mining: Reward Grant failed! Message: {:?}", e),
-                    }),
+                let event_hub = self.event_hub.as_ref();
+                let proof = blockchain
+                    .seal_block_with_progress(&self.transaction_mutex, &mut |progress| {
+                        events::publish(event_hub, ChainEvent::MiningProgress(progress.clone()))
+                    })
+                    .map_err(|e| MiningError {
+                        status: e.status,
+                        report: e.report,
+                    })?;
+
+                if let Some(block) = blockchain.get_last_block() {
+                    events::publish(self.event_hub.as_ref(), ChainEvent::NewBlock(block.clone()));
+                }
+
+                if let Some(store) = self.store.as_ref() {
+                    if let Err(e) = store.save(blockchain) {
+                        eprintln!("Blockchain: Chain could not be stored! Message: {:?}", e);
+                    }
+                }
+
+                for reward in blockchain.build_rewards(&self.reward_address, self.mining_reward) {
+                    if let Err(e) = self.transaction_mutex.add_transaction(reward.clone()) {
+                        return Err(MiningError {
+                            status: "failed".to_owned(),
+                            report: format!("Mining: Reward Grant failed! Message: {:?}", e),
+                        });
+                    }
+
+                    events::publish(
+                        self.event_hub.as_ref(),
+                        ChainEvent::NewPendingTransaction(Box::new(reward)),
+                    );
                 }
+
+                Ok(proof)
             }
             Err(e) => Err(MiningError {
                 status: "failed".to_owned(),
@@ -288,6 +342,37 @@ impl MinerLink {
 //==============================================================================
 // Auxiliary Functions
 
+/// Request a Block at the Start of each Proof of Authority Slot.
+///
+/// The Consensus Engine only seals the Block in the Slots of this Validator.
+pub fn start_slot_timer(link: MinerLink, block_interval: u32) {
+    actix_rt::spawn(async move {
+        let mut ticks = actix_rt::time::interval(time::Duration::from_secs(1));
+        let mut last_slot = 0;
+
+        loop {
+            ticks.tick().await;
+
+            let slot = match time::SystemTime::now().duration_since(time::UNIX_EPOCH) {
+                Ok(n) => n.as_secs() / block_interval.max(1) as u64,
+                Err(_) => continue,
+            };
+
+            if slot == last_slot {
+                continue;
+            }
+
+            last_slot = slot;
+
+            match link.mine_block().await {
+                Ok(rs) => println!("Slot {}: {}", slot, rs.report),
+                Err(e) if e.status == "waiting" => {}
+                Err(e) => eprintln!("Slot {}: Block Sealing failed: {:?}", slot, e),
+            }
+        }
+    });
+}
+
 pub async fn mine_block(link: &MinerLink) -> Result<MiningResponse, MiningError> {
     // Send Email Data message.
     // send() message returns Future object, that resolves to message result
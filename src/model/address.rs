@@ -0,0 +1,293 @@
+/*
+* @author Bodo (Hugo) Barwich
+* @version 2026-10-18
+* @package Blockchain Exercise
+* @subpackage Address Structures
+
+* This Module defines the checksummed Addresses which are derived from the
+* hashes of Public Keys and encoded in Base58Check
+*
+*---------------------------------
+* Requirements:
+* - The Rust Crate "ed25519-dalek" must be installed
+* - The Rust Crate "bs58" must be installed
+*/
+
+use ed25519_dalek::VerifyingKey;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use sha2::{Digest, Sha256};
+use std::fmt;
+use std::str::FromStr;
+
+/// Version Byte of Addresses derived from a single Public Key
+pub const KEY_VERSION: u8 = 0x00;
+
+/// Version Byte of Addresses derived from a Multi-Signature Policy
+pub const MULTISIG_VERSION: u8 = 0x05;
+
+/// Version Byte of Addresses derived from a Spending Script
+pub const SCRIPT_VERSION: u8 = 0x08;
+
+/// Number of Bytes of the Public Key Hash within an Address
+pub const HASH_LENGTH: usize = 20;
+
+/// Address without any Key. Amounts sent to it can never be spent.
+pub const BURN_ADDRESS: &str = "1111111111111111111114oLvT2";
+
+//==============================================================================
+// Structure Address Declaration
+
+/// Structure for an Address made of a Version Byte and a Public Key Hash
+///
+/// The textual form is the Base58Check encoding of the Version Byte followed by
+/// the Hash. The 4 Byte Checksum detects mistyped Addresses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Address {
+    version: u8,
+    hash: [u8; HASH_LENGTH],
+}
+
+/// Reasons why a Text is not a valid Address
+#[derive(Debug, Clone, PartialEq)]
+pub enum AddressError {
+    /// The Text contains Characters outside of the Base58 Alphabet
+    InvalidEncoding(String),
+    /// The Checksum does not match. The Address was probably mistyped.
+    InvalidChecksum,
+    /// The decoded Address does not have the expected Length
+    InvalidLength(usize),
+    /// The Version Byte is not known
+    UnknownVersion(u8),
+}
+
+//==============================================================================
+// Structure Address Implementation
+
+impl Address {
+    /*----------------------------------------------------------------------------
+     * Constructors
+     */
+
+    pub fn from_hash(version: u8, hash: [u8; HASH_LENGTH]) -> Self {
+        Self { version, hash }
+    }
+
+    /// Derive the Address of a Public Key.
+    ///
+    /// The Hash are the first 20 Bytes of the SHA-256 hash of the Public Key.
+    pub fn from_public_key(public_key: &VerifyingKey) -> Self {
+        let digest = Sha256::digest(public_key.as_bytes());
+        let mut hash = [0u8; HASH_LENGTH];
+
+        hash.copy_from_slice(&digest[..HASH_LENGTH]);
+
+        Self::from_hash(KEY_VERSION, hash)
+    }
+
+    /// Derive the Address of a Multi-Signature Policy.
+    ///
+    /// The Hash covers the Threshold and the Public Keys in ascending order so that
+    /// the order in which the Keys are given does not change the Address.
+    pub fn from_multisig(threshold: u8, public_keys: &[VerifyingKey]) -> Self {
+        let mut keys: Vec<&[u8; 32]> = public_keys.iter().map(|k| k.as_bytes()).collect();
+        let mut hasher = Sha256::new();
+        let mut hash = [0u8; HASH_LENGTH];
+
+        keys.sort();
+
+        hasher.update([threshold, keys.len() as u8]);
+        keys.iter().for_each(|k| hasher.update(k));
+        hash.copy_from_slice(&hasher.finalize()[..HASH_LENGTH]);
+
+        Self::from_hash(MULTISIG_VERSION, hash)
+    }
+
+    /// Derive the Address of a Spending Script from its serialized Form.
+    pub fn from_script(script: &[u8]) -> Self {
+        let mut hash = [0u8; HASH_LENGTH];
+
+        hash.copy_from_slice(&Sha256::digest(script)[..HASH_LENGTH]);
+
+        Self::from_hash(SCRIPT_VERSION, hash)
+    }
+
+    /// Decode and validate the textual form of an Address.
+    pub fn parse(address: &str) -> Result<Self, AddressError> {
+        let payload = bs58::decode(address)
+            .with_check(None)
+            .into_vec()
+            .map_err(|e| match e {
+                bs58::decode::Error::InvalidChecksum { .. } => AddressError::InvalidChecksum,
+                e => AddressError::InvalidEncoding(e.to_string()),
+            })?;
+
+        if payload.len() != HASH_LENGTH + 1 {
+            return Err(AddressError::InvalidLength(payload.len()));
+        }
+
+        if !is_known_version(payload[0]) {
+            return Err(AddressError::UnknownVersion(payload[0]));
+        }
+
+        let mut hash = [0u8; HASH_LENGTH];
+
+        hash.copy_from_slice(&payload[1..]);
+
+        Ok(Self::from_hash(payload[0], hash))
+    }
+
+    /*----------------------------------------------------------------------------
+     * Consultation Methods
+     */
+
+    pub fn get_version(&self) -> u8 {
+        self.version
+    }
+
+    pub fn get_hash(&self) -> &[u8; HASH_LENGTH] {
+        &self.hash
+    }
+}
+
+impl fmt::Display for Address {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut payload = Vec::with_capacity(HASH_LENGTH + 1);
+
+        payload.push(self.version);
+        payload.extend_from_slice(&self.hash);
+
+        write!(f, "{}", bs58::encode(payload).with_check().into_string())
+    }
+}
+
+impl FromStr for Address {
+    type Err = AddressError;
+
+    fn from_str(address: &str) -> Result<Self, Self::Err> {
+        Self::parse(address)
+    }
+}
+
+impl Serialize for Address {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for Address {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let address = String::deserialize(deserializer)?;
+
+        Self::parse(&address).map_err(serde::de::Error::custom)
+    }
+}
+
+impl fmt::Display for AddressError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AddressError::InvalidEncoding(e) => write!(f, "Address is not Base58 encoded: {}", e),
+            AddressError::InvalidChecksum => write!(f, "Address Checksum does not match"),
+            AddressError::InvalidLength(l) => write!(f, "Address has an invalid Length of {}", l),
+            AddressError::UnknownVersion(v) => write!(f, "Address Version {} is unknown", v),
+        }
+    }
+}
+
+//==============================================================================
+// Address Functions
+
+/// Derive the textual Address of a Public Key.
+pub fn from_public_key(public_key: &VerifyingKey) -> String {
+    Address::from_public_key(public_key).to_string()
+}
+
+/// Check whether a Text is a valid Address.
+pub fn is_valid(address: &str) -> bool {
+    Address::parse(address).is_ok()
+}
+
+/// Check whether an Address is derived from a single Public Key.
+///
+/// `Transaction`s sent from such an Address must be signed by its Key.
+pub fn is_key_address(address: &str) -> bool {
+    matches!(Address::parse(address), Ok(a) if a.get_version() == KEY_VERSION)
+}
+
+/// Check whether an Address is derived from a Multi-Signature Policy.
+///
+/// `Transaction`s sent from such an Address must be signed by enough of its Keys.
+pub fn is_multisig_address(address: &str) -> bool {
+    matches!(Address::parse(address), Ok(a) if a.get_version() == MULTISIG_VERSION)
+}
+
+/// Check whether an Address is derived from a Spending Script.
+///
+/// `Transaction`s sent from such an Address must reveal the Script and satisfy it.
+pub fn is_script_address(address: &str) -> bool {
+    matches!(Address::parse(address), Ok(a) if a.get_version() == SCRIPT_VERSION)
+}
+
+/// Deserialize a textual Address and reject it if it is malformed.
+///
+/// The Address is kept in its textual form.
+pub fn deserialize_address<'de, D: Deserializer<'de>>(deserializer: D) -> Result<String, D::Error> {
+    Address::deserialize(deserializer).map(|a| a.to_string())
+}
+
+fn is_known_version(version: u8) -> bool {
+    version == KEY_VERSION || version == MULTISIG_VERSION || version == SCRIPT_VERSION
+}
+
+//==============================================================================
+// Unit Tests
+
+#[test]
+fn address_base58check_vectors() {
+    //-------------------------------------
+    // Known Base58Check Vectors with Version Byte 0x00
+
+    let hash = hex::decode("010966776006953d5567439e5e39f86a0d273bee").unwrap();
+    let address = Address::from_hash(KEY_VERSION, hash.try_into().unwrap());
+
+    assert_eq!(address.to_string(), "16UwLL9Risc3QfPqBUvKofHmBQ7wMtjvM");
+    assert_eq!(
+        Address::parse("16UwLL9Risc3QfPqBUvKofHmBQ7wMtjvM").unwrap(),
+        address
+    );
+    assert_eq!(
+        Address::from_hash(KEY_VERSION, [0u8; HASH_LENGTH]).to_string(),
+        BURN_ADDRESS
+    );
+
+    //-------------------------------------
+    // Malformed Addresses
+
+    assert_eq!(
+        Address::parse("16UwLL9Risc3QfPqBUvKofHmBQ7wMtjvN"),
+        Err(AddressError::InvalidChecksum)
+    );
+    assert!(matches!(
+        Address::parse("16UwLL9Risc3QfPqBUvKofHmBQ7wMtjv0"),
+        Err(AddressError::InvalidEncoding(_))
+    ));
+    assert_eq!(
+        Address::parse(&bs58::encode([0u8; 5]).with_check().into_string()),
+        Err(AddressError::InvalidLength(5))
+    );
+    assert_eq!(
+        Address::parse(&bs58::encode([0x42u8; 21]).with_check().into_string()),
+        Err(AddressError::UnknownVersion(0x42))
+    );
+    assert!(!is_valid("receiver1"));
+}
+
+#[test]
+fn address_serde() {
+    let address: Address = serde_json::from_str("\"16UwLL9Risc3QfPqBUvKofHmBQ7wMtjvM\"").unwrap();
+
+    assert_eq!(
+        serde_json::to_string(&address).unwrap(),
+        "\"16UwLL9Risc3QfPqBUvKofHmBQ7wMtjvM\""
+    );
+    assert!(serde_json::from_str::<Address>("\"16UwLL9Risc3QfPqBUvKofHmBQ7wMtjvN\"").is_err());
+}
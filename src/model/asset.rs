@@ -0,0 +1,513 @@
+/*
+* @author Bodo (Hugo) Barwich
+* @version 2026-10-18
+* @package Blockchain Exercise
+* @subpackage Asset Structures
+
+* This Module defines the custom Assets which are issued on the Chain and
+* the Ledger of their Balances per Address
+*
+*---------------------------------
+* Requirements:
+*/
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+use super::blockchain::{Block, LedgerError};
+use super::transaction::Transaction;
+
+/// Maximum number of Characters of an Asset Symbol
+pub const MAX_SYMBOL_LENGTH: usize = 12;
+/// Maximum number of Decimals of an Asset
+pub const MAX_DECIMALS: u8 = 8;
+/// Maximum number of Base Units of an Amount which `f64` represents exactly
+pub const MAX_UNITS: u64 = 1 << 53;
+
+//==============================================================================
+// Structure AssetDefinition Declaration
+
+/// Structure for the Definition of a new Asset within an Issuance `Transaction`
+///
+/// The `amount` of the Issuance is the Supply which the Issuer receives.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AssetDefinition {
+    pub symbol: String,
+    pub decimals: u8,
+}
+
+/// Structure for an issued Asset
+///
+/// The Asset Id is the Transaction Id of its Issuance.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Asset {
+    pub id: String,
+    pub symbol: String,
+    pub decimals: u8,
+    pub supply: f64,
+    pub issuer: String,
+    pub block_index: u64,
+}
+
+/// Structure for the Balance of an Address in an Asset
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AssetHolder {
+    pub address: String,
+    pub balance: f64,
+}
+
+//==============================================================================
+// Structure AssetLedger Declaration
+
+/// Structure for the issued Assets and the Balances per Address and Asset
+///
+/// The Balances are kept in Base Units which are the Amounts shifted by the
+/// Decimals of the Asset.
+#[derive(Debug, Clone, Default)]
+pub struct AssetLedger {
+    assets: BTreeMap<String, Asset>,
+    balances: HashMap<String, HashMap<String, u64>>,
+}
+
+//==============================================================================
+// Structure AssetDefinition Implementation
+
+impl AssetDefinition {
+    /*----------------------------------------------------------------------------
+     * Consultation Methods
+     */
+
+    /// Check that the Symbol has 1 to 12 upper case Letters or Digits and that
+    /// the Decimals do not exceed 8.
+    pub fn is_valid(&self) -> bool {
+        !self.symbol.is_empty()
+            && self.symbol.len() <= MAX_SYMBOL_LENGTH
+            && self
+                .symbol
+                .chars()
+                .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit())
+            && self.decimals <= MAX_DECIMALS
+    }
+
+    /// Check that an Amount is positive and has no more than the allowed Decimals.
+    pub fn is_amount_valid(&self, amount: f64) -> bool {
+        self.to_units(amount).is_some()
+    }
+
+    /// Convert an Amount into Base Units.
+    ///
+    /// The Amount must be exactly the `f64` which is nearest to its Base Units
+    /// shifted by the Decimals, so that no Decimals beyond them are lost.
+    ///
+    /// # Returns:
+    /// - the Base Units or `None` if the Amount is not positive, has more Decimals
+    ///   or exceeds `MAX_UNITS`
+    ///
+    pub fn to_units(&self, amount: f64) -> Option<u64> {
+        let scale = self.get_scale();
+
+        if !amount.is_finite() || amount <= 0f64 || amount * scale > MAX_UNITS as f64 {
+            return None;
+        }
+
+        let units = (amount * scale).round();
+
+        if units < 1f64 || units / scale != amount {
+            return None;
+        }
+
+        Some(units as u64)
+    }
+
+    /// Convert Base Units into an Amount.
+    pub fn to_amount(&self, units: u64) -> f64 {
+        units as f64 / self.get_scale()
+    }
+
+    fn get_scale(&self) -> f64 {
+        10u64.pow(self.decimals as u32) as f64
+    }
+}
+
+//==============================================================================
+// Structure Asset Implementation
+
+impl Asset {
+    /*----------------------------------------------------------------------------
+     * Consultation Methods
+     */
+
+    pub fn get_definition(&self) -> AssetDefinition {
+        AssetDefinition {
+            symbol: self.symbol.clone(),
+            decimals: self.decimals,
+        }
+    }
+}
+
+//==============================================================================
+// Structure AssetLedger Implementation
+
+impl AssetLedger {
+    /*----------------------------------------------------------------------------
+     * Constructors
+     */
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Build the Ledger of a Chain.
+    ///
+    /// # Returns:
+    /// - the Ledger or the Index of the first Block with an invalid Asset `Transaction`
+    ///
+    pub fn from_chain(chain: &[Block]) -> Result<Self, (u64, LedgerError)> {
+        let mut ledger = Self::new();
+
+        for block in chain.iter() {
+            ledger.apply_block(block).map_err(|e| (block.index, e))?;
+        }
+
+        Ok(ledger)
+    }
+
+    /*----------------------------------------------------------------------------
+     * Administration Methods
+     */
+
+    /// Register the Issuances and Transfers of a Block.
+    ///
+    /// A Block with an invalid Asset `Transaction` is not applied at all. The
+    /// `Transaction`s applied before it are undone.
+    pub fn apply_block(&mut self, block: &Block) -> Result<(), LedgerError> {
+        for (position, transaction) in block.transactions.iter().enumerate() {
+            if let Err(e) = self.apply_transaction(block.index, transaction) {
+                self.rollback_transactions(&block.transactions[..position]);
+
+                return Err(e);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Undo applied `Transaction`s from the newest to the oldest.
+    fn rollback_transactions(&mut self, transactions: &[Transaction]) {
+        for transaction in transactions.iter().rev() {
+            if transaction.issuance.is_some() {
+                // Issuances which cannot be encoded were never applied
+                if let Ok(asset_id) = transaction.to_hash() {
+                    self.balances.remove(&asset_id);
+                    self.assets.remove(&asset_id);
+                }
+            } else if let Some((asset_id, units)) = self.get_transfer_units(transaction) {
+                self.sub_balance(&asset_id, &transaction.receiver, units);
+                self.add_balance(&asset_id, &transaction.sender, units);
+            }
+        }
+    }
+
+    /// Register one `Transaction` of the Block `block_index`.
+    ///
+    /// `Transaction`s of the native Currency are ignored.
+    pub fn apply_transaction(
+        &mut self,
+        block_index: u64,
+        transaction: &Transaction,
+    ) -> Result<(), LedgerError> {
+        self.check_transaction(transaction, &[])?;
+
+        if let Some(definition) = transaction.issuance.as_ref() {
+            let asset = Asset {
+                id: transaction.to_hash().map_err(LedgerError::from_encoding)?,
+                symbol: definition.symbol.clone(),
+                decimals: definition.decimals,
+                supply: transaction.amount,
+                issuer: transaction.sender.clone(),
+                block_index,
+            };
+            let units = definition.to_units(asset.supply).unwrap_or_default();
+
+            self.add_balance(&asset.id, &asset.issuer, units);
+            self.assets.insert(asset.id.clone(), asset);
+        } else if let Some((asset_id, units)) = self.get_transfer_units(transaction) {
+            self.sub_balance(&asset_id, &transaction.sender, units);
+            self.add_balance(&asset_id, &transaction.receiver, units);
+        }
+
+        Ok(())
+    }
+
+    fn add_balance(&mut self, asset_id: &str, address: &str, units: u64) {
+        let balances = self.balances.entry(asset_id.to_owned()).or_default();
+
+        *balances.entry(address.to_owned()).or_default() += units;
+    }
+
+    fn sub_balance(&mut self, asset_id: &str, address: &str, units: u64) {
+        if let Some(balances) = self.balances.get_mut(asset_id) {
+            let balance = balances.entry(address.to_owned()).or_default();
+
+            *balance = balance.saturating_sub(units);
+
+            if *balance == 0 {
+                balances.remove(address);
+            }
+        }
+    }
+
+    /*----------------------------------------------------------------------------
+     * Consultation Methods
+     */
+
+    /// Check an Issuance or a Transfer of an Asset.
+    ///
+    /// # Parameters:
+    /// - `pending_amounts`: Amounts of the Asset which the Sender already sends with
+    ///   `Transaction`s waiting for the next Block.
+    ///
+    pub fn check_transaction(
+        &self,
+        transaction: &Transaction,
+        pending_amounts: &[f64],
+    ) -> Result<(), LedgerError> {
+        let txid = transaction.to_hash().map_err(LedgerError::from_encoding)?;
+
+        if let Some(definition) = transaction.issuance.as_ref() {
+            if !transaction.is_valid() || !definition.is_amount_valid(transaction.amount) {
+                return Err(LedgerError::new(
+                    "invalid",
+                    &format!("Asset Issuance ({}): Issuance is invalid", txid),
+                ));
+            }
+
+            if self.get_asset_by_symbol(&definition.symbol).is_some() {
+                return Err(LedgerError::new(
+                    "duplicate",
+                    &format!(
+                        "Asset Issuance ({}): Symbol '{}' is already issued",
+                        txid, definition.symbol
+                    ),
+                ));
+            }
+
+            return Ok(());
+        }
+
+        let asset_id = match transaction.asset.as_ref() {
+            Some(a) => a,
+            None => return Ok(()),
+        };
+        let asset = match self.assets.get(asset_id) {
+            Some(a) => a,
+            None => {
+                return Err(LedgerError::new(
+                    "not_found",
+                    &format!("Asset ({}): Asset does not exist", asset_id),
+                ))
+            }
+        };
+        let definition = asset.get_definition();
+        let units = match definition.to_units(transaction.amount) {
+            Some(u) if transaction.is_valid() => u,
+            _ => {
+                return Err(LedgerError::new(
+                    "invalid",
+                    &format!("Asset Transfer ({}): Transfer is invalid", txid),
+                ))
+            }
+        };
+        let pending_units: u64 = pending_amounts
+            .iter()
+            .filter_map(|a| definition.to_units(*a))
+            .sum();
+        let available = self
+            .get_units(&transaction.sender, asset_id)
+            .saturating_sub(pending_units);
+
+        if available < units {
+            return Err(LedgerError::new(
+                "insufficient",
+                &format!(
+                    "Asset Transfer ({}): Balance of {} {} is insufficient",
+                    txid,
+                    definition.to_amount(available),
+                    asset.symbol
+                ),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Get the Asset Id and the Base Units of a Transfer of a known Asset
+    fn get_transfer_units(&self, transaction: &Transaction) -> Option<(String, u64)> {
+        let asset_id = transaction.asset.as_ref()?;
+        let units = self
+            .assets
+            .get(asset_id)?
+            .get_definition()
+            .to_units(transaction.amount)?;
+
+        Some((asset_id.clone(), units))
+    }
+
+    fn get_units(&self, address: &str, asset_id: &str) -> u64 {
+        self.balances
+            .get(asset_id)
+            .and_then(|b| b.get(address))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    pub fn get_asset(&self, asset_id: &str) -> Option<&Asset> {
+        self.assets.get(asset_id)
+    }
+
+    pub fn get_asset_by_symbol(&self, symbol: &str) -> Option<&Asset> {
+        self.assets.values().find(|a| a.symbol == symbol)
+    }
+
+    /// List the issued Assets ordered by their Asset Id.
+    pub fn get_assets(&self) -> Vec<&Asset> {
+        self.assets.values().collect()
+    }
+
+    pub fn get_balance(&self, address: &str, asset_id: &str) -> f64 {
+        match self.assets.get(asset_id) {
+            Some(a) => a
+                .get_definition()
+                .to_amount(self.get_units(address, asset_id)),
+            None => 0f64,
+        }
+    }
+
+    /// List the Addresses which hold an Asset from the largest to the smallest Balance.
+    pub fn get_holders(&self, asset_id: &str) -> Vec<AssetHolder> {
+        let definition = match self.assets.get(asset_id) {
+            Some(a) => a.get_definition(),
+            None => return Vec::new(),
+        };
+        let mut balances: Vec<(&String, u64)> = self
+            .balances
+            .get(asset_id)
+            .map(|b| b.iter().map(|(address, units)| (address, *units)).collect())
+            .unwrap_or_default();
+
+        balances.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+
+        balances
+            .into_iter()
+            .map(|(address, units)| AssetHolder {
+                address: address.clone(),
+                balance: definition.to_amount(units),
+            })
+            .collect()
+    }
+}
+
+//==============================================================================
+// Unit Tests
+
+#[test]
+fn asset_units() {
+    let definition = AssetDefinition {
+        symbol: String::from("PTS"),
+        decimals: 2,
+    };
+
+    assert_eq!(definition.to_units(1.23), Some(123));
+    assert_eq!(definition.to_amount(123), 1.23);
+    assert_eq!(definition.to_units(1.234), None);
+    assert_eq!(definition.to_units(0f64), None);
+    assert_eq!(definition.to_units(-1f64), None);
+    assert_eq!(definition.to_units(f64::NAN), None);
+    assert_eq!(definition.to_units(1e14), None);
+
+    let definition = AssetDefinition {
+        symbol: String::from("PTS"),
+        decimals: 8,
+    };
+
+    // 0.1 + 0.2 is not the nearest f64 to 0.3 and has more than 8 Decimals
+    assert_eq!(definition.to_units(0.1 + 0.2), None);
+    assert_eq!(definition.to_units(0.3), Some(30_000_000));
+}
+
+#[test]
+fn asset_block_rollback() {
+    use ed25519_dalek::SigningKey;
+
+    let signing_key = SigningKey::from_bytes(&[3u8; 32]);
+    let receiver = String::from("1BcktgV7EjHmxEwQDFFhhztzNqZkd5gdm");
+    let definition = AssetDefinition {
+        symbol: String::from("PTS"),
+        decimals: 2,
+    };
+    let mut issuance =
+        Transaction::from_issuance(&signing_key.verifying_key(), definition.clone(), 10.0, 1);
+
+    issuance.sign(&signing_key).unwrap();
+
+    let asset_id = issuance.to_hash().unwrap();
+    let issuer = issuance.sender.clone();
+    let transfer = |amount: f64, nonce: u64| {
+        let mut transfer = Transaction::from_key(
+            &signing_key.verifying_key(),
+            receiver.clone(),
+            amount,
+            nonce,
+        );
+
+        transfer.asset = Some(asset_id.clone());
+        transfer.sign(&signing_key).unwrap();
+        transfer
+    };
+    let mut ledger = AssetLedger::new();
+
+    //-------------------------------------
+    // A Block whose last Transfer exceeds the Balance is undone completely
+
+    let block = Block::build_block(
+        1,
+        0,
+        "0",
+        Some(vec![issuance.clone(), transfer(9.99, 2), transfer(0.02, 3)]),
+    );
+
+    assert_eq!(
+        ledger.apply_block(&block).unwrap_err().status,
+        "insufficient"
+    );
+    assert!(ledger.get_asset(&asset_id).is_none());
+    assert!(ledger.get_holders(&asset_id).is_empty());
+
+    //-------------------------------------
+    // Transfers keep exact Balances in Base Units
+
+    let block = Block::build_block(
+        1,
+        0,
+        "0",
+        Some(vec![issuance, transfer(0.1, 2), transfer(0.2, 3)]),
+    );
+
+    ledger.apply_block(&block).unwrap();
+
+    assert_eq!(ledger.get_balance(&receiver, &asset_id), 0.3);
+    assert_eq!(ledger.get_balance(&issuer, &asset_id), 9.7);
+    assert_eq!(
+        ledger.get_holders(&asset_id),
+        vec![
+            AssetHolder {
+                address: issuer,
+                balance: 9.7,
+            },
+            AssetHolder {
+                address: receiver,
+                balance: 0.3,
+            },
+        ]
+    );
+}
@@ -11,32 +11,170 @@
 */
 
 use actix_web::web;
+use ed25519_dalek::SigningKey;
+use num::BigUint;
 use serde::{Deserialize, Serialize};
 use serde_json::Error;
 use sha256::digest;
-use std::time::SystemTime;
+use std::borrow::Cow;
+use std::time::{Instant, SystemTime};
 
-use super::transaction::{MutexTransactionList, Transaction};
+use super::address;
+use super::asset::{Asset, AssetHolder, AssetLedger};
+use super::canonical;
+use super::header::{BlockHeader, GENESIS_PREVIOUS_HASH};
+use super::htlc::Htlc;
+use super::index::{
+    AddressHistory, AddressIndex, AddressTransaction, Direction, TransactionIndex,
+    TransactionStatus,
+};
+use super::stake::{Stake, StakeAction, StakeLedger};
+use super::transaction::{MutexTransactionList, Transaction, COINBASE_SENDER};
+use super::utxo::{OutPoint, TxOutput, UtxoSet};
+use crate::consensus::poa::{ProofOfAuthority, ValidatorSet, ValidatorVote};
+use crate::consensus::seal::{self, BlockSeal, DoubleSignEvidence};
+use crate::consensus::{
+    self, pow, AuthorityParams, ConsensusEngine, ConsensusError, ConsensusMode, HashAlgorithm,
+    ProofOfStake, SealContext,
+};
+
+/// Number of previous Blocks whose median Timestamp a new Block must exceed
+pub const MEDIAN_TIME_SPAN: usize = 11;
+/// Seconds a Block Timestamp may be ahead of the Clock of the Node
+pub const MAX_FUTURE_DRIFT: u32 = 2 * 60 * 60;
+/// Version of the stored Chain Format
+///
+/// Version 1 Chains were stored before the Blocks carried their Compact Target.
+/// Version 2 Chains were stored before only the Amounts were encoded as Base Units
+/// and Amounts with more than 8 Decimals were rejected.
+pub const CHAIN_FORMAT_VERSION: u32 = 3;
 
 //==============================================================================
 // Structure Block Declaration
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Block {
     pub index: u64,
     pub timestamp: u32,
+    /// Compact Proof of Work Target the Block was mined for
+    #[serde(default)]
+    pub bits: u32,
     pub proof: u64,
     pub previous_hash: String,
     pub transactions: Vec<Transaction>,
+    /// Signature of the Validator in Proof of Authority Chains
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seal: Option<BlockSeal>,
+    /// Double Signatures of Validators which are punished with this Block
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub evidence: Vec<DoubleSignEvidence>,
 }
 
 //==============================================================================
 // Structure Blockchain Declaration
 
+/// Ledger Model of a Chain
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LedgerMode {
+    /// `Transaction`s send an `amount` from a `sender` to a `receiver`
+    #[default]
+    Account,
+    /// `Transaction`s spend former Outputs and create new Outputs
+    Utxo,
+}
+
+/// Structure for the Errors of `Transaction`s which do not fit the Ledger
+#[derive(Debug, PartialEq)]
+pub struct LedgerError {
+    pub status: String,
+    pub report: String,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Blockchain {
+    /// Format Version of the stored Chain. Chains stored without it have Version 1.
+    #[serde(default = "default_format_version")]
+    pub format_version: u32,
     pub chain: Vec<Block>,
     pub nodes: Vec<String>,
+    #[serde(default = "default_difficulty")]
+    pub difficulty: usize,
+    /// Compact Proof of Work Target. It replaces the Target of the `difficulty` if set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target_bits: Option<u32>,
+    /// Ledger Model of the Chain. It cannot be changed once Blocks are mined.
+    #[serde(default)]
+    pub ledger_mode: LedgerMode,
+    /// Highest Amount the Rewards of an Account Block or the Coinbase of a UTXO Block
+    /// grant besides the Fees. It cannot be changed once Blocks are mined.
+    #[serde(default = "default_mining_reward")]
+    pub mining_reward: f64,
+    /// Consensus Engine of the Chain. It cannot be changed once Blocks are mined.
+    #[serde(default)]
+    pub consensus: ConsensusMode,
+    /// Hash Algorithm of the Block Hashes. It cannot be changed once Blocks are mined.
+    #[serde(default)]
+    pub hash_algorithm: HashAlgorithm,
+    /// Initial Validators and Block Interval of Proof of Authority and Proof of Stake Chains
+    #[serde(default, skip_serializing_if = "AuthorityParams::is_empty")]
+    pub authority: AuthorityParams,
+    /// Key which this Node seals Proof of Authority Blocks with
+    #[serde(skip)]
+    validator_key: Option<SigningKey>,
+    /// Address which receives the Coinbase of the UTXO Blocks this Node seals
+    #[serde(skip)]
+    reward_address: String,
+    /// Changes of the Validator Set this Node votes for
+    #[serde(skip)]
+    proposals: Vec<ValidatorVote>,
+    /// Double Signatures this Node reports in the Blocks it seals
+    #[serde(skip)]
+    evidence: Vec<DoubleSignEvidence>,
+    #[serde(skip)]
+    transaction_index: TransactionIndex,
+    #[serde(skip)]
+    address_index: AddressIndex,
+    #[serde(skip)]
+    utxo_set: UtxoSet,
+    #[serde(skip)]
+    asset_ledger: AssetLedger,
+    #[serde(skip)]
+    stake_ledger: StakeLedger,
+    /// Validators after the last Block of a Proof of Authority Chain
+    #[serde(skip)]
+    validator_set: ValidatorSet,
+    /// Hashes of the Blocks in the Order of the Chain
+    #[serde(skip)]
+    block_hashes: Vec<String>,
+    /// Cumulative Proof of Work of the Chain
+    #[serde(skip)]
+    chainwork: BigUint,
+}
+
+/// Structure for the Ledger State including the `Transaction`s which were
+/// already selected for a new Block
+struct LedgerView {
+    /// Copy of the UTXO Set in UTXO Mode
+    utxo_set: Option<UtxoSet>,
+    /// Fees of the selected UTXO `Transaction`s in Base Units
+    fees: i64,
+    assets: AssetLedger,
+    stakes: StakeLedger,
+}
+
+//==============================================================================
+// Structure MiningProgress Declaration
+
+/// Structure for reporting the Progress of a running Proof of Work
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MiningProgress {
+    pub block_index: u64,
+    pub attempts: u64,
+    pub hash_rate: f64,
+    pub best_hash: String,
+    pub transaction_count: usize,
+    pub elapsed_ms: u64,
 }
 
 //==============================================================================
@@ -67,21 +205,17 @@ impl Block {
         previous_hash: &str,
         transactions: Option<Vec<Transaction>>,
     ) -> Self {
-        let timestamp = match SystemTime::now().duration_since(SystemTime::UNIX_EPOCH) {
-            Ok(n) => n.as_secs() as u32,
-            Err(_) => 0 as u32,
-        };
-        let transactions = match transactions {
-            Some(mut tx) => tx.drain(..).collect(),
-            None => Vec::<Transaction>::new(),
-        };
+        let transactions = transactions.unwrap_or_default();
 
         Self {
-            index: index,
-            timestamp: timestamp,
-            proof: proof,
+            index,
+            timestamp: get_timestamp(),
+            bits: 0,
+            proof,
             previous_hash: previous_hash.to_owned(),
-            transactions: transactions,
+            transactions,
+            seal: None,
+            evidence: Vec::new(),
         }
     }
 
@@ -93,6 +227,11 @@ impl Block {
         serde_json::to_string(&self)
     }
 
+    /// Encode the `Block` as canonical JSON. Its Body Hash covers the same Encoding.
+    pub fn to_canonical_json(&self) -> Result<String, Error> {
+        canonical::to_canonical_json(self)
+    }
+
     /*
     Cálculo del hash de un bloque.
 
@@ -101,30 +240,19 @@ impl Block {
     Returns:
         - hash_block: Devuelve el hash del bloque
     */
-    pub fn to_hash(&self) -> String {
-        // Serialize it to a JSON string.
-        let block_json = match self.to_json() {
-            Ok(j) => j,
-            Err(e) => {
-                eprintln!(
-                    "Block ({}): JSON formatting failed! Message: {:?}",
-                    self.index, e
-                );
-                String::new()
-            }
-        };
+    pub fn to_hash(&self) -> Result<String, ConsensusError> {
+        self.to_hash_with(HashAlgorithm::Sha256)
+    }
 
-        digest(block_json)
+    /// Compute the Block Hash with the Hash Algorithm of the Chain.
+    ///
+    /// The Hash is computed over the binary `BlockHeader`.
+    pub fn to_hash_with(&self, hash_algorithm: HashAlgorithm) -> Result<String, ConsensusError> {
+        BlockHeader::from_block(self)?.to_hash(hash_algorithm)
     }
 
     pub fn update_timestamp(&mut self, timestamp: Option<u32>) -> u32 {
-        self.timestamp = match timestamp {
-            Some(t) => t,
-            None => match SystemTime::now().duration_since(SystemTime::UNIX_EPOCH) {
-                Ok(n) => n.as_secs() as u32,
-                Err(_) => 0 as u32,
-            },
-        };
+        self.timestamp = timestamp.unwrap_or_else(get_timestamp);
 
         self.timestamp
     }
@@ -150,15 +278,63 @@ impl Blockchain {
 
     pub fn new() -> Self {
         let blockchain = Self {
+            format_version: CHAIN_FORMAT_VERSION,
             chain: Vec::<Block>::new(),
             nodes: Vec::<String>::new(),
+            difficulty: default_difficulty(),
+            target_bits: None,
+            ledger_mode: LedgerMode::Account,
+            mining_reward: default_mining_reward(),
+            consensus: ConsensusMode::ProofOfWork,
+            hash_algorithm: HashAlgorithm::Sha256,
+            authority: AuthorityParams::default(),
+            validator_key: None,
+            reward_address: String::new(),
+            proposals: Vec::new(),
+            evidence: Vec::new(),
+            transaction_index: TransactionIndex::new(),
+            address_index: AddressIndex::new(),
+            utxo_set: UtxoSet::new(),
+            asset_ledger: AssetLedger::new(),
+            stake_ledger: StakeLedger::new(),
+            validator_set: ValidatorSet::default(),
+            block_hashes: Vec::new(),
+            chainwork: BigUint::default(),
         };
         // Generate Genesis Block
-        //let _ = blockchain.proof_of_work();
+        //let _ = blockchain.seal_block();
+
+        blockchain
+    }
+
+    /// Create a Blockchain which requires `difficulty` leading zeros in the Block Hashes.
+    pub fn with_difficulty(difficulty: usize) -> Self {
+        let mut blockchain = Self::new();
+
+        blockchain.difficulty = difficulty;
+
+        blockchain
+    }
+
+    /// Create an empty Blockchain with the given Ledger Model.
+    pub fn with_ledger_mode(ledger_mode: LedgerMode) -> Self {
+        let mut blockchain = Self::new();
+
+        blockchain.ledger_mode = ledger_mode;
 
         blockchain
     }
 
+    /// Create a Blockchain from existing Blocks and build its Indices.
+    pub fn from_chain(chain: Vec<Block>) -> Result<Self, LedgerError> {
+        let mut blockchain = Self::new();
+
+        blockchain.chain = chain;
+        blockchain.rebuild_index()?;
+
+        Ok(blockchain)
+    }
+
     /*----------------------------------------------------------------------------
      * Administration Methods
      */
@@ -169,84 +345,748 @@ impl Blockchain {
     /// - `proof`: Nonce of this Block.
     /// - `previous_hash`: Hash of the previous Block.
     ///
+    /// # Returns:
+    /// - the Index of the new Block or the Error if it does not fit the Ledger
+    ///
     pub fn build_block(
         &mut self,
         proof: u64,
         previous_hash: &str,
         transaction_mutex: web::Data<MutexTransactionList>,
-    ) -> u64 {
+    ) -> Result<u64, LedgerError> {
         let next_index = self.get_last_block_index() + 1;
-        let transactions = transaction_mutex.into_vec();
-        let block = Block::build_block(next_index, proof, previous_hash, Some(transactions));
+        let lock_time = self.get_lock_time();
+        let mut block = Block::build_block(next_index, proof, previous_hash, None);
+        let mut ledger_view = self.get_ledger_view();
 
-        self.chain.push(block);
+        block.update_timestamp(Some(block.timestamp.max(lock_time + 1)));
+        block.transactions = self.select_transactions(
+            &mut ledger_view,
+            next_index,
+            lock_time,
+            transaction_mutex.take_unlocked(next_index, lock_time),
+        );
+
+        if self.ledger_mode == LedgerMode::Utxo {
+            block
+                .transactions
+                .insert(0, self.build_coinbase(next_index, ledger_view.fees)?);
+        }
 
-        next_index
+        self.append_block(block)?;
+
+        Ok(next_index)
+    }
+
+    /// Seal a new Block with the Consensus Engine of the Chain.
+    ///
+    /// Time-locked `Transaction`s stay pending until the Block can include them.
+    /// `Transaction`s which do not fit the Ledger or spend spent Outputs are dropped.
+    /// In UTXO Mode the Block starts with a Coinbase for the Reward Address which
+    /// grants the Mining Reward and the Fees.
+    ///
+    /// # Parameters:
+    /// - `transaction_mutex`: List of `Transaction`s to be included in the Block.
+    ///
+    /// # Returns:
+    /// - `new_proof`: The nonce of the sealed Block or the Error of the Consensus Engine
+    ///   if it may not seal the Block now.
+    ///
+    pub fn seal_block(
+        &mut self,
+        transaction_mutex: &web::Data<MutexTransactionList>,
+    ) -> Result<u64, ConsensusError> {
+        self.seal_block_with_progress(transaction_mutex, &mut |_| {})
     }
 
     /// Proof of Work (PoW) Consensus Protocol.
     ///
+    /// It is kept for former Callers and seals the Block like `seal_block()`
+    /// with the Consensus Engine of the Chain.
+    ///
     /// # Parameters:
     /// - `transaction_mutex`: List of `Transaction`s to be included in the Block.
     ///
     /// # Returns:
     /// - `new_proof`: The nonce calculated through the PoW.
     ///
-    pub fn proof_of_work(&mut self, transaction_mutex: &web::Data<MutexTransactionList>) -> u64 {
+    pub fn proof_of_work(
+        &mut self,
+        transaction_mutex: &web::Data<MutexTransactionList>,
+    ) -> Result<u64, ConsensusError> {
+        self.seal_block(transaction_mutex)
+    }
+
+    /// Seal a new Block with Progress Reports.
+    ///
+    /// # Parameters:
+    /// - `transaction_mutex`: List of `Transaction`s to be included in the Block.
+    /// - `report`: Callback which receives a `MiningProgress` when the Seal starts
+    ///   and once per second while it runs.
+    ///
+    /// # Returns:
+    /// - `new_proof`: The nonce of the sealed Block or the Error of the Consensus Engine.
+    ///   The selected `Transaction`s are given back to the pending `Transaction`s
+    ///   if the Block was not sealed.
+    ///
+    pub fn seal_block_with_progress(
+        &mut self,
+        transaction_mutex: &web::Data<MutexTransactionList>,
+        report: &mut dyn FnMut(&MiningProgress),
+    ) -> Result<u64, ConsensusError> {
         let last_block = self.get_last_block();
         let last_hash = match last_block {
-            Some(b) => b.to_hash(),
-            None => String::from("0"),
+            Some(b) => self.get_block_hash(b)?,
+            None => String::from(GENESIS_PREVIOUS_HASH),
         };
         let next_index = match last_block {
             Some(b) => b.index + 1,
             None => 1,
         };
-        let mut new_proof: u64 = 0;
-        let transactions = transaction_mutex.into_vec();
-        let mut new_block = Block::build_block(
+        let lock_time = self.get_lock_time();
+        let mut new_block = Block::build_block(next_index, 0, last_hash.as_str(), None);
+        let start = Instant::now();
+        let mut ledger_view = self.get_ledger_view();
+
+        // The Block must be younger than the Median Time Past
+        new_block.update_timestamp(Some(new_block.timestamp.max(lock_time + 1)));
+        new_block.transactions = self.select_transactions(
+            &mut ledger_view,
             next_index,
-            new_proof,
-            last_hash.as_str(),
-            Some(transactions),
+            lock_time,
+            transaction_mutex.take_unlocked(next_index, lock_time),
         );
-        let mut proof_matches = false;
-        let mut last_timestamp = new_block.timestamp;
 
-        while !proof_matches {
-            new_block.proof = new_proof;
-            if new_block.update_timestamp(None) != last_timestamp {
-                last_timestamp = new_block.timestamp;
+        if self.ledger_mode == LedgerMode::Utxo {
+            match self.build_coinbase(next_index, ledger_view.fees) {
+                Ok(c) => new_block.transactions.insert(0, c),
+                Err(e) => {
+                    transaction_mutex.restore(new_block.transactions);
+
+                    return Err(ConsensusError::new(&e.status, &e.report));
+                }
+            }
+        }
+
+        let engine = self.get_engine();
+        let validator_set = self.get_current_validators();
+        let context = SealContext {
+            ancestors: &self.chain,
+            validator_set: &validator_set,
+            stakes: &self.stake_ledger,
+            block_hash: None,
+            bits: self.get_target_bits(),
+            hash_algorithm: self.hash_algorithm,
+        };
+
+        report(&MiningProgress {
+            block_index: next_index,
+            transaction_count: new_block.transactions.len(),
+            ..Default::default()
+        });
+
+        let sealed = engine.seal(&context, &mut new_block, &mut |block, progress| {
+            if transaction_mutex.get_count() != 0 {
+                let mut tx = self.select_transactions(
+                    &mut ledger_view,
+                    next_index,
+                    lock_time,
+                    transaction_mutex.take_unlocked(next_index, lock_time),
+                );
+
+                tx.drain(..).for_each(|t| block.transactions.push(t));
+
+                // The Coinbase also grants the Fees of the added Transactions
+                if let Some(c) = block.transactions.first_mut().filter(|t| t.is_coinbase()) {
+                    c.outputs[0].amount = self.get_coinbase_amount(ledger_view.fees);
+                }
+            }
+
+            let elapsed = start.elapsed();
+
+            report(&MiningProgress {
+                block_index: next_index,
+                attempts: progress.attempts,
+                hash_rate: progress.attempts as f64 / elapsed.as_secs_f64(),
+                best_hash: progress.best_hash.clone(),
+                transaction_count: block.transactions.len(),
+                elapsed_ms: elapsed.as_millis() as u64,
+            });
+        });
+
+        if let Err(e) = sealed {
+            transaction_mutex.restore(Self::without_coinbase(new_block.transactions));
+
+            return Err(e);
+        }
+
+        let new_proof = new_block.proof;
+        let transactions = new_block.transactions.clone();
+
+        // Store newly sealed Block
+        if let Err(e) = self.append_block(new_block) {
+            transaction_mutex.restore(Self::without_coinbase(transactions));
+
+            return Err(ConsensusError::new(&e.status, &e.report));
+        }
+
+        Ok(new_proof)
+    }
+
+    /// Seal the Proof of Authority Blocks with the Key of a Validator.
+    pub fn set_validator_key(&mut self, validator_key: Option<SigningKey>) {
+        self.validator_key = validator_key;
+    }
+
+    /// Grant the Coinbase of the UTXO Blocks this Node seals to an Address.
+    pub fn set_reward_address(&mut self, reward_address: &str) {
+        self.reward_address = reward_address.to_owned();
+    }
+
+    /// Vote for adding or removing a Validator in the Blocks this Node seals.
+    ///
+    /// A former Proposal for the same Validator is replaced.
+    pub fn propose_validator(&mut self, vote: ValidatorVote) -> Result<(), ConsensusError> {
+        if seal::decode_public_key(&vote.validator).is_none() {
+            return Err(ConsensusError::new(
+                "invalid",
+                "Proposal: Validator must be a hex encoded Ed25519 Public Key",
+            ));
+        }
+
+        self.proposals.retain(|v| v.validator != vote.validator);
+        self.proposals.push(vote);
+
+        Ok(())
+    }
+
+    /// Replace the local Chain with a competing Chain.
+    ///
+    /// The competing Chain is only adopted if it is valid and the Consensus Engine
+    /// prefers it. All Engines follow the Longest Chain Rule by default.
+    ///
+    /// # Parameters:
+    /// - `chain`: The competing Chain as received from another Node.
+    ///
+    /// # Returns:
+    /// - The Blocks of the local Chain which were discarded from the fork point on
+    ///   or `None` if the local Chain was kept.
+    ///
+    pub fn replace_chain(&mut self, chain: Vec<Block>) -> Option<Vec<Block>> {
+        if !self.get_engine().choose_fork(&self.chain, &chain) {
+            return None;
+        }
+
+        let block_hashes = self.check_chain(&chain).ok()?;
+        let fork_position = self
+            .block_hashes
+            .iter()
+            .zip(block_hashes.iter())
+            .take_while(|(local, remote)| local == remote)
+            .count();
+
+        // The UTXO Set is updated on a Copy so that a failed Update keeps the local Chain
+        if self.ledger_mode == LedgerMode::Utxo {
+            let mut utxo_set = self.utxo_set.clone();
+
+            self.chain[fork_position..]
+                .iter()
+                .rev()
+                .for_each(|b| utxo_set.rollback_block(b));
+
+            for block in chain[fork_position..].iter() {
+                if let Err(e) = utxo_set.apply_block(block, self.mining_reward) {
+                    eprintln!(
+                        "Block ({}): UTXO Set update failed! Chain is refused: {}",
+                        block.index, e.report
+                    );
+
+                    return None;
+                }
+            }
+
+            self.utxo_set = utxo_set;
+        }
+
+        let mut previous_chain = std::mem::replace(&mut self.chain, chain);
+        let orphaned_blocks = previous_chain.split_off(fork_position);
+
+        self.block_hashes = block_hashes;
+
+        self.transaction_index
+            .rebuild(&self.chain, &orphaned_blocks);
+        self.address_index = AddressIndex::from_chain(&self.chain);
+
+        self.rebuild_ledgers();
+        self.chainwork = pow::get_chain_work(&self.chain);
+
+        if self.consensus == ConsensusMode::ProofOfStake {
+            // Validators which sealed both an orphaned and an adopted Block signed twice
+            for orphaned_block in orphaned_blocks.iter() {
+                let evidence = self
+                    .get_block(orphaned_block.index)
+                    .and_then(|b| DoubleSignEvidence::from_blocks(orphaned_block, b));
+
+                if let Some(e) = evidence {
+                    if let Err(err) = self.report_evidence(e) {
+                        eprintln!("Block ({}): {}", orphaned_block.index, err.report);
+                    }
+                }
+            }
+        }
+
+        Some(orphaned_blocks)
+    }
+
+    /// Give the `Transaction`s of orphaned Blocks back to the waiting `Transaction`s.
+    ///
+    /// Mining Rewards, `Transaction`s which the new Chain confirmed or which are waiting
+    /// already and `Transaction`s which do not fit the Ledger of the new Chain are left out.
+    ///
+    /// # Returns:
+    /// - The number of `Transaction`s which are waiting again
+    ///
+    pub fn requeue_orphaned(
+        &self,
+        orphaned_blocks: &[Block],
+        transaction_mutex: &MutexTransactionList,
+    ) -> usize {
+        let mut count = 0;
+
+        for transaction in orphaned_blocks.iter().flat_map(|b| b.transactions.iter()) {
+            let txid = match transaction.to_hash() {
+                Ok(t) => t,
+                Err(e) => {
+                    eprintln!("Transaction: Transaction dropped! {}", e);
+
+                    continue;
+                }
+            };
+
+            if transaction.sender == COINBASE_SENDER
+                || self.transaction_index.get_block_index(&txid).is_some()
+                || transaction_mutex.contains(&txid)
+            {
+                continue;
+            }
+
+            if let Err(e) = self.check_transaction(transaction, transaction_mutex) {
+                eprintln!("Transaction ({}): Transaction dropped! {}", txid, e.report);
+
+                continue;
+            }
+
+            match transaction_mutex.add_transaction(transaction.clone()) {
+                Ok(_) => count += 1,
+                Err(e) => eprintln!("Transaction ({}): Requeue failed! {:?}", txid, e),
+            }
+        }
+
+        count
+    }
+
+    /// Upgrade a stored Chain of an older Format Version to `CHAIN_FORMAT_VERSION`.
+    ///
+    /// Proof of Work Blocks of Version 1 Chains get the Compact Target of the stored
+    /// `difficulty` which they were mined for.
+    /// The Links of older Chains are checked against the Hashes of the canonical
+    /// Encoding. Blocks which were hashed with a former Encoding cannot be migrated
+    /// because their Proofs and Signatures cover the former Hashes.
+    ///
+    /// # Returns:
+    /// - an Error if the Chain was stored with a newer Format Version or links
+    ///   to Hashes of a former Encoding
+    ///
+    pub fn migrate(&mut self) -> Result<(), LedgerError> {
+        if self.format_version > CHAIN_FORMAT_VERSION {
+            return Err(LedgerError::new(
+                "unsupported",
+                &format!(
+                    "Blockchain: Format Version {} is newer than {}",
+                    self.format_version, CHAIN_FORMAT_VERSION
+                ),
+            ));
+        }
+
+        if self.format_version < 2 && self.consensus == ConsensusMode::ProofOfWork {
+            let bits = self.get_target_bits();
+
+            self.chain
+                .iter_mut()
+                .filter(|b| b.bits == 0)
+                .for_each(|b| b.bits = bits);
+        }
 
-                if transaction_mutex.get_count() != 0 {
-                    let mut tx = transaction_mutex.into_vec();
+        if self.format_version < 3 {
+            let mut previous_hash = String::from(GENESIS_PREVIOUS_HASH);
 
-                    tx.drain(..).for_each(|t| new_block.transactions.push(t));
+            for block in self.chain.iter() {
+                if block.previous_hash != previous_hash {
+                    return Err(LedgerError::new(
+                        "legacy",
+                        &format!(
+                            "Block ({}): Previous Hash '{}' was computed with a former Encoding. \
+                            The Chain must be synchronised again from the Peers",
+                            block.index, block.previous_hash
+                        ),
+                    ));
                 }
+
+                previous_hash = self
+                    .get_block_hash(block)
+                    .map_err(|e| LedgerError::new(&e.status, &e.report))?;
             }
+        }
+
+        self.format_version = CHAIN_FORMAT_VERSION;
 
-            let block_hash = new_block.to_hash();
+        Ok(())
+    }
+
+    /// Rebuild the Indices after the Chain was loaded.
+    ///
+    /// # Returns:
+    /// - an Error if the UTXO Set cannot be built from the Chain
+    ///
+    pub fn rebuild_index(&mut self) -> Result<(), LedgerError> {
+        self.block_hashes = self
+            .chain
+            .iter()
+            .map(|b| self.get_block_hash(b))
+            .collect::<Result<_, _>>()
+            .map_err(|e| LedgerError::new(&e.status, &e.report))?;
+
+        if self.ledger_mode == LedgerMode::Utxo {
+            self.utxo_set =
+                UtxoSet::from_chain(&self.chain, self.mining_reward).map_err(|(index, e)| {
+                    LedgerError::new(
+                        &e.status,
+                        &format!("Block ({}): UTXO Set build failed! {}", index, e.report),
+                    )
+                })?;
+        }
+
+        self.transaction_index = TransactionIndex::from_chain(&self.chain);
+        self.address_index = AddressIndex::from_chain(&self.chain);
+        self.rebuild_ledgers();
+        self.chainwork = pow::get_chain_work(&self.chain);
 
-            if block_hash.starts_with("0000") {
-                proof_matches = true;
-                println!("Hash (Proof: {}): '{}'", new_proof, block_hash);
-            } else {
-                new_proof += 1;
+        Ok(())
+    }
+
+    /// Rebuild the Asset Ledger, the Stake Ledger and the Validator Set from the Chain.
+    fn rebuild_ledgers(&mut self) {
+        self.validator_set = ValidatorSet::from_chain(&self.authority.validators, &self.chain);
+        self.asset_ledger = match AssetLedger::from_chain(&self.chain) {
+            Ok(l) => l,
+            Err((index, e)) => {
+                eprintln!("Block ({}): Asset Ledger build failed! {}", index, e.report);
+
+                AssetLedger::new()
+            }
+        };
+        self.stake_ledger = match StakeLedger::from_chain(&self.chain) {
+            Ok(l) => l,
+            Err((index, e)) => {
+                eprintln!("Block ({}): Stake Ledger build failed! {}", index, e.report);
+
+                StakeLedger::new()
             }
+        };
+    }
+
+    /// Append a Block to the Chain and register it in the Indices.
+    ///
+    /// A Block which does not fit the UTXO Set is refused.
+    fn append_block(&mut self, block: Block) -> Result<(), LedgerError> {
+        let block_hash = self
+            .get_block_hash(&block)
+            .map_err(|e| LedgerError::new(&e.status, &e.report))?;
+
+        if self.ledger_mode == LedgerMode::Utxo {
+            self.utxo_set.apply_block(&block, self.mining_reward)?;
+        }
+
+        if self.chain.is_empty() {
+            self.validator_set = ValidatorSet::new(&self.authority.validators);
+        }
+
+        self.validator_set.apply_block(&block);
+        self.chainwork += pow::get_block_work(block.bits);
+        self.transaction_index.add_block(&block);
+        self.address_index.add_block(&block);
+
+        if let Err(e) = self.asset_ledger.apply_block(&block) {
+            eprintln!(
+                "Block ({}): Asset Ledger update failed! {}",
+                block.index, e.report
+            );
+        }
+
+        if let Err(e) = self.stake_ledger.apply_block(&block) {
+            eprintln!(
+                "Block ({}): Stake Ledger update failed! {}",
+                block.index, e.report
+            );
         }
 
-        // Store newly mined Block
-        self.chain.push(new_block);
+        // Punished Double Signatures are not reported again
+        self.evidence
+            .retain(|e| self.stake_ledger.check_evidence(e).is_ok());
+
+        self.chain.push(block);
+        self.block_hashes.push(block_hash);
+
+        Ok(())
+    }
+
+    /// Keep the `Transaction`s which a Block can include in the given order.
+    ///
+    /// `ledger_view` is the Ledger State including the `Transaction`s which were
+    /// already selected for the Block. `Transaction`s whose Scripts fail at the
+    /// Block Index and the Median Time Past `lock_time` are dropped.
+    fn select_transactions(
+        &self,
+        ledger_view: &mut LedgerView,
+        block_index: u64,
+        lock_time: u32,
+        transactions: Vec<Transaction>,
+    ) -> Vec<Transaction> {
+        transactions
+            .into_iter()
+            .filter(|t| {
+                let result = match ledger_view.utxo_set.as_mut() {
+                    _ if t.check_scripts(block_index, lock_time).is_err() => Err(LedgerError::new(
+                        "failed",
+                        "Transaction: Spending Script failed",
+                    )),
+                    Some(_) if t.is_coinbase() => Err(LedgerError::new(
+                        "unauthorized",
+                        "Transaction: The Coinbase is created with the Block",
+                    )),
+                    Some(u) => u
+                        .apply_transaction(block_index, t)
+                        .map(|fee| ledger_view.fees += fee),
+                    None if t.is_utxo() => Err(LedgerError::new(
+                        "invalid",
+                        "Transaction: UTXO Transactions are not accepted in Account Mode",
+                    )),
+                    None if t.is_htlc() => Err(LedgerError::new(
+                        "invalid",
+                        "Transaction: Hash Time-Locked Contracts are only accepted in UTXO Mode",
+                    )),
+                    None if t.stake.is_some() && !self.is_staking() => Err(LedgerError::new(
+                        "invalid",
+                        "Transaction: Staking is only accepted in Proof of Stake Mode",
+                    )),
+                    None => ledger_view
+                        .assets
+                        .apply_transaction(block_index, t)
+                        .and_then(|_| ledger_view.stakes.apply_transaction(t)),
+                };
+
+                if let Err(e) = result.as_ref() {
+                    eprintln!("Block ({}): Transaction dropped! {}", block_index, e.report);
+                }
 
-        new_proof
+                result.is_ok()
+            })
+            .collect()
     }
 
     /*----------------------------------------------------------------------------
      * Consultation Methods
      */
 
+    /// Check whether a Block Hash satisfies the Proof of Work at the Target
+    /// of this Blockchain.
+    pub fn is_proof_valid(&self, block_hash: &str) -> bool {
+        pow::is_hash_valid(block_hash, self.get_target_bits())
+    }
+
+    /// Compact Proof of Work Target of new Blocks
+    pub fn get_target_bits(&self) -> u32 {
+        self.target_bits
+            .unwrap_or_else(|| pow::difficulty_to_bits(self.difficulty))
+    }
+
+    /// Median Time Past of the Chain against which the Time Locks of the next Block
+    /// are evaluated
+    pub fn get_lock_time(&self) -> u32 {
+        get_median_time_past(&self.chain)
+    }
+
+    /// Cumulative Proof of Work of the Chain
+    pub fn get_chainwork(&self) -> &BigUint {
+        &self.chainwork
+    }
+
+    /// Create the Consensus Engine which seals and verifies the Blocks of this Chain.
+    pub fn get_engine(&self) -> Box<dyn ConsensusEngine> {
+        match (self.consensus, self.validator_key.as_ref()) {
+            (ConsensusMode::ProofOfAuthority, Some(k)) => Box::new(ProofOfAuthority::with_key(
+                &self.authority,
+                k,
+                &self.proposals,
+            )),
+            (ConsensusMode::ProofOfStake, Some(k)) => {
+                Box::new(ProofOfStake::with_key(&self.authority, k, &self.evidence))
+            }
+            (mode, _) => consensus::build_engine(mode, &self.authority),
+        }
+    }
+
+    /// Current Validators of a Proof of Authority Chain with the pending Votes
+    pub fn get_validator_set(&self) -> ValidatorSet {
+        self.get_current_validators().into_owned()
+    }
+
+    /// Validators after the last Block. The initial Validators apply to an empty Chain.
+    fn get_current_validators(&self) -> Cow<'_, ValidatorSet> {
+        if self.chain.is_empty() {
+            Cow::Owned(ValidatorSet::new(&self.authority.validators))
+        } else {
+            Cow::Borrowed(&self.validator_set)
+        }
+    }
+
+    /// Changes of the Validator Set this Node votes for
+    pub fn get_proposals(&self) -> &[ValidatorVote] {
+        &self.proposals
+    }
+
+    /// Check whether the Chain accepts Staking `Transaction`s and Evidence.
+    pub fn is_staking(&self) -> bool {
+        self.consensus == ConsensusMode::ProofOfStake
+    }
+
+    /// List the Stakes ordered by the Public Keys of the Validators.
+    pub fn get_stakes(&self) -> Vec<&Stake> {
+        self.stake_ledger.get_stakes()
+    }
+
+    pub fn get_total_stake(&self) -> f64 {
+        self.stake_ledger.get_total()
+    }
+
+    /// Double Signatures this Node reports in the next Block it seals
+    pub fn get_pending_evidence(&self) -> &[DoubleSignEvidence] {
+        &self.evidence
+    }
+
+    /// Check whether a Chain of Blocks is valid.
+    ///
+    /// Each Block must link to the hash of its predecessor and its Seal must satisfy
+    /// the Consensus Engine. Its Timestamp must be above the Median Time Past of the
+    /// last `MEDIAN_TIME_SPAN` Blocks and at most `MAX_FUTURE_DRIFT` Seconds ahead.
+    /// Each `Transaction` must be valid with its Signatures like `is_valid_in_chain()`.
+    /// Time-locked `Transaction`s must not be included before their Lock expired and
+    /// their Spending Scripts must succeed in their Block. Both are evaluated against
+    /// the Median Time Past.
+    /// In Account Mode the Rewards of a Block must not exceed the Mining Reward.
+    /// In UTXO Mode each Output must be spent at most once.
+    pub fn is_chain_valid(&self, chain: &[Block]) -> bool {
+        self.find_invalid_block(chain).is_none()
+    }
+
+    /// Find the first Block which does not link to its predecessor, whose Timestamp
+    /// or Seal is rejected or which includes an invalid or unsigned `Transaction`,
+    /// a `Transaction` too early, one whose Scripts fail or one which does not fit
+    /// the Ledger.
+    pub fn find_invalid_block<'a>(&self, chain: &'a [Block]) -> Option<&'a Block> {
+        self.check_chain(chain).err()
+    }
+
+    /// Check a Chain like `find_invalid_block()` and hash each Block only once.
+    ///
+    /// # Returns:
+    /// - the Hashes of the Blocks or the first invalid Block
+    ///
+    fn check_chain<'a>(&self, chain: &'a [Block]) -> Result<Vec<String>, &'a Block> {
+        let mut block_hashes = Vec::with_capacity(chain.len());
+        let mut previous_hash = String::from(GENESIS_PREVIOUS_HASH);
+        let mut previous_index = 0;
+        let mut utxo_set = UtxoSet::new();
+        let mut asset_ledger = AssetLedger::new();
+        let mut stake_ledger = StakeLedger::new();
+        let mut validator_set = ValidatorSet::new(&self.authority.validators);
+        let engine = self.get_engine();
+        let now = get_timestamp();
+
+        for (position, block) in chain.iter().enumerate() {
+            if block.index != previous_index + 1 || block.previous_hash != previous_hash {
+                return Err(block);
+            }
+
+            if !is_timestamp_valid(&chain[..position], block, now) {
+                return Err(block);
+            }
+
+            let lock_time = get_median_time_past(&chain[..position]);
+
+            // Signatures and Form are checked like those of new Transactions
+            if !block.transactions.iter().all(|t| {
+                t.is_valid_in_chain()
+                    && t.is_unlocked(block.index, lock_time)
+                    && t.check_scripts(block.index, lock_time).is_ok()
+            }) {
+                return Err(block);
+            }
+
+            let block_hash = match self.get_block_hash(block) {
+                Ok(h) => h,
+                Err(_) => return Err(block),
+            };
+            // The Seal is checked against the Validators and Stakes of the Ancestors
+            let context = SealContext {
+                ancestors: &chain[..position],
+                validator_set: &validator_set,
+                stakes: &stake_ledger,
+                block_hash: Some(&block_hash),
+                bits: self.get_target_bits(),
+                hash_algorithm: self.hash_algorithm,
+            };
+
+            if !engine.verify_seal(&context, block) {
+                return Err(block);
+            }
+
+            let is_staking_valid = self.is_staking()
+                || (block.evidence.is_empty()
+                    && block.transactions.iter().all(|t| t.stake.is_none()));
+            let is_ledger_valid = match self.ledger_mode {
+                LedgerMode::Account => {
+                    block
+                        .transactions
+                        .iter()
+                        .all(|t| !t.is_utxo() && !t.is_htlc())
+                        && StakeLedger::check_rewards(block, self.mining_reward).is_ok()
+                        && asset_ledger.apply_block(block).is_ok()
+                        && stake_ledger.apply_block(block).is_ok()
+                }
+                LedgerMode::Utxo => utxo_set.apply_block(block, self.mining_reward).is_ok(),
+            };
+
+            if !is_staking_valid {
+                return Err(block);
+            }
+
+            if !is_ledger_valid {
+                return Err(block);
+            }
+
+            validator_set.apply_block(block);
+
+            previous_hash = block_hash.clone();
+            previous_index = block.index;
+            block_hashes.push(block_hash);
+        }
+
+        Ok(block_hashes)
+    }
+
     pub fn get_last_block_index(&self) -> u64 {
-        if self.chain.len() > 0 {
+        if !self.chain.is_empty() {
             self.chain[self.chain.len() - 1].index
         } else {
             0
@@ -254,17 +1094,613 @@ impl Blockchain {
     }
 
     pub fn get_last_block(&self) -> Option<&Block> {
-        if self.chain.len() > 0 {
+        if !self.chain.is_empty() {
             Some(&self.chain[self.chain.len() - 1])
         } else {
             None
         }
     }
-}
 
-//==============================================================================
+    /// Look up a Block by its index.
+    ///
+    /// Block indices start at ` 1 ` with the Genesis Block.
+    pub fn get_block(&self, index: u64) -> Option<&Block> {
+        if index == 0 {
+            return None;
+        }
+
+        match self.chain.get((index - 1) as usize) {
+            Some(b) if b.index == index => Some(b),
+            _ => self.chain.iter().find(|b| b.index == index),
+        }
+    }
+
+    /// Compute the Hash of a Block with the Hash Algorithm of the Chain.
+    pub fn get_block_hash(&self, block: &Block) -> Result<String, ConsensusError> {
+        block.to_hash_with(self.hash_algorithm)
+    }
+
+    /// Look up the Hash of a Block of the Chain by its index without hashing it again.
+    pub fn get_block_hash_by_index(&self, index: u64) -> Option<&str> {
+        let position = match self.chain.get(index.checked_sub(1)? as usize) {
+            Some(b) if b.index == index => index as usize - 1,
+            _ => self.chain.iter().position(|b| b.index == index)?,
+        };
+
+        self.block_hashes.get(position).map(|h| h.as_str())
+    }
+
+    /// Look up a Block by its hash.
+    pub fn get_block_by_hash(&self, hash: &str) -> Option<&Block> {
+        let position = self.block_hashes.iter().position(|h| h == hash)?;
+
+        self.chain.get(position)
+    }
+
+    /// Look up a confirmed `Transaction` by its Transaction Id.
+    pub fn get_transaction(&self, txid: &str) -> Option<&Transaction> {
+        let (block_index, position) = *self.transaction_index.get_locations(txid).first()?;
+
+        self.get_block(block_index)?.transactions.get(position)
+    }
+
+    /// Look up the Status of a `Transaction` in the Chain.
+    ///
+    /// # Parameters:
+    /// - `txid`: The Transaction Id as calculated by `Transaction::to_hash()`.
+    /// - `transaction_mutex`: List of `Transaction`s waiting for the next Block.
+    ///
+    pub fn get_transaction_status(
+        &self,
+        txid: &str,
+        transaction_mutex: &MutexTransactionList,
+    ) -> TransactionStatus {
+        if let Some(block_index) = self.transaction_index.get_block_index(txid) {
+            return TransactionStatus::Confirmed {
+                block_index,
+                confirmations: self.get_last_block_index() + 1 - block_index,
+            };
+        }
+
+        if transaction_mutex.contains(txid) {
+            TransactionStatus::Pending
+        } else if self.transaction_index.is_dropped(txid) {
+            TransactionStatus::Dropped
+        } else {
+            TransactionStatus::Unknown
+        }
+    }
+
+    /// Check whether a new `Transaction` fits the Ledger of the Chain.
+    ///
+    /// In UTXO Mode its Inputs must be unspent in the Chain and must not be spent
+    /// by any `Transaction` waiting for the next Block. Mining Rewards are only
+    /// created by the Node.
+    /// In Account Mode Asset Transfers must be covered by the Asset Balance of the
+    /// Sender minus the Amounts it sends with pending `Transaction`s.
+    /// In Account Mode the native Balance of the Sender minus its pending Amounts must
+    /// cover the `amount` and the `nonce` must be higher than any former Nonce of the Sender.
+    /// Spending Scripts must succeed in the next Block at the Median Time Past of the Chain.
+    /// A `Transaction` which is confirmed or waiting already is a Duplicate.
+    pub fn check_transaction(
+        &self,
+        transaction: &Transaction,
+        transaction_mutex: &MutexTransactionList,
+    ) -> Result<(), LedgerError> {
+        let next_index = self.get_last_block_index() + 1;
+        let txid = transaction.to_hash().map_err(LedgerError::from_encoding)?;
+
+        if self.transaction_index.get_block_index(&txid).is_some()
+            || transaction_mutex.contains(&txid)
+        {
+            return Err(LedgerError::new(
+                "duplicate",
+                &format!("Transaction ({}): Transaction is already known", txid),
+            ));
+        }
+
+        Self::check_sender(transaction)?;
+
+        if let Err(e) = transaction.check_scripts(next_index, self.get_lock_time()) {
+            return Err(LedgerError::new(&e.status, &e.report));
+        }
+
+        if self.ledger_mode == LedgerMode::Account && !transaction.is_utxo() {
+            let last_nonce = self
+                .stake_ledger
+                .get_nonce(&transaction.sender)
+                .max(transaction_mutex.get_pending_nonce(&transaction.sender));
+
+            if transaction.nonce <= last_nonce {
+                return Err(LedgerError::new(
+                    "replay",
+                    &format!(
+                        "Transaction ({}): Nonce must be higher than {}",
+                        txid, last_nonce
+                    ),
+                ));
+            }
+        }
+
+        match self.ledger_mode {
+            LedgerMode::Account if transaction.is_utxo() => Err(LedgerError::new(
+                "invalid",
+                "Transaction: UTXO Transactions are not accepted in Account Mode",
+            )),
+            LedgerMode::Account if transaction.is_htlc() => Err(LedgerError::new(
+                "invalid",
+                "Transaction: Hash Time-Locked Contracts are only accepted in UTXO Mode",
+            )),
+            LedgerMode::Account if transaction.stake.is_some() => match transaction.stake {
+                _ if !self.is_staking() => Err(LedgerError::new(
+                    "invalid",
+                    "Transaction: Staking is only accepted in Proof of Stake Mode",
+                )),
+                Some(StakeAction::Stake) => self.stake_ledger.check_transaction(
+                    transaction,
+                    transaction_mutex.get_pending_debit(&transaction.sender),
+                ),
+                Some(action) => self.stake_ledger.check_transaction(
+                    transaction,
+                    transaction_mutex.get_pending_stake(&transaction.sender, action),
+                ),
+                None => Ok(()),
+            },
+            LedgerMode::Account if transaction.is_native() => self.stake_ledger.check_transaction(
+                transaction,
+                transaction_mutex.get_pending_debit(&transaction.sender),
+            ),
+            LedgerMode::Account => {
+                let pending_amounts = match transaction.asset.as_ref() {
+                    Some(a) => transaction_mutex.get_pending_amounts(&transaction.sender, a),
+                    None => Vec::new(),
+                };
+
+                self.asset_ledger
+                    .check_transaction(transaction, &pending_amounts)
+            }
+            LedgerMode::Utxo if !transaction.is_utxo() || transaction.is_coinbase() => {
+                Err(LedgerError::new(
+                    "invalid",
+                    "Transaction: Only signed UTXO Transactions are accepted in UTXO Mode",
+                ))
+            }
+            LedgerMode::Utxo => {
+                let outpoints: Vec<OutPoint> =
+                    transaction.inputs.iter().map(|i| i.to_outpoint()).collect();
+
+                self.utxo_set.check_transaction(transaction)?;
+
+                if transaction_mutex.is_any_spent(&outpoints) {
+                    return Err(LedgerError::new(
+                        "spent",
+                        "Transaction: Outputs are already spent by a pending Transaction",
+                    ));
+                }
+
+                Ok(())
+            }
+        }
+    }
+
+    /// Check that a new `Transaction` is authorised by its Sender.
+    ///
+    /// Mining Rewards are only created by the Node. Every other `Transaction` must be sent
+    /// from an Address whose Key, Multi-Signature Policy or Script authorises it.
+    fn check_sender(transaction: &Transaction) -> Result<(), LedgerError> {
+        if transaction.sender == COINBASE_SENDER {
+            return Err(LedgerError::new(
+                "unauthorized",
+                "Transaction: Mining Rewards are only created by the Node",
+            ));
+        }
+
+        if !transaction.is_utxo() && !address::is_valid(&transaction.sender) {
+            return Err(LedgerError::new(
+                "unauthorized",
+                &format!(
+                    "Transaction: Sender '{}' is no valid Address",
+                    transaction.sender
+                ),
+            ));
+        }
+
+        if !transaction.is_valid() {
+            return Err(LedgerError::new(
+                "invalid",
+                "Transaction: Signature is missing or invalid",
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Report a Double Signature of a Validator.
+    ///
+    /// The Evidence is added to the next Block this Node seals and the Validator
+    /// loses its whole Stake.
+    pub fn report_evidence(&mut self, evidence: DoubleSignEvidence) -> Result<(), LedgerError> {
+        if !self.is_staking() {
+            return Err(LedgerError::new(
+                "invalid",
+                "Evidence: Evidence is only accepted in Proof of Stake Mode",
+            ));
+        }
+
+        self.stake_ledger.check_evidence(&evidence)?;
+
+        let is_reported = self.evidence.iter().any(|e| {
+            e.get_signer() == evidence.get_signer() && e.get_index() == evidence.get_index()
+        });
+
+        if !is_reported {
+            self.evidence.push(evidence);
+        }
+
+        Ok(())
+    }
+
+    /// Build the `Transaction`s which grant the Mining Reward for the next Block.
+    ///
+    /// In Proof of Stake Mode the Reward is distributed to the Stakers in proportion
+    /// to their Stakes. In UTXO Mode each Block grants its Reward with its own Coinbase.
+    pub fn build_rewards(&self, reward_address: &str, mining_reward: f64) -> Vec<Transaction> {
+        let total = self.stake_ledger.get_total();
+
+        if self.ledger_mode == LedgerMode::Utxo {
+            return Vec::new();
+        }
+
+        if !self.is_staking() || total <= 0f64 {
+            return vec![self.build_reward(reward_address, mining_reward)];
+        }
+
+        self.stake_ledger
+            .get_stakes()
+            .into_iter()
+            .map(|s| {
+                let mut reward = Transaction::from_data(
+                    String::from(COINBASE_SENDER),
+                    s.address.clone(),
+                    // Shares are rounded down so that they never exceed the Reward
+                    (mining_reward * s.amount / total * canonical::AMOUNT_UNITS).floor()
+                        / canonical::AMOUNT_UNITS,
+                );
+
+                // The Rewards of equal Stakes must have distinct Transaction Ids
+                reward.nonce = self.get_last_block_index() + 1;
+
+                reward
+            })
+            .collect()
+    }
+
+    /// Build the `Transaction` which grants the Mining Reward for the next Block.
+    ///
+    /// The Index of the next Block is its Nonce so that each Reward has its own
+    /// Transaction Id.
+    pub fn build_reward(&self, reward_address: &str, mining_reward: f64) -> Transaction {
+        match self.ledger_mode {
+            LedgerMode::Account => {
+                let mut reward = Transaction::from_data(
+                    String::from(COINBASE_SENDER),
+                    reward_address.to_owned(),
+                    mining_reward,
+                );
+
+                reward.nonce = self.get_last_block_index() + 1;
+
+                reward
+            }
+            LedgerMode::Utxo => Transaction::from_coinbase(
+                reward_address.to_owned(),
+                mining_reward,
+                self.get_last_block_index() + 1,
+            ),
+        }
+    }
+
+    /// Build the Coinbase of the next UTXO Block which grants the Mining Reward
+    /// and the Fees to the Reward Address.
+    ///
+    /// The Block Index is its Nonce to keep the Coinbase Ids unique.
+    fn build_coinbase(&self, block_index: u64, fees: i64) -> Result<Transaction, LedgerError> {
+        if self.reward_address.is_empty() {
+            return Err(LedgerError::new(
+                "invalid",
+                "Blockchain: UTXO Blocks need a Reward Address for their Coinbase",
+            ));
+        }
+
+        Ok(Transaction::from_coinbase(
+            self.reward_address.clone(),
+            self.get_coinbase_amount(fees),
+            block_index,
+        ))
+    }
+
+    /// Amount of the Coinbase which grants the Mining Reward and the Fees.
+    ///
+    /// It is summed in Base Units like `UtxoSet::apply_block()` checks it.
+    fn get_coinbase_amount(&self, fees: i64) -> f64 {
+        canonical::to_amount(canonical::round_amount_units(self.mining_reward) + fees)
+    }
+
+    /// Keep the `Transaction`s of an unsealed Block which can wait for the next Block
+    fn without_coinbase(transactions: Vec<Transaction>) -> Vec<Transaction> {
+        transactions
+            .into_iter()
+            .filter(|t| !t.is_coinbase())
+            .collect()
+    }
+
+    /// List the unspent Outputs of an Address in UTXO Mode.
+    pub fn get_unspent(&self, address: &str) -> Vec<(OutPoint, TxOutput)> {
+        self.utxo_set.get_unspent(address)
+    }
+
+    /// List the issued Assets ordered by their Asset Id.
+    pub fn get_assets(&self) -> Vec<&Asset> {
+        self.asset_ledger.get_assets()
+    }
+
+    pub fn get_asset(&self, asset_id: &str) -> Option<&Asset> {
+        self.asset_ledger.get_asset(asset_id)
+    }
+
+    pub fn get_asset_balance(&self, address: &str, asset_id: &str) -> f64 {
+        self.asset_ledger.get_balance(address, asset_id)
+    }
+
+    /// List the Addresses which hold an Asset from the largest to the smallest Balance.
+    pub fn get_asset_holders(&self, asset_id: &str) -> Vec<AssetHolder> {
+        self.asset_ledger.get_holders(asset_id)
+    }
+
+    /// Look up the Secret which a mined Claim of a Hash Time-Locked Contract revealed.
+    pub fn find_htlc_secret(&self, htlc: &Htlc) -> Option<Vec<u8>> {
+        self.get_address_transactions(&htlc.to_address())
+            .find_map(|(_, _, t)| htlc.get_secret(t))
+    }
+
+    /// Check whether an Address sent or received any `Transaction` in the mined Blocks.
+    pub fn is_address_used(&self, address: &str) -> bool {
+        !self.address_index.get_locations(address).is_empty()
+    }
+
+    /// Calculate the Balance of an Address.
+    ///
+    /// The Balance is the sum of all amounts received minus all amounts sent
+    /// within the `Transaction`s of the mined Blocks.
+    pub fn get_balance(&self, address: &str) -> f64 {
+        let (received, sent) = self.get_address_transactions(address).fold(
+            (0f64, 0f64),
+            |(received, sent), (block_index, _, t)| {
+                let (tx_received, tx_sent) = self.get_transfer(block_index, t, address);
+
+                (received + tx_received, sent + tx_sent)
+            },
+        );
+
+        received - sent
+    }
+
+    /// List the `Transaction`s of an Address from the newest to the oldest.
+    ///
+    /// # Parameters:
+    /// - `address`: The Address which sent or received the `Transaction`s.
+    /// - `direction`: Only list the `Transaction`s `Sent` or `Received` by the Address.
+    /// - `offset`: Number of `Transaction`s to skip.
+    /// - `limit`: Maximum number of `Transaction`s to list.
+    ///
+    pub fn get_address_history(
+        &self,
+        address: &str,
+        direction: Direction,
+        offset: usize,
+        limit: usize,
+    ) -> AddressHistory {
+        let mut total_received = 0f64;
+        let mut total_sent = 0f64;
+        let mut total_count = 0usize;
+
+        for (block_index, _, t) in self.get_address_transactions(address) {
+            let (received, sent) = self.get_transfer(block_index, t, address);
+
+            total_received += received;
+            total_sent += sent;
+
+            if direction.matches(t, address) {
+                total_count += 1;
+            }
+        }
+
+        // Only the `Transaction`s of the requested Page are copied
+        let transactions = self
+            .get_address_transactions(address)
+            .rev()
+            .filter(|(_, _, t)| direction.matches(t, address))
+            .skip(offset)
+            .take(limit)
+            .filter_map(|(block_index, position, t)| {
+                Some(AddressTransaction {
+                    txid: t.to_hash().ok()?,
+                    block_index,
+                    position,
+                    direction: Direction::of(t, address),
+                    transaction: t.clone(),
+                })
+            })
+            .collect();
+
+        AddressHistory {
+            address: address.to_owned(),
+            direction,
+            total_count,
+            total_received,
+            total_sent,
+            balance: total_received - total_sent,
+            offset,
+            limit,
+            transactions,
+        }
+    }
+
+    /// Calculate the Amounts an Address received and sent with a mined `Transaction`.
+    ///
+    /// The Amounts of spent Outputs are looked up in the UTXO Set.
+    /// Asset `Transaction`s do not move any Amount of the native Currency.
+    fn get_transfer(
+        &self,
+        block_index: u64,
+        transaction: &Transaction,
+        address: &str,
+    ) -> (f64, f64) {
+        let mut received: f64 = transaction
+            .outputs
+            .iter()
+            .filter(|o| o.address == address)
+            .map(|o| o.amount)
+            .sum();
+        let mut sent: f64 = transaction
+            .inputs
+            .iter()
+            .filter_map(|i| {
+                self.utxo_set
+                    .get_spent_output(block_index, &i.to_outpoint())
+            })
+            .filter(|o| o.address == address)
+            .map(|o| o.amount)
+            .sum();
+
+        if !transaction.is_native() {
+            return (received, sent);
+        }
+
+        // Stakes are locked away from the Balance of the Sender until they are released
+        match transaction.stake {
+            Some(StakeAction::Stake) if transaction.sender == address => {
+                return (received, sent + transaction.amount);
+            }
+            Some(StakeAction::Unstake) if transaction.sender == address => {
+                return (received + transaction.amount, sent);
+            }
+            Some(_) => return (received, sent),
+            None => {}
+        }
+
+        if transaction.receiver == address {
+            received += transaction.amount;
+        }
+
+        if transaction.sender == address {
+            sent += transaction.amount;
+        }
+
+        (received, sent)
+    }
+
+    /// Copy the Ledger State to check the `Transaction`s of a new Block
+    fn get_ledger_view(&self) -> LedgerView {
+        let utxo_set = match self.ledger_mode {
+            LedgerMode::Account => None,
+            LedgerMode::Utxo => Some(self.utxo_set.clone()),
+        };
+
+        LedgerView {
+            utxo_set,
+            fees: 0,
+            assets: self.asset_ledger.clone(),
+            stakes: self.stake_ledger.clone(),
+        }
+    }
+
+    /// Iterate over the `Transaction`s of an Address with their Block Index and Position
+    fn get_address_transactions<'a>(
+        &'a self,
+        address: &str,
+    ) -> impl DoubleEndedIterator<Item = (u64, usize, &'a Transaction)> + 'a {
+        self.address_index.get_locations(address).iter().filter_map(
+            move |&(block_index, position)| {
+                self.get_block(block_index)
+                    .and_then(|b| b.transactions.get(position))
+                    .map(|t| (block_index, position, t))
+            },
+        )
+    }
+}
+
+//==============================================================================
+// Structure LedgerError Implementation
+
+impl LedgerError {
+    /*----------------------------------------------------------------------------
+     * Constructors
+     */
+
+    pub fn new(status: &str, report: &str) -> Self {
+        Self {
+            status: status.to_owned(),
+            report: report.to_owned(),
+        }
+    }
+
+    /// Error for a `Transaction` whose canonical Encoding failed.
+    pub fn from_encoding(e: serde_json::Error) -> Self {
+        Self::new(
+            "invalid",
+            &format!("Transaction: Canonical Encoding failed! {}", e),
+        )
+    }
+}
+
+//==============================================================================
 // Auxiliary Functions
 
+fn default_format_version() -> u32 {
+    1
+}
+
+fn default_difficulty() -> usize {
+    4
+}
+
+fn default_mining_reward() -> f64 {
+    10.0
+}
+
+/// Current Unix Timestamp in Seconds
+pub fn get_timestamp() -> u32 {
+    match SystemTime::now().duration_since(SystemTime::UNIX_EPOCH) {
+        Ok(n) => n.as_secs() as u32,
+        Err(_) => 0,
+    }
+}
+
+/// Median Timestamp of the last `MEDIAN_TIME_SPAN` Blocks.
+///
+/// A single Miner cannot move it ahead, so Time Locks are evaluated against it.
+/// Without Blocks it is `0`.
+pub fn get_median_time_past(ancestors: &[Block]) -> u32 {
+    let mut timestamps: Vec<u32> = ancestors
+        .iter()
+        .rev()
+        .take(MEDIAN_TIME_SPAN)
+        .map(|b| b.timestamp)
+        .collect();
+
+    timestamps.sort_unstable();
+
+    timestamps.get(timestamps.len() / 2).copied().unwrap_or(0)
+}
+
+/// Check that the Timestamp of a Block is above the Median Time Past of its
+/// Ancestors and at most `MAX_FUTURE_DRIFT` Seconds ahead of `now`.
+pub fn is_timestamp_valid(ancestors: &[Block], block: &Block, now: u32) -> bool {
+    (ancestors.is_empty() || block.timestamp > get_median_time_past(ancestors))
+        && block.timestamp as u64 <= now as u64 + MAX_FUTURE_DRIFT as u64
+}
+
 /*    Protocolo de concenso Proof of Work (PoW).
       Arguments:
         - previous_proof: Nounce del bloque previo.
@@ -292,3 +1728,1456 @@ pub fn proof_of_work(previous_proof: u64) -> u64 {
 
     new_proof
 }
+
+//==============================================================================
+// Unit Tests
+
+#[test]
+fn instant_seal() {
+    let transactions = web::Data::new(MutexTransactionList::new());
+    let mut blockchain = Blockchain::with_difficulty(64);
+
+    blockchain.consensus = ConsensusMode::Instant;
+
+    let _ = transactions
+        .add_transaction(blockchain.build_reward("16Jswqk47s9PUcyCc88MMVwzgvHPvtEpf", 1.5));
+
+    assert_eq!(blockchain.seal_block(&transactions), Ok(0));
+    assert_eq!(blockchain.seal_block(&transactions), Ok(0));
+    assert_eq!(blockchain.chain.len(), 2);
+    assert_eq!(blockchain.chain[0].transactions.len(), 1);
+    assert!(blockchain.is_chain_valid(&blockchain.chain));
+
+    //-------------------------------------
+    // Proof of Work Chains reject the instantly sealed Blocks
+
+    let mut pow_chain = Blockchain::with_difficulty(64);
+
+    assert!(pow_chain.find_invalid_block(&blockchain.chain).is_some());
+    assert!(pow_chain.replace_chain(blockchain.chain.clone()).is_none());
+
+    //-------------------------------------
+    // Instantly sealed Chains never replace each other
+
+    let mut node = Blockchain::with_difficulty(64);
+
+    node.consensus = ConsensusMode::Instant;
+
+    assert!(node.replace_chain(blockchain.chain.clone()).is_none());
+    assert!(node.chain.is_empty());
+
+    let mut forged = blockchain.chain.clone();
+
+    forged[1].proof = 1;
+
+    assert_eq!(
+        blockchain.find_invalid_block(&forged).map(|b| b.index),
+        Some(2)
+    );
+
+    //-------------------------------------
+    // The Blocks must still link to their Predecessors
+
+    let mut broken = blockchain.chain.clone();
+
+    broken[1].previous_hash = String::from("0");
+
+    assert_eq!(
+        blockchain.find_invalid_block(&broken).map(|b| b.index),
+        Some(2)
+    );
+}
+
+#[test]
+fn hash_algorithms() {
+    use crate::consensus::hasher::HASH_ALGORITHMS;
+
+    for hash_algorithm in HASH_ALGORITHMS {
+        let transactions = web::Data::new(MutexTransactionList::new());
+        let mut blockchain = Blockchain::with_difficulty(1);
+
+        blockchain.hash_algorithm = hash_algorithm;
+
+        let _ = transactions.add_transaction_from_data(
+            "sender1",
+            "16Jswqk47s9PUcyCc88MMVwzgvHPvtEpf",
+            1.5,
+        );
+
+        blockchain.seal_block(&transactions).unwrap();
+        blockchain.seal_block(&transactions).unwrap();
+
+        let first_hash = blockchain.chain[0].to_hash_with(hash_algorithm).unwrap();
+
+        assert_eq!(blockchain.chain[1].previous_hash, first_hash);
+        assert!(blockchain
+            .get_block_hash(&blockchain.chain[1])
+            .unwrap()
+            .starts_with('0'));
+        assert_eq!(
+            blockchain.get_block_hash_by_index(1),
+            Some(first_hash.as_str())
+        );
+        assert_eq!(
+            blockchain.get_block_by_hash(&first_hash).map(|b| b.index),
+            Some(1)
+        );
+        assert!(blockchain.is_chain_valid(&blockchain.chain));
+
+        //-------------------------------------
+        // Chains with other Hash Algorithms reject the Blocks
+
+        let mut other = Blockchain::with_difficulty(1);
+
+        other.hash_algorithm = match hash_algorithm {
+            HashAlgorithm::Sha256 => HashAlgorithm::Blake3,
+            _ => HashAlgorithm::Sha256,
+        };
+
+        assert!(other.replace_chain(blockchain.chain.clone()).is_none());
+    }
+}
+
+#[test]
+fn proof_of_authority() {
+    let validator_key = SigningKey::from_bytes(&[4u8; 32]);
+    let transactions = web::Data::new(MutexTransactionList::new());
+    let mut blockchain = Blockchain::new();
+
+    blockchain.consensus = ConsensusMode::ProofOfAuthority;
+    // All Blocks fall into the same Slot
+    blockchain.authority = AuthorityParams {
+        validators: vec![hex::encode(validator_key.verifying_key().as_bytes())],
+        block_interval: u32::MAX,
+    };
+
+    // Without a Validator Key the Node cannot seal Blocks
+    assert_eq!(
+        blockchain.seal_block(&transactions).map_err(|e| e.status),
+        Err(String::from("unauthorized"))
+    );
+
+    blockchain.set_validator_key(Some(validator_key));
+
+    let _ = transactions
+        .add_transaction(blockchain.build_reward("16Jswqk47s9PUcyCc88MMVwzgvHPvtEpf", 1.5));
+
+    assert_eq!(blockchain.seal_block(&transactions), Ok(0));
+    assert_eq!(blockchain.chain[0].transactions.len(), 1);
+    assert!(blockchain.is_chain_valid(&blockchain.chain));
+
+    //-------------------------------------
+    // Each Slot is sealed only once and the Transactions stay pending
+
+    let _ = transactions
+        .add_transaction(blockchain.build_reward("1BcktgV7EjHmxEwQDFFhhztzNqZkd5gdm", 2.5));
+
+    assert_eq!(
+        blockchain.seal_block(&transactions).map_err(|e| e.status),
+        Err(String::from("waiting"))
+    );
+    assert_eq!(transactions.get_count(), 1);
+    assert_eq!(blockchain.chain.len(), 1);
+
+    //-------------------------------------
+    // Other Nodes verify the Seals without a Validator Key
+
+    let mut node = Blockchain::new();
+
+    node.consensus = ConsensusMode::ProofOfAuthority;
+    node.authority = blockchain.authority.clone();
+
+    assert!(node.replace_chain(blockchain.chain.clone()).is_some());
+    assert!(Blockchain::with_difficulty(64)
+        .find_invalid_block(&blockchain.chain)
+        .is_some());
+}
+
+#[test]
+fn proof_of_stake() {
+    let validator_key = SigningKey::from_bytes(&[5u8; 32]);
+    let staker_key = SigningKey::from_bytes(&[6u8; 32]);
+    let transactions = web::Data::new(MutexTransactionList::new());
+    let mut blockchain = Blockchain::new();
+
+    blockchain.consensus = ConsensusMode::ProofOfStake;
+    blockchain.authority = AuthorityParams {
+        validators: vec![hex::encode(validator_key.verifying_key().as_bytes())],
+        block_interval: 10,
+    };
+    blockchain.set_validator_key(Some(validator_key.clone()));
+
+    //-------------------------------------
+    // The first Block funds and stakes both Keys
+
+    let mut stakes = Vec::new();
+
+    for (key, amount) in [(&validator_key, 4.0), (&staker_key, 1.0)] {
+        let mut stake =
+            Transaction::from_stake(&key.verifying_key(), StakeAction::Stake, amount, 1);
+
+        stake.sign(key).unwrap();
+        transactions
+            .add_transaction(Transaction::from_data(
+                String::from(COINBASE_SENDER),
+                stake.sender.clone(),
+                5.0,
+            ))
+            .unwrap();
+        stakes.push(stake);
+    }
+
+    for stake in stakes.iter() {
+        assert_eq!(
+            blockchain
+                .check_transaction(stake, &transactions)
+                .map_err(|e| e.status),
+            Err(String::from("insufficient"))
+        );
+
+        transactions.add_transaction(stake.clone()).unwrap();
+    }
+
+    // The initial Validator is drawn while nothing is staked
+    assert_eq!(blockchain.seal_block(&transactions), Ok(0));
+    assert_eq!(blockchain.chain[0].transactions.len(), 4);
+    assert!(blockchain.is_chain_valid(&blockchain.chain));
+    assert_eq!(blockchain.get_total_stake(), 5.0);
+    assert_eq!(blockchain.get_balance(&stakes[0].sender), 1.0);
+
+    //-------------------------------------
+    // Rewards are shared by the Stakes and only staked Amounts are released
+
+    let rewards = blockchain.build_rewards("16Jswqk47s9PUcyCc88MMVwzgvHPvtEpf", 10.0);
+    let mut reward_block = Block::build_block(2, 0, "0", Some(rewards.clone()));
+
+    assert_eq!(
+        rewards
+            .iter()
+            .map(|r| (r.receiver.as_str(), r.amount))
+            .collect::<Vec<_>>(),
+        blockchain
+            .get_stakes()
+            .iter()
+            .map(|s| (s.address.as_str(), 10.0 * s.amount / 5.0))
+            .collect::<Vec<_>>()
+    );
+    assert!(StakeLedger::check_rewards(&reward_block, 10.0).is_ok());
+
+    // The Shares together must not exceed the Mining Reward
+    reward_block.transactions.push(rewards[0].clone());
+
+    assert!(StakeLedger::check_rewards(&reward_block, 10.0).is_err());
+
+    let mut unstake =
+        Transaction::from_stake(&staker_key.verifying_key(), StakeAction::Unstake, 1.5, 2);
+
+    unstake.sign(&staker_key).unwrap();
+
+    assert_eq!(
+        blockchain
+            .check_transaction(&unstake, &transactions)
+            .map_err(|e| e.status),
+        Err(String::from("insufficient"))
+    );
+
+    //-------------------------------------
+    // A second Signature at the same Height is reported once
+
+    let mut conflicting = blockchain.chain[0].clone();
+
+    conflicting.transactions.clear();
+    seal::sign_block(&mut conflicting, &validator_key, None);
+
+    let evidence = DoubleSignEvidence::from_blocks(&blockchain.chain[0], &conflicting).unwrap();
+
+    assert!(blockchain.report_evidence(evidence.clone()).is_ok());
+    assert!(blockchain.report_evidence(evidence.clone()).is_ok());
+    assert_eq!(
+        blockchain.get_pending_evidence(),
+        std::slice::from_ref(&evidence)
+    );
+
+    //-------------------------------------
+    // Other Consensus Modes reject Stakes and Evidence
+
+    let mut node = Blockchain::new();
+
+    assert_eq!(
+        node.check_transaction(&unstake, &transactions)
+            .map_err(|e| e.status),
+        Err(String::from("invalid"))
+    );
+    assert_eq!(
+        node.report_evidence(evidence).map_err(|e| e.status),
+        Err(String::from("invalid"))
+    );
+
+    node.consensus = ConsensusMode::ProofOfAuthority;
+    node.authority = blockchain.authority.clone();
+
+    assert!(node.find_invalid_block(&blockchain.chain).is_some());
+}
+
+#[test]
+fn chainwork_fork_choice() {
+    //-------------------------------------
+    // Targets between the Leading Zero Steps are met numerically
+
+    let transactions = web::Data::new(MutexTransactionList::new());
+    let mut easy = Blockchain::with_difficulty(1);
+
+    // Half of the Target of 1 leading zero
+    easy.target_bits = Some(0x2007_ffff);
+
+    for _ in 0..3 {
+        easy.seal_block(&transactions).unwrap();
+    }
+
+    for block in easy.chain.iter() {
+        assert_eq!(block.bits, 0x2007_ffff);
+        assert!(easy.get_block_hash(block).unwrap().as_str() < "08");
+    }
+
+    assert!(easy.is_chain_valid(&easy.chain));
+    assert_eq!(
+        easy.get_chainwork(),
+        &(pow::get_block_work(0x2007_ffff) * BigUint::from(3u32))
+    );
+
+    //-------------------------------------
+    // A shorter Chain with more Work replaces the longer Chain
+
+    let mut hard = Blockchain::with_difficulty(2);
+
+    hard.seal_block(&transactions).unwrap();
+    hard.seal_block(&transactions).unwrap();
+
+    assert!(hard.get_chainwork() > easy.get_chainwork());
+    assert!(hard.replace_chain(easy.chain.clone()).is_none());
+    assert_eq!(
+        easy.replace_chain(hard.chain.clone()).map(|o| o.len()),
+        Some(3)
+    );
+    assert_eq!(easy.get_last_block_index(), 2);
+    assert_eq!(easy.get_chainwork(), hard.get_chainwork());
+
+    // Blocks mined for a higher Target than the Chain requires are rejected
+    assert!(Blockchain::with_difficulty(3)
+        .find_invalid_block(&hard.chain)
+        .is_some());
+}
+
+#[test]
+fn replace_chain_longest_valid() {
+    //-------------------------------------
+    // A longer valid Chain replaces the local Chain from the fork point on
+
+    let transactions = web::Data::new(MutexTransactionList::new());
+    let mut local = Blockchain::new();
+
+    // The Genesis Block funds both Senders
+    for sender in ["sender1", "sender2"] {
+        let _ = transactions.add_transaction(local.build_reward(sender, 5.0));
+    }
+
+    local.seal_block(&transactions).unwrap();
+
+    let mut remote = Blockchain::from_chain(local.chain.clone()).unwrap();
+
+    let _ =
+        transactions.add_transaction_from_data("sender1", "16Jswqk47s9PUcyCc88MMVwzgvHPvtEpf", 1.5);
+    local.seal_block(&transactions).unwrap();
+
+    let _ =
+        transactions.add_transaction_from_data("sender2", "1BcktgV7EjHmxEwQDFFhhztzNqZkd5gdm", 2.5);
+    remote.seal_block(&transactions).unwrap();
+    remote.proof_of_work(&transactions).unwrap();
+
+    assert!(remote.is_chain_valid(&remote.chain));
+
+    // A shorter Chain is rejected
+    assert!(remote.replace_chain(local.chain.clone()).is_none());
+
+    let orphaned = local.replace_chain(remote.chain.clone()).unwrap();
+
+    assert_eq!(orphaned.len(), 1);
+    assert_eq!(orphaned[0].index, 2);
+    assert_eq!(local.get_last_block_index(), 3);
+
+    // The Transaction of the discarded Block is dropped
+    let dropped = Transaction::from_data(
+        "sender1".to_owned(),
+        "16Jswqk47s9PUcyCc88MMVwzgvHPvtEpf".to_owned(),
+        1.5,
+    );
+    let confirmed = Transaction::from_data(
+        "sender2".to_owned(),
+        "1BcktgV7EjHmxEwQDFFhhztzNqZkd5gdm".to_owned(),
+        2.5,
+    );
+
+    assert_eq!(
+        local.get_transaction_status(&dropped.to_hash().unwrap(), &transactions),
+        TransactionStatus::Dropped
+    );
+    assert_eq!(
+        local.get_transaction_status(&confirmed.to_hash().unwrap(), &transactions),
+        TransactionStatus::Confirmed {
+            block_index: 2,
+            confirmations: 2
+        }
+    );
+
+    // A tampered Chain is rejected
+    let mut tampered = local.chain.clone();
+
+    tampered[1].transactions[0].amount = 100f64;
+    tampered.push(Block::build_block(4, 0, "0", None));
+
+    assert!(!local.is_chain_valid(&tampered));
+    assert!(local.replace_chain(tampered).is_none());
+}
+
+#[test]
+fn replace_chain_forged_transfer() {
+    //-------------------------------------
+    // A longer Chain with an unsigned Transfer out of a Key Address is rejected
+
+    let victim_key = SigningKey::from_bytes(&[9u8; 32]);
+    let victim = address::from_public_key(&victim_key.verifying_key());
+    let transactions = web::Data::new(MutexTransactionList::new());
+    let mut local = Blockchain::new();
+
+    let _ = transactions.add_transaction(local.build_reward(&victim, 10.0));
+
+    local.seal_block(&transactions).unwrap();
+
+    let mut remote = Blockchain::from_chain(local.chain.clone()).unwrap();
+    let mut forged = Transaction::from_data(
+        victim.clone(),
+        "16Jswqk47s9PUcyCc88MMVwzgvHPvtEpf".to_owned(),
+        10.0,
+    );
+
+    forged.nonce = 1;
+
+    let _ = transactions.add_transaction(forged.clone());
+
+    remote.seal_block(&transactions).unwrap();
+    remote.proof_of_work(&transactions).unwrap();
+
+    assert_eq!(remote.get_balance(&victim), 0f64);
+    assert_eq!(
+        local.find_invalid_block(&remote.chain).map(|b| b.index),
+        Some(2)
+    );
+    assert!(local.replace_chain(remote.chain.clone()).is_none());
+    assert_eq!(local.get_balance(&victim), 10f64);
+
+    //-------------------------------------
+    // The same Transfer signed by the Victim is adopted
+
+    let mut signed = Blockchain::from_chain(local.chain.clone()).unwrap();
+
+    forged.sign(&victim_key).unwrap();
+
+    let _ = transactions.add_transaction(forged);
+
+    signed.seal_block(&transactions).unwrap();
+    signed.proof_of_work(&transactions).unwrap();
+
+    assert!(local.replace_chain(signed.chain.clone()).is_some());
+    assert_eq!(local.get_balance(&victim), 0f64);
+}
+
+#[test]
+fn reward_cap() {
+    //-------------------------------------
+    // A longer Chain whose Block grants more than the Mining Reward is rejected
+
+    let transactions = web::Data::new(MutexTransactionList::new());
+    let mut local = Blockchain::new();
+
+    let _ = transactions.add_transaction(local.build_reward("miner", local.mining_reward));
+
+    local.seal_block(&transactions).unwrap();
+
+    let mut remote = Blockchain::from_chain(local.chain.clone()).unwrap();
+
+    let _ = transactions.add_transaction(remote.build_reward("attacker", 10.00000001));
+
+    remote.seal_block(&transactions).unwrap();
+    remote.proof_of_work(&transactions).unwrap();
+
+    assert_eq!(remote.get_balance("attacker"), 10.00000001);
+    assert_eq!(
+        local.find_invalid_block(&remote.chain).map(|b| b.index),
+        Some(2)
+    );
+    assert!(local.replace_chain(remote.chain.clone()).is_none());
+
+    //-------------------------------------
+    // Several Rewards together are capped as well
+
+    let mut block = Block::build_block(2, 0, "0", None);
+
+    block.transactions = vec![
+        local.build_reward("miner", 6.0),
+        local.build_reward("attacker", 4.0),
+    ];
+
+    assert!(StakeLedger::check_rewards(&block, 10.0).is_ok());
+
+    block
+        .transactions
+        .push(local.build_reward("attacker", 0.00000001));
+
+    assert_eq!(
+        StakeLedger::check_rewards(&block, 10.0).map_err(|e| e.status),
+        Err(String::from("invalid"))
+    );
+}
+
+#[test]
+fn address_history_pages() {
+    //-------------------------------------
+    // The Address Index lists the Transactions of an Address newest first
+
+    let transactions = web::Data::new(MutexTransactionList::new());
+    let mut blockchain = Blockchain::new();
+
+    let _ = transactions.add_transaction_from_data("blockchain", "alice", 10.0);
+    let _ = transactions.add_transaction_from_data("blockchain", "bob", 10.0);
+    blockchain
+        .build_block(0, "0", transactions.clone())
+        .unwrap();
+
+    let _ = transactions.add_transaction_from_data("alice", "bob", 2.5);
+    let _ = transactions.add_transaction_from_data("bob", "alice", 1.0);
+    let _ = transactions.add_transaction_from_data("alice", "carol", 3.0);
+    let last_hash = blockchain.chain[0].to_hash().unwrap();
+
+    blockchain
+        .build_block(0, &last_hash, transactions.clone())
+        .unwrap();
+
+    let history = blockchain.get_address_history("alice", Direction::All, 0, 2);
+
+    assert_eq!(history.total_count, 4);
+    assert_eq!(history.total_received, 11.0);
+    assert_eq!(history.total_sent, 5.5);
+    assert_eq!(history.balance, blockchain.get_balance("alice"));
+    assert_eq!(history.transactions.len(), 2);
+    assert_eq!(history.transactions[0].block_index, 2);
+    assert_eq!(history.transactions[0].position, 2);
+    assert_eq!(history.transactions[0].transaction.receiver, "carol");
+
+    let history = blockchain.get_address_history("alice", Direction::Received, 1, 20);
+
+    assert_eq!(history.total_count, 2);
+    assert_eq!(history.transactions.len(), 1);
+    assert_eq!(history.transactions[0].transaction.sender, "blockchain");
+
+    // The Index is rebuilt for a loaded Chain
+    let loaded = Blockchain::from_chain(blockchain.chain.clone()).unwrap();
+
+    assert_eq!(
+        loaded
+            .get_address_history("bob", Direction::Sent, 0, 20)
+            .total_sent,
+        1.0
+    );
+    assert_eq!(
+        loaded
+            .get_address_history("nobody", Direction::All, 0, 20)
+            .total_count,
+        0
+    );
+}
+
+#[test]
+fn transaction_locations() {
+    //-------------------------------------
+    // Each Mining Reward has its own Transaction Id
+
+    let transactions = web::Data::new(MutexTransactionList::new());
+    let mut blockchain = Blockchain::new();
+
+    let first_reward = blockchain.build_reward("alice", 12.5);
+
+    let _ = transactions.add_transaction(first_reward.clone());
+    blockchain
+        .build_block(0, "0", transactions.clone())
+        .unwrap();
+
+    let second_reward = blockchain.build_reward("miner", 12.5);
+
+    assert_ne!(
+        first_reward.to_hash().unwrap(),
+        second_reward.to_hash().unwrap()
+    );
+
+    //-------------------------------------
+    // Identical Transactions of legacy Chains are indexed at every Location
+
+    let legacy = Transaction::from_data("alice".to_owned(), "bob".to_owned(), 1.0);
+    let txid = legacy.to_hash().unwrap();
+
+    for _ in 0..2 {
+        let last_hash = blockchain.chain.last().unwrap().to_hash().unwrap();
+
+        let _ = transactions.add_transaction(legacy.clone());
+        blockchain
+            .build_block(0, &last_hash, transactions.clone())
+            .unwrap();
+    }
+
+    let loaded = Blockchain::from_chain(blockchain.chain.clone()).unwrap();
+
+    assert_eq!(
+        loaded.transaction_index.get_locations(&txid),
+        &[(2, 0), (3, 0)]
+    );
+    assert_eq!(
+        loaded.get_transaction(&txid).map(|t| t.to_hash().unwrap()),
+        Some(txid.clone())
+    );
+
+    // A new Duplicate is rejected
+    let error = loaded
+        .check_transaction(&legacy, &transactions)
+        .unwrap_err();
+
+    assert_eq!(error.status, "duplicate");
+}
+
+#[test]
+fn account_nonces() {
+    use ed25519_dalek::SigningKey;
+
+    let signing_key = SigningKey::from_bytes(&[21u8; 32]);
+    let sender = address::from_public_key(&signing_key.verifying_key());
+    let transactions = web::Data::new(MutexTransactionList::new());
+    let mut blockchain = Blockchain::with_difficulty(1);
+
+    transactions
+        .add_transaction(blockchain.build_reward(&sender, 10.0))
+        .unwrap();
+    blockchain.seal_block(&transactions).unwrap();
+
+    let sign = |amount: f64, nonce: u64| {
+        let mut payment = Transaction::from_key(
+            &signing_key.verifying_key(),
+            String::from(address::BURN_ADDRESS),
+            amount,
+            nonce,
+        );
+
+        payment.sign(&signing_key).unwrap();
+
+        payment
+    };
+
+    assert!(blockchain
+        .check_transaction(&sign(1.0, 5), &transactions)
+        .is_ok());
+    assert_eq!(
+        blockchain
+            .check_transaction(&sign(11.0, 5), &transactions)
+            .unwrap_err()
+            .status,
+        "insufficient"
+    );
+
+    transactions.add_transaction(sign(1.0, 5)).unwrap();
+    blockchain.seal_block(&transactions).unwrap();
+
+    //-------------------------------------
+    // A used Nonce is refused in the Pool and in Blocks
+
+    let replay = sign(2.0, 5);
+
+    assert_eq!(
+        blockchain
+            .check_transaction(&replay, &transactions)
+            .unwrap_err()
+            .status,
+        "replay"
+    );
+
+    let mut chain = blockchain.chain.clone();
+    let mut invalid = Block::build_block(3, 0, &chain[1].to_hash().unwrap(), Some(vec![replay]));
+
+    invalid.bits = blockchain.get_target_bits();
+
+    while !blockchain.is_proof_valid(&invalid.to_hash().unwrap()) {
+        invalid.proof += 1;
+    }
+
+    chain.push(invalid);
+
+    assert_eq!(
+        blockchain.find_invalid_block(&chain).map(|b| b.index),
+        Some(3)
+    );
+}
+
+#[test]
+fn time_locked_transactions() {
+    use super::transaction::TimeLock;
+
+    let transactions = web::Data::new(MutexTransactionList::new());
+    let mut blockchain = Blockchain::with_difficulty(1);
+
+    transactions
+        .add_transaction(Transaction {
+            sender: COINBASE_SENDER.to_owned(),
+            receiver: "16Jswqk47s9PUcyCc88MMVwzgvHPvtEpf".to_owned(),
+            amount: 5.67f64,
+            lock_until: Some(TimeLock::Height(3)),
+            ..Default::default()
+        })
+        .unwrap();
+    transactions
+        .add_transaction(blockchain.build_reward("1BcktgV7EjHmxEwQDFFhhztzNqZkd5gdm", 7.89107))
+        .unwrap();
+
+    //-------------------------------------
+    // The locked Transaction stays pending until Block 3
+
+    blockchain.seal_block(&transactions).unwrap();
+
+    assert_eq!(blockchain.chain[0].transactions.len(), 1);
+    assert_eq!(transactions.get_count(), 1);
+
+    blockchain.seal_block(&transactions).unwrap();
+
+    assert!(blockchain.chain[1].transactions.is_empty());
+    assert_eq!(transactions.get_count(), 1);
+
+    blockchain.seal_block(&transactions).unwrap();
+
+    assert_eq!(blockchain.chain[2].transactions.len(), 1);
+    assert_eq!(transactions.get_count(), 0);
+    assert!(blockchain.is_chain_valid(&blockchain.chain));
+
+    //-------------------------------------
+    // A Block which includes the Transaction too early is rejected
+
+    let mut chain = blockchain.chain[..1].to_vec();
+    let mut early = Block::build_block(
+        2,
+        0,
+        &chain[0].to_hash().unwrap(),
+        Some(blockchain.chain[2].transactions.clone()),
+    );
+
+    early.bits = blockchain.get_target_bits();
+
+    while !blockchain.is_proof_valid(&early.to_hash().unwrap()) {
+        early.proof += 1;
+    }
+
+    chain.push(early);
+
+    assert_eq!(
+        blockchain.find_invalid_block(&chain).map(|b| b.index),
+        Some(2)
+    );
+}
+
+#[test]
+fn block_timestamps() {
+    use super::transaction::TimeLock;
+
+    let transactions = web::Data::new(MutexTransactionList::new());
+    let mut blockchain = Blockchain::with_difficulty(1);
+
+    for _ in 0..3 {
+        blockchain.seal_block(&transactions).unwrap();
+    }
+
+    // Blocks sealed within the same Second still move ahead of the Median Time Past
+    assert!(blockchain.chain[2].timestamp > get_median_time_past(&blockchain.chain[..2]));
+    assert!(blockchain.is_chain_valid(&blockchain.chain));
+
+    //-------------------------------------
+    // A Block at the Median Time Past or too far ahead is rejected
+
+    let median = blockchain.get_lock_time();
+
+    for timestamp in [median, get_timestamp() + MAX_FUTURE_DRIFT + 60] {
+        let mut chain = blockchain.chain.clone();
+        let mut block = Block::build_block(4, 0, &chain[2].to_hash().unwrap(), None);
+
+        block.bits = blockchain.get_target_bits();
+        block.timestamp = timestamp;
+
+        while !blockchain.is_proof_valid(&block.to_hash().unwrap()) {
+            block.proof += 1;
+        }
+
+        chain.push(block);
+
+        assert_eq!(
+            blockchain.find_invalid_block(&chain).map(|b| b.index),
+            Some(4)
+        );
+    }
+
+    //-------------------------------------
+    // Time Locks wait for the Median Time Past and not for the Block Timestamp
+
+    let lock_time = blockchain.get_lock_time();
+
+    transactions
+        .add_transaction(Transaction {
+            sender: COINBASE_SENDER.to_owned(),
+            receiver: "16Jswqk47s9PUcyCc88MMVwzgvHPvtEpf".to_owned(),
+            amount: 5.67f64,
+            lock_until: Some(TimeLock::Timestamp(lock_time + 1)),
+            ..Default::default()
+        })
+        .unwrap();
+    blockchain.seal_block(&transactions).unwrap();
+
+    assert!(blockchain.chain[3].timestamp > lock_time);
+    assert!(blockchain.chain[3].transactions.is_empty());
+    assert_eq!(transactions.get_count(), 1);
+}
+
+#[test]
+fn utxo_ledger() {
+    use super::address;
+    use ed25519_dalek::SigningKey;
+
+    let signing_key = SigningKey::from_bytes(&[1u8; 32]);
+    let owner = address::from_public_key(&signing_key.verifying_key());
+    let miner = address::from_public_key(&SigningKey::from_bytes(&[2u8; 32]).verifying_key());
+    let receiver = String::from("1BcktgV7EjHmxEwQDFFhhztzNqZkd5gdm");
+    let transactions = web::Data::new(MutexTransactionList::new());
+    let mut blockchain = Blockchain::with_ledger_mode(LedgerMode::Utxo);
+
+    blockchain.difficulty = 1;
+    blockchain.set_reward_address(&owner);
+    blockchain.seal_block(&transactions).unwrap();
+
+    assert_eq!(blockchain.get_balance(&owner), 10f64);
+
+    blockchain.set_reward_address(&miner);
+
+    //-------------------------------------
+    // Spend the Reward with 2 Outputs and a Fee of 0.5
+
+    let reward = OutPoint {
+        txid: blockchain.chain[0].transactions[0].to_hash().unwrap(),
+        output: 0,
+    };
+    let mut payment = Transaction::from_outputs(
+        std::slice::from_ref(&reward),
+        vec![
+            TxOutput {
+                address: receiver.clone(),
+                amount: 4.0,
+            },
+            TxOutput {
+                address: owner.clone(),
+                amount: 5.5,
+            },
+        ],
+        1,
+    );
+    let mut double_spend = Transaction::from_outputs(
+        &[reward],
+        vec![TxOutput {
+            address: receiver.clone(),
+            amount: 9.0,
+        }],
+        2,
+    );
+
+    assert!(!payment.is_valid());
+
+    payment.sign_inputs(&signing_key).unwrap();
+    double_spend.sign_inputs(&signing_key).unwrap();
+
+    assert!(payment.is_valid());
+    assert!(blockchain
+        .check_transaction(&payment, &transactions)
+        .is_ok());
+    assert!(Blockchain::new()
+        .check_transaction(&payment, &transactions)
+        .is_err());
+
+    transactions.add_transaction(payment.clone()).unwrap();
+
+    assert_eq!(
+        blockchain
+            .check_transaction(&double_spend, &transactions)
+            .unwrap_err()
+            .status,
+        "spent"
+    );
+
+    blockchain.seal_block(&transactions).unwrap();
+
+    assert_eq!(blockchain.get_balance(&owner), 5.5);
+    assert_eq!(blockchain.get_balance(&receiver), 4f64);
+    assert_eq!(blockchain.get_balance(&miner), 10.5);
+    assert_eq!(blockchain.get_unspent(&owner).len(), 1);
+
+    let history = blockchain.get_address_history(&owner, Direction::Sent, 0, 10);
+
+    assert_eq!(history.total_count, 1);
+    assert_eq!((history.total_received, history.total_sent), (15.5, 10f64));
+    assert!(blockchain
+        .check_transaction(&double_spend, &transactions)
+        .is_err());
+
+    //-------------------------------------
+    // A Double Spend in the Pool is dropped and a Block with it is rejected
+
+    transactions.add_transaction(double_spend.clone()).unwrap();
+    blockchain.seal_block(&transactions).unwrap();
+
+    assert_eq!(blockchain.chain[2].transactions.len(), 1);
+    assert!(blockchain.chain[2].transactions[0].is_coinbase());
+    assert!(blockchain.is_chain_valid(&blockchain.chain));
+
+    let mut chain = blockchain.chain.clone();
+    let mut invalid =
+        Block::build_block(4, 0, &chain[2].to_hash().unwrap(), Some(vec![double_spend]));
+
+    invalid.bits = blockchain.get_target_bits();
+
+    while !blockchain.is_proof_valid(&invalid.to_hash().unwrap()) {
+        invalid.proof += 1;
+    }
+
+    chain.push(invalid);
+
+    assert_eq!(
+        blockchain.find_invalid_block(&chain).map(|b| b.index),
+        Some(4)
+    );
+
+    //-------------------------------------
+    // Replacing the Chain rolls back the Payment
+
+    let mut fork = Blockchain::with_ledger_mode(LedgerMode::Utxo);
+
+    fork.difficulty = 1;
+    fork.set_reward_address(&miner);
+    fork.chain = blockchain.chain[..1].to_vec();
+    fork.rebuild_index().unwrap();
+
+    for _ in 0..3 {
+        fork.seal_block(&web::Data::new(MutexTransactionList::new()))
+            .unwrap();
+    }
+
+    assert!(blockchain.replace_chain(fork.chain.clone()).is_some());
+    assert_eq!(blockchain.get_balance(&owner), 10f64);
+    assert_eq!(blockchain.get_balance(&receiver), 0f64);
+    assert!(blockchain
+        .check_transaction(&payment, &transactions)
+        .is_ok());
+
+    //-------------------------------------
+    // Totals and Fees are exact in Base Units
+
+    let utxo_set = UtxoSet::from_chain(&blockchain.chain, blockchain.mining_reward).unwrap();
+    let split = |amounts: [f64; 3]| {
+        let mut split = Transaction::from_outputs(
+            std::slice::from_ref(&payment.inputs[0].to_outpoint()),
+            amounts
+                .iter()
+                .map(|a| TxOutput {
+                    address: receiver.clone(),
+                    amount: *a,
+                })
+                .collect(),
+            3,
+        );
+
+        split.sign_inputs(&signing_key).unwrap();
+        split
+    };
+
+    // The Outputs sum up to 9.999999999999998 as f64
+    assert_eq!(utxo_set.check_transaction(&split([9.7, 0.2, 0.1])), Ok(0));
+    assert_eq!(
+        utxo_set.check_transaction(&split([9.7, 0.2, 0.09999999])),
+        Ok(1)
+    );
+    assert_eq!(
+        utxo_set
+            .check_transaction(&split([9.7, 0.2, 0.10000001]))
+            .map_err(|e| e.status),
+        Err(String::from("invalid"))
+    );
+
+    //-------------------------------------
+    // A Coinbase with the Fee of 9.94 fits the Mining Reward and the Fees in Base Units
+
+    let mut spend = Transaction::from_outputs(
+        std::slice::from_ref(&payment.inputs[0].to_outpoint()),
+        vec![TxOutput {
+            address: receiver.clone(),
+            amount: 0.06,
+        }],
+        4,
+    );
+
+    spend.sign_inputs(&signing_key).unwrap();
+    transactions.add_transaction(spend).unwrap();
+    blockchain.seal_block(&transactions).unwrap();
+
+    let last_block = blockchain.get_last_block().unwrap();
+
+    assert_eq!(last_block.transactions.len(), 2);
+    assert_eq!(last_block.transactions[0].get_output_total(), 19.94);
+    assert!(blockchain.is_chain_valid(&blockchain.chain));
+}
+
+#[test]
+fn utxo_coinbase() {
+    let miner = String::from("1BcktgV7EjHmxEwQDFFhhztzNqZkd5gdm");
+    let transactions = web::Data::new(MutexTransactionList::new());
+    let mut blockchain = Blockchain::with_ledger_mode(LedgerMode::Utxo);
+
+    blockchain.difficulty = 1;
+
+    assert!(blockchain.seal_block(&transactions).is_err());
+    assert!(blockchain.chain.is_empty());
+
+    blockchain.set_reward_address(&miner);
+    blockchain.seal_block(&transactions).unwrap();
+
+    assert_eq!(blockchain.get_balance(&miner), 10f64);
+
+    //-------------------------------------
+    // Only Blocks with exactly 1 Coinbase within the Mining Reward are accepted
+
+    let mine = |coinbases: Vec<Transaction>| {
+        let mut block = Block::build_block(
+            2,
+            0,
+            &blockchain.chain[0].to_hash().unwrap(),
+            Some(coinbases),
+        );
+
+        block.timestamp = blockchain.chain[0].timestamp + 1;
+        block.bits = blockchain.get_target_bits();
+
+        while !blockchain.is_proof_valid(&block.to_hash().unwrap()) {
+            block.proof += 1;
+        }
+
+        block
+    };
+    let blocks = vec![
+        mine(Vec::new()),
+        mine(vec![
+            Transaction::from_coinbase(miner.clone(), 5.0, 2),
+            Transaction::from_coinbase(miner.clone(), 5.0, 3),
+        ]),
+        mine(vec![Transaction::from_coinbase(miner.clone(), 10.5, 2)]),
+    ];
+    let valid = mine(vec![Transaction::from_coinbase(miner.clone(), 10.0, 2)]);
+
+    for block in blocks {
+        let mut chain = blockchain.chain.clone();
+
+        chain.push(block.clone());
+
+        assert_eq!(
+            blockchain.find_invalid_block(&chain).map(|b| b.index),
+            Some(2)
+        );
+        assert!(blockchain.replace_chain(chain).is_none());
+        assert!(blockchain.append_block(block).is_err());
+        assert_eq!(blockchain.chain.len(), 1);
+        assert_eq!(blockchain.get_balance(&miner), 10f64);
+    }
+
+    blockchain.append_block(valid).unwrap();
+    assert_eq!(blockchain.get_balance(&miner), 20f64);
+}
+
+#[test]
+fn custom_assets() {
+    use super::address;
+    use super::asset::AssetDefinition;
+    use ed25519_dalek::SigningKey;
+
+    let signing_key = SigningKey::from_bytes(&[2u8; 32]);
+    let issuer = address::from_public_key(&signing_key.verifying_key());
+    let receiver = String::from("1BcktgV7EjHmxEwQDFFhhztzNqZkd5gdm");
+    let transactions = web::Data::new(MutexTransactionList::new());
+    let mut blockchain = Blockchain::new();
+
+    blockchain.difficulty = 1;
+
+    //-------------------------------------
+    // Issue 1000 Points with 2 Decimals
+
+    let definition = AssetDefinition {
+        symbol: String::from("POINTS"),
+        decimals: 2,
+    };
+    let mut issuance =
+        Transaction::from_issuance(&signing_key.verifying_key(), definition.clone(), 1000.0, 1);
+
+    issuance.sign(&signing_key).unwrap();
+
+    let asset_id = issuance.to_hash().unwrap();
+
+    assert!(blockchain
+        .check_transaction(&issuance, &transactions)
+        .is_ok());
+
+    transactions.add_transaction(issuance.clone()).unwrap();
+    blockchain.seal_block(&transactions).unwrap();
+
+    assert_eq!(blockchain.get_assets().len(), 1);
+    assert_eq!(blockchain.get_asset(&asset_id).unwrap().symbol, "POINTS");
+    assert_eq!(blockchain.get_asset_balance(&issuer, &asset_id), 1000f64);
+    assert_eq!(blockchain.get_balance(&issuer), 0f64);
+
+    let mut duplicate =
+        Transaction::from_issuance(&signing_key.verifying_key(), definition, 10.0, 2);
+
+    duplicate.sign(&signing_key).unwrap();
+
+    assert_eq!(
+        blockchain
+            .check_transaction(&duplicate, &transactions)
+            .unwrap_err()
+            .status,
+        "duplicate"
+    );
+
+    //-------------------------------------
+    // Transfer Points while pending Transfers reduce the available Balance
+
+    let mut transfer =
+        Transaction::from_key(&signing_key.verifying_key(), receiver.clone(), 600.0, 3);
+
+    transfer.asset = Some(asset_id.clone());
+    transfer.sign(&signing_key).unwrap();
+
+    let mut overdraft =
+        Transaction::from_key(&signing_key.verifying_key(), receiver.clone(), 500.0, 4);
+
+    overdraft.asset = Some(asset_id.clone());
+    overdraft.sign(&signing_key).unwrap();
+
+    assert!(blockchain
+        .check_transaction(&transfer, &transactions)
+        .is_ok());
+
+    transactions.add_transaction(transfer).unwrap();
+
+    assert_eq!(
+        blockchain
+            .check_transaction(&overdraft, &transactions)
+            .unwrap_err()
+            .status,
+        "insufficient"
+    );
+
+    blockchain.seal_block(&transactions).unwrap();
+
+    assert_eq!(blockchain.get_asset_balance(&issuer, &asset_id), 400f64);
+    assert_eq!(blockchain.get_asset_balance(&receiver, &asset_id), 600f64);
+    assert_eq!(blockchain.get_balance(&receiver), 0f64);
+    assert_eq!(
+        blockchain
+            .get_asset_holders(&asset_id)
+            .iter()
+            .map(|h| h.address.as_str())
+            .collect::<Vec<&str>>(),
+        vec![receiver.as_str(), issuer.as_str()]
+    );
+
+    //-------------------------------------
+    // An Overdraft in the Pool is dropped and a Block with it is rejected
+
+    transactions.add_transaction(overdraft.clone()).unwrap();
+    blockchain.seal_block(&transactions).unwrap();
+
+    assert!(blockchain.chain[2].transactions.is_empty());
+    assert!(blockchain.is_chain_valid(&blockchain.chain));
+
+    let mut chain = blockchain.chain.clone();
+    let mut invalid = Block::build_block(4, 0, &chain[2].to_hash().unwrap(), Some(vec![overdraft]));
+
+    invalid.bits = blockchain.get_target_bits();
+
+    while !blockchain.is_proof_valid(&invalid.to_hash().unwrap()) {
+        invalid.proof += 1;
+    }
+
+    chain.push(invalid);
+
+    assert_eq!(
+        blockchain.find_invalid_block(&chain).map(|b| b.index),
+        Some(4)
+    );
+
+    //-------------------------------------
+    // The Ledger is rebuilt when the Chain is loaded
+
+    let loaded = Blockchain::from_chain(blockchain.chain.clone()).unwrap();
+
+    assert_eq!(loaded.get_asset_balance(&receiver, &asset_id), 600f64);
+}
+
+#[test]
+fn htlc_account_mode() {
+    use super::htlc::{self, Htlc};
+    use ed25519_dalek::SigningKey;
+
+    let sender_key = SigningKey::from_bytes(&[6u8; 32]);
+    let receiver_key = SigningKey::from_bytes(&[7u8; 32]);
+    let receiver = address::from_public_key(&receiver_key.verifying_key());
+    let secret = b"swap secret";
+    let htlc = Htlc::new(
+        &htlc::to_hash_lock(secret),
+        &receiver_key.verifying_key(),
+        &sender_key.verifying_key(),
+        5,
+    );
+    let transactions = web::Data::new(MutexTransactionList::new());
+    let mut blockchain = Blockchain::new();
+
+    blockchain.difficulty = 1;
+    transactions
+        .add_transaction(blockchain.build_reward(&htlc.to_address(), 10.0))
+        .unwrap();
+    blockchain.seal_block(&transactions).unwrap();
+
+    assert_eq!(blockchain.get_balance(&htlc.to_address()), 10f64);
+
+    //-------------------------------------
+    // The Contract cannot be claimed in Account Mode
+
+    let mut claim = Transaction::from_script(htlc.to_script(), receiver.clone(), 10.0, 1);
+
+    claim.claim_htlc(secret, &receiver_key).unwrap();
+
+    assert!(claim.is_htlc());
+    assert!(claim.is_valid());
+    assert_eq!(
+        blockchain
+            .check_transaction(&claim, &transactions)
+            .unwrap_err()
+            .status,
+        "invalid"
+    );
+
+    transactions.add_transaction(claim.clone()).unwrap();
+    blockchain.seal_block(&transactions).unwrap();
+
+    assert!(blockchain.chain[1].transactions.is_empty());
+    assert_eq!(blockchain.get_balance(&receiver), 0f64);
+
+    let mut chain = blockchain.chain[..1].to_vec();
+    let mut invalid = Block::build_block(2, 0, &chain[0].to_hash().unwrap(), Some(vec![claim]));
+
+    invalid.timestamp = chain[0].timestamp + 1;
+    invalid.bits = blockchain.get_target_bits();
+
+    while !blockchain.is_proof_valid(&invalid.to_hash().unwrap()) {
+        invalid.proof += 1;
+    }
+
+    chain.push(invalid);
+
+    assert_eq!(
+        blockchain.find_invalid_block(&chain).map(|b| b.index),
+        Some(2)
+    );
+}
+
+#[test]
+fn script_spending() {
+    use crate::script::{Op, Script};
+    use sha2::{Digest, Sha256};
+
+    let preimage = b"secret";
+    let receiver = String::from("1BcktgV7EjHmxEwQDFFhhztzNqZkd5gdm");
+    // The Preimage unlocks the Funds at once. Anyone can spend them from Block 4 on.
+    let script = Script::new(vec![
+        Op::If,
+        Op::Sha256,
+        Op::Push(hex::encode(Sha256::digest(preimage))),
+        Op::Equal,
+        Op::Else,
+        Op::PushInt(4),
+        Op::CheckHeight,
+        Op::EndIf,
+    ]);
+    let script_address = script.to_address();
+    let transactions = web::Data::new(MutexTransactionList::new());
+
+    //-------------------------------------
+    // Account Mode: the Script Address is the Sender
+
+    let mut blockchain = Blockchain::new();
+
+    blockchain.difficulty = 1;
+    transactions
+        .add_transaction(blockchain.build_reward(&script_address, 10.0))
+        .unwrap();
+    blockchain.seal_block(&transactions).unwrap();
+
+    let mut claim = Transaction::from_script(script.clone(), receiver.clone(), 2.0, 1);
+    let mut timeout = Transaction::from_script(script.clone(), receiver.clone(), 2.0, 2);
+
+    assert!(claim.check_scripts(1, 0).is_err());
+
+    claim.witness = vec![hex::encode(preimage), String::from("01")];
+    timeout.witness = vec![String::new()];
+
+    assert!(claim.is_valid());
+    assert!(timeout.is_valid());
+    assert!(blockchain.check_transaction(&claim, &transactions).is_ok());
+    assert_eq!(
+        blockchain
+            .check_transaction(&timeout, &transactions)
+            .unwrap_err()
+            .status,
+        "failed"
+    );
+
+    let mut wrong_script = claim.clone();
+
+    wrong_script.script = Some(Script::new(vec![Op::PushInt(1)]));
+
+    assert!(!wrong_script.is_valid());
+
+    transactions.add_transaction(claim).unwrap();
+    transactions.add_transaction(timeout.clone()).unwrap();
+    blockchain.seal_block(&transactions).unwrap();
+
+    assert_eq!(blockchain.chain[1].transactions.len(), 1);
+    assert_eq!(blockchain.get_balance(&receiver), 2f64);
+
+    let mut chain = blockchain.chain.clone();
+    let mut early = Block::build_block(3, 0, &chain[1].to_hash().unwrap(), Some(vec![timeout]));
+
+    early.bits = blockchain.get_target_bits();
+
+    while !blockchain.is_proof_valid(&early.to_hash().unwrap()) {
+        early.proof += 1;
+    }
+
+    chain.push(early);
+
+    assert_eq!(
+        blockchain.find_invalid_block(&chain).map(|b| b.index),
+        Some(3)
+    );
+
+    //-------------------------------------
+    // UTXO Mode: the Script Address owns an Output
+
+    let mut blockchain = Blockchain::with_ledger_mode(LedgerMode::Utxo);
+
+    blockchain.difficulty = 1;
+    blockchain.set_reward_address(&script_address);
+    blockchain.seal_block(&transactions).unwrap();
+    blockchain.set_reward_address(&receiver);
+
+    let mut spend = Transaction::from_outputs(
+        &[OutPoint {
+            txid: blockchain.chain[0].transactions[0].to_hash().unwrap(),
+            output: 0,
+        }],
+        vec![TxOutput {
+            address: receiver.clone(),
+            amount: 9.0,
+        }],
+        1,
+    );
+
+    spend.inputs[0].script = Some(script);
+    spend.inputs[0].witness = vec![hex::encode(b"guess"), String::from("01")];
+
+    assert!(spend.is_valid());
+    assert!(blockchain.check_transaction(&spend, &transactions).is_err());
+
+    spend.inputs[0].witness[0] = hex::encode(preimage);
+
+    assert!(blockchain.check_transaction(&spend, &transactions).is_ok());
+
+    transactions.add_transaction(spend).unwrap();
+    blockchain.seal_block(&transactions).unwrap();
+
+    assert_eq!(blockchain.get_balance(&receiver), 20f64);
+    assert_eq!(blockchain.get_balance(&script_address), 0f64);
+}
+
+#[test]
+fn account_debits() {
+    let blockchain = Blockchain::with_difficulty(1);
+    let mine = |previous: Option<&Block>, transactions: Vec<Transaction>| {
+        let mut block = Block::build_block(
+            previous.map(|b| b.index + 1).unwrap_or(1),
+            0,
+            &previous
+                .map(|b| b.to_hash().unwrap())
+                .unwrap_or(String::from("0")),
+            Some(transactions),
+        );
+
+        block.timestamp = previous.map(|b| b.timestamp + 1).unwrap_or(block.timestamp);
+        block.bits = blockchain.get_target_bits();
+
+        while !blockchain.is_proof_valid(&block.to_hash().unwrap()) {
+            block.proof += 1;
+        }
+
+        block
+    };
+    let funding = mine(
+        None,
+        vec![
+            blockchain.build_reward("alice", 2.0),
+            Transaction::from_data("alice".to_owned(), "bob".to_owned(), 1.5),
+        ],
+    );
+
+    //-------------------------------------
+    // Every Debit must be covered by the Balance of the Sender
+
+    let covered = mine(
+        Some(&funding),
+        vec![Transaction::from_data(
+            "bob".to_owned(),
+            "alice".to_owned(),
+            1.5,
+        )],
+    );
+    let overdraft = mine(
+        Some(&funding),
+        vec![Transaction::from_data(
+            "bob".to_owned(),
+            "alice".to_owned(),
+            2.5,
+        )],
+    );
+
+    assert!(blockchain
+        .find_invalid_block(&[funding.clone(), covered])
+        .is_none());
+    assert_eq!(
+        blockchain
+            .find_invalid_block(&[funding, overdraft])
+            .map(|b| b.index),
+        Some(2)
+    );
+}
@@ -0,0 +1,290 @@
+/*
+* @author Bodo (Hugo) Barwich
+* @version 2026-10-18
+* @package Blockchain Exercise
+* @subpackage Canonical Encoding
+
+* This Module defines the deterministic Encoding of Blocks and Transactions
+* which their Hashes and Signatures are computed from
+*
+*---------------------------------
+* Requirements:
+* - The Rust Crate "serde_json" must be installed
+*/
+
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::Value;
+
+/// Number of Base Units of one Coin. Amounts are committed to with 8 Decimals.
+pub const AMOUNT_UNITS: f64 = 100_000_000.0;
+/// Maximum number of Base Units of an Amount which `f64` represents exactly
+pub const MAX_AMOUNT_UNITS: i64 = 1 << 53;
+/// Name of the Fields which hold Amounts
+pub const AMOUNT_FIELD: &str = "amount";
+
+//==============================================================================
+// Auxiliary Functions
+
+/// Encode a Value as canonical JSON.
+///
+/// The Encoding is the JSON of the Serde Representation with these Rules:
+/// - no Whitespace
+/// - Object Keys sorted by their UTF-8 Bytes
+/// - Fields named `amount` written as Integers of Base Units (`amount * 10^8`)
+/// - other Numbers written like `serde_json` does
+/// - Strings escaped like `serde_json` does: only `"`, `\` and Control Characters
+///
+/// So Field Order, Float Formatting and Serde Features do not change Hashes and
+/// Signatures.
+pub fn to_canonical_json<T: Serialize + ?Sized>(value: &T) -> Result<String, serde_json::Error> {
+    let value = serde_json::to_value(value)?;
+    let mut output = String::new();
+
+    write_value(&value, &mut output)?;
+
+    Ok(output)
+}
+
+/// Encode a Value as canonical JSON Bytes as they are hashed and signed.
+///
+/// # Returns:
+/// - an Error if the Value cannot be represented in JSON or holds invalid Amounts
+///
+pub fn to_canonical_bytes<T: Serialize + ?Sized>(value: &T) -> Result<Vec<u8>, serde_json::Error> {
+    to_canonical_json(value).map(String::into_bytes)
+}
+
+/// Convert an Amount to Base Units.
+///
+/// The Amount must be exactly the `f64` which is nearest to its Base Units, so that
+/// no Decimals beyond 8 are lost.
+///
+/// # Returns:
+/// - the Base Units or `None` if the Amount has more Decimals or its Base Units
+///   exceed `MAX_AMOUNT_UNITS`
+///
+pub fn to_amount_units(amount: f64) -> Option<i64> {
+    let units = (amount * AMOUNT_UNITS).round();
+
+    if !units.is_finite() || units.abs() > MAX_AMOUNT_UNITS as f64 || units / AMOUNT_UNITS != amount
+    {
+        return None;
+    }
+
+    Some(units as i64)
+}
+
+/// Convert Base Units into an Amount.
+pub fn to_amount(units: i64) -> f64 {
+    units as f64 / AMOUNT_UNITS
+}
+
+/// Check whether an Amount is a Multiple of `10^-8` within `MAX_AMOUNT_UNITS`.
+pub fn is_amount_valid(amount: f64) -> bool {
+    to_amount_units(amount).is_some()
+}
+
+/// Round a computed Amount like a Share of the Reward to Base Units.
+pub fn round_amount(amount: f64) -> f64 {
+    (amount * AMOUNT_UNITS).round() / AMOUNT_UNITS
+}
+
+/// Convert a configured Amount like the Mining Reward to Base Units.
+///
+/// Decimals beyond 8 are rounded. Amounts beyond `MAX_AMOUNT_UNITS` give `0`.
+pub fn round_amount_units(amount: f64) -> i64 {
+    to_amount_units(round_amount(amount)).unwrap_or(0)
+}
+
+/// Deserialize an Amount and reject it if it is no Multiple of `10^-8` or exceeds
+/// `MAX_AMOUNT_UNITS`.
+pub fn deserialize_amount<'de, D: Deserializer<'de>>(deserializer: D) -> Result<f64, D::Error> {
+    let amount = f64::deserialize(deserializer)?;
+
+    if !is_amount_valid(amount) {
+        return Err(serde::de::Error::custom(format!(
+            "Amount {} is no Multiple of 10^-8 within {} Base Units",
+            amount, MAX_AMOUNT_UNITS
+        )));
+    }
+
+    Ok(amount)
+}
+
+fn write_value(value: &Value, output: &mut String) -> Result<(), serde_json::Error> {
+    match value {
+        Value::Number(n) => output.push_str(&n.to_string()),
+        Value::String(s) => output.push_str(&serde_json::to_string(s)?),
+        Value::Array(items) => {
+            output.push('[');
+
+            for (position, item) in items.iter().enumerate() {
+                if position > 0 {
+                    output.push(',');
+                }
+
+                write_value(item, output)?;
+            }
+
+            output.push(']');
+        }
+        Value::Object(fields) => {
+            let mut fields: Vec<_> = fields.iter().collect();
+
+            fields.sort_by(|a, b| a.0.as_bytes().cmp(b.0.as_bytes()));
+            output.push('{');
+
+            for (position, (key, item)) in fields.into_iter().enumerate() {
+                if position > 0 {
+                    output.push(',');
+                }
+
+                output.push_str(&serde_json::to_string(key)?);
+                output.push(':');
+
+                match item {
+                    Value::Number(n) if key.as_str() == AMOUNT_FIELD => write_amount(n, output)?,
+                    _ => write_value(item, output)?,
+                }
+            }
+
+            output.push('}');
+        }
+        // null, true and false
+        _ => output.push_str(&value.to_string()),
+    }
+
+    Ok(())
+}
+
+fn write_amount(amount: &serde_json::Number, output: &mut String) -> Result<(), serde_json::Error> {
+    let units = amount.as_f64().and_then(to_amount_units).ok_or_else(|| {
+        serde::ser::Error::custom(format!(
+            "Amount {} is no Multiple of 10^-8 within {} Base Units",
+            amount, MAX_AMOUNT_UNITS
+        ))
+    })?;
+
+    output.push_str(&units.to_string());
+
+    Ok(())
+}
+
+//==============================================================================
+// Unit Tests
+
+#[test]
+fn canonical_golden_vectors() {
+    use crate::model::blockchain::Block;
+    use crate::model::header::BlockHeader;
+    use crate::model::transaction::Transaction;
+
+    let vectors: Value =
+        serde_json::from_str(include_str!("../../tests/vectors/canonical.json")).unwrap();
+
+    for vector in vectors["transactions"].as_array().unwrap() {
+        let transaction: Transaction =
+            serde_json::from_value(vector["transaction"].clone()).unwrap();
+        let name = vector["name"].as_str().unwrap();
+
+        assert_eq!(
+            transaction.to_canonical_json().unwrap(),
+            vector["canonical"].as_str().unwrap(),
+            "{}",
+            name
+        );
+        assert_eq!(transaction.to_hash().unwrap(), vector["txid"], "{}", name);
+
+        if let Some(payload) = vector["signing_payload"].as_str() {
+            assert_eq!(
+                transaction.to_signing_payload().unwrap(),
+                payload.as_bytes()
+            );
+            assert!(transaction.is_valid_in_chain(), "{}", name);
+        }
+    }
+
+    for vector in vectors["blocks"].as_array().unwrap() {
+        let block: Block = serde_json::from_value(vector["block"].clone()).unwrap();
+        let header = BlockHeader::from_block(&block).unwrap();
+
+        assert_eq!(hex::encode(header.body_hash), vector["body_hash"]);
+        assert_eq!(hex::encode(header.to_bytes()), vector["header"]);
+        assert_eq!(block.to_hash().unwrap(), vector["hash"]);
+    }
+
+    for vector in vectors["invalid_amounts"].as_array().unwrap() {
+        let transaction = serde_json::from_value::<Transaction>(vector.clone());
+
+        assert!(transaction.is_err(), "{}", vector);
+    }
+}
+
+#[test]
+fn canonical_encoding() {
+    // Key Order and Float Formatting do not matter
+    let first: Value =
+        serde_json::from_str(r#"{"b": 1.5, "amount": 1.5, "a": [true, null, 7]}"#).unwrap();
+    let second: Value =
+        serde_json::from_str(r#"{"a":[true,null,7],"amount":1.50,"b":1.5}"#).unwrap();
+
+    // Only Amounts are written as Base Units
+    assert_eq!(
+        to_canonical_json(&first).unwrap(),
+        r#"{"a":[true,null,7],"amount":150000000,"b":1.5}"#
+    );
+    assert_eq!(
+        to_canonical_bytes(&first).unwrap(),
+        to_canonical_bytes(&second).unwrap()
+    );
+
+    // Amounts must be exact Multiples of Base Units within the Range
+    assert_eq!(to_amount_units(0.3), Some(30_000_000));
+    assert_eq!(to_amount_units(-2.0), Some(-200_000_000));
+    assert_eq!(to_amount_units(0.1 + 0.2), None);
+    assert_eq!(to_amount_units(0.123456789), None);
+    assert_eq!(to_amount_units(1e300), None);
+    assert_eq!(to_amount_units(f64::NAN), None);
+    assert_eq!(round_amount(0.1 + 0.2), 0.3);
+    assert!(to_canonical_json(&serde_json::json!({"amount": 0.1 + 0.2})).is_err());
+    assert_eq!(
+        to_canonical_json(&("\u{e4}\"\n", 1u64 << 63)).unwrap(),
+        "[\"\u{e4}\\\"\\n\",9223372036854775808]"
+    );
+}
+
+#[test]
+fn canonical_encoding_failure() {
+    use crate::model::blockchain::Block;
+    use crate::model::header::BlockHeader;
+    use crate::model::transaction::Transaction;
+    use ed25519_dalek::SigningKey;
+
+    let signing_key = SigningKey::from_bytes(&[9u8; 32]);
+    let receiver = String::from("16Jswqk47s9PUcyCc88MMVwzgvHPvtEpf");
+
+    //-------------------------------------
+    // Amounts set in Memory are not checked by the Deserialisation
+
+    let mut first =
+        Transaction::from_key(&signing_key.verifying_key(), receiver.clone(), 0.1 + 0.2, 1);
+    let second = Transaction::from_key(&signing_key.verifying_key(), receiver, 0.123456789, 1);
+
+    // Unencodable Transactions share no empty Transaction Id or Signing Payload
+    assert!(first.to_hash().is_err());
+    assert!(second.to_hash().is_err());
+    assert!(first.to_signing_payload().is_err());
+    assert!(first.sign(&signing_key).is_err());
+    assert!(first.signature.is_none());
+    assert!(!first.is_valid());
+
+    //-------------------------------------
+    // The Header cannot commit to an unencodable Body
+
+    let mut block = Block::build_block(1, 7, "0", None);
+
+    block.transactions.push(second);
+
+    assert!(BlockHeader::from_block(&block).is_err());
+    assert!(block.to_hash().is_err());
+}
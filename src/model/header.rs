@@ -0,0 +1,202 @@
+/*
+* @author Bodo (Hugo) Barwich
+* @version 2026-10-18
+* @package Blockchain Exercise
+* @subpackage Block Headers
+
+* This Module defines the fixed Binary Layout of the Block Headers which the
+* Block Hashes are computed from
+*
+*---------------------------------
+* Requirements:
+* - The Rust Crate "sha2" must be installed
+*/
+
+use sha2::{Digest, Sha256};
+
+use super::blockchain::Block;
+use super::canonical;
+use crate::consensus::{ConsensusError, HashAlgorithm};
+
+/// Length of a serialized Block Header in Bytes
+pub const HEADER_SIZE: usize = 88;
+/// Previous Hash of the Genesis Block
+pub const GENESIS_PREVIOUS_HASH: &str = "0";
+
+//==============================================================================
+// Structure BlockHeader Declaration
+
+/// Structure for the Fields of a Block which its Hash commits to
+///
+/// The Body with the `Transaction`s, the Evidence and the Seal is only included
+/// by the SHA-256 Digest of its canonical JSON Array. So a Miner hashes the same 88 Bytes for each Nonce.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlockHeader {
+    pub index: u64,
+    pub timestamp: u32,
+    /// Compact Proof of Work Target
+    pub bits: u32,
+    pub proof: u64,
+    pub previous_hash: [u8; 32],
+    pub body_hash: [u8; 32],
+}
+
+//==============================================================================
+// Structure BlockHeader Implementation
+
+impl BlockHeader {
+    /*----------------------------------------------------------------------------
+     * Constructors
+     */
+
+    /// Build the Header of a Block.
+    ///
+    /// # Returns:
+    /// - an Error if the `previous_hash` is no hex encoded 256 bit Hash or the Body
+    ///   cannot be encoded
+    ///
+    pub fn from_block(block: &Block) -> Result<Self, ConsensusError> {
+        let previous_hash = decode_hash(&block.previous_hash).ok_or_else(|| {
+            ConsensusError::new(
+                "invalid",
+                &format!(
+                    "Block ({}): Previous Hash '{}' is no 256 bit Hash",
+                    block.index, block.previous_hash
+                ),
+            )
+        })?;
+        let body =
+            canonical::to_canonical_bytes(&(&block.transactions, &block.evidence, &block.seal))
+                .map_err(|e| {
+                    ConsensusError::new(
+                        "invalid",
+                        &format!("Block ({}): Body cannot be encoded! {}", block.index, e),
+                    )
+                })?;
+
+        Ok(Self {
+            index: block.index,
+            timestamp: block.timestamp,
+            bits: block.bits,
+            proof: block.proof,
+            previous_hash,
+            body_hash: Sha256::digest(body).into(),
+        })
+    }
+
+    /*----------------------------------------------------------------------------
+     * Consultation Methods
+     */
+
+    /// Serialize the Header with the Integers in Little Endian Byte Order.
+    ///
+    /// | Offset | Size | Field           |
+    /// |--------|------|-----------------|
+    /// | 0      | 8    | `index`         |
+    /// | 8      | 4    | `timestamp`     |
+    /// | 12     | 4    | `bits`          |
+    /// | 16     | 8    | `proof`         |
+    /// | 24     | 32   | `previous_hash` |
+    /// | 56     | 32   | `body_hash`     |
+    pub fn to_bytes(&self) -> [u8; HEADER_SIZE] {
+        let mut bytes = [0u8; HEADER_SIZE];
+
+        bytes[0..8].copy_from_slice(&self.index.to_le_bytes());
+        bytes[8..12].copy_from_slice(&self.timestamp.to_le_bytes());
+        bytes[12..16].copy_from_slice(&self.bits.to_le_bytes());
+        bytes[16..24].copy_from_slice(&self.proof.to_le_bytes());
+        bytes[24..56].copy_from_slice(&self.previous_hash);
+        bytes[56..88].copy_from_slice(&self.body_hash);
+
+        bytes
+    }
+
+    /// Compute the hex encoded Block Hash with the Hash Algorithm of the Chain.
+    pub fn to_hash(&self, hash_algorithm: HashAlgorithm) -> Result<String, ConsensusError> {
+        hash_algorithm.hash(&self.to_bytes())
+    }
+}
+
+//==============================================================================
+// Auxiliary Functions
+
+/// Decode the hex encoded Hash of the previous Block.
+///
+/// The Genesis Block links to `GENESIS_PREVIOUS_HASH` which is encoded as zeros.
+/// Any other Value must be a hex encoded 256 bit Hash.
+fn decode_hash(previous_hash: &str) -> Option<[u8; 32]> {
+    if previous_hash == GENESIS_PREVIOUS_HASH {
+        return Some([0u8; 32]);
+    }
+
+    // Upper Case Digits would encode the same Bytes as another Hash
+    if previous_hash.chars().any(|c| c.is_ascii_uppercase()) {
+        return None;
+    }
+
+    hex::decode(previous_hash)
+        .ok()
+        .and_then(|h| h.try_into().ok())
+}
+
+//==============================================================================
+// Unit Tests
+
+#[test]
+fn header_layout() {
+    let mut block = Block::build_block(2, 7, &"ab".repeat(32), None);
+
+    block.timestamp = 0x0102_0304;
+    block.bits = 0x1f00_ffff;
+
+    let header = BlockHeader::from_block(&block).unwrap();
+    let bytes = header.to_bytes();
+
+    assert_eq!(bytes[0..8], [2, 0, 0, 0, 0, 0, 0, 0]);
+    assert_eq!(bytes[8..12], [4, 3, 2, 1]);
+    assert_eq!(bytes[12..16], [0xff, 0xff, 0, 0x1f]);
+    assert_eq!(bytes[16..24], [7, 0, 0, 0, 0, 0, 0, 0]);
+    assert_eq!(bytes[24..56], [0xab; 32]);
+    assert_eq!(block.to_hash(), header.to_hash(HashAlgorithm::Sha256));
+
+    //-------------------------------------
+    // Each Field changes the Hash
+
+    let mut changed = block.clone();
+
+    changed.proof += 1;
+
+    assert_ne!(changed.to_hash(), block.to_hash());
+
+    changed = block.clone();
+    changed.bits -= 1;
+
+    assert_ne!(changed.to_hash(), block.to_hash());
+
+    changed = block.clone();
+    changed
+        .transactions
+        .push(crate::model::transaction::Transaction::from_data(
+            String::from("sender1"),
+            String::from("16Jswqk47s9PUcyCc88MMVwzgvHPvtEpf"),
+            1.5,
+        ));
+
+    assert_ne!(changed.to_hash(), block.to_hash());
+    assert_eq!(
+        BlockHeader::from_block(&Block::build_block(1, 0, "0", None))
+            .unwrap()
+            .previous_hash,
+        [0u8; 32]
+    );
+
+    //-------------------------------------
+    // Only the Genesis Sentinel is no 256 bit Hash
+
+    for previous_hash in ["", "00", "not a hash", &"AB".repeat(32), &"ab".repeat(33)] {
+        assert!(BlockHeader::from_block(&Block::build_block(2, 0, previous_hash, None)).is_err());
+        assert!(Block::build_block(2, 0, previous_hash, None)
+            .to_hash()
+            .is_err());
+    }
+}
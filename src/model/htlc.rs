@@ -0,0 +1,232 @@
+/*
+* @author Bodo (Hugo) Barwich
+* @version 2026-10-18
+* @package Blockchain Exercise
+* @subpackage HTLC Structures
+
+* This Module defines Hash Time-Locked Contracts which lock Funds to a Receiver
+* who knows a Secret and refund them to the Sender after a Timeout.
+* They enable atomic Swaps between two Chains.
+*
+*---------------------------------
+* Requirements:
+* - The Rust Crate "ed25519-dalek" must be installed
+* - The Rust Crate "sha2" must be installed
+*/
+
+use ed25519_dalek::VerifyingKey;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use super::transaction::Transaction;
+use crate::script::{Op, Script};
+
+//==============================================================================
+// Structure Htlc Declaration
+
+/// Structure for the Terms of a Hash Time-Locked Contract
+///
+/// The Funds are sent to the Address of its Script. The Receiver claims them by
+/// revealing the Secret whose SHA-256 Hash is the Hash Lock. From the Timeout on
+/// the Sender can take them back.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Htlc {
+    /// Hex encoded SHA-256 Hash of the Secret
+    pub hash_lock: String,
+    /// Hex encoded Ed25519 Public Key of the Receiver
+    pub receiver_key: String,
+    /// Hex encoded Ed25519 Public Key of the Sender
+    pub sender_key: String,
+    /// Index of the first Block which can include the Refund
+    pub timeout: u64,
+}
+
+//==============================================================================
+// Structure Htlc Implementation
+
+impl Htlc {
+    /*----------------------------------------------------------------------------
+     * Constructors
+     */
+
+    pub fn new(
+        hash_lock: &str,
+        receiver_key: &VerifyingKey,
+        sender_key: &VerifyingKey,
+        timeout: u64,
+    ) -> Self {
+        Self {
+            hash_lock: hash_lock.to_owned(),
+            receiver_key: hex::encode(receiver_key.as_bytes()),
+            sender_key: hex::encode(sender_key.as_bytes()),
+            timeout,
+        }
+    }
+
+    /// Recognise the Terms of a Contract from its Script.
+    pub fn from_script(script: &Script) -> Option<Self> {
+        let htlc = match script.ops.as_slice() {
+            [_, _, Op::Push(h), _, Op::Push(r), _, _, Op::PushInt(t), _, _, Op::Push(s), _, _] => {
+                Self {
+                    hash_lock: h.clone(),
+                    receiver_key: r.clone(),
+                    sender_key: s.clone(),
+                    timeout: u64::try_from(*t).ok()?,
+                }
+            }
+            _ => return None,
+        };
+
+        // All other Operations must match the Template
+        (htlc.to_script() == *script).then_some(htlc)
+    }
+
+    /*----------------------------------------------------------------------------
+     * Consultation Methods
+     */
+
+    /// Check that the Hash Lock is a SHA-256 Hash, that both Keys are valid and
+    /// that the Timeout is a Block Index.
+    pub fn is_valid(&self) -> bool {
+        let is_key_valid = |key: &str| {
+            hex::decode(key)
+                .ok()
+                .and_then(|k| <[u8; 32]>::try_from(k).ok())
+                .and_then(|k| VerifyingKey::from_bytes(&k).ok())
+                .is_some()
+        };
+
+        matches!(hex::decode(&self.hash_lock), Ok(h) if h.len() == 32)
+            && is_key_valid(&self.receiver_key)
+            && is_key_valid(&self.sender_key)
+            && self.timeout > 0
+            && self.timeout <= i64::MAX as u64
+    }
+
+    /// Build the Script which enforces the Contract.
+    ///
+    /// The Claim Witness is `[signature, secret, 01]` and the Refund Witness
+    /// is `[signature, ""]`.
+    pub fn to_script(&self) -> Script {
+        Script::new(vec![
+            Op::If,
+            Op::Sha256,
+            Op::Push(self.hash_lock.clone()),
+            Op::EqualVerify,
+            Op::Push(self.receiver_key.clone()),
+            Op::CheckSig,
+            Op::Else,
+            Op::PushInt(self.timeout as i64),
+            Op::CheckHeight,
+            Op::Verify,
+            Op::Push(self.sender_key.clone()),
+            Op::CheckSig,
+            Op::EndIf,
+        ])
+    }
+
+    /// Derive the Address which the locked Funds are sent to.
+    pub fn to_address(&self) -> String {
+        self.to_script().to_address()
+    }
+
+    /// Extract the Secret which a Claim of this Contract revealed.
+    ///
+    /// The other Party of a Swap uses it to claim the Funds on the other Chain.
+    pub fn get_secret(&self, transaction: &Transaction) -> Option<Vec<u8>> {
+        let script = self.to_script();
+        let sender_witness = match transaction.script.as_ref() {
+            Some(s) if *s == script => Some(&transaction.witness),
+            _ => None,
+        };
+
+        sender_witness
+            .into_iter()
+            .chain(
+                transaction
+                    .inputs
+                    .iter()
+                    .filter(|i| i.script.as_ref() == Some(&script))
+                    .map(|i| &i.witness),
+            )
+            .filter(|w| w.len() == 3)
+            .filter_map(|w| hex::decode(&w[1]).ok())
+            .find(|secret| to_hash_lock(secret) == self.hash_lock)
+    }
+}
+
+//==============================================================================
+// Auxiliary Functions
+
+/// Calculate the hex encoded Hash Lock of a Secret.
+pub fn to_hash_lock(secret: &[u8]) -> String {
+    hex::encode(Sha256::digest(secret))
+}
+
+//==============================================================================
+// Unit Tests
+
+#[test]
+fn htlc_claim_and_refund() {
+    use super::utxo::{OutPoint, TxOutput};
+    use ed25519_dalek::SigningKey;
+
+    let sender_key = SigningKey::from_bytes(&[6u8; 32]);
+    let receiver_key = SigningKey::from_bytes(&[7u8; 32]);
+    let receiver = super::address::from_public_key(&receiver_key.verifying_key());
+    let secret = b"swap secret";
+    let outpoint = OutPoint {
+        txid: "00".repeat(32),
+        output: 0,
+    };
+    let outputs = vec![TxOutput {
+        address: receiver,
+        amount: 1.0,
+    }];
+    let htlc = Htlc::new(
+        &to_hash_lock(secret),
+        &receiver_key.verifying_key(),
+        &sender_key.verifying_key(),
+        5,
+    );
+
+    assert!(htlc.is_valid());
+    assert_eq!(Htlc::from_script(&htlc.to_script()), Some(htlc.clone()));
+    assert!(!Htlc {
+        hash_lock: String::from("00"),
+        ..htlc.clone()
+    }
+    .is_valid());
+
+    //-------------------------------------
+    // Only the Receiver can claim with the Secret
+
+    let mut claim = Transaction::from_htlc_output(&htlc, &outpoint, outputs.clone(), 1);
+    let mut theft = claim.clone();
+    let mut guess = claim.clone();
+
+    claim.claim_htlc(secret, &receiver_key).unwrap();
+    theft.claim_htlc(secret, &sender_key).unwrap();
+    guess.claim_htlc(b"guess", &receiver_key).unwrap();
+
+    assert!(claim.is_valid());
+    assert!(claim.check_scripts(1, 0).is_ok());
+    assert!(theft.check_scripts(1, 0).is_err());
+    assert!(guess.check_scripts(1, 0).is_err());
+    assert_eq!(htlc.get_secret(&claim), Some(secret.to_vec()));
+    assert_eq!(htlc.get_secret(&guess), None);
+
+    //-------------------------------------
+    // Only the Sender can take the Funds back from the Timeout on
+
+    let mut refund = Transaction::from_htlc_output(&htlc, &outpoint, outputs, 2);
+    let mut early_claim = refund.clone();
+
+    refund.refund_htlc(&sender_key).unwrap();
+    early_claim.refund_htlc(&receiver_key).unwrap();
+
+    assert!(refund.check_scripts(4, 0).is_err());
+    assert!(refund.check_scripts(5, 0).is_ok());
+    assert!(early_claim.check_scripts(5, 0).is_err());
+    assert_eq!(htlc.get_secret(&refund), None);
+}
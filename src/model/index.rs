@@ -0,0 +1,256 @@
+/*
+* @author Bodo (Hugo) Barwich
+* @version 2026-10-18
+* @package Blockchain Exercise
+* @subpackage Blockchain Index Structures
+
+* This Module defines the Rust Structures to look up the data of the Blockchain
+* without scanning all Blocks
+*
+*---------------------------------
+* Requirements:
+*/
+
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+use super::blockchain::Block;
+use super::transaction::Transaction;
+
+//==============================================================================
+// Structure TransactionStatus Declaration
+
+/// Status of a `Transaction` as reported to the Clients
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "camelCase")]
+pub enum TransactionStatus {
+    Pending,
+    Confirmed {
+        block_index: u64,
+        confirmations: u64,
+    },
+    Dropped,
+    Unknown,
+}
+
+/// Direction of a `Transaction` as seen from an Address
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Direction {
+    All,
+    Sent,
+    Received,
+}
+
+//==============================================================================
+// Structure AddressHistory Declaration
+
+/// Structure for a `Transaction` in the History of an Address
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AddressTransaction {
+    pub txid: String,
+    pub block_index: u64,
+    pub position: usize,
+    pub direction: Direction,
+    pub transaction: Transaction,
+}
+
+/// Structure for a Page of the Transaction History of an Address
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AddressHistory {
+    pub address: String,
+    pub direction: Direction,
+    pub total_count: usize,
+    pub total_received: f64,
+    pub total_sent: f64,
+    pub balance: f64,
+    pub offset: usize,
+    pub limit: usize,
+    pub transactions: Vec<AddressTransaction>,
+}
+
+//==============================================================================
+// Structure TransactionIndex Declaration
+
+/// Structure to find the Blocks of a `Transaction` by its Transaction Id
+///
+/// Each Transaction Id maps to the Locations (Block Index, Position in the Block)
+/// in chain order. Chains from before unique Mining Rewards can include the same
+/// `Transaction` several times.
+#[derive(Debug, Clone, Default)]
+pub struct TransactionIndex {
+    locations: HashMap<String, Vec<(u64, usize)>>,
+    dropped: HashSet<String>,
+}
+
+//==============================================================================
+// Structure AddressIndex Declaration
+
+/// Structure to find the `Transaction`s of an Address
+///
+/// Each Address maps to the Locations (Block Index, Position in the Block) of the
+/// `Transaction`s it sent or received in chain order.
+#[derive(Debug, Clone, Default)]
+pub struct AddressIndex {
+    locations: HashMap<String, Vec<(u64, usize)>>,
+}
+
+//==============================================================================
+// Structure TransactionIndex Implementation
+
+impl TransactionIndex {
+    /*----------------------------------------------------------------------------
+     * Constructors
+     */
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn from_chain(chain: &[Block]) -> Self {
+        let mut index = Self::new();
+
+        chain.iter().for_each(|b| index.add_block(b));
+
+        index
+    }
+
+    /*----------------------------------------------------------------------------
+     * Administration Methods
+     */
+
+    /// Register the `Transaction`s of a Block appended to the Chain
+    ///
+    /// `Transaction`s which cannot be encoded have no Transaction Id. They cannot be
+    /// part of a valid Block because its Header could not be built either.
+    pub fn add_block(&mut self, block: &Block) {
+        for (position, transaction) in block.transactions.iter().enumerate() {
+            let txid = match transaction.to_hash() {
+                Ok(t) => t,
+                Err(_) => continue,
+            };
+
+            self.dropped.remove(&txid);
+            self.locations
+                .entry(txid)
+                .or_default()
+                .push((block.index, position));
+        }
+    }
+
+    /// Rebuild the Index after the Chain was replaced.
+    ///
+    /// `Transaction`s of the discarded Blocks which are not part of the new Chain
+    /// are remembered as dropped.
+    pub fn rebuild(&mut self, chain: &[Block], orphaned_blocks: &[Block]) {
+        let mut dropped = std::mem::take(&mut self.dropped);
+
+        *self = Self::from_chain(chain);
+
+        orphaned_blocks
+            .iter()
+            .flat_map(|b| b.transactions.iter())
+            .filter_map(|t| t.to_hash().ok())
+            .for_each(|txid| {
+                dropped.insert(txid);
+            });
+        dropped.retain(|txid| !self.locations.contains_key(txid));
+
+        self.dropped = dropped;
+    }
+
+    /*----------------------------------------------------------------------------
+     * Consultation Methods
+     */
+
+    /// Get the Locations of a `Transaction` in chain order
+    pub fn get_locations(&self, txid: &str) -> &[(u64, usize)] {
+        match self.locations.get(txid) {
+            Some(l) => l.as_slice(),
+            None => &[],
+        }
+    }
+
+    /// Get the Index of the Block which confirmed a `Transaction` first
+    pub fn get_block_index(&self, txid: &str) -> Option<u64> {
+        self.get_locations(txid)
+            .first()
+            .map(|(block_index, _)| *block_index)
+    }
+
+    pub fn is_dropped(&self, txid: &str) -> bool {
+        self.dropped.contains(txid)
+    }
+}
+
+//==============================================================================
+// Structure AddressIndex Implementation
+
+impl AddressIndex {
+    /*----------------------------------------------------------------------------
+     * Constructors
+     */
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn from_chain(chain: &[Block]) -> Self {
+        let mut index = Self::new();
+
+        chain.iter().for_each(|b| index.add_block(b));
+
+        index
+    }
+
+    /*----------------------------------------------------------------------------
+     * Administration Methods
+     */
+
+    /// Register the `Transaction`s of a Block appended to the Chain
+    pub fn add_block(&mut self, block: &Block) {
+        for (position, transaction) in block.transactions.iter().enumerate() {
+            for address in transaction.get_addresses() {
+                self.locations
+                    .entry(address)
+                    .or_default()
+                    .push((block.index, position));
+            }
+        }
+    }
+
+    /*----------------------------------------------------------------------------
+     * Consultation Methods
+     */
+
+    /// Get the Locations of the `Transaction`s of an Address in chain order
+    pub fn get_locations(&self, address: &str) -> &[(u64, usize)] {
+        match self.locations.get(address) {
+            Some(l) => l.as_slice(),
+            None => &[],
+        }
+    }
+}
+
+//==============================================================================
+// Enum Direction Implementation
+
+impl Direction {
+    /*----------------------------------------------------------------------------
+     * Consultation Methods
+     */
+
+    /// Get the Direction of a `Transaction` as seen from an Address
+    pub fn of(transaction: &Transaction, address: &str) -> Self {
+        if transaction.is_sent_by(address) {
+            Direction::Sent
+        } else {
+            Direction::Received
+        }
+    }
+
+    /// Check whether a `Transaction` of an Address is listed for this Direction
+    pub fn matches(&self, transaction: &Transaction, address: &str) -> bool {
+        *self == Direction::All || *self == Self::of(transaction, address)
+    }
+}
@@ -10,5 +10,14 @@
 * Requirements:
 */
 
+pub mod address;
+pub mod asset;
 pub mod blockchain;
+pub mod canonical;
+pub mod header;
+pub mod htlc;
+pub mod index;
+pub mod stake;
 pub mod transaction;
+pub mod utxo;
+pub mod wire;
@@ -0,0 +1,380 @@
+/*
+* @author Bodo (Hugo) Barwich
+* @version 2026-10-18
+* @package Blockchain Exercise
+* @subpackage Stake Structures
+
+* This Module defines the Stakes which Validators lock for Proof of Stake Chains
+* and the Ledger of the Stakes, the native Balances and the Nonces of the Senders
+*
+*---------------------------------
+* Requirements:
+*/
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+
+use super::address;
+use super::blockchain::{Block, LedgerError};
+use super::canonical;
+use super::transaction::{Transaction, COINBASE_SENDER};
+use crate::consensus::seal::DoubleSignEvidence;
+
+/// Stake Units per Coin for the weighted Validator Selection
+pub const STAKE_UNITS: f64 = 1_000_000.0;
+
+//==============================================================================
+// Structure Stake Declaration
+
+/// Action of a Staking `Transaction`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StakeAction {
+    /// Lock the `amount` of the Balance of the Sender as Stake
+    Stake,
+    /// Release the `amount` of the Stake to the Balance of the Sender
+    Unstake,
+}
+
+/// Structure for the Stake of a Validator
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Stake {
+    /// Hex encoded Ed25519 Public Key which the Validator seals Blocks with
+    pub public_key: String,
+    pub address: String,
+    pub amount: f64,
+}
+
+/// Structure for the Ledger of the Stakes, the native Balances and the Nonces
+///
+/// Every Debit is checked against the Balances. Mining Rewards are credited as given.
+#[derive(Debug, Clone, Default)]
+pub struct StakeLedger {
+    /// Stakes by the Public Key of the Validator
+    stakes: BTreeMap<String, Stake>,
+    /// Balances of the native Currency by Address
+    balances: BTreeMap<String, f64>,
+    /// Highest Nonce used by each Sender Address
+    nonces: BTreeMap<String, u64>,
+    /// Validators and Block Indices of the punished Double Signatures
+    slashed: BTreeSet<(String, u64)>,
+}
+
+//==============================================================================
+// Structure StakeLedger Implementation
+
+impl StakeLedger {
+    /*----------------------------------------------------------------------------
+     * Constructors
+     */
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Build the Ledger of a Chain.
+    ///
+    /// # Returns:
+    /// - the Ledger or the Index of the first Block with an invalid Staking
+    ///   `Transaction` or Evidence
+    ///
+    pub fn from_chain(chain: &[Block]) -> Result<Self, (u64, LedgerError)> {
+        let mut ledger = Self::new();
+
+        for block in chain.iter() {
+            ledger.apply_block(block).map_err(|e| (block.index, e))?;
+        }
+
+        Ok(ledger)
+    }
+
+    /*----------------------------------------------------------------------------
+     * Administration Methods
+     */
+
+    /// Register the `Transaction`s and slash the Validators of the Evidence of a Block.
+    ///
+    /// A Block with an invalid Staking `Transaction` or Evidence is not applied at all.
+    pub fn apply_block(&mut self, block: &Block) -> Result<(), LedgerError> {
+        let mut ledger = self.clone();
+
+        for transaction in block.transactions.iter() {
+            ledger.apply_transaction(transaction)?;
+        }
+
+        for evidence in block.evidence.iter() {
+            ledger.apply_evidence(evidence)?;
+        }
+
+        *self = ledger;
+
+        Ok(())
+    }
+
+    /// Register one `Transaction` of the native Currency.
+    ///
+    /// Only the Nonce of Asset `Transaction`s is registered. UTXO `Transaction`s are ignored.
+    pub fn apply_transaction(&mut self, transaction: &Transaction) -> Result<(), LedgerError> {
+        if transaction.is_utxo() {
+            return Ok(());
+        }
+
+        self.apply_nonce(transaction)?;
+
+        if !transaction.is_native() {
+            return Ok(());
+        }
+
+        self.check_transaction(transaction, 0f64)?;
+
+        match transaction.stake {
+            Some(StakeAction::Stake) => {
+                let public_key = transaction.public_key.clone().unwrap_or_default();
+                let stake = self.stakes.entry(public_key.clone()).or_insert(Stake {
+                    public_key,
+                    address: transaction.sender.clone(),
+                    amount: 0f64,
+                });
+
+                stake.amount += transaction.amount;
+                self.add_balance(&transaction.sender, -transaction.amount);
+            }
+            Some(StakeAction::Unstake) => {
+                let public_key = transaction.public_key.as_deref().unwrap_or_default();
+
+                if let Some(stake) = self.stakes.get_mut(public_key) {
+                    stake.amount -= transaction.amount;
+
+                    if stake.amount <= 0f64 {
+                        self.stakes.remove(public_key);
+                    }
+                }
+
+                self.add_balance(&transaction.sender, transaction.amount);
+            }
+            None => {
+                if transaction.sender != COINBASE_SENDER {
+                    self.add_balance(&transaction.sender, -transaction.amount);
+                }
+
+                self.add_balance(&transaction.receiver, transaction.amount);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Burn the whole Stake of a Validator which signed twice.
+    pub fn apply_evidence(&mut self, evidence: &DoubleSignEvidence) -> Result<(), LedgerError> {
+        self.check_evidence(evidence)?;
+
+        self.stakes.remove(evidence.get_signer());
+        self.slashed
+            .insert((evidence.get_signer().to_owned(), evidence.get_index()));
+
+        Ok(())
+    }
+
+    /// Register the Nonce of a `Transaction`.
+    ///
+    /// The Nonces of a Sender Address must increase so that its `Transaction`s cannot be
+    /// replayed. Senders which are no Address like the Mining Rewards are not tracked.
+    fn apply_nonce(&mut self, transaction: &Transaction) -> Result<(), LedgerError> {
+        if !address::is_valid(&transaction.sender) {
+            return Ok(());
+        }
+
+        if transaction.nonce <= self.get_nonce(&transaction.sender) {
+            return Err(LedgerError::new(
+                "replay",
+                &format!(
+                    "Transaction ({}): Nonce {} of '{}' is not higher than its last Nonce",
+                    transaction.to_hash().map_err(LedgerError::from_encoding)?,
+                    transaction.nonce,
+                    transaction.sender
+                ),
+            ));
+        }
+
+        self.nonces
+            .insert(transaction.sender.clone(), transaction.nonce);
+
+        Ok(())
+    }
+
+    fn add_balance(&mut self, address: &str, amount: f64) {
+        let balance = self.balances.entry(address.to_owned()).or_default();
+
+        *balance += amount;
+
+        if *balance == 0f64 {
+            self.balances.remove(address);
+        }
+    }
+
+    /*----------------------------------------------------------------------------
+     * Consultation Methods
+     */
+
+    /// Check that the Balance or the Stake of the Sender covers a `Transaction`
+    /// of the native Currency.
+    ///
+    /// # Parameters:
+    /// - `pending_amount`: Amount which the Sender already debits, stakes or unstakes with
+    ///   `Transaction`s waiting for the next Block.
+    ///
+    pub fn check_transaction(
+        &self,
+        transaction: &Transaction,
+        pending_amount: f64,
+    ) -> Result<(), LedgerError> {
+        let txid = transaction.to_hash().map_err(LedgerError::from_encoding)?;
+        let available = match transaction.stake {
+            // Mining Rewards create the Amount they credit
+            None if transaction.sender == COINBASE_SENDER => return Ok(()),
+            None => self.get_balance(&transaction.sender),
+            Some(_) if !transaction.is_valid() => {
+                return Err(LedgerError::new(
+                    "invalid",
+                    &format!("Staking ({}): Transaction is invalid", txid),
+                ));
+            }
+            Some(StakeAction::Stake) => self.get_balance(&transaction.sender),
+            Some(StakeAction::Unstake) => {
+                self.get_stake(transaction.public_key.as_deref().unwrap_or_default())
+            }
+        };
+
+        if available - pending_amount < transaction.amount {
+            return Err(LedgerError::new(
+                "insufficient",
+                &format!(
+                    "Transaction ({}): Available Amount of {} is insufficient",
+                    txid,
+                    available - pending_amount
+                ),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Check that the Mining Rewards of a Block do not exceed the Mining Reward.
+    ///
+    /// The Rewards are the `Transaction`s from `COINBASE_SENDER`. Under Proof of Stake
+    /// the Shares of all Stakers together must fit the Mining Reward. Account Blocks
+    /// have no Fees, so nothing else can be granted. The Amounts are compared in
+    /// Base Units.
+    pub fn check_rewards(block: &Block, mining_reward: f64) -> Result<(), LedgerError> {
+        let limit = canonical::round_amount_units(mining_reward);
+        let mut total = 0i64;
+
+        for reward in block
+            .transactions
+            .iter()
+            .filter(|t| t.sender == COINBASE_SENDER && !t.is_utxo())
+        {
+            total = canonical::to_amount_units(reward.amount)
+                .and_then(|units| total.checked_add(units))
+                .ok_or_else(|| {
+                    LedgerError::new(
+                        "invalid",
+                        &format!(
+                            "Block ({}): Reward of {} is no valid Amount",
+                            block.index, reward.amount
+                        ),
+                    )
+                })?;
+        }
+
+        if total > limit {
+            return Err(LedgerError::new(
+                "invalid",
+                &format!(
+                    "Block ({}): Rewards of {} exceed the Mining Reward of {}",
+                    block.index,
+                    total as f64 / canonical::AMOUNT_UNITS,
+                    mining_reward
+                ),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Check that the Evidence is valid, that the Validator has a Stake and that
+    /// the Double Signature was not punished yet.
+    pub fn check_evidence(&self, evidence: &DoubleSignEvidence) -> Result<(), LedgerError> {
+        let signer = evidence.get_signer();
+
+        if !evidence.is_valid() {
+            return Err(LedgerError::new(
+                "invalid",
+                "Evidence: Headers are no Double Signature",
+            ));
+        }
+
+        if self
+            .slashed
+            .contains(&(signer.to_owned(), evidence.get_index()))
+        {
+            return Err(LedgerError::new(
+                "duplicate",
+                &format!(
+                    "Evidence: Validator '{}' is already slashed for Block {}",
+                    signer,
+                    evidence.get_index()
+                ),
+            ));
+        }
+
+        if self.get_stake(signer) <= 0f64 {
+            return Err(LedgerError::new(
+                "not_found",
+                &format!("Evidence: Validator '{}' has no Stake", signer),
+            ));
+        }
+
+        Ok(())
+    }
+
+    pub fn get_stake(&self, public_key: &str) -> f64 {
+        self.stakes
+            .get(public_key)
+            .map(|s| s.amount)
+            .unwrap_or(0f64)
+    }
+
+    /// List the Stakes ordered by the Public Keys of the Validators.
+    pub fn get_stakes(&self) -> Vec<&Stake> {
+        self.stakes.values().collect()
+    }
+
+    pub fn get_total(&self) -> f64 {
+        self.stakes.values().map(|s| s.amount).sum()
+    }
+
+    /// Balance of the native Currency which an Address can spend or stake
+    pub fn get_balance(&self, address: &str) -> f64 {
+        self.balances.get(address).copied().unwrap_or(0f64)
+    }
+
+    /// Highest Nonce of the `Transaction`s sent from an Address or `0`
+    pub fn get_nonce(&self, address: &str) -> u64 {
+        self.nonces.get(address).copied().unwrap_or(0)
+    }
+
+    /// Weights of the Validators in Stake Units ordered by their Public Keys
+    pub fn get_weights(&self) -> Vec<(String, u64)> {
+        self.stakes
+            .values()
+            .map(|s| {
+                (
+                    s.public_key.clone(),
+                    (s.amount * STAKE_UNITS).round() as u64,
+                )
+            })
+            .filter(|(_, w)| *w > 0)
+            .collect()
+    }
+}
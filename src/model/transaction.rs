@@ -10,18 +10,126 @@
 * Requirements:
 */
 
+use ed25519_dalek::{Signature, Signer, SigningKey, VerifyingKey};
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha256::digest;
 use std::ops::{Deref, DerefMut};
 use std::sync::Mutex;
 
+use super::address;
+use super::asset::AssetDefinition;
+use super::canonical;
+use super::htlc::Htlc;
+use super::stake::StakeAction;
+use super::utxo::{OutPoint, TxInput, TxOutput};
+use crate::script::{Script, ScriptContext, ScriptError};
+
 //==============================================================================
 // Structure Transaction Declaration
 
-#[derive(Debug, Default, Serialize, Deserialize)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct Transaction {
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     pub sender: String,
+    /// Checksummed Address of the Receiver. Chains from before Addresses can hold
+    /// other Receivers, so new `Transaction`s are checked by `is_valid()`.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     pub receiver: String,
+    #[serde(default, deserialize_with = "canonical::deserialize_amount")]
     pub amount: f64,
+    /// Id of the Asset which is sent instead of the native Currency
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub asset: Option<String>,
+    /// Definition of a new Asset whose Supply of `amount` is issued to the Sender
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub issuance: Option<AssetDefinition>,
+    /// Locks or releases the `amount` as Stake of the Key of the Sender
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stake: Option<StakeAction>,
+    /// Number chosen by the Sender to tell apart otherwise identical Transactions
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub nonce: u64,
+    /// Block Height or Timestamp before which the Transaction cannot be mined
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lock_until: Option<TimeLock>,
+    /// Hex encoded Ed25519 Public Key of the Sender
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub public_key: Option<String>,
+    /// Hex encoded Ed25519 Signature of the Signing Payload
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+    /// Policy of the Multi-Signature Address of the Sender
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub multisig: Option<MultisigPolicy>,
+    /// Signatures of the Keys of the Multi-Signature Policy
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub signatures: Vec<PartialSignature>,
+    /// Script whose Address is the Sender
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub script: Option<Script>,
+    /// Hex encoded Items which satisfy the Script of the Sender
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub witness: Vec<String>,
+    /// Former Outputs spent by a UTXO Transaction
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub inputs: Vec<TxInput>,
+    /// Outputs created by a UTXO Transaction
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub outputs: Vec<TxOutput>,
+}
+
+/// Condition which a Block must meet to include a time-locked `Transaction`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TimeLock {
+    /// Index of the first Block which can include the `Transaction`
+    Height(u64),
+    /// Unix Timestamp in Seconds from which on a Block can include the `Transaction`.
+    /// It is compared with the Median Time Past of the Blocks before.
+    Timestamp(u32),
+}
+
+/// Structure for an M-of-N Policy which defines a Multi-Signature Address
+///
+/// `Transaction`s sent from the Address need Signatures of at least `threshold`
+/// distinct Keys of the Policy.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MultisigPolicy {
+    pub threshold: u8,
+    /// Hex encoded Ed25519 Public Keys
+    pub public_keys: Vec<String>,
+}
+
+/// Structure for the Signature of one Key of a Multi-Signature Policy
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PartialSignature {
+    /// Hex encoded Ed25519 Public Key
+    pub public_key: String,
+    /// Hex encoded Ed25519 Signature of the Signing Payload
+    pub signature: String,
+}
+
+/// Fields of a `Transaction` which are covered by its Signature
+#[derive(Serialize)]
+struct SigningPayload<'a> {
+    sender: &'a str,
+    receiver: &'a str,
+    amount: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    asset: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    issuance: Option<&'a AssetDefinition>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stake: Option<StakeAction>,
+    nonce: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    lock_until: Option<TimeLock>,
+    public_key: &'a str,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    inputs: Vec<OutPoint>,
+    #[serde(skip_serializing_if = "<[_]>::is_empty")]
+    outputs: &'a [TxOutput],
 }
 
 //==============================================================================
@@ -32,6 +140,12 @@ pub struct MutexTransactionList {
     pub transaction_mutex: Mutex<Vec<Transaction>>,
 }
 
+/// Sender of the Mining Rewards
+pub const COINBASE_SENDER: &str = "blockchain";
+
+/// Maximum number of Keys of a Multi-Signature Policy
+pub const MAX_MULTISIG_KEYS: usize = 15;
+
 /// Structure for Email Sending Errors
 #[derive(Debug, PartialEq)]
 pub struct TransactionMutexError {
@@ -61,25 +175,686 @@ impl Transaction {
     /// ```
     ///    use blockchain_api::model::transaction::Transaction;
     ///
-    ///    let reward = Transaction::from_data("Blockchain".to_owned(), "Miner".to_owned(), 10f64);
+    ///    let reward = Transaction::from_data(
+    ///        "blockchain".to_owned(),
+    ///        "16Jswqk47s9PUcyCc88MMVwzgvHPvtEpf".to_owned(),
+    ///        10f64,
+    ///    );
     /// ```
     pub fn from_data(sender: String, receiver: String, amount: f64) -> Self {
         Self {
-            sender: sender,
-            receiver: receiver,
-            amount: amount,
+            sender,
+            receiver,
+            amount,
+            ..Default::default()
+        }
+    }
+
+    /// Create a new Transaction sent from the Address of a Key Pair.
+    ///
+    /// The `Transaction` still needs to be signed with `sign()`.
+    pub fn from_key(public_key: &VerifyingKey, receiver: String, amount: f64, nonce: u64) -> Self {
+        Self {
+            sender: address::from_public_key(public_key),
+            receiver,
+            amount,
+            nonce,
+            public_key: Some(hex::encode(public_key.as_bytes())),
+            ..Default::default()
+        }
+    }
+
+    /// Create a new Transaction sent from a Multi-Signature Address.
+    ///
+    /// The `Transaction` still needs enough Signatures added with `add_signature()`.
+    pub fn from_multisig(
+        policy: MultisigPolicy,
+        receiver: String,
+        amount: f64,
+        nonce: u64,
+    ) -> Self {
+        Self {
+            sender: policy.to_address().unwrap_or_default(),
+            receiver,
+            amount,
+            nonce,
+            multisig: Some(policy),
+            ..Default::default()
+        }
+    }
+
+    /// Create a new Transaction sent from the Address of a Script.
+    ///
+    /// The `Transaction` still needs the `witness` Items which satisfy the Script.
+    pub fn from_script(script: Script, receiver: String, amount: f64, nonce: u64) -> Self {
+        Self {
+            sender: script.to_address(),
+            receiver,
+            amount,
+            nonce,
+            script: Some(script),
+            ..Default::default()
+        }
+    }
+
+    /// Create a new UTXO Transaction which spends an Output locked by a Hash
+    /// Time-Locked Contract.
+    ///
+    /// The `Transaction` still needs to be claimed with `claim_htlc()` or
+    /// refunded with `refund_htlc()`.
+    pub fn from_htlc_output(
+        htlc: &Htlc,
+        outpoint: &OutPoint,
+        outputs: Vec<TxOutput>,
+        nonce: u64,
+    ) -> Self {
+        let mut transaction = Self::from_outputs(std::slice::from_ref(outpoint), outputs, nonce);
+
+        transaction.inputs[0].script = Some(htlc.to_script());
+
+        transaction
+    }
+
+    /// Create a new Transaction which issues the `supply` of a new Asset to the Address
+    /// of a Key Pair.
+    ///
+    /// The `Transaction` still needs to be signed with `sign()`.
+    pub fn from_issuance(
+        public_key: &VerifyingKey,
+        definition: AssetDefinition,
+        supply: f64,
+        nonce: u64,
+    ) -> Self {
+        let mut transaction = Self::from_key(public_key, String::new(), supply, nonce);
+
+        transaction.receiver = transaction.sender.clone();
+        transaction.issuance = Some(definition);
+
+        transaction
+    }
+
+    /// Create a new Transaction which stakes or unstakes an `amount` for the Key.
+    ///
+    /// The `Transaction` still needs to be signed with `sign()`.
+    pub fn from_stake(
+        public_key: &VerifyingKey,
+        action: StakeAction,
+        amount: f64,
+        nonce: u64,
+    ) -> Self {
+        let mut transaction = Self::from_key(public_key, String::new(), amount, nonce);
+
+        transaction.receiver = transaction.sender.clone();
+        transaction.stake = Some(action);
+
+        transaction
+    }
+
+    /// Create a new UTXO Transaction which spends former Outputs.
+    ///
+    /// Each Input still needs to be signed with `sign_inputs()`.
+    pub fn from_outputs(inputs: &[OutPoint], outputs: Vec<TxOutput>, nonce: u64) -> Self {
+        Self {
+            nonce,
+            inputs: inputs.iter().map(TxInput::from_outpoint).collect(),
+            outputs,
+            ..Default::default()
         }
     }
 
+    /// Create a UTXO Transaction for the Mining Reward.
+    ///
+    /// The `nonce` must be unique for each Reward. The Index of the Block is used.
+    pub fn from_coinbase(receiver: String, amount: f64, nonce: u64) -> Self {
+        Self {
+            sender: String::from(COINBASE_SENDER),
+            nonce,
+            outputs: vec![TxOutput {
+                address: receiver,
+                amount,
+            }],
+            ..Default::default()
+        }
+    }
+
+    /*----------------------------------------------------------------------------
+     * Administration Methods
+     */
+
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string(&self)
+    }
+
+    /// Encode the `Transaction` as canonical JSON which its Id is computed from.
+    ///
+    /// The `witness` Items of the Sender and of the Inputs are left out. The Signatures
+    /// cannot cover them, so otherwise anyone who relays the `Transaction` could change
+    /// its Id by altering them.
+    pub fn to_canonical_json(&self) -> Result<String, serde_json::Error> {
+        let mut value = serde_json::to_value(self)?;
+
+        if let Some(fields) = value.as_object_mut() {
+            fields.remove("witness");
+
+            if let Some(Value::Array(inputs)) = fields.get_mut("inputs") {
+                inputs
+                    .iter_mut()
+                    .filter_map(|i| i.as_object_mut())
+                    .for_each(|i| {
+                        i.remove("witness");
+                    });
+            }
+        }
+
+        canonical::to_canonical_json(&value)
+    }
+
+    /// Sign the `Transaction` with the Key of the Sender.
+    ///
+    /// The Public Key of the Signing Key is stored in the `Transaction` so that
+    /// the Node can verify the Signature.
+    ///
+    /// # Returns:
+    /// - an Error if the Signing Payload cannot be encoded
+    ///
+    pub fn sign(&mut self, signing_key: &SigningKey) -> Result<(), serde_json::Error> {
+        self.public_key = Some(hex::encode(signing_key.verifying_key().as_bytes()));
+
+        let signature = signing_key.sign(&self.to_signing_payload()?);
+
+        self.signature = Some(hex::encode(signature.to_bytes()));
+
+        Ok(())
+    }
+
+    /// Sign all Inputs of a UTXO Transaction with the Key which owns the spent Outputs.
+    pub fn sign_inputs(&mut self, signing_key: &SigningKey) -> Result<(), serde_json::Error> {
+        for position in 0..self.inputs.len() {
+            self.sign_input(position, signing_key)?;
+        }
+
+        Ok(())
+    }
+
+    /// Sign one Input of a UTXO Transaction.
+    ///
+    /// The Signature covers all Inputs and Outputs so the Inputs can be signed
+    /// by different Keys in any order.
+    pub fn sign_input(
+        &mut self,
+        position: usize,
+        signing_key: &SigningKey,
+    ) -> Result<(), serde_json::Error> {
+        let signature = signing_key.sign(&self.to_signing_payload()?);
+
+        if let Some(input) = self.inputs.get_mut(position) {
+            input.public_key = hex::encode(signing_key.verifying_key().as_bytes());
+            input.signature = hex::encode(signature.to_bytes());
+        }
+
+        Ok(())
+    }
+
+    /// Claim the Funds of the Hash Time-Locked Contracts spent by the `Transaction`
+    /// with the Secret and the Key of the Receiver.
+    pub fn claim_htlc(
+        &mut self,
+        secret: &[u8],
+        signing_key: &SigningKey,
+    ) -> Result<(), serde_json::Error> {
+        let signature = signing_key.sign(&self.to_signing_payload()?);
+
+        self.set_script_witness(vec![
+            hex::encode(signature.to_bytes()),
+            hex::encode(secret),
+            String::from("01"),
+        ]);
+
+        Ok(())
+    }
+
+    /// Take back the Funds of the Hash Time-Locked Contracts spent by the
+    /// `Transaction` with the Key of the Sender after their Timeout.
+    pub fn refund_htlc(&mut self, signing_key: &SigningKey) -> Result<(), serde_json::Error> {
+        let signature = signing_key.sign(&self.to_signing_payload()?);
+
+        self.set_script_witness(vec![hex::encode(signature.to_bytes()), String::new()]);
+
+        Ok(())
+    }
+
+    /// Set the Witness of the Sender Script and of all Inputs with a Script.
+    fn set_script_witness(&mut self, witness: Vec<String>) {
+        if self.script.is_some() {
+            self.witness = witness.clone();
+        }
+
+        self.inputs
+            .iter_mut()
+            .filter(|i| i.script.is_some())
+            .for_each(|i| i.witness = witness.clone());
+    }
+
+    /// Add the Signature of one Key of the Multi-Signature Policy.
+    ///
+    /// A former Signature of the same Key is replaced.
+    pub fn add_signature(&mut self, signing_key: &SigningKey) -> Result<(), serde_json::Error> {
+        let signature = signing_key.sign(&self.to_signing_payload()?);
+
+        self.add_partial_signature(PartialSignature {
+            public_key: hex::encode(signing_key.verifying_key().as_bytes()),
+            signature: hex::encode(signature.to_bytes()),
+        });
+
+        Ok(())
+    }
+
+    /// Add a Signature which was made elsewhere.
+    ///
+    /// A former Signature of the same Key is replaced.
+    pub fn add_partial_signature(&mut self, signature: PartialSignature) {
+        self.signatures
+            .retain(|s| s.public_key != signature.public_key);
+        self.signatures.push(signature);
+    }
+
+    /// Build the Data which is signed by the Sender.
+    ///
+    /// The Wallet and the Node both use this function so that the signed Data
+    /// is always identical. It is the canonical JSON of the covered Fields.
+    ///
+    /// # Returns:
+    /// - an Error if the Fields hold invalid Amounts
+    ///
+    pub fn to_signing_payload(&self) -> Result<Vec<u8>, serde_json::Error> {
+        let payload = SigningPayload {
+            sender: &self.sender,
+            receiver: &self.receiver,
+            amount: self.amount,
+            asset: self.asset.as_deref(),
+            issuance: self.issuance.as_ref(),
+            stake: self.stake,
+            nonce: self.nonce,
+            lock_until: self.lock_until,
+            public_key: self.public_key.as_deref().unwrap_or(""),
+            inputs: self.inputs.iter().map(|i| i.to_outpoint()).collect(),
+            outputs: &self.outputs,
+        };
+
+        canonical::to_canonical_bytes(&payload)
+    }
+
+    /// Calculate the Transaction Id.
+    ///
+    /// The Transaction Id is the SHA-256 hash of the canonical JSON of the `Transaction`.
+    ///
+    /// # Returns:
+    /// - an Error if the `Transaction` holds invalid Amounts
+    ///
+    pub fn to_hash(&self) -> Result<String, serde_json::Error> {
+        self.to_canonical_json().map(digest)
+    }
+
     /*----------------------------------------------------------------------------
      * Consultation Methods
      */
 
     /// Check if a Transaction is valid.
     ///
-    /// The fields `sender` and `receiver` must not be empty and the `amount` field must not be ` 0 `
+    /// The field `sender` must not be empty, the `receiver` must be a valid Address
+    /// and the `amount` field must not be ` 0 `.
+    /// Signed Transactions and Transactions sent from the Address of a Key Pair must carry
+    /// a valid Signature.
+    /// Transactions sent from a Multi-Signature Address must carry enough valid Signatures
+    /// of its Policy.
+    /// Asset `Transaction`s must be sent from a valid Address with a positive `amount`.
+    /// Issuances are sent to the Issuer.
     pub fn is_valid(&self) -> bool {
-        !self.sender.is_empty() && !self.receiver.is_empty() && self.amount != 0f64
+        self.is_valid_in_chain() && (self.is_utxo() || address::is_valid(&self.receiver))
+    }
+
+    /// Check if a Transaction stored in a Chain is valid.
+    ///
+    /// Like `is_valid()` but any non-empty `receiver` is accepted because Chains from
+    /// before Addresses send to plain Names.
+    pub fn is_valid_in_chain(&self) -> bool {
+        if self.is_utxo() {
+            return self.is_utxo_valid();
+        }
+
+        if !self.is_native() && !self.is_asset_valid() {
+            return false;
+        }
+
+        // Stakes belong to Keys
+        if self.stake.is_some()
+            && !(self.is_native()
+                && self.receiver == self.sender
+                && self.amount > 0f64
+                && address::is_key_address(&self.sender))
+        {
+            return false;
+        }
+
+        let is_signed = self.public_key.is_some() || self.signature.is_some();
+        let is_multisig = self.multisig.is_some()
+            || !self.signatures.is_empty()
+            || address::is_multisig_address(&self.sender);
+        let is_script = self.script.is_some()
+            || !self.witness.is_empty()
+            || address::is_script_address(&self.sender);
+
+        !self.sender.is_empty()
+            && !self.receiver.is_empty()
+            && self.amount != 0f64
+            && canonical::is_amount_valid(self.amount)
+            && if is_script {
+                !(is_signed || is_multisig) && self.is_script_form_valid()
+            } else if is_multisig {
+                self.is_multisig_valid()
+            } else {
+                !(is_signed || address::is_key_address(&self.sender)) || self.is_signature_valid()
+            }
+    }
+
+    /// Check whether the Transaction sends the native Currency.
+    pub fn is_native(&self) -> bool {
+        self.asset.is_none() && self.issuance.is_none()
+    }
+
+    fn is_asset_valid(&self) -> bool {
+        let is_form_valid = address::is_valid(&self.sender) && self.amount > 0f64;
+
+        match (self.asset.as_ref(), self.issuance.as_ref()) {
+            (Some(_), None) => is_form_valid,
+            (None, Some(d)) => is_form_valid && d.is_valid() && self.receiver == self.sender,
+            _ => false,
+        }
+    }
+
+    /// Check whether the Transaction spends and creates Outputs instead of sending
+    /// an `amount` from the `sender` to the `receiver`.
+    pub fn is_utxo(&self) -> bool {
+        !self.inputs.is_empty() || !self.outputs.is_empty()
+    }
+
+    /// Check whether the Sender is the Script of a Hash Time-Locked Contract.
+    ///
+    /// Such `Transaction`s are only accepted in UTXO Mode where the Contract
+    /// locks one Output which can be spent only once.
+    pub fn is_htlc(&self) -> bool {
+        self.script.as_ref().and_then(Htlc::from_script).is_some()
+    }
+
+    /// Check whether the UTXO Transaction creates the Mining Reward.
+    pub fn is_coinbase(&self) -> bool {
+        self.is_utxo() && self.inputs.is_empty() && self.sender == COINBASE_SENDER
+    }
+
+    /// List the Addresses which send or receive with the Transaction.
+    pub fn get_addresses(&self) -> Vec<String> {
+        let mut addresses = Vec::<String>::with_capacity(2 + self.outputs.len());
+        let candidates = [self.sender.clone(), self.receiver.clone()]
+            .into_iter()
+            .chain(self.inputs.iter().filter_map(|i| i.get_address()))
+            .chain(self.outputs.iter().map(|o| o.address.clone()));
+
+        for address in candidates {
+            if !address.is_empty() && !addresses.contains(&address) {
+                addresses.push(address);
+            }
+        }
+
+        addresses
+    }
+
+    /// Check whether the Transaction sends from an Address.
+    pub fn is_sent_by(&self, address: &str) -> bool {
+        self.sender == address
+            || self
+                .inputs
+                .iter()
+                .any(|i| i.get_address().as_deref() == Some(address))
+    }
+
+    pub fn get_output_total(&self) -> f64 {
+        self.outputs.iter().map(|o| o.amount).sum()
+    }
+
+    /// Sum the Outputs in Base Units.
+    ///
+    /// # Returns:
+    /// - the Total or `None` if an Output Amount is no valid Amount or the Total overflows
+    ///
+    pub fn get_output_units(&self) -> Option<i64> {
+        self.outputs.iter().try_fold(0i64, |total, o| {
+            canonical::to_amount_units(o.amount).and_then(|units| total.checked_add(units))
+        })
+    }
+
+    /// Check the Form and the Signatures of a UTXO Transaction.
+    ///
+    /// It must create at least one Output with a positive Amount. Except for the
+    /// Mining Reward it must spend at least one Output and each Input must be signed.
+    /// Whether the Inputs are unspent is checked against the `UtxoSet`.
+    pub fn is_utxo_valid(&self) -> bool {
+        let payload = match self.to_signing_payload() {
+            Ok(p) => p,
+            Err(_) => return false,
+        };
+        let is_form_valid = self.receiver.is_empty()
+            && self.amount == 0f64
+            && self.is_native()
+            && self.public_key.is_none()
+            && self.signature.is_none()
+            && self.multisig.is_none()
+            && self.signatures.is_empty()
+            && self.script.is_none()
+            && self.witness.is_empty()
+            && !self.outputs.is_empty()
+            && self.outputs.iter().all(|o| {
+                address::is_valid(&o.address)
+                    && canonical::is_amount_valid(o.amount)
+                    && o.amount > 0f64
+            });
+
+        is_form_valid
+            && if self.inputs.is_empty() {
+                self.sender == COINBASE_SENDER
+            } else {
+                self.sender.is_empty()
+                    && self.inputs.iter().all(|i| match i.script.as_ref() {
+                        Some(s) => {
+                            s.is_valid() && i.public_key.is_empty() && i.signature.is_empty()
+                        }
+                        None => {
+                            i.witness.is_empty()
+                                && is_signature_valid(&i.public_key, &i.signature, &payload)
+                        }
+                    })
+            }
+    }
+
+    /// Check whether a Block with the given Index can include the `Transaction`.
+    ///
+    /// `lock_time` is the Median Time Past of the Blocks before. Unlike the Timestamp
+    /// of the Block itself the Miner cannot move it ahead.
+    /// `Transaction`s without `lock_until` can be included in any Block.
+    pub fn is_unlocked(&self, block_index: u64, lock_time: u32) -> bool {
+        match self.lock_until {
+            Some(TimeLock::Height(h)) => block_index >= h,
+            Some(TimeLock::Timestamp(t)) => lock_time >= t,
+            None => true,
+        }
+    }
+
+    /// Execute the Scripts of the Sender and of the Inputs for the Block with the
+    /// given Index and the Median Time Past of the Blocks before.
+    ///
+    /// `Transaction`s without Scripts always pass.
+    pub fn check_scripts(&self, block_index: u64, lock_time: u32) -> Result<(), ScriptError> {
+        if !self.has_script() {
+            return Ok(());
+        }
+
+        let payload = self.to_signing_payload().map_err(|e| {
+            ScriptError::new(
+                "invalid",
+                &format!("Script: Signing Payload cannot be encoded! {}", e),
+            )
+        })?;
+        let context = ScriptContext {
+            payload: &payload,
+            block_index,
+            block_timestamp: lock_time,
+        };
+
+        if let Some(script) = self.script.as_ref() {
+            script.execute(&self.witness, &context)?;
+        }
+
+        for input in self.inputs.iter() {
+            if let Some(script) = input.script.as_ref() {
+                script.execute(&input.witness, &context)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Check whether the Sender or any Input is a Script.
+    pub fn has_script(&self) -> bool {
+        self.script.is_some() || self.inputs.iter().any(|i| i.script.is_some())
+    }
+
+    /// Count the distinct Keys of the Multi-Signature Policy with a valid Signature.
+    ///
+    /// # Returns:
+    /// - `None` if the Policy does not match the Sender or a Signature is invalid
+    ///
+    pub fn count_valid_signatures(&self) -> Option<usize> {
+        let policy = self.multisig.as_ref()?;
+        let payload = self.to_signing_payload().ok()?;
+        let mut signers = Vec::<&str>::with_capacity(self.signatures.len());
+
+        if policy.to_address().as_deref() != Some(self.sender.as_str()) {
+            return None;
+        }
+
+        for s in self.signatures.iter() {
+            if !policy.public_keys.contains(&s.public_key)
+                || signers.contains(&s.public_key.as_str())
+                || !is_signature_valid(&s.public_key, &s.signature, &payload)
+            {
+                return None;
+            }
+
+            signers.push(&s.public_key);
+        }
+
+        Some(signers.len())
+    }
+
+    /// Check that enough distinct Keys of the Multi-Signature Policy signed the `Transaction`.
+    ///
+    /// A Multi-Signature `Transaction` must not carry a single Signature besides.
+    pub fn is_multisig_valid(&self) -> bool {
+        match (self.multisig.as_ref(), self.count_valid_signatures()) {
+            (Some(p), Some(count)) => {
+                self.public_key.is_none()
+                    && self.signature.is_none()
+                    && count >= p.threshold as usize
+            }
+            _ => false,
+        }
+    }
+
+    /// Check that the Signature was made by the Key of the Sender Address.
+    pub fn is_signature_valid(&self) -> bool {
+        let (public_key, signature) = match (self.public_key.as_ref(), self.signature.as_ref()) {
+            (Some(k), Some(s)) => (k, s),
+            _ => return false,
+        };
+        let public_key_address = match decode_public_key(public_key) {
+            Some(k) => address::from_public_key(&k),
+            None => return false,
+        };
+
+        public_key_address == self.sender
+            && self
+                .to_signing_payload()
+                .is_ok_and(|p| is_signature_valid(public_key, signature, &p))
+    }
+
+    /// Check that the revealed Script is well-formed and belongs to the Sender Address.
+    ///
+    /// The Script is executed with `check_scripts()` once the Block is known.
+    fn is_script_form_valid(&self) -> bool {
+        match self.script.as_ref() {
+            Some(s) => s.is_valid() && s.to_address() == self.sender,
+            None => false,
+        }
+    }
+}
+
+//==============================================================================
+// Structure MultisigPolicy Implementation
+
+impl MultisigPolicy {
+    /*----------------------------------------------------------------------------
+     * Constructors
+     */
+
+    pub fn new(threshold: u8, public_keys: &[VerifyingKey]) -> Self {
+        Self {
+            threshold,
+            public_keys: public_keys
+                .iter()
+                .map(|k| hex::encode(k.as_bytes()))
+                .collect(),
+        }
+    }
+
+    /*----------------------------------------------------------------------------
+     * Consultation Methods
+     */
+
+    /// Check that the Policy is an M-of-N Policy with 1 <= M <= N distinct valid Keys.
+    pub fn is_valid(&self) -> bool {
+        self.decode_keys().is_some()
+    }
+
+    /// Derive the Multi-Signature Address of the Policy.
+    ///
+    /// # Returns:
+    /// - `None` if the Policy is invalid
+    ///
+    pub fn to_address(&self) -> Option<String> {
+        self.decode_keys()
+            .map(|keys| address::Address::from_multisig(self.threshold, &keys).to_string())
+    }
+
+    fn decode_keys(&self) -> Option<Vec<VerifyingKey>> {
+        let mut keys = Vec::<VerifyingKey>::with_capacity(self.public_keys.len());
+
+        if self.threshold == 0
+            || self.threshold as usize > self.public_keys.len()
+            || self.public_keys.len() > MAX_MULTISIG_KEYS
+        {
+            return None;
+        }
+
+        for k in self.public_keys.iter() {
+            let key = decode_public_key(k)?;
+
+            if keys.contains(&key) {
+                return None;
+            }
+
+            keys.push(key);
+        }
+
+        Some(keys)
     }
 }
 
@@ -134,7 +909,8 @@ impl MutexTransactionList {
         self.add_transaction(Transaction {
             sender: sender.to_owned(),
             receiver: receiver.to_owned(),
-            amount: amount,
+            amount,
+            ..Default::default()
         })
     }
 
@@ -143,7 +919,7 @@ impl MutexTransactionList {
     /// # Parameters:
     ///
     /// - `transaction`: `Transaction` to be added. It will be published as soon as
-    /// a new block is mined.
+    ///   a new block is mined.
     ///
     pub fn add_transaction(&self, transaction: Transaction) -> Result<(), TransactionMutexError> {
         match self.transaction_mutex.lock() {
@@ -161,10 +937,152 @@ impl MutexTransactionList {
         }
     }
 
+    /// Take the `Transaction`s which a Block with the given Index and Median Time Past
+    /// can include.
+    ///
+    /// Time-locked `Transaction`s which are not eligible yet stay in the List.
+    pub fn take_unlocked(&self, block_index: u64, lock_time: u32) -> Vec<Transaction> {
+        let mut guard = match self.transaction_mutex.lock() {
+            Ok(guard) => guard,
+            Err(e) => {
+                eprintln!("Transaction List: Mutex Lock failed! Message: {:?}", e);
+
+                e.into_inner()
+            }
+        };
+        let (unlocked, locked) = guard
+            .drain(..)
+            .partition(|t| t.is_unlocked(block_index, lock_time));
+
+        *guard = locked;
+
+        unlocked
+    }
+
+    /// Give taken `Transaction`s back in front of the waiting `Transaction`s.
+    pub fn restore(&self, transactions: Vec<Transaction>) {
+        let mut guard = match self.transaction_mutex.lock() {
+            Ok(guard) => guard,
+            Err(e) => {
+                eprintln!("Transaction List: Mutex Lock failed! Message: {:?}", e);
+
+                e.into_inner()
+            }
+        };
+
+        guard.splice(0..0, transactions);
+    }
+
     /*----------------------------------------------------------------------------
      * Consultation Methods
      */
 
+    /// List the Amounts of an Asset which an Address sends with the waiting `Transaction`s.
+    pub fn get_pending_amounts(&self, sender: &str, asset_id: &str) -> Vec<f64> {
+        let list = |transactions: &Vec<Transaction>| {
+            transactions
+                .iter()
+                .filter(|t| t.sender == sender && t.asset.as_deref() == Some(asset_id))
+                .map(|t| t.amount)
+                .collect()
+        };
+
+        match self.transaction_mutex.lock() {
+            Ok(guard) => list(guard.deref()),
+            Err(e) => {
+                eprintln!("Transaction List: Mutex Lock failed! Message: {:?}", e);
+
+                list(e.get_ref())
+            }
+        }
+    }
+
+    /// Sum the Amounts of the native Currency which an Address sends or stakes with the
+    /// waiting `Transaction`s.
+    pub fn get_pending_debit(&self, sender: &str) -> f64 {
+        let sum = |transactions: &Vec<Transaction>| {
+            transactions
+                .iter()
+                .filter(|t| {
+                    t.sender == sender
+                        && t.is_native()
+                        && !t.is_utxo()
+                        && t.stake != Some(StakeAction::Unstake)
+                })
+                .map(|t| t.amount)
+                .sum()
+        };
+
+        match self.transaction_mutex.lock() {
+            Ok(guard) => sum(guard.deref()),
+            Err(e) => {
+                eprintln!("Transaction List: Mutex Lock failed! Message: {:?}", e);
+
+                sum(e.get_ref())
+            }
+        }
+    }
+
+    /// Highest Nonce of the waiting `Transaction`s of an Address or `0`
+    pub fn get_pending_nonce(&self, sender: &str) -> u64 {
+        let max = |transactions: &Vec<Transaction>| {
+            transactions
+                .iter()
+                .filter(|t| t.sender == sender)
+                .map(|t| t.nonce)
+                .max()
+                .unwrap_or(0)
+        };
+
+        match self.transaction_mutex.lock() {
+            Ok(guard) => max(guard.deref()),
+            Err(e) => {
+                eprintln!("Transaction List: Mutex Lock failed! Message: {:?}", e);
+
+                max(e.get_ref())
+            }
+        }
+    }
+
+    /// Sum the Amounts which an Address stakes or unstakes with the waiting `Transaction`s.
+    pub fn get_pending_stake(&self, sender: &str, action: StakeAction) -> f64 {
+        let sum = |transactions: &Vec<Transaction>| {
+            transactions
+                .iter()
+                .filter(|t| t.sender == sender && t.stake == Some(action))
+                .map(|t| t.amount)
+                .sum()
+        };
+
+        match self.transaction_mutex.lock() {
+            Ok(guard) => sum(guard.deref()),
+            Err(e) => {
+                eprintln!("Transaction List: Mutex Lock failed! Message: {:?}", e);
+
+                sum(e.get_ref())
+            }
+        }
+    }
+
+    /// Check whether a waiting `Transaction` already spends any of the Outputs.
+    pub fn is_any_spent(&self, outpoints: &[OutPoint]) -> bool {
+        let is_spent = |transactions: &Vec<Transaction>| {
+            transactions
+                .iter()
+                .flat_map(|t| t.inputs.iter())
+                .any(|i| outpoints.contains(&i.to_outpoint()))
+        };
+
+        match self.transaction_mutex.lock() {
+            Ok(guard) => is_spent(guard.deref()),
+            Err(e) => {
+                eprintln!("Transaction List: Mutex Lock failed! Message: {:?}", e);
+
+                is_spent(e.get_ref())
+            }
+        }
+    }
+
     pub fn into_vec(&self) -> Vec<Transaction> {
         match self.transaction_mutex.lock() {
             Ok(mut guard) => {
@@ -188,6 +1106,23 @@ impl MutexTransactionList {
         }
     }
 
+    /// Check whether a `Transaction` is waiting for the next Block.
+    pub fn contains(&self, txid: &str) -> bool {
+        match self.transaction_mutex.lock() {
+            Ok(guard) => guard
+                .deref()
+                .iter()
+                .any(|t| t.to_hash().is_ok_and(|h| h == txid)),
+            Err(e) => {
+                eprintln!("Transaction List: Mutex Lock failed! Message: {:?}", e);
+
+                e.get_ref()
+                    .iter()
+                    .any(|t| t.to_hash().is_ok_and(|h| h == txid))
+            }
+        }
+    }
+
     pub fn get_count(&self) -> usize {
         match self.transaction_mutex.lock() {
             Ok(guard) => {
@@ -206,17 +1141,49 @@ impl MutexTransactionList {
     }
 }
 
+//==============================================================================
+// Auxiliary Functions
+
+fn is_zero(value: &u64) -> bool {
+    *value == 0
+}
+
+fn decode_public_key(public_key: &str) -> Option<VerifyingKey> {
+    hex::decode(public_key)
+        .ok()
+        .and_then(|k| <[u8; 32]>::try_from(k).ok())
+        .and_then(|k| VerifyingKey::from_bytes(&k).ok())
+}
+
+/// Verify a hex encoded Signature of a hex encoded Public Key
+fn is_signature_valid(public_key: &str, signature: &str, payload: &[u8]) -> bool {
+    let public_key = match decode_public_key(public_key) {
+        Some(k) => k,
+        None => return false,
+    };
+    let signature = match hex::decode(signature)
+        .ok()
+        .and_then(|s| Signature::from_slice(&s).ok())
+    {
+        Some(s) => s,
+        None => return false,
+    };
+
+    public_key.verify_strict(payload, &signature).is_ok()
+}
+
 //==============================================================================
 // Unit Tests
 
 /*
 Recreating the Test Data:
 
-    $ curl -v http://localhost:3100/add_transaction -d '{"sender":"sender1","receiver":"receiver1","amount":5.67}' | jq '.'
+    $ curl -v http://localhost:3100/add_transaction -d '{"sender":"sender1","receiver":"16Jswqk47s9PUcyCc88MMVwzgvHPvtEpf","amount":5.67}' | jq '.'
 
 */
 
 #[test]
+#[allow(clippy::vec_init_then_push)]
 fn transaction_list_from_vec() {
     //-------------------------------------
     // Create a `MutexTransactionList` from a Vector
@@ -227,16 +1194,19 @@ fn transaction_list_from_vec() {
         sender: "sender1".to_owned(),
         receiver: "receiver1".to_owned(),
         amount: 5.67f64,
+        ..Default::default()
     });
     transactions.push(Transaction {
         sender: "sender2".to_owned(),
         receiver: "receiver2".to_owned(),
         amount: 7.89107f64,
+        ..Default::default()
     });
     transactions.push(Transaction {
         sender: "sender3".to_owned(),
         receiver: "receiver3".to_owned(),
         amount: 9.101113f64,
+        ..Default::default()
     });
 
     let transaction_count = transactions.len();
@@ -299,6 +1269,7 @@ fn add_transaction_as_structure() {
         sender: "sender1".to_owned(),
         receiver: "receiver1".to_owned(),
         amount: 5.67f64,
+        ..Default::default()
     });
 
     assert_eq!(result.err(), None);
@@ -307,6 +1278,7 @@ fn add_transaction_as_structure() {
         sender: "sender2".to_owned(),
         receiver: "receiver2".to_owned(),
         amount: 7.89107f64,
+        ..Default::default()
     });
 
     assert_eq!(result.err(), None);
@@ -315,6 +1287,7 @@ fn add_transaction_as_structure() {
         sender: "sender3".to_owned(),
         receiver: "receiver3".to_owned(),
         amount: 9.101113f64,
+        ..Default::default()
     });
 
     assert_eq!(result.err(), None);
@@ -323,3 +1296,242 @@ fn add_transaction_as_structure() {
 
     assert_eq!(transaction_count, 3);
 }
+
+#[test]
+fn transaction_signature() {
+    let signing_key = SigningKey::from_bytes(&[7u8; 32]);
+    let mut transaction = Transaction::from_key(
+        &signing_key.verifying_key(),
+        "16Jswqk47s9PUcyCc88MMVwzgvHPvtEpf".to_owned(),
+        5.67,
+        1,
+    );
+
+    assert!(address::is_key_address(&transaction.sender));
+    assert!(!transaction.is_valid());
+
+    transaction.sign(&signing_key).unwrap();
+
+    assert!(transaction.is_valid());
+
+    //-------------------------------------
+    // The Signature does not cover a changed Amount
+
+    let mut forged = transaction.clone();
+
+    forged.amount = 567.0;
+
+    assert!(!forged.is_valid());
+
+    //-------------------------------------
+    // An unsigned Transaction can not be sent from a Key Address
+
+    let mut unsigned = transaction.clone();
+
+    unsigned.public_key = None;
+    unsigned.signature = None;
+
+    assert!(!unsigned.is_valid());
+
+    //-------------------------------------
+    // Another Key can not sign for the Sender Address
+
+    let mut foreign = transaction.clone();
+
+    foreign.sign(&SigningKey::from_bytes(&[8u8; 32])).unwrap();
+
+    assert!(!foreign.is_valid());
+}
+
+#[test]
+fn transaction_receiver_address() {
+    //-------------------------------------
+    // A mistyped Receiver Address is rejected for new Transactions
+
+    let transaction: Transaction = serde_json::from_str(
+        r#"{"sender":"sender1","receiver":"16Jswqk47s9PUcyCc88MMVwzgvHPvtEpf","amount":1.5}"#,
+    )
+    .unwrap();
+
+    assert!(transaction.is_valid());
+
+    let transaction: Transaction = serde_json::from_str(
+        r#"{"sender":"sender1","receiver":"16Jswqk47s9PUcyCc88MMVwzgvHPvtEpF","amount":1.5}"#,
+    )
+    .unwrap();
+
+    assert!(!transaction.is_valid());
+
+    //-------------------------------------
+    // Transactions of older Chains send to plain Names
+
+    let transaction: Transaction =
+        serde_json::from_str(r#"{"sender":"Blockchain","receiver":"Miner","amount":10.0}"#)
+            .unwrap();
+
+    assert!(!transaction.is_valid());
+    assert!(transaction.is_valid_in_chain());
+}
+
+#[test]
+fn transaction_multisig() {
+    let keys: Vec<SigningKey> = (1..=3u8)
+        .map(|i| SigningKey::from_bytes(&[i; 32]))
+        .collect();
+    let public_keys: Vec<VerifyingKey> = keys.iter().map(|k| k.verifying_key()).collect();
+    let policy = MultisigPolicy::new(2, &public_keys);
+    let mut reversed = public_keys.clone();
+
+    reversed.reverse();
+
+    assert!(policy.is_valid());
+    assert!(address::is_multisig_address(&policy.to_address().unwrap()));
+    assert_eq!(
+        policy.to_address(),
+        MultisigPolicy::new(2, &reversed).to_address()
+    );
+    assert_ne!(
+        policy.to_address(),
+        MultisigPolicy::new(3, &public_keys).to_address()
+    );
+    assert!(!MultisigPolicy::new(0, &public_keys).is_valid());
+    assert!(!MultisigPolicy::new(4, &public_keys).is_valid());
+    assert!(!MultisigPolicy::new(1, &[public_keys[0], public_keys[0]]).is_valid());
+
+    //-------------------------------------
+    // 2 of 3 Signatures are needed
+
+    let mut transaction = Transaction::from_multisig(
+        policy.clone(),
+        "16Jswqk47s9PUcyCc88MMVwzgvHPvtEpf".to_owned(),
+        5.67,
+        1,
+    );
+
+    assert!(!transaction.is_valid());
+
+    transaction.add_signature(&keys[0]).unwrap();
+    transaction.add_signature(&keys[0]).unwrap();
+
+    assert_eq!(transaction.signatures.len(), 1);
+    assert_eq!(transaction.count_valid_signatures(), Some(1));
+    assert!(!transaction.is_valid());
+
+    transaction.add_signature(&keys[2]).unwrap();
+
+    assert!(transaction.is_valid());
+
+    //-------------------------------------
+    // Duplicate, foreign and stale Signatures are rejected
+
+    let mut duplicate = transaction.clone();
+
+    duplicate.signatures[1] = duplicate.signatures[0].clone();
+
+    assert!(!duplicate.is_valid());
+
+    let mut foreign = transaction.clone();
+
+    foreign
+        .add_signature(&SigningKey::from_bytes(&[9u8; 32]))
+        .unwrap();
+
+    assert!(!foreign.is_valid());
+
+    let mut forged = transaction.clone();
+
+    forged.amount = 567.0;
+
+    assert!(!forged.is_valid());
+
+    //-------------------------------------
+    // The Policy must match the Sender Address
+
+    let mut replaced = transaction.clone();
+
+    replaced.multisig = Some(MultisigPolicy::new(1, &public_keys));
+
+    assert!(!replaced.is_valid());
+
+    let mut stripped = transaction;
+
+    stripped.multisig = None;
+
+    assert!(!stripped.is_valid());
+}
+
+#[test]
+fn transaction_time_lock() {
+    let transaction_mutex = MutexTransactionList::new();
+    let signing_key = SigningKey::from_bytes(&[7u8; 32]);
+    let mut locked = Transaction::from_key(
+        &signing_key.verifying_key(),
+        "16Jswqk47s9PUcyCc88MMVwzgvHPvtEpf".to_owned(),
+        5.67,
+        1,
+    );
+
+    locked.lock_until = Some(TimeLock::Height(3));
+    locked.sign(&signing_key).unwrap();
+
+    assert!(locked.is_valid());
+    assert!(!locked.is_unlocked(2, u32::MAX));
+    assert!(locked.is_unlocked(3, 0));
+
+    //-------------------------------------
+    // The Signature covers the Time Lock
+
+    let mut released = locked.clone();
+
+    released.lock_until = None;
+
+    assert!(!released.is_valid());
+
+    transaction_mutex.add_transaction(locked).unwrap();
+    transaction_mutex
+        .add_transaction(Transaction {
+            sender: "sender2".to_owned(),
+            receiver: "1BcktgV7EjHmxEwQDFFhhztzNqZkd5gdm".to_owned(),
+            amount: 7.89107f64,
+            lock_until: Some(TimeLock::Timestamp(1_000)),
+            ..Default::default()
+        })
+        .unwrap();
+    transaction_mutex
+        .add_transaction_from_data("sender3", "1GvdqXEAMbSARrubpNP44Vqz4kr6TDPgC", 9.101113)
+        .unwrap();
+
+    assert_eq!(transaction_mutex.take_unlocked(2, 999).len(), 1);
+    assert_eq!(transaction_mutex.get_count(), 2);
+    assert_eq!(transaction_mutex.take_unlocked(2, 1_000).len(), 1);
+    assert_eq!(transaction_mutex.take_unlocked(3, 0).len(), 1);
+    assert_eq!(transaction_mutex.get_count(), 0);
+}
+
+#[test]
+fn transaction_witness_id() {
+    let mut transaction = Transaction::from_outputs(
+        &[OutPoint {
+            txid: "00".repeat(32),
+            output: 0,
+        }],
+        vec![TxOutput {
+            address: "16Jswqk47s9PUcyCc88MMVwzgvHPvtEpf".to_owned(),
+            amount: 5.67,
+        }],
+        1,
+    );
+    let txid = transaction.to_hash().unwrap();
+
+    //-------------------------------------
+    // Changed Witness Items do not change the Transaction Id
+
+    transaction.inputs[0].witness = vec![String::from("01")];
+    transaction.witness = vec![String::from("02")];
+
+    assert_eq!(transaction.to_hash().unwrap(), txid);
+
+    transaction.nonce = 2;
+
+    assert_ne!(transaction.to_hash().unwrap(), txid);
+}
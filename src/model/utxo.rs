@@ -0,0 +1,415 @@
+/*
+* @author Bodo (Hugo) Barwich
+* @version 2026-10-18
+* @package Blockchain Exercise
+* @subpackage UTXO Structures
+
+* This Module defines the Inputs and Outputs of UTXO `Transaction`s and the
+* Set of unspent Outputs which is maintained while Blocks are applied and
+* rolled back
+*
+*---------------------------------
+* Requirements:
+*/
+
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+use super::address;
+use super::blockchain::{Block, LedgerError};
+use super::canonical;
+use super::transaction::Transaction;
+use crate::script::Script;
+
+//==============================================================================
+// Structure OutPoint Declaration
+
+/// Structure for the Reference to an Output of a former `Transaction`
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct OutPoint {
+    /// Transaction Id of the `Transaction` which created the Output
+    pub txid: String,
+    /// Position of the Output within the `Transaction`
+    pub output: u32,
+}
+
+/// Structure for an Input which spends a former Output
+///
+/// The Input is signed by the Key of the Address which owns the Output.
+/// Outputs of a Script Address are spent by revealing the Script and the Witness
+/// Items which satisfy it instead.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TxInput {
+    pub txid: String,
+    pub output: u32,
+    /// Hex encoded Ed25519 Public Key of the Owner of the Output
+    #[serde(default)]
+    pub public_key: String,
+    /// Hex encoded Ed25519 Signature of the Signing Payload
+    #[serde(default)]
+    pub signature: String,
+    /// Script whose Address owns the Output
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub script: Option<Script>,
+    /// Hex encoded Items which satisfy the Script
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub witness: Vec<String>,
+}
+
+/// Structure for an Output which can be spent by its Address
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TxOutput {
+    #[serde(deserialize_with = "address::deserialize_address")]
+    pub address: String,
+    #[serde(deserialize_with = "canonical::deserialize_amount")]
+    pub amount: f64,
+}
+
+//==============================================================================
+// Structure UtxoSet Declaration
+
+/// Structure for the unspent Outputs of a Chain
+///
+/// The Outputs spent by each Block are kept so that the Block can be rolled back
+/// when the Chain is replaced.
+#[derive(Debug, Clone, Default)]
+pub struct UtxoSet {
+    outputs: HashMap<OutPoint, TxOutput>,
+    spent: HashMap<u64, Vec<(OutPoint, TxOutput)>>,
+}
+
+//==============================================================================
+// Structure TxInput Implementation
+
+impl TxInput {
+    /*----------------------------------------------------------------------------
+     * Constructors
+     */
+
+    pub fn from_outpoint(outpoint: &OutPoint) -> Self {
+        Self {
+            txid: outpoint.txid.clone(),
+            output: outpoint.output,
+            ..Default::default()
+        }
+    }
+
+    /*----------------------------------------------------------------------------
+     * Consultation Methods
+     */
+
+    pub fn to_outpoint(&self) -> OutPoint {
+        OutPoint {
+            txid: self.txid.clone(),
+            output: self.output,
+        }
+    }
+
+    /// Derive the Address of the Script or of the Key which signed the Input.
+    pub fn get_address(&self) -> Option<String> {
+        if let Some(script) = self.script.as_ref() {
+            return Some(script.to_address());
+        }
+
+        hex::decode(&self.public_key)
+            .ok()
+            .and_then(|k| <[u8; 32]>::try_from(k).ok())
+            .and_then(|k| ed25519_dalek::VerifyingKey::from_bytes(&k).ok())
+            .map(|k| address::from_public_key(&k))
+    }
+}
+
+//==============================================================================
+// Structure UtxoSet Implementation
+
+impl UtxoSet {
+    /*----------------------------------------------------------------------------
+     * Constructors
+     */
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Build the Set of a Chain whose Coinbases may grant up to `mining_reward`
+    /// plus the Fees of their Block.
+    ///
+    /// # Returns:
+    /// - the Set or the Index of the first Block which spends unavailable Outputs
+    ///   or whose Coinbase is invalid
+    ///
+    pub fn from_chain(chain: &[Block], mining_reward: f64) -> Result<Self, (u64, LedgerError)> {
+        let mut utxo_set = Self::new();
+
+        for block in chain.iter() {
+            utxo_set
+                .apply_block(block, mining_reward)
+                .map_err(|e| (block.index, e))?;
+        }
+
+        Ok(utxo_set)
+    }
+
+    /*----------------------------------------------------------------------------
+     * Administration Methods
+     */
+
+    /// Spend the Inputs and add the Outputs of the `Transaction`s of a Block.
+    ///
+    /// The Block must have exactly one Coinbase which grants at most `mining_reward`
+    /// plus the Fees of the other `Transaction`s. Both are compared in Base Units.
+    /// A Block which spends unavailable Outputs or whose Coinbase is invalid is not
+    /// applied at all.
+    pub fn apply_block(&mut self, block: &Block, mining_reward: f64) -> Result<(), LedgerError> {
+        let mut fees = 0i64;
+        let mut coinbase_total: Option<Option<i64>> = None;
+
+        for (position, transaction) in block.transactions.iter().enumerate() {
+            let result = if transaction.is_coinbase() && coinbase_total.is_some() {
+                Err(LedgerError::new(
+                    "invalid",
+                    &format!("Block ({}): Block has more than 1 Coinbase", block.index),
+                ))
+            } else {
+                self.apply_transaction(block.index, transaction)
+            };
+
+            match result {
+                Ok(fee) => fees += fee,
+                Err(e) => {
+                    self.rollback_transactions(block.index, &block.transactions[..position]);
+
+                    return Err(e);
+                }
+            }
+
+            if transaction.is_coinbase() {
+                coinbase_total = Some(transaction.get_output_units());
+            }
+        }
+
+        let error = match coinbase_total {
+            None => Some(format!("Block ({}): Block has no Coinbase", block.index)),
+            Some(None) => Some(format!(
+                "Block ({}): Coinbase Amounts are invalid",
+                block.index
+            )),
+            Some(Some(t)) if t > canonical::round_amount_units(mining_reward) + fees => {
+                Some(format!(
+                    "Block ({}): Coinbase of {} exceeds the Mining Reward of {} and the Fees of {}",
+                    block.index,
+                    canonical::to_amount(t),
+                    mining_reward,
+                    canonical::to_amount(fees)
+                ))
+            }
+            Some(_) => None,
+        };
+
+        if let Some(report) = error {
+            self.rollback_block(block);
+
+            return Err(LedgerError::new("invalid", &report));
+        }
+
+        Ok(())
+    }
+
+    /// Remove the Outputs of a Block and restore the Outputs it spent.
+    ///
+    /// The Blocks must be rolled back from the newest to the oldest.
+    pub fn rollback_block(&mut self, block: &Block) {
+        self.rollback_transactions(block.index, &block.transactions);
+    }
+
+    fn rollback_transactions(&mut self, block_index: u64, transactions: &[Transaction]) {
+        for transaction in transactions.iter() {
+            // Transactions which cannot be encoded never created Outputs
+            let txid = match transaction.to_hash() {
+                Ok(t) => t,
+                Err(_) => continue,
+            };
+
+            for position in 0..transaction.outputs.len() {
+                self.outputs.remove(&OutPoint {
+                    txid: txid.clone(),
+                    output: position as u32,
+                });
+            }
+        }
+
+        for (outpoint, output) in self.spent.remove(&block_index).unwrap_or_default() {
+            self.outputs.insert(outpoint, output);
+        }
+    }
+
+    /// Spend the Inputs and add the Outputs of one `Transaction` of the Block `block_index`.
+    ///
+    /// # Returns:
+    /// - the Fee in Base Units which is the Amount spent but not sent to any Output
+    ///
+    pub fn apply_transaction(
+        &mut self,
+        block_index: u64,
+        transaction: &Transaction,
+    ) -> Result<i64, LedgerError> {
+        let fee = self.check_transaction(transaction)?;
+
+        let txid = transaction.to_hash().map_err(LedgerError::from_encoding)?;
+        let spent = self.spent.entry(block_index).or_default();
+
+        for input in transaction.inputs.iter() {
+            let outpoint = input.to_outpoint();
+
+            if let Some(output) = self.outputs.remove(&outpoint) {
+                spent.push((outpoint, output));
+            }
+        }
+
+        for (position, output) in transaction.outputs.iter().enumerate() {
+            self.outputs.insert(
+                OutPoint {
+                    txid: txid.clone(),
+                    output: position as u32,
+                },
+                output.clone(),
+            );
+        }
+
+        Ok(fee)
+    }
+
+    /*----------------------------------------------------------------------------
+     * Consultation Methods
+     */
+
+    /// Check that a `Transaction` only spends available Outputs of the Signers
+    /// and does not create more than it spends.
+    ///
+    /// The Totals are kept in Base Units so that they are exact.
+    ///
+    /// # Returns:
+    /// - the Fee in Base Units which is the Amount spent but not sent to any Output
+    ///
+    pub fn check_transaction(&self, transaction: &Transaction) -> Result<i64, LedgerError> {
+        let txid = transaction.to_hash().map_err(LedgerError::from_encoding)?;
+
+        if !transaction.is_utxo() || !transaction.is_valid() {
+            return Err(LedgerError::new(
+                "invalid",
+                &format!("Transaction ({}): Transaction is invalid", txid),
+            ));
+        }
+
+        if !transaction.outputs.is_empty()
+            && self.outputs.contains_key(&OutPoint {
+                txid: txid.clone(),
+                output: 0,
+            })
+        {
+            return Err(LedgerError::new(
+                "duplicate",
+                &format!("Transaction ({}): Transaction already exists", txid),
+            ));
+        }
+
+        if transaction.is_coinbase() {
+            return Ok(0);
+        }
+
+        let invalid_amount = || {
+            LedgerError::new(
+                "invalid",
+                &format!("Transaction ({}): Amounts are invalid", txid),
+            )
+        };
+        let mut spent = HashSet::<OutPoint>::with_capacity(transaction.inputs.len());
+        let mut input_total = 0i64;
+
+        for input in transaction.inputs.iter() {
+            let outpoint = input.to_outpoint();
+            let output = match self.outputs.get(&outpoint) {
+                Some(o) => o,
+                None => {
+                    return Err(LedgerError::new(
+                        "spent",
+                        &format!(
+                            "Transaction ({}): Output {}:{} is spent or does not exist",
+                            txid, outpoint.txid, outpoint.output
+                        ),
+                    ))
+                }
+            };
+
+            if input.get_address().as_deref() != Some(output.address.as_str()) {
+                return Err(LedgerError::new(
+                    "invalid",
+                    &format!(
+                        "Transaction ({}): Output {}:{} does not belong to the Signer or Script",
+                        txid, outpoint.txid, outpoint.output
+                    ),
+                ));
+            }
+
+            if !spent.insert(outpoint) {
+                return Err(LedgerError::new(
+                    "spent",
+                    &format!("Transaction ({}): Output is spent twice", txid),
+                ));
+            }
+
+            input_total = canonical::to_amount_units(output.amount)
+                .and_then(|units| input_total.checked_add(units))
+                .ok_or_else(invalid_amount)?;
+        }
+
+        let output_total = transaction.get_output_units().ok_or_else(invalid_amount)?;
+
+        if output_total > input_total {
+            return Err(LedgerError::new(
+                "invalid",
+                &format!(
+                    "Transaction ({}): Outputs of {} exceed the Inputs of {}",
+                    txid,
+                    canonical::to_amount(output_total),
+                    canonical::to_amount(input_total)
+                ),
+            ));
+        }
+
+        Ok(input_total - output_total)
+    }
+
+    pub fn get_output(&self, outpoint: &OutPoint) -> Option<&TxOutput> {
+        self.outputs.get(outpoint)
+    }
+
+    /// Look up an Output which was spent by the Block `block_index`.
+    pub fn get_spent_output(&self, block_index: u64, outpoint: &OutPoint) -> Option<&TxOutput> {
+        self.spent
+            .get(&block_index)
+            .and_then(|s| s.iter().find(|(o, _)| o == outpoint))
+            .map(|(_, output)| output)
+    }
+
+    /// List the unspent Outputs of an Address.
+    pub fn get_unspent(&self, address: &str) -> Vec<(OutPoint, TxOutput)> {
+        self.outputs
+            .iter()
+            .filter(|(_, o)| o.address == address)
+            .map(|(p, o)| (p.clone(), o.clone()))
+            .collect()
+    }
+
+    pub fn get_balance(&self, address: &str) -> f64 {
+        self.outputs
+            .values()
+            .filter(|o| o.address == address)
+            .map(|o| o.amount)
+            .sum()
+    }
+
+    pub fn get_count(&self) -> usize {
+        self.outputs.len()
+    }
+}
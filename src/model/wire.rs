@@ -0,0 +1,402 @@
+/*
+* @author Bodo (Hugo) Barwich
+* @version 2026-10-18
+* @package Blockchain Exercise
+* @subpackage Wire Format
+
+* This Module defines the compact binary Format of Blocks, Transactions and
+* Chain Exports
+*
+*---------------------------------
+* Requirements:
+* - The Rust Crate "ciborium" must be installed
+*/
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use super::blockchain::Block;
+use super::transaction::Transaction;
+
+/// First Bytes of all binary Documents
+pub const WIRE_MAGIC: [u8; 4] = *b"ABCW";
+
+/// Version of the binary Format
+pub const WIRE_VERSION: u8 = 1;
+
+/// Length of the Header before the CBOR Payload: Magic, Version and Kind
+pub const WIRE_HEADER_SIZE: usize = 6;
+
+/// Media Type of the versioned binary Format
+pub const MIME_OCTET_STREAM: &str = "application/octet-stream";
+
+/// Media Type of the plain CBOR Payload without Header
+pub const MIME_CBOR: &str = "application/cbor";
+
+//==============================================================================
+// Structure WireFormat Declaration
+
+/// Representation which a Client requests with its `Accept` Header
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum WireFormat {
+    #[default]
+    Json,
+    /// Versioned Header followed by the CBOR Payload
+    Binary,
+    /// Plain CBOR Document which any CBOR Library can read
+    Cbor,
+}
+
+/// Kind of the Value in a binary Document
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WireKind {
+    Transaction = 1,
+    Block = 2,
+    /// Export of all Blocks of a Chain
+    Chain = 3,
+}
+
+/// Values which can be sent in the binary Format
+pub trait WireObject: Serialize + DeserializeOwned {
+    const KIND: WireKind;
+}
+
+/// Structure for the Errors of binary Documents which cannot be encoded or decoded
+#[derive(Debug, Clone, PartialEq)]
+pub struct WireError {
+    pub status: String,
+    pub report: String,
+}
+
+//==============================================================================
+// Structure WireFormat Implementation
+
+impl WireFormat {
+    /*----------------------------------------------------------------------------
+     * Constructors
+     */
+
+    /// Choose the Format from the Value of an `Accept` Header.
+    ///
+    /// Each Format gets the Quality `q` of the most specific Media Range which
+    /// matches it. The Format with the highest Quality wins, of equal Qualities the
+    /// first listed one. If no Media Range matches any Format JSON is sent.
+    ///
+    /// # Returns:
+    /// - `None` if the Header refuses all Formats with `q=0`
+    ///
+    pub fn from_accept(accept: &str) -> Option<Self> {
+        let ranges: Vec<(String, f32)> = accept
+            .split(',')
+            .filter(|r| !r.trim().is_empty())
+            .map(parse_media_range)
+            .collect();
+        let mut best: Option<(WireFormat, f32, usize)> = None;
+        let mut is_json_refused = false;
+
+        for format in [WireFormat::Json, WireFormat::Binary, WireFormat::Cbor] {
+            let mime_type = format.get_mime_type();
+            let matched = [mime_type, "application/*", "*/*"].iter().find_map(|m| {
+                ranges
+                    .iter()
+                    .position(|r| r.0 == *m)
+                    .map(|position| (ranges[position].1, position))
+            });
+
+            match matched {
+                Some((quality, _)) if quality <= 0f32 => {
+                    is_json_refused |= format == WireFormat::Json;
+                }
+                Some((quality, position))
+                    if best.is_none_or(|b| quality > b.1 || (quality == b.1 && position < b.2)) =>
+                {
+                    best = Some((format, quality, position));
+                }
+                _ => {}
+            }
+        }
+
+        match best {
+            Some((format, _, _)) => Some(format),
+            None if is_json_refused => None,
+            None => Some(WireFormat::default()),
+        }
+    }
+
+    /*----------------------------------------------------------------------------
+     * Consultation Methods
+     */
+
+    pub fn get_mime_type(self) -> &'static str {
+        match self {
+            WireFormat::Json => "application/json",
+            WireFormat::Binary => MIME_OCTET_STREAM,
+            WireFormat::Cbor => MIME_CBOR,
+        }
+    }
+
+    /// Encode a Value in this Format.
+    pub fn encode<T: WireObject>(self, value: &T) -> Result<Vec<u8>, WireError> {
+        match self {
+            WireFormat::Json => serde_json::to_vec(value)
+                .map_err(|e| WireError::new("invalid", &format!("JSON: {}", e))),
+            WireFormat::Binary => encode(value),
+            WireFormat::Cbor => to_cbor(value),
+        }
+    }
+}
+
+//==============================================================================
+// Structure WireKind Implementation
+
+impl WireKind {
+    /*----------------------------------------------------------------------------
+     * Constructors
+     */
+
+    pub fn from_byte(kind: u8) -> Option<Self> {
+        match kind {
+            1 => Some(WireKind::Transaction),
+            2 => Some(WireKind::Block),
+            3 => Some(WireKind::Chain),
+            _ => None,
+        }
+    }
+}
+
+impl WireObject for Transaction {
+    const KIND: WireKind = WireKind::Transaction;
+}
+
+impl WireObject for Block {
+    const KIND: WireKind = WireKind::Block;
+}
+
+impl WireObject for Vec<Block> {
+    const KIND: WireKind = WireKind::Chain;
+}
+
+//==============================================================================
+// Structure WireError Implementation
+
+impl WireError {
+    /*----------------------------------------------------------------------------
+     * Constructors
+     */
+
+    pub fn new(status: &str, report: &str) -> Self {
+        Self {
+            status: status.to_owned(),
+            report: report.to_owned(),
+        }
+    }
+}
+
+//==============================================================================
+// Auxiliary Functions
+
+/// Encode a Value in the versioned binary Format.
+///
+/// | Offset | Size | Field                        |
+/// |--------|------|------------------------------|
+/// | 0      | 4    | Magic `ABCW`                 |
+/// | 4      | 1    | Version                      |
+/// | 5      | 1    | Kind (`WireKind`)            |
+/// | 6      | ...  | CBOR Payload                 |
+pub fn encode<T: WireObject>(value: &T) -> Result<Vec<u8>, WireError> {
+    let mut bytes = Vec::with_capacity(WIRE_HEADER_SIZE);
+
+    bytes.extend_from_slice(&WIRE_MAGIC);
+    bytes.push(WIRE_VERSION);
+    bytes.push(T::KIND as u8);
+
+    ciborium::into_writer(value, &mut bytes)
+        .map_err(|e| WireError::new("invalid", &format!("CBOR: {}", e)))?;
+
+    Ok(bytes)
+}
+
+/// Decode a Value from the versioned binary Format.
+///
+/// Documents of unknown Versions or of another Kind are refused.
+pub fn decode<T: WireObject>(bytes: &[u8]) -> Result<T, WireError> {
+    if !is_wire_data(bytes) {
+        return Err(WireError::new("invalid", "Header: Magic Bytes are missing"));
+    }
+
+    if bytes[4] != WIRE_VERSION {
+        return Err(WireError::new(
+            "unsupported",
+            &format!("Header: Version {} is not supported", bytes[4]),
+        ));
+    }
+
+    match WireKind::from_byte(bytes[5]) {
+        Some(k) if k == T::KIND => from_cbor(&bytes[WIRE_HEADER_SIZE..]),
+        _ => Err(WireError::new(
+            "invalid",
+            &format!(
+                "Header: Kind {} does not match the expected Kind {}",
+                bytes[5],
+                T::KIND as u8
+            ),
+        )),
+    }
+}
+
+/// Check whether Data starts with the Header of the binary Format.
+pub fn is_wire_data(bytes: &[u8]) -> bool {
+    bytes.len() >= WIRE_HEADER_SIZE && bytes[..WIRE_MAGIC.len()] == WIRE_MAGIC
+}
+
+/// Encode a Value as plain CBOR Document.
+pub fn to_cbor<T: Serialize>(value: &T) -> Result<Vec<u8>, WireError> {
+    let mut bytes = Vec::new();
+
+    ciborium::into_writer(value, &mut bytes)
+        .map_err(|e| WireError::new("invalid", &format!("CBOR: {}", e)))?;
+
+    Ok(bytes)
+}
+
+/// Decode a Value from a plain CBOR Document.
+pub fn from_cbor<T: DeserializeOwned>(bytes: &[u8]) -> Result<T, WireError> {
+    ciborium::from_reader(bytes).map_err(|e| WireError::new("invalid", &format!("CBOR: {}", e)))
+}
+
+/// Split a Media Range of an `Accept` Header into its lower case Media Type and
+/// its Quality. Missing or malformed Qualities count as `1`.
+fn parse_media_range(range: &str) -> (String, f32) {
+    let mut parts = range.split(';');
+    let media_type = parts.next().unwrap_or("").trim().to_ascii_lowercase();
+    let quality = parts
+        .filter_map(|p| p.split_once('='))
+        .find(|(name, _)| name.trim().eq_ignore_ascii_case("q"))
+        .and_then(|(_, value)| value.trim().parse::<f32>().ok())
+        .map_or(1f32, |q| q.clamp(0f32, 1f32));
+
+    (media_type, quality)
+}
+
+//==============================================================================
+// Unit Tests
+
+#[test]
+fn wire_round_trip() {
+    use super::blockchain::Blockchain;
+    use super::canonical;
+    use ed25519_dalek::SigningKey;
+
+    let signing_key = SigningKey::from_bytes(&[7u8; 32]);
+    let sender = super::address::from_public_key(&signing_key.verifying_key());
+    let mut blockchain = Blockchain::new();
+
+    for nonce in 1..=3 {
+        let mut transaction = Transaction::from_data(
+            sender.clone(),
+            String::from("16Jswqk47s9PUcyCc88MMVwzgvHPvtEpf"),
+            nonce as f64 / 10.0,
+        );
+
+        transaction.nonce = nonce;
+        transaction.sign(&signing_key).unwrap();
+
+        let last_hash = match blockchain.get_last_block() {
+            Some(b) => blockchain.get_block_hash(b).unwrap(),
+            None => String::from("0"),
+        };
+        let mut block =
+            Block::build_block(nonce + 1, 0, &last_hash, Some(vec![transaction.clone()]));
+
+        block.bits = blockchain.get_target_bits();
+        blockchain.chain.push(block);
+
+        //-------------------------------------
+        // A Transaction decodes to the same JSON and Id
+
+        let bytes = encode(&transaction).unwrap();
+        let decoded: Transaction = decode(&bytes).unwrap();
+
+        assert_eq!(decoded.to_json().unwrap(), transaction.to_json().unwrap());
+        assert_eq!(decoded.to_hash().unwrap(), transaction.to_hash().unwrap());
+        assert!(decoded.is_signature_valid());
+    }
+
+    let chain = blockchain.chain.clone();
+    let bytes = encode(&chain).unwrap();
+    let chain_json = serde_json::to_string(&chain).unwrap();
+    let decoded: Vec<Block> = decode(&bytes).unwrap();
+
+    assert_eq!(&bytes[..WIRE_HEADER_SIZE], b"ABCW\x01\x03");
+    assert!(bytes.len() < chain_json.len());
+    assert_eq!(serde_json::to_string(&decoded).unwrap(), chain_json);
+    assert_eq!(
+        canonical::to_canonical_json(&decoded).unwrap(),
+        canonical::to_canonical_json(&chain).unwrap()
+    );
+    assert_eq!(
+        decoded.last().unwrap().to_hash(),
+        chain.last().unwrap().to_hash()
+    );
+
+    // The plain CBOR Document is the Payload without Header
+    assert_eq!(to_cbor(&chain).unwrap(), bytes[WIRE_HEADER_SIZE..]);
+
+    //-------------------------------------
+    // Malformed Documents are refused
+
+    assert_eq!(decode::<Block>(&bytes).unwrap_err().status, "invalid");
+    assert_eq!(
+        decode::<Vec<Block>>(chain_json.as_bytes())
+            .unwrap_err()
+            .status,
+        "invalid"
+    );
+
+    let mut future = bytes.clone();
+
+    future[4] = WIRE_VERSION + 1;
+
+    assert_eq!(
+        decode::<Vec<Block>>(&future).unwrap_err().status,
+        "unsupported"
+    );
+    assert!(decode::<Vec<Block>>(&bytes[..bytes.len() - 1]).is_err());
+
+    //-------------------------------------
+    // Content Negotiation
+
+    assert_eq!(WireFormat::from_accept(""), Some(WireFormat::Json));
+    assert_eq!(WireFormat::from_accept("*/*"), Some(WireFormat::Json));
+    assert_eq!(WireFormat::from_accept("text/html"), Some(WireFormat::Json));
+    assert_eq!(
+        WireFormat::from_accept("application/octet-stream"),
+        Some(WireFormat::Binary)
+    );
+
+    // The highest Quality wins, of equal Qualities the first listed Media Type
+    assert_eq!(
+        WireFormat::from_accept("text/html, Application/CBOR;q=0.9, application/json"),
+        Some(WireFormat::Json)
+    );
+    assert_eq!(
+        WireFormat::from_accept("application/json;q=0.5, application/cbor;q=0.8"),
+        Some(WireFormat::Cbor)
+    );
+    assert_eq!(
+        WireFormat::from_accept("application/cbor, application/octet-stream"),
+        Some(WireFormat::Cbor)
+    );
+
+    // Media Types with q=0 are not acceptable
+    assert_eq!(
+        WireFormat::from_accept("application/json;q=0, */*;q=0.1"),
+        Some(WireFormat::Binary)
+    );
+    assert_eq!(
+        WireFormat::from_accept("application/octet-stream;q=0, application/json"),
+        Some(WireFormat::Json)
+    );
+    assert_eq!(WireFormat::from_accept("application/json;q=0"), None);
+    assert_eq!(WireFormat::from_accept("*/*;q=0"), None);
+}
@@ -0,0 +1,499 @@
+/*
+* @author Bodo (Hugo) Barwich
+* @version 2026-10-18
+* @package Blockchain Exercise
+* @subpackage Multi-Signature Drafts
+
+* This Module collects the partial Signatures of the Keys of a Multi-Signature
+* Address on draft `Transaction`s until enough Keys approved them
+*
+*---------------------------------
+* Requirements:
+* - The Rust Crate "actix-web" must be installed
+* - The Rust Crate "ed25519-dalek" must be installed
+*/
+
+use actix::Addr;
+use actix_web::{error, web, Error, HttpResponse};
+use serde::{Deserialize, Serialize};
+use sha256::digest;
+use std::collections::HashMap;
+use std::sync::{Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use crate::events::{self, ChainEvent, EventHub};
+use crate::model::address;
+use crate::model::blockchain::Blockchain;
+use crate::model::transaction::{
+    MultisigPolicy, MutexTransactionList, PartialSignature, Transaction,
+};
+
+/// Maximum number of Drafts waiting for Signatures
+pub const MAX_DRAFTS: usize = 1000;
+/// Seconds a Draft waits for its Signatures before it is discarded
+pub const DRAFT_TTL: u64 = 3600;
+
+//==============================================================================
+// Structure MultisigDrafts Declaration
+
+/// Structure for the draft `Transaction`s of Multi-Signature Addresses
+///
+/// The Drafts are identified by the SHA-256 hash of their Signing Payload.
+/// As soon as a Draft has enough Signatures it is queued for the next Block.
+/// Drafts expire after the Time To Live. When the Limit is reached the oldest Draft
+/// makes room for a new one.
+#[derive(Debug)]
+pub struct MultisigDrafts {
+    drafts: Mutex<HashMap<String, DraftEntry>>,
+    ttl: Duration,
+}
+
+/// Structure for a Draft with the Time it was registered
+#[derive(Debug, Clone)]
+struct DraftEntry {
+    transaction: Transaction,
+    /// Signing Payload which does not change while Signatures are added
+    signing_payload: Vec<u8>,
+    created: Instant,
+}
+
+/// Structure for the State of a Draft as it is shown to the Signers
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DraftData {
+    pub id: String,
+    pub transaction: Transaction,
+    /// Hex encoded Data each Key must sign
+    pub signing_payload: String,
+    pub signature_count: usize,
+    pub threshold: u8,
+    pub complete: bool,
+    /// Id of the queued `Transaction` once the Draft is complete
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub txid: Option<String>,
+}
+
+/// Structure for a Multi-Signature Address and its Policy
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MultisigAddressData {
+    pub address: String,
+    #[serde(flatten)]
+    pub policy: MultisigPolicy,
+}
+
+/// Structure for Multi-Signature Draft Errors
+#[derive(Debug, PartialEq)]
+pub struct MultisigError {
+    pub status: String,
+    pub report: String,
+}
+
+//==============================================================================
+// Structure MultisigDrafts Implementation
+
+impl MultisigDrafts {
+    /*----------------------------------------------------------------------------
+     * Constructors
+     */
+
+    pub fn new() -> Self {
+        Self::with_ttl(Duration::from_secs(DRAFT_TTL))
+    }
+
+    pub fn with_ttl(ttl: Duration) -> Self {
+        Self {
+            drafts: Mutex::new(HashMap::new()),
+            ttl,
+        }
+    }
+
+    /*----------------------------------------------------------------------------
+     * Administration Methods
+     */
+
+    /// Register a new Draft.
+    ///
+    /// The Draft must carry the Policy of its Sender. Signatures it already carries
+    /// must be valid. A Draft which is registered already keeps its Signatures and
+    /// gains the new ones.
+    ///
+    /// # Returns:
+    /// - the State of the Draft. A complete Draft must be queued by the Caller and
+    ///   removed with `remove_draft()`.
+    ///
+    pub fn add_draft(&self, mut transaction: Transaction) -> Result<DraftData, MultisigError> {
+        transaction.public_key = None;
+        transaction.signature = None;
+
+        let policy = match transaction.multisig.as_ref() {
+            Some(p) => p,
+            None => {
+                return Err(MultisigError::new(
+                    "invalid",
+                    "Draft: Draft has no Multi-Signature Policy",
+                ))
+            }
+        };
+
+        if policy.to_address().as_deref() != Some(transaction.sender.as_str()) {
+            return Err(MultisigError::new(
+                "invalid",
+                "Draft: Sender is not the Address of the Multi-Signature Policy",
+            ));
+        }
+
+        if !address::is_valid(&transaction.receiver) || transaction.amount == 0f64 {
+            return Err(MultisigError::new("invalid", "Draft: Draft is invalid"));
+        }
+
+        if transaction.count_valid_signatures().is_none() {
+            return Err(MultisigError::new(
+                "invalid",
+                "Draft: Draft carries invalid Signatures",
+            ));
+        }
+
+        let signing_payload = match transaction.to_signing_payload() {
+            Ok(p) => p,
+            Err(e) => {
+                return Err(MultisigError::new(
+                    "invalid",
+                    &format!("Draft: Draft cannot be encoded! {}", e),
+                ))
+            }
+        };
+        let id = digest(&signing_payload);
+        let mut drafts = self.lock();
+
+        self.remove_expired(&mut drafts);
+
+        if let Some(entry) = drafts.get_mut(&id) {
+            for signature in transaction.signatures {
+                entry.transaction.add_partial_signature(signature);
+            }
+
+            return Ok(to_draft_data(&id, entry));
+        }
+
+        if drafts.len() >= MAX_DRAFTS {
+            let oldest = drafts
+                .iter()
+                .min_by_key(|(_, e)| e.created)
+                .map(|(k, _)| k.clone());
+
+            if let Some(k) = oldest {
+                eprintln!(
+                    "Draft ({}): Limit of {} Drafts is reached. Draft is discarded",
+                    k, MAX_DRAFTS
+                );
+                drafts.remove(&k);
+            }
+        }
+
+        let entry = DraftEntry {
+            transaction,
+            signing_payload,
+            created: Instant::now(),
+        };
+        let data = to_draft_data(&id, &entry);
+
+        drafts.insert(id, entry);
+
+        Ok(data)
+    }
+
+    /// Add the Signature of one Key of the Policy to a Draft.
+    ///
+    /// # Returns:
+    /// - the State of the Draft. A complete Draft must be queued by the Caller and
+    ///   removed with `remove_draft()`.
+    ///
+    pub fn add_signature(
+        &self,
+        id: &str,
+        signature: PartialSignature,
+    ) -> Result<DraftData, MultisigError> {
+        let mut drafts = self.lock();
+
+        self.remove_expired(&mut drafts);
+
+        let entry = match drafts.get_mut(id) {
+            Some(e) => e,
+            None => {
+                return Err(MultisigError::new(
+                    "not_found",
+                    &format!("Draft ({}): Draft does not exist", id),
+                ))
+            }
+        };
+
+        let mut transaction = entry.transaction.clone();
+
+        transaction.add_partial_signature(signature);
+
+        if transaction.count_valid_signatures().is_none() {
+            return Err(MultisigError::new(
+                "invalid",
+                &format!("Draft ({}): Signature is invalid", id),
+            ));
+        }
+
+        entry.transaction = transaction;
+
+        Ok(to_draft_data(id, entry))
+    }
+
+    /// Remove a Draft after its `Transaction` was queued
+    pub fn remove_draft(&self, id: &str) {
+        self.lock().remove(id);
+    }
+
+    fn remove_expired(&self, drafts: &mut HashMap<String, DraftEntry>) {
+        drafts.retain(|_, e| e.created.elapsed() < self.ttl);
+    }
+
+    /*----------------------------------------------------------------------------
+     * Consultation Methods
+     */
+
+    pub fn get_draft(&self, id: &str) -> Option<DraftData> {
+        self.lock()
+            .get(id)
+            .filter(|e| e.created.elapsed() < self.ttl)
+            .map(|e| to_draft_data(id, e))
+    }
+
+    pub fn get_count(&self) -> usize {
+        self.lock()
+            .values()
+            .filter(|e| e.created.elapsed() < self.ttl)
+            .count()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, HashMap<String, DraftEntry>> {
+        match self.drafts.lock() {
+            Ok(guard) => guard,
+            Err(e) => {
+                eprintln!("Multisig Drafts: Mutex Lock failed! Message: {:?}", e);
+
+                e.into_inner()
+            }
+        }
+    }
+}
+
+impl Default for MultisigDrafts {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+//==============================================================================
+// Structure MultisigError Implementation
+
+impl MultisigError {
+    /*----------------------------------------------------------------------------
+     * Constructors
+     */
+
+    pub fn new(status: &str, report: &str) -> Self {
+        Self {
+            status: status.to_owned(),
+            report: report.to_owned(),
+        }
+    }
+}
+
+//==============================================================================
+// HTTP Handlers
+
+/// Handler to derive the Multi-Signature Address of a Policy
+pub async fn create_multisig_address(
+    request: web::Json<MultisigPolicy>,
+) -> Result<HttpResponse, Error> {
+    let policy = request.into_inner();
+
+    match policy.to_address() {
+        Some(address) => Ok(HttpResponse::Ok().json(MultisigAddressData { address, policy })),
+        None => Err(error::ErrorBadRequest(
+            "Multisig: Policy must have 1 <= Threshold <= Keys and distinct valid Keys",
+        )),
+    }
+}
+
+/// Handler to register a draft `Transaction` which collects Signatures.
+///
+/// A Draft which already carries enough Signatures is queued for the next Block right away.
+pub async fn create_draft(
+    drafts: web::Data<MultisigDrafts>,
+    blockchain_mutex: web::Data<Mutex<Blockchain>>,
+    transaction_mutex: web::Data<MutexTransactionList>,
+    event_hub: Option<web::Data<Addr<EventHub>>>,
+    request: web::Json<Transaction>,
+) -> Result<HttpResponse, Error> {
+    let data = match drafts.add_draft(request.into_inner()) {
+        Ok(d) => d,
+        Err(e) => return Err(error::ErrorBadRequest(e.report)),
+    };
+
+    if !data.complete {
+        return Ok(HttpResponse::Created().json(data));
+    }
+
+    let data = queue_draft(
+        data,
+        &drafts,
+        &blockchain_mutex,
+        &transaction_mutex,
+        event_hub.as_ref().map(|h| h.get_ref()),
+    )?;
+
+    Ok(HttpResponse::Created().json(data))
+}
+
+/// Handler to show a Draft with the Data which must be signed
+pub async fn get_draft(
+    drafts: web::Data<MultisigDrafts>,
+    path: web::Path<String>,
+) -> Result<HttpResponse, Error> {
+    match drafts.get_draft(&path) {
+        Some(data) => Ok(HttpResponse::Ok().json(data)),
+        None => Err(error::ErrorNotFound(format!(
+            "Draft ({}): Draft does not exist",
+            *path
+        ))),
+    }
+}
+
+/// Handler to add a partial Signature to a Draft.
+///
+/// As soon as enough Keys signed the Draft its `Transaction` is queued for the next Block.
+pub async fn add_draft_signature(
+    drafts: web::Data<MultisigDrafts>,
+    blockchain_mutex: web::Data<Mutex<Blockchain>>,
+    transaction_mutex: web::Data<MutexTransactionList>,
+    event_hub: Option<web::Data<Addr<EventHub>>>,
+    path: web::Path<String>,
+    request: web::Json<PartialSignature>,
+) -> Result<HttpResponse, Error> {
+    let data = match drafts.add_signature(&path, request.into_inner()) {
+        Ok(d) => d,
+        Err(e) if e.status == "not_found" => return Err(error::ErrorNotFound(e.report)),
+        Err(e) => return Err(error::ErrorBadRequest(e.report)),
+    };
+
+    if !data.complete {
+        return Ok(HttpResponse::Ok().json(data));
+    }
+
+    let data = queue_draft(
+        data,
+        &drafts,
+        &blockchain_mutex,
+        &transaction_mutex,
+        event_hub.as_ref().map(|h| h.get_ref()),
+    )?;
+
+    Ok(HttpResponse::Created().json(data))
+}
+
+//==============================================================================
+// Auxiliary Functions
+
+/// Queue the `Transaction` of a complete Draft for the next Block.
+///
+/// Like any other `Transaction` it is checked against the Ledger of the Chain first.
+/// The Draft is only removed once its `Transaction` is queued.
+fn queue_draft(
+    mut data: DraftData,
+    drafts: &MultisigDrafts,
+    blockchain_mutex: &Mutex<Blockchain>,
+    transaction_mutex: &MutexTransactionList,
+    event_hub: Option<&Addr<EventHub>>,
+) -> Result<DraftData, Error> {
+    match blockchain_mutex.lock() {
+        Ok(guard) => {
+            if let Err(e) = guard.check_transaction(&data.transaction, transaction_mutex) {
+                return Err(error::ErrorBadRequest(format!(
+                    "Draft ({}): {}",
+                    data.id, e.report
+                )));
+            }
+        }
+        Err(e) => {
+            return Err(error::ErrorInternalServerError(format!(
+                "Blockchain: Mutex Lock failed! Message: {:?}",
+                e
+            )))
+        }
+    }
+
+    if let Err(e) = transaction_mutex.add_transaction(data.transaction.clone()) {
+        return Err(error::ErrorInternalServerError(format!(
+            "Transactions: Transaction could not be added! Message: {:?}",
+            e
+        )));
+    }
+
+    drafts.remove_draft(&data.id);
+
+    events::publish(
+        event_hub,
+        ChainEvent::NewPendingTransaction(Box::new(data.transaction.clone())),
+    );
+
+    data.txid = data.transaction.to_hash().ok();
+
+    Ok(data)
+}
+
+fn to_draft_data(id: &str, entry: &DraftEntry) -> DraftData {
+    let transaction = &entry.transaction;
+    let threshold = transaction
+        .multisig
+        .as_ref()
+        .map(|p| p.threshold)
+        .unwrap_or_default();
+
+    DraftData {
+        id: id.to_owned(),
+        transaction: transaction.clone(),
+        signing_payload: hex::encode(&entry.signing_payload),
+        signature_count: transaction.signatures.len(),
+        threshold,
+        complete: transaction.is_multisig_valid(),
+        txid: None,
+    }
+}
+
+//==============================================================================
+// Unit Tests
+
+#[test]
+fn draft_expiry() {
+    use ed25519_dalek::SigningKey;
+
+    let keys: Vec<_> = (1..=2u8)
+        .map(|i| SigningKey::from_bytes(&[i; 32]).verifying_key())
+        .collect();
+    let draft = Transaction::from_multisig(
+        MultisigPolicy::new(2, &keys),
+        String::from("16Jswqk47s9PUcyCc88MMVwzgvHPvtEpf"),
+        1.5,
+        1,
+    );
+
+    let drafts = MultisigDrafts::new();
+    let data = drafts.add_draft(draft.clone()).unwrap();
+
+    assert_eq!(drafts.get_count(), 1);
+    assert!(drafts.get_draft(&data.id).is_some());
+
+    //-------------------------------------
+    // Expired Drafts are not shown and cannot be signed
+
+    let drafts = MultisigDrafts::with_ttl(Duration::ZERO);
+    let data = drafts.add_draft(draft).unwrap();
+
+    assert_eq!(drafts.get_count(), 0);
+    assert!(drafts.get_draft(&data.id).is_none());
+}
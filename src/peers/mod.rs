@@ -0,0 +1,253 @@
+/*
+* @author Bodo (Hugo) Barwich
+* @version 2026-10-18
+* @package Blockchain Exercise
+* @subpackage Peer Synchronisation
+
+* This Module defines the Adoption of longer valid Chains from the configured
+* Peer Nodes
+*
+*---------------------------------
+* Requirements:
+* - The Rust Crate "actix-web" must be installed
+*/
+
+use actix::Addr;
+use actix_web::client::Client;
+use actix_web::web;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use crate::events::{self, ChainEvent, EventHub};
+use crate::model::blockchain::{Block, Blockchain};
+use crate::model::transaction::MutexTransactionList;
+use crate::model::wire::{self, MIME_OCTET_STREAM};
+use crate::storage::ChainStore;
+
+/// Maximum Size of a Chain Export downloaded from a Peer
+pub const MAX_CHAIN_SIZE: usize = 64 * 1024 * 1024;
+
+//==============================================================================
+// Structure ChainReplacement Declaration
+
+/// Structure for the Outcome of a Chain Replacement
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChainReplacement {
+    /// Index of the first Block which was replaced or added
+    pub fork_index: u64,
+    pub previous_height: u64,
+    pub height: u64,
+    /// Number of `Transaction`s of orphaned Blocks which are waiting again
+    pub requeued_count: usize,
+}
+
+//==============================================================================
+// Auxiliary Functions
+
+/// Adopt a competing Chain if it is valid and the Consensus Engine prefers it.
+///
+/// The adopted Chain is stored, the `Transaction`s of orphaned Blocks are given back
+/// to the waiting `Transaction`s and a `chainReorg` Event is published.
+///
+/// # Returns:
+/// - `None` if the local Chain was kept
+///
+pub fn adopt_chain(
+    blockchain: &mut Blockchain,
+    transaction_mutex: &MutexTransactionList,
+    event_hub: Option<&Addr<EventHub>>,
+    store: Option<&ChainStore>,
+    chain: Vec<Block>,
+) -> Option<ChainReplacement> {
+    let previous_height = blockchain.get_last_block_index();
+    let orphaned_blocks = blockchain.replace_chain(chain)?;
+    let height = blockchain.get_last_block_index();
+    let fork_index = match orphaned_blocks.first() {
+        Some(b) => b.index,
+        None => previous_height + 1,
+    };
+    let adopted_blocks: Vec<Block> = blockchain
+        .chain
+        .iter()
+        .filter(|b| b.index >= fork_index)
+        .cloned()
+        .collect();
+    let requeued_count = blockchain.requeue_orphaned(&orphaned_blocks, transaction_mutex);
+
+    if let Some(s) = store {
+        if let Err(e) = s.save(blockchain) {
+            eprintln!("Blockchain: Chain could not be stored! Message: {:?}", e);
+        }
+    }
+
+    if orphaned_blocks.is_empty() {
+        // The local Chain was only extended
+        adopted_blocks
+            .into_iter()
+            .for_each(|b| events::publish(event_hub, ChainEvent::NewBlock(b)));
+    } else {
+        events::publish(
+            event_hub,
+            ChainEvent::ChainReorg {
+                fork_index,
+                previous_height,
+                height,
+                orphaned_blocks,
+                adopted_blocks,
+            },
+        );
+    }
+
+    Some(ChainReplacement {
+        fork_index,
+        previous_height,
+        height,
+        requeued_count,
+    })
+}
+
+/// Download the Chain of a Peer Node in the binary Wire Format.
+///
+/// # Parameters:
+/// - `peer`: Base URL of the Peer API like `http://10.0.0.2:3100/`
+///
+pub async fn fetch_chain(client: &Client, peer: &str) -> Result<Vec<Block>, String> {
+    let url = format!("{}/chain", peer.trim_end_matches('/'));
+    let mut response = client
+        .get(url.as_str())
+        .header("Accept", MIME_OCTET_STREAM)
+        .send()
+        .await
+        .map_err(|e| format!("Peer ({}): Request failed! Message: {}", url, e))?;
+
+    if !response.status().is_success() {
+        return Err(format!(
+            "Peer ({}): Request failed with HTTP Status: {}",
+            url,
+            response.status()
+        ));
+    }
+
+    let body = response
+        .body()
+        .limit(MAX_CHAIN_SIZE)
+        .await
+        .map_err(|e| format!("Peer ({}): Download failed! Message: {}", url, e))?;
+
+    wire::decode::<Vec<Block>>(&body)
+        .map_err(|e| format!("Peer ({}): Chain is invalid! {}", url, e.report))
+}
+
+/// Poll the Peer Nodes for longer valid Chains.
+///
+/// # Parameters:
+/// - `peers`: Base URLs of the Peer APIs
+/// - `sync_interval`: Seconds between the Polls
+///
+pub fn start_peer_sync(
+    peers: Vec<String>,
+    sync_interval: u32,
+    blockchain_mutex: web::Data<Mutex<Blockchain>>,
+    transaction_mutex: web::Data<MutexTransactionList>,
+    event_hub: Option<Addr<EventHub>>,
+    store: Option<ChainStore>,
+) {
+    actix_rt::spawn(async move {
+        let client = Client::builder().timeout(Duration::from_secs(30)).finish();
+        let mut ticks = actix_rt::time::interval(Duration::from_secs(sync_interval.max(1) as u64));
+
+        loop {
+            ticks.tick().await;
+
+            for peer in peers.iter() {
+                let chain = match fetch_chain(&client, peer).await {
+                    Ok(c) => c,
+                    Err(e) => {
+                        eprintln!("{}", e);
+                        continue;
+                    }
+                };
+                let mut blockchain = match blockchain_mutex.lock() {
+                    Ok(guard) => guard,
+                    Err(e) => {
+                        eprintln!("Blockchain: Mutex Lock failed! Message: {:?}", e);
+                        continue;
+                    }
+                };
+
+                if let Some(r) = adopt_chain(
+                    &mut blockchain,
+                    &transaction_mutex,
+                    event_hub.as_ref(),
+                    store.as_ref(),
+                    chain,
+                ) {
+                    println!(
+                        "Peer ({}): Chain was adopted from Block {} on (Height: {} -> {})",
+                        peer, r.fork_index, r.previous_height, r.height
+                    );
+                }
+            }
+        }
+    });
+}
+
+//==============================================================================
+// Unit Tests
+
+#[test]
+fn adopt_chain_forged() {
+    use crate::model::address;
+    use crate::model::transaction::Transaction;
+    use ed25519_dalek::SigningKey;
+
+    let victim_key = SigningKey::from_bytes(&[11u8; 32]);
+    let victim = address::from_public_key(&victim_key.verifying_key());
+    let transactions = web::Data::new(MutexTransactionList::new());
+    let mut local = Blockchain::new();
+
+    let _ = transactions.add_transaction(local.build_reward(&victim, 10.0));
+
+    local.seal_block(&transactions).unwrap();
+
+    //-------------------------------------
+    // Longer Peer Chains with an unsigned Transfer or an oversized Reward are kept out
+
+    let mut forged = Transaction::from_data(
+        victim.clone(),
+        "16Jswqk47s9PUcyCc88MMVwzgvHPvtEpf".to_owned(),
+        10.0,
+    );
+
+    forged.nonce = 1;
+
+    for transaction in [forged, local.build_reward("attacker", 1_000_000.0)] {
+        let mut peer = Blockchain::from_chain(local.chain.clone()).unwrap();
+        let pending = web::Data::new(MutexTransactionList::new());
+
+        let _ = pending.add_transaction(transaction);
+
+        peer.seal_block(&pending).unwrap();
+        peer.proof_of_work(&pending).unwrap();
+
+        assert_eq!(
+            adopt_chain(&mut local, &pending, None, None, peer.chain.clone()),
+            None
+        );
+        assert_eq!(local.get_last_block_index(), 1);
+        assert_eq!(local.get_balance(&victim), 10f64);
+    }
+
+    //-------------------------------------
+    // A valid longer Peer Chain is adopted
+
+    let mut peer = Blockchain::from_chain(local.chain.clone()).unwrap();
+    let pending = web::Data::new(MutexTransactionList::new());
+
+    peer.proof_of_work(&pending).unwrap();
+
+    let replacement = adopt_chain(&mut local, &pending, None, None, peer.chain.clone()).unwrap();
+
+    assert_eq!(replacement.fork_index, 2);
+    assert_eq!(replacement.height, 2);
+}
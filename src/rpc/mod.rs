@@ -0,0 +1,478 @@
+/*
+* @author Bodo (Hugo) Barwich
+* @version 2026-10-18
+* @package Blockchain Exercise
+* @subpackage Blockchain JSON-RPC Interface
+
+* This Module defines the JSON-RPC 2.0 Interface for interacting with the Blockchain
+* alongside the REST Interface
+*
+*---------------------------------
+* Requirements:
+* - The Rust Crate "actix-web" must be installed
+* - The Rust Crate "serde" must be installed
+* - The Rust Crate "serde-json" must be installed
+*/
+
+use actix::Addr;
+use actix_web::{web, HttpResponse};
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::{json, Value};
+use std::ops::Deref;
+use std::sync::Mutex;
+
+use crate::events::{self, ChainEvent, EventHub};
+use crate::miner::{self, MinerLink};
+use crate::model::blockchain::Blockchain;
+use crate::model::transaction::{MutexTransactionList, Transaction};
+
+pub const JSONRPC_VERSION: &str = "2.0";
+
+pub const PARSE_ERROR: i64 = -32700;
+pub const INVALID_REQUEST: i64 = -32600;
+pub const METHOD_NOT_FOUND: i64 = -32601;
+pub const INVALID_PARAMS: i64 = -32602;
+pub const INTERNAL_ERROR: i64 = -32603;
+pub const NOT_FOUND: i64 = -32001;
+
+//==============================================================================
+// Structure RpcRequest Declaration
+
+/// Structure for a single JSON-RPC Call
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RpcRequest {
+    pub jsonrpc: String,
+    pub method: String,
+    #[serde(default)]
+    pub params: Value,
+    /// `None` if the Member is missing. Only such Calls are Notifications,
+    /// `"id": null` is answered with `null`.
+    #[serde(
+        default,
+        deserialize_with = "deserialize_id",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub id: Option<Value>,
+}
+
+/// Structure for a single JSON-RPC Response
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RpcResponse {
+    pub jsonrpc: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub result: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<RpcError>,
+    pub id: Value,
+}
+
+/// Structure for JSON-RPC Errors
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RpcError {
+    pub code: i64,
+    pub message: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<Value>,
+}
+
+//==============================================================================
+// Structure RpcResponse Implementation
+
+impl RpcResponse {
+    /*----------------------------------------------------------------------------
+     * Constructors
+     */
+
+    pub fn from_result(id: Value, result: Value) -> Self {
+        Self {
+            jsonrpc: JSONRPC_VERSION.to_owned(),
+            result: Some(result),
+            error: None,
+            id,
+        }
+    }
+
+    pub fn from_error(id: Value, error: RpcError) -> Self {
+        Self {
+            jsonrpc: JSONRPC_VERSION.to_owned(),
+            result: None,
+            error: Some(error),
+            id,
+        }
+    }
+}
+
+//==============================================================================
+// Structure RpcError Implementation
+
+impl RpcError {
+    /*----------------------------------------------------------------------------
+     * Constructors
+     */
+
+    pub fn new(code: i64, message: &str) -> Self {
+        Self {
+            code,
+            message: message.to_owned(),
+            data: None,
+        }
+    }
+
+    pub fn with_data(code: i64, message: &str, data: Value) -> Self {
+        Self {
+            code,
+            message: message.to_owned(),
+            data: Some(data),
+        }
+    }
+}
+
+//==============================================================================
+// JSON-RPC Handler
+
+/// Handler to dispatch JSON-RPC 2.0 Calls and Batches of Calls
+///
+/// All Methods work on the same `Blockchain`, `MutexTransactionList` and `MinerLink`
+/// as the REST Handlers.
+pub async fn dispatch_rpc_request(
+    blockchain_mutex: web::Data<Mutex<Blockchain>>,
+    transaction_mutex: web::Data<MutexTransactionList>,
+    link: web::Data<MinerLink>,
+    event_hub: Option<web::Data<Addr<EventHub>>>,
+    body: web::Bytes,
+) -> HttpResponse {
+    let event_hub = event_hub.as_ref().map(|h| h.get_ref());
+
+    let payload: Value = match serde_json::from_slice(&body) {
+        Ok(v) => v,
+        Err(e) => {
+            eprintln!("JSON-RPC: JSON parsing failed: {:?}", e);
+
+            return HttpResponse::Ok().json(RpcResponse::from_error(
+                Value::Null,
+                RpcError::with_data(PARSE_ERROR, "Parse error", json!(e.to_string())),
+            ));
+        }
+    };
+
+    match payload {
+        Value::Array(calls) => {
+            if calls.is_empty() {
+                return HttpResponse::Ok().json(RpcResponse::from_error(
+                    Value::Null,
+                    RpcError::new(INVALID_REQUEST, "Invalid Request"),
+                ));
+            }
+
+            let mut responses = Vec::<RpcResponse>::with_capacity(calls.len());
+
+            for call in calls {
+                if let Some(rs) = dispatch_call(
+                    &blockchain_mutex,
+                    &transaction_mutex,
+                    &link,
+                    event_hub,
+                    call,
+                )
+                .await
+                {
+                    responses.push(rs);
+                }
+            }
+
+            if responses.is_empty() {
+                // A Batch of Notifications does not produce any Response
+                HttpResponse::NoContent().finish()
+            } else {
+                HttpResponse::Ok().json(responses)
+            }
+        }
+        call => match dispatch_call(
+            &blockchain_mutex,
+            &transaction_mutex,
+            &link,
+            event_hub,
+            call,
+        )
+        .await
+        {
+            Some(rs) => HttpResponse::Ok().json(rs),
+            None => HttpResponse::NoContent().finish(),
+        },
+    }
+}
+
+/// Execute a single JSON-RPC Call.
+///
+/// # Returns:
+/// - `None` if the Call is a Notification which must not be answered
+///
+async fn dispatch_call(
+    blockchain_mutex: &web::Data<Mutex<Blockchain>>,
+    transaction_mutex: &web::Data<MutexTransactionList>,
+    link: &web::Data<MinerLink>,
+    event_hub: Option<&Addr<EventHub>>,
+    call: Value,
+) -> Option<RpcResponse> {
+    let request: RpcRequest = match serde_json::from_value(call) {
+        Ok(rq) => rq,
+        Err(e) => {
+            return Some(RpcResponse::from_error(
+                Value::Null,
+                RpcError::with_data(INVALID_REQUEST, "Invalid Request", json!(e.to_string())),
+            ));
+        }
+    };
+
+    let id = request.id.clone();
+
+    if request.jsonrpc != JSONRPC_VERSION {
+        return Some(RpcResponse::from_error(
+            id.unwrap_or(Value::Null),
+            RpcError::new(INVALID_REQUEST, "Invalid Request"),
+        ));
+    }
+
+    let result = match request.method.as_str() {
+        "getblockcount" => get_block_count(blockchain_mutex),
+        "getblockchaininfo" => get_blockchain_info(blockchain_mutex),
+        "getblock" => get_block(blockchain_mutex, &request.params),
+        "getblockhash" => get_block_hash(blockchain_mutex, &request.params),
+        "getbalance" => get_balance(blockchain_mutex, &request.params),
+        "getmempoolinfo" => Ok(json!({ "size": transaction_mutex.get_count() })),
+        "sendtransaction" => send_transaction(
+            blockchain_mutex,
+            transaction_mutex,
+            event_hub,
+            &request.params,
+        ),
+        "mine" => match miner::mine_block(link).await {
+            Ok(rs) => Ok(json!(rs)),
+            Err(e) => Err(RpcError::with_data(
+                INTERNAL_ERROR,
+                "Mining failed",
+                json!(format!("{:?}", e)),
+            )),
+        },
+        _ => Err(RpcError::new(METHOD_NOT_FOUND, "Method not found")),
+    };
+
+    id.map(|id| match result {
+        Ok(r) => RpcResponse::from_result(id, r),
+        Err(e) => RpcResponse::from_error(id, e),
+    })
+}
+
+//==============================================================================
+// JSON-RPC Methods
+
+fn get_block_count(blockchain_mutex: &web::Data<Mutex<Blockchain>>) -> Result<Value, RpcError> {
+    match blockchain_mutex.lock() {
+        Ok(guard) => Ok(json!(guard.deref().get_last_block_index())),
+        Err(e) => Err(lock_error(e)),
+    }
+}
+
+/// Height, Tip, Proof of Work Target and cumulative Work of the Chain
+fn get_blockchain_info(blockchain_mutex: &web::Data<Mutex<Blockchain>>) -> Result<Value, RpcError> {
+    match blockchain_mutex.lock() {
+        Ok(guard) => {
+            let blockchain = guard.deref();
+
+            Ok(json!({
+                "blocks": blockchain.get_last_block_index(),
+                "bestblockhash": blockchain.get_block_hash_by_index(blockchain.get_last_block_index()),
+                "bits": format!("{:08x}", blockchain.get_target_bits()),
+                "chainwork": format!("{:064x}", blockchain.get_chainwork()),
+            }))
+        }
+        Err(e) => Err(lock_error(e)),
+    }
+}
+
+fn get_block(
+    blockchain_mutex: &web::Data<Mutex<Blockchain>>,
+    params: &Value,
+) -> Result<Value, RpcError> {
+    let param = get_param(params, 0, "index")
+        .or_else(|| get_param(params, 0, "hash"))
+        .ok_or_else(|| RpcError::new(INVALID_PARAMS, "Block index or hash expected"))?;
+
+    match blockchain_mutex.lock() {
+        Ok(guard) => {
+            let blockchain = guard.deref();
+            let block = match param {
+                Value::Number(n) => n.as_u64().and_then(|i| blockchain.get_block(i)),
+                Value::String(h) => blockchain.get_block_by_hash(h.as_str()),
+                _ => {
+                    return Err(RpcError::new(
+                        INVALID_PARAMS,
+                        "Block index or hash expected",
+                    ))
+                }
+            };
+
+            match block {
+                Some(b) => Ok(json!(b)),
+                None => Err(RpcError::new(NOT_FOUND, "Block not found")),
+            }
+        }
+        Err(e) => Err(lock_error(e)),
+    }
+}
+
+fn get_block_hash(
+    blockchain_mutex: &web::Data<Mutex<Blockchain>>,
+    params: &Value,
+) -> Result<Value, RpcError> {
+    let index = get_param(params, 0, "index")
+        .and_then(|p| p.as_u64())
+        .ok_or_else(|| RpcError::new(INVALID_PARAMS, "Block index expected"))?;
+
+    match blockchain_mutex.lock() {
+        Ok(guard) => match guard.deref().get_block_hash_by_index(index) {
+            Some(h) => Ok(json!(h)),
+            None => Err(RpcError::new(NOT_FOUND, "Block not found")),
+        },
+        Err(e) => Err(lock_error(e)),
+    }
+}
+
+fn get_balance(
+    blockchain_mutex: &web::Data<Mutex<Blockchain>>,
+    params: &Value,
+) -> Result<Value, RpcError> {
+    let address = get_param(params, 0, "address")
+        .and_then(|p| p.as_str())
+        .ok_or_else(|| RpcError::new(INVALID_PARAMS, "Address expected"))?;
+    // The optional second parameter selects an Asset instead of the native Currency
+    let asset_id = get_param(params, 1, "asset").and_then(|p| p.as_str());
+
+    match blockchain_mutex.lock() {
+        Ok(guard) => match asset_id {
+            Some(a) => Ok(json!(guard.deref().get_asset_balance(address, a))),
+            None => Ok(json!(guard.deref().get_balance(address))),
+        },
+        Err(e) => Err(lock_error(e)),
+    }
+}
+
+fn send_transaction(
+    blockchain_mutex: &web::Data<Mutex<Blockchain>>,
+    transaction_mutex: &web::Data<MutexTransactionList>,
+    event_hub: Option<&Addr<EventHub>>,
+    params: &Value,
+) -> Result<Value, RpcError> {
+    // The Transaction can be sent as single positional parameter, as named parameter
+    // `transaction` or with its Fields as named parameters
+    let param = match params {
+        Value::Object(o) => Some(o.get("transaction").unwrap_or(params)),
+        _ => get_param(params, 0, "transaction"),
+    }
+    .ok_or_else(|| RpcError::new(INVALID_PARAMS, "Transaction expected"))?;
+
+    let transaction: Transaction = serde_json::from_value(param.clone()).map_err(|e| {
+        RpcError::with_data(INVALID_PARAMS, "Invalid Transaction", json!(e.to_string()))
+    })?;
+
+    if !transaction.is_valid() {
+        return Err(RpcError::new(INVALID_PARAMS, "Transaction is invalid"));
+    }
+
+    match blockchain_mutex.lock() {
+        Ok(guard) => guard
+            .deref()
+            .check_transaction(&transaction, transaction_mutex)
+            .map_err(|e| {
+                RpcError::with_data(INVALID_PARAMS, "Transaction is invalid", json!(e.report))
+            })?,
+        Err(e) => return Err(lock_error(e)),
+    }
+
+    let txid = transaction.to_hash().map_err(|e| {
+        RpcError::with_data(
+            INVALID_PARAMS,
+            "Transaction is invalid",
+            json!(e.to_string()),
+        )
+    })?;
+
+    match transaction_mutex.add_transaction(transaction.clone()) {
+        Ok(_) => {
+            events::publish(
+                event_hub,
+                ChainEvent::NewPendingTransaction(Box::new(transaction)),
+            );
+
+            // The Transaction Id is returned to look up the Transaction Status
+            Ok(json!(txid))
+        }
+        Err(e) => Err(RpcError::with_data(
+            INTERNAL_ERROR,
+            "Transaction could not be added",
+            json!(format!("{:?}", e)),
+        )),
+    }
+}
+
+//==============================================================================
+// Auxiliary Functions
+
+/// Look up a Parameter by its Position in positional Parameters or by its Name
+/// in named Parameters.
+fn get_param<'a>(params: &'a Value, position: usize, name: &str) -> Option<&'a Value> {
+    match params {
+        Value::Array(a) => a.get(position),
+        Value::Object(o) => o.get(name),
+        _ => None,
+    }
+}
+
+/// Keep an explicit `"id": null` apart from a missing `id`.
+fn deserialize_id<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Value>, D::Error> {
+    Value::deserialize(deserializer).map(Some)
+}
+
+fn lock_error<E: std::fmt::Debug>(e: E) -> RpcError {
+    RpcError::with_data(
+        INTERNAL_ERROR,
+        "Blockchain: Mutex Lock failed",
+        json!(format!("{:?}", e)),
+    )
+}
+
+//==============================================================================
+// Unit Tests
+
+#[test]
+fn rpc_request_notification() {
+    //-------------------------------------
+    // A Call without `id` is a Notification
+
+    let request: RpcRequest =
+        serde_json::from_str(r#"{"jsonrpc":"2.0","method":"getblockcount"}"#).unwrap();
+
+    assert_eq!(request.method.as_str(), "getblockcount");
+    assert_eq!(request.params, Value::Null);
+    assert!(request.id.is_none());
+
+    // A Call with `"id": null` must be answered
+    let request: RpcRequest =
+        serde_json::from_str(r#"{"jsonrpc":"2.0","method":"getblockcount","id":null}"#).unwrap();
+
+    assert_eq!(request.id, Some(Value::Null));
+    assert_eq!(serde_json::to_value(&request).unwrap()["id"], Value::Null);
+}
+
+#[test]
+fn rpc_params_positional_and_named() {
+    let positional = json!([5, "asset"]);
+    let named = json!({"index": 5, "asset": "asset"});
+
+    assert_eq!(get_param(&positional, 0, "index"), Some(&json!(5)));
+    assert_eq!(get_param(&named, 0, "index"), Some(&json!(5)));
+    assert_eq!(get_param(&positional, 1, "asset"), Some(&json!("asset")));
+    assert_eq!(get_param(&named, 1, "asset"), Some(&json!("asset")));
+    assert_eq!(get_param(&named, 0, "hash"), None);
+    assert_eq!(get_param(&Value::Null, 0, "index"), None);
+}
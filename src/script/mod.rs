@@ -0,0 +1,545 @@
+/*
+* @author Bodo (Hugo) Barwich
+* @version 2026-10-18
+* @package Blockchain Exercise
+* @subpackage Spending Scripts
+
+* This Module defines a small stack-based Script Language for Spending Conditions
+* and the Interpreter which executes a Script when its Funds are spent
+*
+*---------------------------------
+* Requirements:
+* - The Rust Crate "ed25519-dalek" must be installed
+* - The Rust Crate "sha2" must be installed
+*/
+
+use ed25519_dalek::{Signature, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::model::address::Address;
+
+/// Maximum number of Operations of a Script
+pub const MAX_SCRIPT_OPS: usize = 200;
+/// Maximum number of Items on the Stack
+pub const MAX_STACK_SIZE: usize = 100;
+/// Maximum number of Bytes of one Stack Item
+pub const MAX_ITEM_SIZE: usize = 520;
+/// Maximum Gas which one Execution can consume
+pub const MAX_GAS: u64 = 10_000;
+
+//==============================================================================
+// Structure Script Declaration
+
+/// Operations of the Script Language
+///
+/// Conditions push a Boolean. Operations ending in `Verify` abort the Execution
+/// if the Condition does not hold.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Op {
+    /// Push hex encoded Bytes
+    Push(String),
+    /// Push an Integer
+    PushInt(i64),
+    Dup,
+    Drop,
+    Swap,
+    /// Replace the top Item by its SHA-256 Hash
+    Sha256,
+    Equal,
+    EqualVerify,
+    Verify,
+    Not,
+    BoolAnd,
+    BoolOr,
+    /// Pop a Public Key and a Signature and check the Signature of the Signing Payload
+    CheckSig,
+    /// Pop a Block Index and check that the spending Block is not below it
+    CheckHeight,
+    /// Pop a Unix Timestamp and check that the Median Time Past before the spending
+    /// Block is not older
+    CheckTimestamp,
+    /// Execute the following Operations only if the popped Item is true
+    If,
+    Else,
+    EndIf,
+    /// Fail the Execution
+    Return,
+}
+
+/// Structure for a Script which defines the Conditions to spend Funds
+///
+/// The Funds are sent to the Address of the Script. The Spender reveals the Script
+/// and the Witness Items which satisfy it.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Script {
+    pub ops: Vec<Op>,
+}
+
+/// Structure for the Data of the spending `Transaction` and Block which a
+/// Script can check
+#[derive(Debug, Clone)]
+pub struct ScriptContext<'a> {
+    /// Signing Payload of the spending `Transaction`
+    pub payload: &'a [u8],
+    pub block_index: u64,
+    /// Median Time Past of the Blocks before which `CheckTimestamp` compares with
+    pub block_timestamp: u32,
+}
+
+/// Structure for Script Errors
+#[derive(Debug, PartialEq)]
+pub struct ScriptError {
+    pub status: String,
+    pub report: String,
+}
+
+/// Items on the Stack of the Interpreter
+#[derive(Debug, Clone, PartialEq)]
+enum Item {
+    Bytes(Vec<u8>),
+    Int(i64),
+    Bool(bool),
+}
+
+/// Structure for the State of one Script Execution
+struct Interpreter<'a> {
+    context: &'a ScriptContext<'a>,
+    stack: Vec<Item>,
+    /// Conditions of the enclosing `If` Blocks
+    branches: Vec<bool>,
+    gas: u64,
+}
+
+//==============================================================================
+// Structure Script Implementation
+
+impl Script {
+    /*----------------------------------------------------------------------------
+     * Constructors
+     */
+
+    pub fn new(ops: Vec<Op>) -> Self {
+        Self { ops }
+    }
+
+    /*----------------------------------------------------------------------------
+     * Consultation Methods
+     */
+
+    /// Serialize the Script in the Form from which its Address is derived.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        serde_json::to_vec(&self.ops).unwrap_or_default()
+    }
+
+    /// Derive the Address which the Funds locked by the Script are sent to.
+    pub fn to_address(&self) -> String {
+        Address::from_script(&self.to_bytes()).to_string()
+    }
+
+    /// Check that the Script is not too long, that its `If` Blocks are balanced
+    /// with at most one `Else` each and that it only pushes valid Data.
+    pub fn is_valid(&self) -> bool {
+        // Whether each open `If` Block already has its `Else`
+        let mut blocks: Vec<bool> = Vec::new();
+
+        if self.ops.is_empty() || self.ops.len() > MAX_SCRIPT_OPS {
+            return false;
+        }
+
+        for op in self.ops.iter() {
+            match op {
+                Op::If => blocks.push(false),
+                Op::Else => match blocks.last_mut() {
+                    Some(has_else) if !*has_else => *has_else = true,
+                    _ => return false,
+                },
+                Op::EndIf if blocks.is_empty() => return false,
+                Op::EndIf => {
+                    blocks.pop();
+                }
+                Op::Push(data) => match hex::decode(data) {
+                    Ok(d) if d.len() <= MAX_ITEM_SIZE => {}
+                    _ => return false,
+                },
+                _ => {}
+            }
+        }
+
+        blocks.is_empty()
+    }
+
+    /// Execute the Script on top of the Witness Items.
+    ///
+    /// # Parameters:
+    /// - `witness`: hex encoded Items which are pushed before the Script runs.
+    ///
+    /// # Returns:
+    /// - the consumed Gas if the Script ends with a true Item on the Stack
+    ///
+    pub fn execute(&self, witness: &[String], context: &ScriptContext) -> Result<u64, ScriptError> {
+        if !self.is_valid() {
+            return Err(ScriptError::new("invalid", "Script: Script is malformed"));
+        }
+
+        let mut interpreter = Interpreter {
+            context,
+            stack: Vec::new(),
+            branches: Vec::new(),
+            gas: 0,
+        };
+
+        for item in witness.iter() {
+            match hex::decode(item) {
+                Ok(data) if data.len() <= MAX_ITEM_SIZE => interpreter.push(Item::Bytes(data))?,
+                _ => {
+                    return Err(ScriptError::new(
+                        "invalid",
+                        "Script: Witness Item is not valid hex or too long",
+                    ))
+                }
+            }
+        }
+
+        for op in self.ops.iter() {
+            interpreter.step(op)?;
+        }
+
+        match interpreter.stack.last() {
+            Some(item) if item.is_true() => Ok(interpreter.gas),
+            _ => Err(ScriptError::new(
+                "failed",
+                "Script: Script did not end with a true Item",
+            )),
+        }
+    }
+}
+
+//==============================================================================
+// Structure Interpreter Implementation
+
+impl Interpreter<'_> {
+    /*----------------------------------------------------------------------------
+     * Administration Methods
+     */
+
+    /// Execute one Operation. Operations within a false `If` Block only consume Gas.
+    fn step(&mut self, op: &Op) -> Result<(), ScriptError> {
+        self.gas += get_gas_cost(op);
+
+        if self.gas > MAX_GAS {
+            return Err(ScriptError::new(
+                "gas",
+                &format!("Script: Gas Limit of {} is exceeded", MAX_GAS),
+            ));
+        }
+
+        let is_executing = self.branches.iter().all(|b| *b);
+
+        match op {
+            Op::If => {
+                let condition = is_executing && self.pop()?.is_true();
+
+                self.branches.push(condition);
+            }
+            Op::Else => {
+                if let Some(b) = self.branches.last_mut() {
+                    *b = !*b;
+                }
+            }
+            Op::EndIf => {
+                self.branches.pop();
+            }
+            _ if !is_executing => {}
+            Op::Push(data) => self.push(Item::Bytes(hex::decode(data).unwrap_or_default()))?,
+            Op::PushInt(n) => self.push(Item::Int(*n))?,
+            Op::Dup => {
+                let item = self.pop()?;
+
+                self.push(item.clone())?;
+                self.push(item)?;
+            }
+            Op::Drop => {
+                self.pop()?;
+            }
+            Op::Swap => {
+                let top = self.pop()?;
+                let below = self.pop()?;
+
+                self.push(top)?;
+                self.push(below)?;
+            }
+            Op::Sha256 => {
+                let data = self.pop()?.to_bytes();
+
+                self.push(Item::Bytes(Sha256::digest(data).to_vec()))?;
+            }
+            Op::Equal => {
+                let is_equal = self.pop()? == self.pop()?;
+
+                self.push(Item::Bool(is_equal))?;
+            }
+            Op::EqualVerify => {
+                if self.pop()? != self.pop()? {
+                    return Err(ScriptError::new("failed", "Script: Items are not equal"));
+                }
+            }
+            Op::Verify => {
+                if !self.pop()?.is_true() {
+                    return Err(ScriptError::new("failed", "Script: Condition is false"));
+                }
+            }
+            Op::Not => {
+                let item = self.pop()?;
+
+                self.push(Item::Bool(!item.is_true()))?;
+            }
+            Op::BoolAnd => {
+                let (a, b) = (self.pop()?.is_true(), self.pop()?.is_true());
+
+                self.push(Item::Bool(a && b))?;
+            }
+            Op::BoolOr => {
+                let (a, b) = (self.pop()?.is_true(), self.pop()?.is_true());
+
+                self.push(Item::Bool(a || b))?;
+            }
+            Op::CheckSig => {
+                let public_key = self.pop()?.to_bytes();
+                let signature = self.pop()?.to_bytes();
+
+                self.push(Item::Bool(is_signature_valid(
+                    &public_key,
+                    &signature,
+                    self.context.payload,
+                )))?;
+            }
+            Op::CheckHeight => {
+                let height = self.pop_int()?;
+
+                self.push(Item::Bool(
+                    self.context.block_index as i128 >= height as i128,
+                ))?;
+            }
+            Op::CheckTimestamp => {
+                let timestamp = self.pop_int()?;
+
+                self.push(Item::Bool(self.context.block_timestamp as i64 >= timestamp))?;
+            }
+            Op::Return => {
+                return Err(ScriptError::new("failed", "Script: Return was executed"));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn push(&mut self, item: Item) -> Result<(), ScriptError> {
+        if self.stack.len() >= MAX_STACK_SIZE {
+            return Err(ScriptError::new(
+                "invalid",
+                &format!("Script: Stack exceeds {} Items", MAX_STACK_SIZE),
+            ));
+        }
+
+        self.stack.push(item);
+
+        Ok(())
+    }
+
+    fn pop(&mut self) -> Result<Item, ScriptError> {
+        self.stack
+            .pop()
+            .ok_or_else(|| ScriptError::new("invalid", "Script: Stack is empty"))
+    }
+
+    fn pop_int(&mut self) -> Result<i64, ScriptError> {
+        match self.pop()? {
+            Item::Int(n) => Ok(n),
+            _ => Err(ScriptError::new("invalid", "Script: Integer expected")),
+        }
+    }
+}
+
+//==============================================================================
+// Structure Item Implementation
+
+impl Item {
+    /*----------------------------------------------------------------------------
+     * Consultation Methods
+     */
+
+    /// Bytes are true if any of them is not zero.
+    fn is_true(&self) -> bool {
+        match self {
+            Item::Bytes(b) => b.iter().any(|x| *x != 0),
+            Item::Int(n) => *n != 0,
+            Item::Bool(b) => *b,
+        }
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        match self {
+            Item::Bytes(b) => b.clone(),
+            Item::Int(n) => n.to_le_bytes().to_vec(),
+            Item::Bool(b) => vec![*b as u8],
+        }
+    }
+}
+
+//==============================================================================
+// Structure ScriptError Implementation
+
+impl ScriptError {
+    /*----------------------------------------------------------------------------
+     * Constructors
+     */
+
+    pub fn new(status: &str, report: &str) -> Self {
+        Self {
+            status: status.to_owned(),
+            report: report.to_owned(),
+        }
+    }
+}
+
+//==============================================================================
+// Auxiliary Functions
+
+/// Signature Checks and Hashes cost more Gas than Stack Operations
+fn get_gas_cost(op: &Op) -> u64 {
+    match op {
+        Op::CheckSig => 100,
+        Op::Sha256 => 20,
+        _ => 1,
+    }
+}
+
+fn is_signature_valid(public_key: &[u8], signature: &[u8], payload: &[u8]) -> bool {
+    let public_key = match <[u8; 32]>::try_from(public_key)
+        .ok()
+        .and_then(|k| VerifyingKey::from_bytes(&k).ok())
+    {
+        Some(k) => k,
+        None => return false,
+    };
+
+    match Signature::from_slice(signature) {
+        Ok(s) => public_key.verify_strict(payload, &s).is_ok(),
+        Err(_) => false,
+    }
+}
+
+//==============================================================================
+// Unit Tests
+
+#[test]
+fn script_hash_lock() {
+    let preimage = b"open sesame";
+    let script = Script::new(vec![
+        Op::Sha256,
+        Op::Push(hex::encode(Sha256::digest(preimage))),
+        Op::Equal,
+    ]);
+    let context = ScriptContext {
+        payload: b"payload",
+        block_index: 1,
+        block_timestamp: 0,
+    };
+
+    assert!(script.is_valid());
+    assert!(script.to_address().starts_with('4'));
+    assert_eq!(script.execute(&[hex::encode(preimage)], &context), Ok(22));
+    assert_eq!(
+        script
+            .execute(&[hex::encode(b"wrong")], &context)
+            .unwrap_err()
+            .status,
+        "failed"
+    );
+    assert_eq!(script.execute(&[], &context).unwrap_err().status, "invalid");
+}
+
+#[test]
+fn script_signature_or_timeout() {
+    use ed25519_dalek::{Signer, SigningKey};
+
+    let owner = SigningKey::from_bytes(&[4u8; 32]);
+    let other = SigningKey::from_bytes(&[5u8; 32]);
+    let payload = b"spending transaction";
+    // The Owner can spend at once. Anyone can spend from Block 10 on.
+    let script = Script::new(vec![
+        Op::If,
+        Op::Push(hex::encode(owner.verifying_key().as_bytes())),
+        Op::CheckSig,
+        Op::Else,
+        Op::PushInt(10),
+        Op::CheckHeight,
+        Op::EndIf,
+    ]);
+    let early = ScriptContext {
+        payload,
+        block_index: 9,
+        block_timestamp: 0,
+    };
+    let late = ScriptContext {
+        block_index: 10,
+        ..early.clone()
+    };
+    let owner_witness = [
+        hex::encode(owner.sign(payload).to_bytes()),
+        String::from("01"),
+    ];
+    let other_witness = [
+        hex::encode(other.sign(payload).to_bytes()),
+        String::from("01"),
+    ];
+
+    assert!(script.execute(&owner_witness, &early).is_ok());
+    assert!(script.execute(&other_witness, &early).is_err());
+    assert!(script.execute(&[String::new()], &early).is_err());
+    assert!(script.execute(&[String::new()], &late).is_ok());
+}
+
+#[test]
+fn script_limits() {
+    let context = ScriptContext {
+        payload: b"",
+        block_index: 1,
+        block_timestamp: 0,
+    };
+    let unbalanced = Script::new(vec![Op::PushInt(1), Op::If, Op::PushInt(1)]);
+    // Each Pair costs 101 Gas
+    let expensive = Script::new(
+        [Op::Dup, Op::CheckSig]
+            .iter()
+            .cycle()
+            .take(MAX_SCRIPT_OPS)
+            .cloned()
+            .collect(),
+    );
+
+    assert!(!unbalanced.is_valid());
+    assert!(!Script::new(vec![Op::Else]).is_valid());
+    assert!(!Script::new(vec![
+        Op::PushInt(0),
+        Op::If,
+        Op::Else,
+        Op::PushInt(1),
+        Op::Else,
+        Op::EndIf
+    ])
+    .is_valid());
+    assert!(!Script::new(vec![Op::Push(String::from("zz"))]).is_valid());
+    assert!(!Script::new(Vec::new()).is_valid());
+    assert!(!Script::new(vec![Op::PushInt(1); MAX_SCRIPT_OPS + 1]).is_valid());
+    assert_eq!(
+        expensive
+            .execute(&[String::from("00")], &context)
+            .unwrap_err()
+            .status,
+        "gas"
+    );
+}
@@ -0,0 +1,263 @@
+/*
+* @author Bodo (Hugo) Barwich
+* @version 2026-10-18
+* @package Blockchain Exercise
+* @subpackage Blockchain Storage
+
+* This Module defines the Storage of the Blockchain in the Data Directory
+*
+*---------------------------------
+* Requirements:
+* - The Rust Crate "serde-json" must be installed
+*/
+
+use std::fs;
+use std::io::{Error, ErrorKind};
+use std::path::{Path, PathBuf};
+
+use crate::model::blockchain::Blockchain;
+use crate::model::transaction::Transaction;
+
+const CHAIN_FILE: &str = "chain.json";
+const PENDING_FILE: &str = "pending.json";
+
+//==============================================================================
+// Structure ChainStore Declaration
+
+/// Structure for storing the Blockchain as JSON Document in a Data Directory
+#[derive(Debug, Clone)]
+pub struct ChainStore {
+    directory: PathBuf,
+}
+
+//==============================================================================
+// Structure ChainStore Implementation
+
+impl ChainStore {
+    /*----------------------------------------------------------------------------
+     * Constructors
+     */
+
+    pub fn new(directory: &Path) -> Self {
+        Self {
+            directory: directory.to_path_buf(),
+        }
+    }
+
+    /// Create a Store for the configured Data Directory.
+    ///
+    /// # Returns:
+    /// - `None` if no Data Directory is configured and the Blockchain is only kept in memory
+    ///
+    pub fn from_directory(directory: &str) -> Option<Self> {
+        if directory.is_empty() {
+            None
+        } else {
+            Some(Self::new(Path::new(directory)))
+        }
+    }
+
+    /*----------------------------------------------------------------------------
+     * Administration Methods
+     */
+
+    /// Load the stored Blockchain, upgrade its Format and build its Indices.
+    ///
+    /// # Returns:
+    /// - `None` if no Blockchain was stored yet
+    ///
+    pub fn load(&self) -> Result<Option<Blockchain>, Error> {
+        let chain_file = self.get_chain_file();
+
+        if !chain_file.exists() {
+            return Ok(None);
+        }
+
+        let chain_json = fs::read_to_string(&chain_file)?;
+        let mut blockchain: Blockchain = serde_json::from_str(&chain_json).map_err(|e| {
+            Error::new(
+                ErrorKind::InvalidData,
+                format!(
+                    "Chain File {:?}: parse file failed with Error: '{:?}'",
+                    chain_file, e
+                ),
+            )
+        })?;
+
+        blockchain
+            .migrate()
+            .and_then(|_| blockchain.rebuild_index())
+            .map_err(|e| {
+                Error::new(
+                    ErrorKind::InvalidData,
+                    format!("Chain File {:?}: {}", chain_file, e.report),
+                )
+            })?;
+
+        Ok(Some(blockchain))
+    }
+
+    /// Store the Blockchain.
+    ///
+    /// The Chain File is replaced atomically so that a crash does not leave a
+    /// partially written Chain behind.
+    pub fn save(&self, blockchain: &Blockchain) -> Result<(), Error> {
+        fs::create_dir_all(&self.directory)?;
+
+        let chain_file = self.get_chain_file();
+        let temp_file = chain_file.with_extension("json.tmp");
+        let chain_json = serde_json::to_string(blockchain).map_err(Error::other)?;
+
+        fs::write(&temp_file, chain_json)?;
+        fs::rename(&temp_file, &chain_file)
+    }
+
+    /// Load the `Transaction`s which were left waiting for the next Block.
+    ///
+    /// # Returns:
+    /// - an empty List if no `Transaction`s were stored
+    ///
+    pub fn load_pending(&self) -> Result<Vec<Transaction>, Error> {
+        let pending_file = self.get_pending_file();
+
+        if !pending_file.exists() {
+            return Ok(Vec::new());
+        }
+
+        let pending_json = fs::read_to_string(&pending_file)?;
+
+        serde_json::from_str(&pending_json).map_err(|e| {
+            Error::new(
+                ErrorKind::InvalidData,
+                format!(
+                    "Pending File {:?}: parse file failed with Error: '{:?}'",
+                    pending_file, e
+                ),
+            )
+        })
+    }
+
+    /// Store the `Transaction`s waiting for the next Block like the Mining Rewards
+    /// of the last Block.
+    pub fn save_pending(&self, transactions: &[Transaction]) -> Result<(), Error> {
+        fs::create_dir_all(&self.directory)?;
+
+        let pending_file = self.get_pending_file();
+        let temp_file = pending_file.with_extension("json.tmp");
+        let pending_json = serde_json::to_string(transactions).map_err(Error::other)?;
+
+        fs::write(&temp_file, pending_json)?;
+        fs::rename(&temp_file, &pending_file)
+    }
+
+    /*----------------------------------------------------------------------------
+     * Consultation Methods
+     */
+
+    pub fn get_directory(&self) -> &Path {
+        self.directory.as_path()
+    }
+
+    pub fn get_chain_file(&self) -> PathBuf {
+        self.directory.join(CHAIN_FILE)
+    }
+
+    pub fn get_pending_file(&self) -> PathBuf {
+        self.directory.join(PENDING_FILE)
+    }
+}
+
+//==============================================================================
+// Unit Tests
+
+#[test]
+fn chain_store_round_trip() {
+    use crate::model::transaction::MutexTransactionList;
+
+    let directory = std::env::temp_dir().join(format!("blockchain-store-{}", std::process::id()));
+    let store = ChainStore::new(&directory);
+    let transactions = actix_web::web::Data::new(MutexTransactionList::new());
+    let mut blockchain = Blockchain::with_difficulty(1);
+
+    assert!(store.load().unwrap().is_none());
+    assert!(ChainStore::from_directory("").is_none());
+
+    blockchain.seal_block(&transactions).unwrap();
+    transactions
+        .add_transaction(blockchain.build_reward("16Jswqk47s9PUcyCc88MMVwzgvHPvtEpf", 5f64))
+        .unwrap();
+    blockchain.seal_block(&transactions).unwrap();
+
+    store.save(&blockchain).unwrap();
+
+    let loaded = store.load().unwrap().unwrap();
+
+    fs::remove_dir_all(&directory).unwrap();
+
+    assert_eq!(loaded.chain.len(), 2);
+    assert_eq!(loaded.difficulty, 1);
+    assert_eq!(
+        loaded.get_balance("16Jswqk47s9PUcyCc88MMVwzgvHPvtEpf"),
+        5f64
+    );
+}
+
+#[test]
+fn chain_store_migration() {
+    use crate::consensus::pow;
+    use crate::model::blockchain::{Block, CHAIN_FORMAT_VERSION};
+
+    let directory =
+        std::env::temp_dir().join(format!("blockchain-migration-{}", std::process::id()));
+    let store = ChainStore::new(&directory);
+
+    //-------------------------------------
+    // Chains without Format Version are upgraded with the Target of their Difficulty
+
+    let legacy = serde_json::json!({
+        "chain": [Block::build_block(1, 7, "0", None)],
+        "nodes": [],
+        "difficulty": 2,
+    });
+
+    fs::create_dir_all(&directory).unwrap();
+    fs::write(store.get_chain_file(), legacy.to_string()).unwrap();
+
+    let loaded = store.load().unwrap().unwrap();
+
+    assert_eq!(loaded.format_version, CHAIN_FORMAT_VERSION);
+    assert_eq!(loaded.chain[0].bits, pow::difficulty_to_bits(2));
+    assert!(loaded.get_chainwork() > &num::BigUint::default());
+
+    //-------------------------------------
+    // Chains linked with Hashes of a former Encoding are refused
+
+    let first = Block::build_block(1, 7, "0", None);
+    let second = Block::build_block(2, 7, &sha256::digest(first.to_json().unwrap()), None);
+    let relinked = serde_json::json!({
+        "format_version": 2,
+        "chain": [first, second],
+        "nodes": [],
+    });
+
+    fs::write(store.get_chain_file(), relinked.to_string()).unwrap();
+
+    let error = store.load().unwrap_err();
+
+    assert_eq!(error.kind(), ErrorKind::InvalidData);
+    assert!(error.to_string().contains("former Encoding"));
+
+    //-------------------------------------
+    // Chains of a newer Format are refused
+
+    let mut future = legacy.clone();
+
+    future["format_version"] = serde_json::json!(CHAIN_FORMAT_VERSION + 1);
+    fs::write(store.get_chain_file(), future.to_string()).unwrap();
+
+    let error = store.load().unwrap_err();
+
+    fs::remove_dir_all(&directory).unwrap();
+
+    assert_eq!(error.kind(), ErrorKind::InvalidData);
+}
@@ -0,0 +1,273 @@
+/*
+* @author Bodo (Hugo) Barwich
+* @version 2026-10-18
+* @package Blockchain Exercise
+* @subpackage Hierarchical Deterministic Wallet
+
+* This Module derives an unlimited sequence of Key Pairs from a BIP-39 Mnemonic
+* following the SLIP-0010 Derivation for Ed25519 Keys
+*
+*---------------------------------
+* Requirements:
+* - The Rust Crate "bip39" must be installed
+* - The Rust Crate "hmac" must be installed
+* - The Rust Crate "sha2" must be installed
+*/
+
+use bip39::Mnemonic;
+use ed25519_dalek::SigningKey;
+use hmac::{Hmac, Mac};
+use rand::rngs::OsRng;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use sha2::Sha512;
+
+use super::WalletError;
+use crate::model::address;
+
+/// Hardened Derivation Path of the Accounts: m/44'/1'/0'/0'/<index>'
+pub const DERIVATION_PATH: [u32; 4] = [44, 1, 0, 0];
+
+/// Number of consecutive unused Addresses after which the Discovery stops
+pub const DEFAULT_GAP_LIMIT: u32 = 20;
+
+const HARDENED_OFFSET: u32 = 0x8000_0000;
+const SEED_KEY: &[u8] = b"ed25519 seed";
+
+type HmacSha512 = Hmac<Sha512>;
+
+//==============================================================================
+// Structure HdWallet Declaration
+
+/// Structure for deriving Key Pairs from a Mnemonic Seed
+pub struct HdWallet {
+    seed: Vec<u8>,
+}
+
+/// Structure for an Address found by the Address Discovery
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DerivedAddress {
+    pub index: u32,
+    pub address: String,
+    pub used: bool,
+}
+
+//==============================================================================
+// Structure HdWallet Implementation
+
+impl HdWallet {
+    /*----------------------------------------------------------------------------
+     * Constructors
+     */
+
+    pub fn from_seed(seed: &[u8]) -> Self {
+        Self {
+            seed: seed.to_vec(),
+        }
+    }
+
+    /// Restore the Wallet from a Mnemonic and an optional Passphrase.
+    pub fn from_mnemonic(mnemonic: &str, passphrase: &str) -> Result<Self, WalletError> {
+        let mnemonic = Mnemonic::parse(mnemonic).map_err(|e| {
+            WalletError::new("invalid", &format!("Mnemonic: Mnemonic is invalid: {}", e))
+        })?;
+
+        Ok(Self::from_seed(&mnemonic.to_seed(passphrase)))
+    }
+
+    /// Generate a new random Mnemonic of 12 or 24 Words.
+    pub fn generate_mnemonic(word_count: usize) -> Result<String, WalletError> {
+        let mut entropy = vec![0u8; word_count * 4 / 3];
+
+        OsRng.fill_bytes(&mut entropy);
+
+        Mnemonic::from_entropy(&entropy)
+            .map(|m| m.to_string())
+            .map_err(|e| {
+                WalletError::new(
+                    "invalid",
+                    &format!("Mnemonic: {} Words are not supported: {}", word_count, e),
+                )
+            })
+    }
+
+    /*----------------------------------------------------------------------------
+     * Consultation Methods
+     */
+
+    /// Derive the Key Pair at `index` of the Derivation Path.
+    pub fn derive_key(&self, index: u32) -> SigningKey {
+        let mut path = DERIVATION_PATH.to_vec();
+
+        path.push(index);
+
+        SigningKey::from_bytes(&self.derive_path(&path))
+    }
+
+    pub fn derive_address(&self, index: u32) -> String {
+        address::from_public_key(&self.derive_key(index).verifying_key())
+    }
+
+    /// Derive the Secret Key of a hardened Derivation Path with SLIP-0010.
+    ///
+    /// Ed25519 only supports hardened Derivation so each Index is hardened.
+    pub fn derive_path(&self, path: &[u32]) -> [u8; 32] {
+        let (mut key, mut chain_code) = split_hmac(SEED_KEY, &[&self.seed]);
+
+        for index in path {
+            let hardened = (index | HARDENED_OFFSET).to_be_bytes();
+
+            (key, chain_code) = split_hmac(&chain_code, &[&[0u8], &key, &hardened]);
+        }
+
+        key
+    }
+
+    /// Discover the Addresses of the Wallet.
+    ///
+    /// The Addresses are derived in order until `gap_limit` consecutive Addresses
+    /// are unused. `is_used` looks up whether an Address has any `Transaction`s.
+    ///
+    /// # Returns:
+    /// - all used Addresses followed by the first unused Address
+    ///
+    pub fn discover<E>(
+        &self,
+        gap_limit: u32,
+        mut is_used: impl FnMut(&str) -> Result<bool, E>,
+    ) -> Result<Vec<DerivedAddress>, E> {
+        let mut addresses = Vec::<DerivedAddress>::new();
+        let mut first_unused: Option<DerivedAddress> = None;
+        let mut gap = 0;
+        let mut index = 0;
+
+        while gap < gap_limit.max(1) {
+            let address = self.derive_address(index);
+            let used = is_used(&address)?;
+
+            if used {
+                addresses.push(DerivedAddress {
+                    index,
+                    address,
+                    used,
+                });
+                first_unused = None;
+                gap = 0;
+            } else {
+                if first_unused.is_none() {
+                    first_unused = Some(DerivedAddress {
+                        index,
+                        address,
+                        used,
+                    });
+                }
+
+                gap += 1;
+            }
+
+            index += 1;
+        }
+
+        addresses.extend(first_unused);
+
+        Ok(addresses)
+    }
+}
+
+//==============================================================================
+// Auxiliary Functions
+
+/// Calculate the HMAC-SHA512 and split it into the Key and the Chain Code
+fn split_hmac(key: &[u8], data: &[&[u8]]) -> ([u8; 32], [u8; 32]) {
+    let mut mac = HmacSha512::new_from_slice(key).expect("HMAC accepts Keys of any Length");
+
+    data.iter().for_each(|d| mac.update(d));
+
+    let digest = mac.finalize().into_bytes();
+    let mut secret = [0u8; 32];
+    let mut chain_code = [0u8; 32];
+
+    secret.copy_from_slice(&digest[..32]);
+    chain_code.copy_from_slice(&digest[32..]);
+
+    (secret, chain_code)
+}
+
+//==============================================================================
+// Unit Tests
+
+#[test]
+fn slip10_test_vector() {
+    //-------------------------------------
+    // SLIP-0010 Test Vector 1 for ed25519
+
+    let wallet = HdWallet::from_seed(&hex::decode("000102030405060708090a0b0c0d0e0f").unwrap());
+
+    assert_eq!(
+        hex::encode(wallet.derive_path(&[])),
+        "2b4be7f19ee27bbf30c667b642d5f4aa69fd169872f8fc3059c08ebae2eb19e7"
+    );
+    assert_eq!(
+        hex::encode(wallet.derive_path(&[0])),
+        "68e0fe46dfb67e368c75379acec591dad19df3cde26e63b93a8e704f1dade7a3"
+    );
+    assert_eq!(
+        hex::encode(wallet.derive_path(&[0, 1])),
+        "b1d0bad404bf35da785a64ca1ac54b2617211d2777696fbffaf208f746ae84f2"
+    );
+}
+
+#[test]
+fn hd_wallet_discovery() {
+    use crate::model::blockchain::Blockchain;
+    use crate::model::transaction::MutexTransactionList;
+
+    let mnemonic = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+    let wallet = HdWallet::from_mnemonic(mnemonic, "").unwrap();
+    let restored = HdWallet::from_mnemonic(mnemonic, "").unwrap();
+
+    assert_eq!(wallet.derive_address(3), restored.derive_address(3));
+    assert_ne!(wallet.derive_address(3), wallet.derive_address(4));
+    assert_ne!(
+        wallet.derive_address(0),
+        HdWallet::from_mnemonic(mnemonic, "TREZOR")
+            .unwrap()
+            .derive_address(0)
+    );
+    assert!(HdWallet::from_mnemonic("abandon about", "").is_err());
+    assert_eq!(
+        HdWallet::generate_mnemonic(24)
+            .unwrap()
+            .split_whitespace()
+            .count(),
+        24
+    );
+
+    //-------------------------------------
+    // Addresses 0 and 2 are used. The Discovery stops after 3 unused Addresses
+
+    let transactions = actix_web::web::Data::new(MutexTransactionList::new());
+    let mut blockchain = Blockchain::with_difficulty(1);
+
+    transactions
+        .add_transaction_from_data("blockchain", &wallet.derive_address(0), 10.0)
+        .unwrap();
+    transactions
+        .add_transaction_from_data("blockchain", &wallet.derive_address(2), 2.5)
+        .unwrap();
+    blockchain.seal_block(&transactions).unwrap();
+
+    let mut lookups = 0;
+    let found = wallet
+        .discover(3, |a| {
+            lookups += 1;
+            Ok::<bool, ()>(blockchain.is_address_used(a))
+        })
+        .unwrap();
+
+    assert_eq!(lookups, 6);
+    assert_eq!(
+        found.iter().map(|a| (a.index, a.used)).collect::<Vec<_>>(),
+        vec![(0, true), (2, true), (3, false)]
+    );
+}
@@ -0,0 +1,532 @@
+/*
+* @author Bodo (Hugo) Barwich
+* @version 2026-10-18
+* @package Blockchain Exercise
+* @subpackage Blockchain Wallet
+
+* This Module defines the encrypted Keystore of the Wallet which holds the
+* Key Pairs to sign `Transaction`s with
+*
+*---------------------------------
+* Requirements:
+* - The Rust Crate "ed25519-dalek" must be installed
+* - The Rust Crate "argon2" must be installed
+* - The Rust Crate "chacha20poly1305" must be installed
+*/
+
+pub mod hd;
+
+use argon2::{Algorithm, Argon2, Params, Version};
+use chacha20poly1305::aead::{Aead, KeyInit, Payload};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use ed25519_dalek::SigningKey;
+use rand::rngs::OsRng;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+use crate::model::address;
+use hd::HdWallet;
+
+pub const KEYSTORE_VERSION: u32 = 1;
+
+const SALT_LENGTH: usize = 16;
+const NONCE_LENGTH: usize = 12;
+
+//==============================================================================
+// Structure Keystore Declaration
+
+/// Structure for the Keystore File holding the encrypted Secret Keys
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Keystore {
+    pub version: u32,
+    pub keys: Vec<KeyEntry>,
+    #[serde(default)]
+    pub seeds: Vec<SeedEntry>,
+    /// Cost of the Password Hashing for newly added Keys
+    #[serde(skip)]
+    kdf: KdfParams,
+}
+
+/// Structure for one encrypted Key Pair
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KeyEntry {
+    pub name: String,
+    pub address: String,
+    pub public_key: String,
+    #[serde(flatten)]
+    pub secret: EncryptedSecret,
+}
+
+/// Structure for one encrypted Mnemonic to derive Key Pairs from
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SeedEntry {
+    pub name: String,
+    #[serde(flatten)]
+    pub secret: EncryptedSecret,
+}
+
+/// Structure for a Secret encrypted with a Key derived from the Password
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EncryptedSecret {
+    pub kdf: KdfParams,
+    /// Hex encoded Salt of the Password Hashing
+    pub salt: String,
+    /// Hex encoded Nonce of the Encryption
+    pub nonce: String,
+    /// Hex encoded encrypted Secret
+    pub ciphertext: String,
+}
+
+/// Cost Parameters of the Argon2id Password Hashing
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct KdfParams {
+    pub memory_cost: u32,
+    pub time_cost: u32,
+    pub parallelism: u32,
+}
+
+/// Structure for Wallet Errors
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WalletError {
+    pub status: String,
+    pub report: String,
+}
+
+//==============================================================================
+// Structure Keystore Implementation
+
+impl Default for Keystore {
+    /*----------------------------------------------------------------------------
+     * Default Constructor
+     */
+
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Keystore {
+    /*----------------------------------------------------------------------------
+     * Constructors
+     */
+
+    pub fn new() -> Self {
+        Self {
+            version: KEYSTORE_VERSION,
+            keys: Vec::new(),
+            seeds: Vec::new(),
+            kdf: KdfParams::default(),
+        }
+    }
+
+    /// Create a Keystore which hashes the Passwords of new Keys with the given Cost
+    pub fn with_kdf(kdf: KdfParams) -> Self {
+        Self { kdf, ..Self::new() }
+    }
+
+    /// Read the Keystore File.
+    ///
+    /// # Returns:
+    /// - an empty Keystore if the File does not exist yet
+    ///
+    pub fn load(file: &Path) -> Result<Self, WalletError> {
+        if !file.exists() {
+            return Ok(Self::new());
+        }
+
+        let keystore_json = fs::read_to_string(file).map_err(|e| {
+            WalletError::new(
+                "failed",
+                &format!(
+                    "Keystore {:?}: read file failed with Error: '{:?}'",
+                    file, e
+                ),
+            )
+        })?;
+        let keystore: Keystore = serde_json::from_str(&keystore_json).map_err(|e| {
+            WalletError::new(
+                "invalid",
+                &format!(
+                    "Keystore {:?}: parse file failed with Error: '{:?}'",
+                    file, e
+                ),
+            )
+        })?;
+
+        if keystore.version != KEYSTORE_VERSION {
+            return Err(WalletError::new(
+                "invalid",
+                &format!(
+                    "Keystore {:?}: Version {} is not supported",
+                    file, keystore.version
+                ),
+            ));
+        }
+
+        Ok(keystore)
+    }
+
+    /*----------------------------------------------------------------------------
+     * Administration Methods
+     */
+
+    /// Write the Keystore File.
+    ///
+    /// The File is replaced atomically so that no Key gets lost on a crash.
+    pub fn save(&self, file: &Path) -> Result<(), WalletError> {
+        let keystore_json = serde_json::to_string_pretty(self)
+            .map_err(|e| WalletError::new("failed", &format!("Keystore: {:?}", e)))?;
+        let temp_file = file.with_extension("tmp");
+
+        fs::write(&temp_file, keystore_json)
+            .and_then(|_| fs::rename(&temp_file, file))
+            .map_err(|e| {
+                WalletError::new(
+                    "failed",
+                    &format!(
+                        "Keystore {:?}: write file failed with Error: '{:?}'",
+                        file, e
+                    ),
+                )
+            })
+    }
+
+    /// Generate a new Key Pair and store it encrypted with the Password.
+    pub fn generate_key(&mut self, name: &str, password: &str) -> Result<&KeyEntry, WalletError> {
+        self.add_key(name, &SigningKey::generate(&mut OsRng), password)
+    }
+
+    /// Store a Key Pair encrypted with the Password.
+    pub fn add_key(
+        &mut self,
+        name: &str,
+        signing_key: &SigningKey,
+        password: &str,
+    ) -> Result<&KeyEntry, WalletError> {
+        if self.keys.iter().any(|k| k.name == name) {
+            return Err(WalletError::new(
+                "exists",
+                &format!("Keystore: Key '{}' does already exist", name),
+            ));
+        }
+
+        let verifying_key = signing_key.verifying_key();
+        let address = address::from_public_key(&verifying_key);
+        let secret = encrypt_secret(
+            &self.kdf,
+            password,
+            signing_key.as_bytes(),
+            address.as_bytes(),
+        )?;
+
+        self.keys.push(KeyEntry {
+            name: name.to_owned(),
+            address,
+            public_key: hex::encode(verifying_key.as_bytes()),
+            secret,
+        });
+
+        Ok(&self.keys[self.keys.len() - 1])
+    }
+
+    /// Store a Mnemonic encrypted with the Password to derive Key Pairs from.
+    pub fn add_seed(
+        &mut self,
+        name: &str,
+        mnemonic: &str,
+        password: &str,
+    ) -> Result<&SeedEntry, WalletError> {
+        if self.seeds.iter().any(|s| s.name == name) {
+            return Err(WalletError::new(
+                "exists",
+                &format!("Keystore: Seed '{}' does already exist", name),
+            ));
+        }
+
+        // Only valid Mnemonics are stored
+        HdWallet::from_mnemonic(mnemonic, "")?;
+
+        let secret = encrypt_secret(&self.kdf, password, mnemonic.as_bytes(), name.as_bytes())?;
+
+        self.seeds.push(SeedEntry {
+            name: name.to_owned(),
+            secret,
+        });
+
+        Ok(&self.seeds[self.seeds.len() - 1])
+    }
+
+    /// Decrypt the Secret Key of a Key Pair with the Password.
+    pub fn unlock(&self, name_or_address: &str, password: &str) -> Result<SigningKey, WalletError> {
+        let entry = self.get_key(name_or_address).ok_or_else(|| {
+            WalletError::new(
+                "not_found",
+                &format!("Keystore: Key '{}' does not exist", name_or_address),
+            )
+        })?;
+        let secret = decrypt_secret(&entry.secret, password, entry.address.as_bytes())
+            .map_err(|e| e.for_entry(&entry.name))?;
+        let secret = <[u8; 32]>::try_from(secret).map_err(|_| {
+            WalletError::new(
+                "invalid",
+                &format!("Keystore: Key '{}': Secret Key is invalid", entry.name),
+            )
+        })?;
+
+        Ok(SigningKey::from_bytes(&secret))
+    }
+
+    /// Decrypt a Mnemonic with the Password to derive its Key Pairs.
+    pub fn unlock_seed(&self, name: &str, password: &str) -> Result<HdWallet, WalletError> {
+        let entry = self.seeds.iter().find(|s| s.name == name).ok_or_else(|| {
+            WalletError::new(
+                "not_found",
+                &format!("Keystore: Seed '{}' does not exist", name),
+            )
+        })?;
+        let mnemonic = decrypt_secret(&entry.secret, password, entry.name.as_bytes())
+            .map_err(|e| e.for_entry(&entry.name))?;
+        let mnemonic = String::from_utf8(mnemonic).map_err(|_| {
+            WalletError::new(
+                "invalid",
+                &format!("Keystore: Seed '{}': Mnemonic is invalid", entry.name),
+            )
+        })?;
+
+        HdWallet::from_mnemonic(&mnemonic, "")
+    }
+
+    /*----------------------------------------------------------------------------
+     * Consultation Methods
+     */
+
+    /// Look up a Key Pair by its Name or its Address
+    pub fn get_key(&self, name_or_address: &str) -> Option<&KeyEntry> {
+        self.keys
+            .iter()
+            .find(|k| k.name == name_or_address || k.address == name_or_address)
+    }
+}
+
+//==============================================================================
+// Structure KdfParams Implementation
+
+impl Default for KdfParams {
+    /*----------------------------------------------------------------------------
+     * Default Constructor
+     */
+
+    fn default() -> Self {
+        Self {
+            memory_cost: Params::DEFAULT_M_COST,
+            time_cost: Params::DEFAULT_T_COST,
+            parallelism: Params::DEFAULT_P_COST,
+        }
+    }
+}
+
+//==============================================================================
+// Structure WalletError Implementation
+
+impl WalletError {
+    /*----------------------------------------------------------------------------
+     * Constructors
+     */
+
+    pub fn new(status: &str, report: &str) -> Self {
+        Self {
+            status: status.to_owned(),
+            report: report.to_owned(),
+        }
+    }
+}
+
+impl WalletError {
+    /*----------------------------------------------------------------------------
+     * Administration Methods
+     */
+
+    /// Name the Keystore Entry the Error occurred for
+    fn for_entry(self, name: &str) -> Self {
+        Self {
+            status: self.status,
+            report: format!("Keystore: Entry '{}': {}", name, self.report),
+        }
+    }
+}
+
+impl fmt::Display for WalletError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.report)
+    }
+}
+
+//==============================================================================
+// Auxiliary Functions
+
+/// Encrypt a Secret with ChaCha20-Poly1305 under a Key derived from the Password.
+///
+/// The Associated Data binds the Secret to its Keystore Entry.
+fn encrypt_secret(
+    kdf: &KdfParams,
+    password: &str,
+    secret: &[u8],
+    associated_data: &[u8],
+) -> Result<EncryptedSecret, WalletError> {
+    let mut salt = [0u8; SALT_LENGTH];
+    let mut nonce = [0u8; NONCE_LENGTH];
+
+    OsRng.fill_bytes(&mut salt);
+    OsRng.fill_bytes(&mut nonce);
+
+    let cipher = ChaCha20Poly1305::new(&derive_key(kdf, password, &salt)?);
+    let ciphertext = cipher
+        .encrypt(
+            Nonce::from_slice(&nonce),
+            Payload {
+                msg: secret,
+                aad: associated_data,
+            },
+        )
+        .map_err(|e| WalletError::new("failed", &format!("Keystore: {:?}", e)))?;
+
+    Ok(EncryptedSecret {
+        kdf: *kdf,
+        salt: hex::encode(salt),
+        nonce: hex::encode(nonce),
+        ciphertext: hex::encode(ciphertext),
+    })
+}
+
+fn decrypt_secret(
+    secret: &EncryptedSecret,
+    password: &str,
+    associated_data: &[u8],
+) -> Result<Vec<u8>, WalletError> {
+    let invalid =
+        |field: &str| WalletError::new("invalid", &format!("Field '{}' is invalid", field));
+    let salt = hex::decode(&secret.salt).map_err(|_| invalid("salt"))?;
+    let nonce = hex::decode(&secret.nonce).map_err(|_| invalid("nonce"))?;
+    let ciphertext = hex::decode(&secret.ciphertext).map_err(|_| invalid("ciphertext"))?;
+
+    if nonce.len() != NONCE_LENGTH {
+        return Err(invalid("nonce"));
+    }
+
+    let cipher = ChaCha20Poly1305::new(&derive_key(&secret.kdf, password, &salt)?);
+
+    cipher
+        .decrypt(
+            Nonce::from_slice(&nonce),
+            Payload {
+                msg: &ciphertext,
+                aad: associated_data,
+            },
+        )
+        .map_err(|_| WalletError::new("denied", "Password is wrong"))
+}
+
+/// Derive the Encryption Key from the Password with Argon2id
+fn derive_key(kdf: &KdfParams, password: &str, salt: &[u8]) -> Result<Key, WalletError> {
+    let params = Params::new(kdf.memory_cost, kdf.time_cost, kdf.parallelism, Some(32))
+        .map_err(|e| WalletError::new("invalid", &format!("Keystore: {:?}", e)))?;
+    let mut key = Key::default();
+
+    Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+        .hash_password_into(password.as_bytes(), salt, &mut key)
+        .map_err(|e| WalletError::new("failed", &format!("Keystore: {:?}", e)))?;
+
+    Ok(key)
+}
+
+//==============================================================================
+// Unit Tests
+
+#[test]
+fn keystore_encrypted_keys() {
+    let kdf = KdfParams {
+        memory_cost: 64,
+        time_cost: 1,
+        parallelism: 1,
+    };
+    let file = std::env::temp_dir().join(format!("blockchain-wallet-{}.json", std::process::id()));
+    let mut keystore = Keystore::with_kdf(kdf);
+
+    let address = keystore
+        .generate_key("main", "secret")
+        .unwrap()
+        .address
+        .clone();
+
+    assert!(address::is_key_address(&address));
+    assert_eq!(
+        keystore.generate_key("main", "secret").unwrap_err().status,
+        "exists"
+    );
+
+    keystore.save(&file).unwrap();
+
+    let keystore = Keystore::load(&file).unwrap();
+
+    fs::remove_file(&file).unwrap();
+
+    let keystore_json = serde_json::to_string(&keystore).unwrap();
+    let signing_key = keystore.unlock(&address, "secret").unwrap();
+
+    assert_eq!(
+        address::from_public_key(&signing_key.verifying_key()),
+        address
+    );
+    assert!(!keystore_json.contains(&hex::encode(signing_key.as_bytes())));
+    assert_eq!(
+        keystore.unlock("main", "wrong").unwrap_err().status,
+        "denied"
+    );
+    assert_eq!(
+        keystore.unlock("other", "secret").unwrap_err().status,
+        "not_found"
+    );
+}
+
+#[test]
+fn keystore_encrypted_seeds() {
+    let mnemonic = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+    let mut keystore = Keystore::with_kdf(KdfParams {
+        memory_cost: 64,
+        time_cost: 1,
+        parallelism: 1,
+    });
+
+    keystore.add_seed("custody", mnemonic, "secret").unwrap();
+
+    assert_eq!(
+        keystore
+            .add_seed("other", "abandon about", "secret")
+            .unwrap_err()
+            .status,
+        "invalid"
+    );
+
+    let keystore_json = serde_json::to_string(&keystore).unwrap();
+    let keystore: Keystore = serde_json::from_str(&keystore_json).unwrap();
+    let wallet = keystore.unlock_seed("custody", "secret").unwrap();
+
+    assert!(!keystore_json.contains("abandon"));
+    assert_eq!(
+        wallet.derive_address(7),
+        HdWallet::from_mnemonic(mnemonic, "")
+            .unwrap()
+            .derive_address(7)
+    );
+    assert_eq!(
+        keystore
+            .unlock_seed("custody", "wrong")
+            .err()
+            .unwrap()
+            .status,
+        "denied"
+    );
+}
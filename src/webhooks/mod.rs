@@ -0,0 +1,846 @@
+/*
+* @author Bodo (Hugo) Barwich
+* @version 2026-10-18
+* @package Blockchain Exercise
+* @subpackage Blockchain Webhooks
+
+* This Module defines the Actor that notifies registered Webhooks about
+* Blockchain Events with signed JSON Callbacks
+*
+*---------------------------------
+* Requirements:
+* - The Rust Crate "actix" must be installed
+* - The Rust Crate "actix-web" must be installed
+* - The Rust Crate "hmac" must be installed
+* - The Rust Crate "sha2" must be installed
+*/
+
+use actix::prelude::*;
+use actix_web::client::Client;
+use actix_web::http::Uri;
+use actix_web::{error, web, Error, HttpRequest, HttpResponse};
+use hmac::{Hmac, Mac};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sha2::Sha256;
+use std::collections::{BTreeMap, VecDeque};
+use std::net::{IpAddr, ToSocketAddrs};
+use std::time::{Duration, SystemTime};
+
+use crate::check_admin;
+use crate::config::AppConfig;
+use crate::consensus::HashAlgorithm;
+use crate::events::{ChainEvent, Connect, Disconnect, EventHub, Subscription, Topic};
+use crate::model::blockchain::Block;
+use crate::model::transaction::Transaction;
+
+/// Maximum number of Confirmations a Webhook can wait for
+pub const MAX_CONFIRMATIONS: u64 = 100;
+/// Maximum number of Records kept in the Delivery Log
+pub const MAX_DELIVERY_LOG: usize = 1000;
+
+pub const SIGNATURE_HEADER: &str = "X-Webhook-Signature";
+pub const EVENT_HEADER: &str = "X-Webhook-Event";
+pub const DELIVERY_HEADER: &str = "X-Webhook-Delivery";
+
+type HmacSha256 = Hmac<Sha256>;
+
+//==============================================================================
+// Structure Webhook Declaration
+
+/// Events a Webhook can be registered for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum WebhookEvent {
+    BlockMined,
+    TransactionConfirmed,
+    ChainReorg,
+}
+
+/// Structure for the Webhook Registration Request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookRequest {
+    pub url: String,
+    pub event: WebhookEvent,
+    #[serde(default)]
+    pub address: Option<String>,
+    #[serde(default)]
+    pub confirmations: Option<u64>,
+}
+
+/// Structure for a registered Webhook
+///
+/// The `secret` to verify the Callback Signatures is only disclosed once at registration.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Webhook {
+    pub id: usize,
+    pub url: String,
+    pub event: WebhookEvent,
+    pub address: Option<String>,
+    pub confirmations: u64,
+    #[serde(skip_serializing, default)]
+    secret: String,
+}
+
+/// Structure for the Webhook Registration Response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookRegistration {
+    pub webhook: Webhook,
+    pub secret: String,
+}
+
+/// Structure for a Record in the Delivery Log
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeliveryRecord {
+    pub delivery_id: usize,
+    pub webhook_id: usize,
+    pub event: WebhookEvent,
+    pub attempt: u32,
+    pub success: bool,
+    pub status_code: Option<u16>,
+    pub report: String,
+    pub timestamp: u64,
+}
+
+/// Structure for Webhook Errors
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WebhookError {
+    pub status: String,
+    pub report: String,
+}
+
+//==============================================================================
+// Registry Messages Declaration
+
+#[derive(Debug, Message)]
+#[rtype(result = "Result<WebhookRegistration, WebhookError>")]
+pub struct RegisterWebhook(pub WebhookRequest);
+
+#[derive(Debug, Message)]
+#[rtype(result = "bool")]
+pub struct RemoveWebhook {
+    pub id: usize,
+}
+
+#[derive(Debug, Message)]
+#[rtype(result = "Vec<Webhook>")]
+pub struct ListWebhooks;
+
+#[derive(Debug, Message)]
+#[rtype(result = "Vec<DeliveryRecord>")]
+pub struct ListDeliveries {
+    pub webhook_id: Option<usize>,
+}
+
+#[derive(Debug, Message)]
+#[rtype(result = "()")]
+struct DeliveryReport(DeliveryRecord);
+
+//==============================================================================
+// Structure WebhookRegistry Declaration
+
+/// Structure for delivering the Blockchain Events to the registered Webhooks
+// Define actor
+pub struct WebhookRegistry {
+    hub: Option<Addr<EventHub>>,
+    subscriber_id: usize,
+    webhooks: Vec<Webhook>,
+    next_webhook_id: usize,
+    next_delivery_id: usize,
+    deliveries: VecDeque<DeliveryRecord>,
+    recent_blocks: BTreeMap<u64, Block>,
+    max_attempts: u32,
+    retry_delay: Duration,
+    hash_algorithm: HashAlgorithm,
+    /// Accept Callback URLs on this Host or in private Networks
+    allow_private_targets: bool,
+}
+
+//==============================================================================
+// Structure WebhookRegistry Implementation
+
+impl Default for WebhookRegistry {
+    /*----------------------------------------------------------------------------
+     * Default Constructor
+     */
+
+    fn default() -> Self {
+        Self::new(None)
+    }
+}
+
+impl WebhookRegistry {
+    /*----------------------------------------------------------------------------
+     * Constructors
+     */
+
+    /// Create a new Registry.
+    ///
+    /// # Parameters:
+    ///
+    /// - `hub`: The `EventHub` to receive the Blockchain Events from. Without a Hub the
+    ///   Events must be sent to the Registry directly.
+    ///
+    pub fn new(hub: Option<Addr<EventHub>>) -> Self {
+        Self {
+            hub,
+            subscriber_id: 0,
+            webhooks: Vec::new(),
+            next_webhook_id: 0,
+            next_delivery_id: 0,
+            deliveries: VecDeque::new(),
+            recent_blocks: BTreeMap::new(),
+            max_attempts: 5,
+            retry_delay: Duration::from_secs(1),
+            hash_algorithm: HashAlgorithm::Sha256,
+            allow_private_targets: false,
+        }
+    }
+
+    /// Configure the Retries of failed Deliveries.
+    ///
+    /// The Delay doubles after each failed Attempt.
+    pub fn with_retry(mut self, max_attempts: u32, retry_delay: Duration) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self.retry_delay = retry_delay;
+        self
+    }
+
+    /// Report the Hashes of orphaned Blocks with the Hash Algorithm of the Chain.
+    pub fn with_hash_algorithm(mut self, hash_algorithm: HashAlgorithm) -> Self {
+        self.hash_algorithm = hash_algorithm;
+        self
+    }
+
+    /// Accept Callback URLs on this Host or in private Networks.
+    ///
+    /// By default they are rejected so that Clients cannot reach internal Services
+    /// through the Node. Only meant for local Test Setups.
+    pub fn with_private_targets(mut self, allow_private_targets: bool) -> Self {
+        self.allow_private_targets = allow_private_targets;
+        self
+    }
+
+    /*----------------------------------------------------------------------------
+     * Administration Methods
+     */
+
+    fn add_block(&mut self, block: Block, ctx: &mut Context<Self>) {
+        let height = block.index;
+
+        for webhook in self.find_webhooks(WebhookEvent::BlockMined) {
+            if matches_address(&webhook, block.transactions.iter()) {
+                self.deliver(&webhook, json!({ "block": &block }), ctx);
+            }
+        }
+
+        self.recent_blocks.insert(height, block);
+
+        // Only the Blocks which can still gain relevant Confirmations are kept
+        while let Some((&index, _)) = self.recent_blocks.iter().next() {
+            if index + MAX_CONFIRMATIONS > height {
+                break;
+            }
+
+            self.recent_blocks.remove(&index);
+        }
+
+        self.check_confirmations(height, 0, ctx);
+    }
+
+    /// Notify the Webhooks whose `Transaction`s reach their Confirmations at the Height.
+    ///
+    /// Only `Transaction`s in Blocks from `first_index` on are reported.
+    fn check_confirmations(&mut self, height: u64, first_index: u64, ctx: &mut Context<Self>) {
+        for webhook in self.find_webhooks(WebhookEvent::TransactionConfirmed) {
+            if webhook.confirmations > height {
+                continue;
+            }
+
+            let block_index = height + 1 - webhook.confirmations;
+
+            if block_index < first_index {
+                continue;
+            }
+            let confirmed: Vec<Transaction> = match self.recent_blocks.get(&block_index) {
+                Some(b) => b
+                    .transactions
+                    .iter()
+                    .filter(|t| matches_address(&webhook, std::iter::once(*t)))
+                    .cloned()
+                    .collect(),
+                None => Vec::new(),
+            };
+
+            for transaction in confirmed {
+                self.deliver(
+                    &webhook,
+                    json!({
+                        "txid": transaction.to_hash().ok(),
+                        "transaction": transaction,
+                        "block_index": block_index,
+                        "confirmations": webhook.confirmations,
+                    }),
+                    ctx,
+                );
+            }
+        }
+    }
+
+    fn reorganise(
+        &mut self,
+        fork_index: u64,
+        previous_height: u64,
+        height: u64,
+        orphaned_blocks: Vec<Block>,
+        adopted_blocks: Vec<Block>,
+        ctx: &mut Context<Self>,
+    ) {
+        let orphaned_hashes = match orphaned_blocks
+            .iter()
+            .map(|b| b.to_hash_with(self.hash_algorithm))
+            .collect::<Result<Vec<String>, _>>()
+        {
+            Ok(h) => h,
+            Err(e) => {
+                eprintln!("Webhook Registry: Orphaned Blocks not hashed: {}", e.report);
+
+                Vec::new()
+            }
+        };
+
+        for webhook in self.find_webhooks(WebhookEvent::ChainReorg) {
+            if matches_address(
+                &webhook,
+                orphaned_blocks.iter().flat_map(|b| b.transactions.iter()),
+            ) {
+                self.deliver(
+                    &webhook,
+                    json!({
+                        "fork_index": fork_index,
+                        "previous_height": previous_height,
+                        "height": height,
+                        "orphaned_blocks": orphaned_hashes,
+                    }),
+                    ctx,
+                );
+            }
+        }
+
+        self.recent_blocks.retain(|index, _| *index < fork_index);
+
+        for block in adopted_blocks {
+            let block_index = block.index;
+            // Blocks below the Fork reached this Height in the former Chain already
+            // and their Transactions were reported then
+            let first_index = if block_index <= previous_height {
+                fork_index
+            } else {
+                0
+            };
+
+            self.recent_blocks.insert(block_index, block);
+            self.check_confirmations(block_index, first_index, ctx);
+        }
+    }
+
+    /// Deliver a signed Callback to a Webhook and retry with Backoff on Failure
+    fn deliver(&mut self, webhook: &Webhook, data: Value, ctx: &mut Context<Self>) {
+        self.next_delivery_id += 1;
+
+        let delivery_id = self.next_delivery_id;
+        let body = json!({
+            "delivery_id": delivery_id,
+            "webhook_id": webhook.id,
+            "event": webhook.event,
+            "timestamp": unix_timestamp(),
+            "data": data,
+        })
+        .to_string();
+        let signature = sign_payload(&webhook.secret, body.as_bytes());
+        let event_name = serde_json::to_value(webhook.event)
+            .ok()
+            .and_then(|v| v.as_str().map(String::from))
+            .unwrap_or_default();
+        let webhook = webhook.clone();
+        let max_attempts = self.max_attempts;
+        let retry_delay = self.retry_delay;
+        let registry = ctx.address();
+
+        actix::spawn(async move {
+            let client = Client::builder().timeout(Duration::from_secs(10)).finish();
+            let mut delay = retry_delay;
+
+            for attempt in 1..=max_attempts {
+                let result = client
+                    .post(webhook.url.as_str())
+                    .header(SIGNATURE_HEADER, signature.as_str())
+                    .header(EVENT_HEADER, event_name.as_str())
+                    .header(DELIVERY_HEADER, delivery_id.to_string())
+                    .content_type("application/json")
+                    .send_body(body.clone())
+                    .await;
+                let (success, status_code, report) = match result {
+                    Ok(rs) => (
+                        rs.status().is_success(),
+                        Some(rs.status().as_u16()),
+                        format!("HTTP Status: {}", rs.status()),
+                    ),
+                    Err(e) => (false, None, format!("Delivery failed: {}", e)),
+                };
+
+                registry.do_send(DeliveryReport(DeliveryRecord {
+                    delivery_id,
+                    webhook_id: webhook.id,
+                    event: webhook.event,
+                    attempt,
+                    success,
+                    status_code,
+                    report,
+                    timestamp: unix_timestamp(),
+                }));
+
+                if success {
+                    break;
+                }
+
+                if attempt < max_attempts {
+                    actix::clock::delay_for(delay).await;
+                    delay *= 2;
+                }
+            }
+        });
+    }
+
+    /*----------------------------------------------------------------------------
+     * Consultation Methods
+     */
+
+    fn find_webhooks(&self, event: WebhookEvent) -> Vec<Webhook> {
+        self.webhooks
+            .iter()
+            .filter(|w| w.event == event)
+            .cloned()
+            .collect()
+    }
+}
+
+impl Actor for WebhookRegistry {
+    type Context = Context<Self>;
+
+    fn started(&mut self, ctx: &mut Self::Context) {
+        println!("Webhook Registry Actor is alive");
+
+        if let Some(hub) = self.hub.clone() {
+            hub.send(Connect {
+                recipient: ctx.address().recipient(),
+            })
+            .into_actor(self)
+            .then(|res, act, _ctx| {
+                match res {
+                    Ok(id) => {
+                        act.subscriber_id = id;
+
+                        if let Some(h) = act.hub.as_ref() {
+                            h.do_send(Subscription {
+                                id,
+                                topics: vec![Topic::NewBlock, Topic::ChainReorg],
+                                subscribe: true,
+                            });
+                        }
+                    }
+                    Err(e) => eprintln!("Webhook Registry: Hub Registration failed: {:?}", e),
+                }
+
+                fut::ready(())
+            })
+            .wait(ctx);
+        }
+    }
+
+    fn stopping(&mut self, _ctx: &mut Self::Context) -> Running {
+        if let Some(h) = self.hub.as_ref() {
+            h.do_send(Disconnect {
+                id: self.subscriber_id,
+            });
+        }
+
+        Running::Stop
+    }
+}
+
+impl Handler<ChainEvent> for WebhookRegistry {
+    type Result = ();
+
+    fn handle(&mut self, msg: ChainEvent, ctx: &mut Self::Context) {
+        match msg {
+            ChainEvent::NewBlock(block) => self.add_block(block, ctx),
+            ChainEvent::ChainReorg {
+                fork_index,
+                previous_height,
+                height,
+                orphaned_blocks,
+                adopted_blocks,
+            } => self.reorganise(
+                fork_index,
+                previous_height,
+                height,
+                orphaned_blocks,
+                adopted_blocks,
+                ctx,
+            ),
+            _ => (),
+        }
+    }
+}
+
+impl Handler<RegisterWebhook> for WebhookRegistry {
+    type Result = Result<WebhookRegistration, WebhookError>;
+
+    fn handle(&mut self, msg: RegisterWebhook, _ctx: &mut Self::Context) -> Self::Result {
+        let request = msg.0;
+
+        if !request.url.starts_with("http://") && !request.url.starts_with("https://") {
+            return Err(WebhookError {
+                status: "invalid".to_owned(),
+                report: format!("Webhook: URL '{}' is not a HTTP URL", request.url),
+            });
+        }
+
+        if !self.allow_private_targets {
+            check_target_url(&request.url)?;
+        }
+
+        let confirmations = request.confirmations.unwrap_or(1);
+
+        if confirmations == 0 || confirmations > MAX_CONFIRMATIONS {
+            return Err(WebhookError {
+                status: "invalid".to_owned(),
+                report: format!(
+                    "Webhook: Confirmations must be between 1 and {}",
+                    MAX_CONFIRMATIONS
+                ),
+            });
+        }
+
+        let mut secret = [0u8; 32];
+
+        rand::thread_rng().fill_bytes(&mut secret);
+        self.next_webhook_id += 1;
+
+        let webhook = Webhook {
+            id: self.next_webhook_id,
+            url: request.url,
+            event: request.event,
+            address: request.address.filter(|a| !a.is_empty()),
+            confirmations,
+            secret: hex::encode(secret),
+        };
+
+        self.webhooks.push(webhook.clone());
+
+        Ok(WebhookRegistration {
+            secret: webhook.secret.clone(),
+            webhook,
+        })
+    }
+}
+
+impl Handler<RemoveWebhook> for WebhookRegistry {
+    type Result = bool;
+
+    fn handle(&mut self, msg: RemoveWebhook, _ctx: &mut Self::Context) -> Self::Result {
+        let count = self.webhooks.len();
+
+        self.webhooks.retain(|w| w.id != msg.id);
+
+        self.webhooks.len() != count
+    }
+}
+
+impl Handler<ListWebhooks> for WebhookRegistry {
+    type Result = MessageResult<ListWebhooks>;
+
+    fn handle(&mut self, _msg: ListWebhooks, _ctx: &mut Self::Context) -> Self::Result {
+        MessageResult(self.webhooks.clone())
+    }
+}
+
+impl Handler<ListDeliveries> for WebhookRegistry {
+    type Result = MessageResult<ListDeliveries>;
+
+    fn handle(&mut self, msg: ListDeliveries, _ctx: &mut Self::Context) -> Self::Result {
+        MessageResult(
+            self.deliveries
+                .iter()
+                .filter(|d| msg.webhook_id.is_none_or(|id| d.webhook_id == id))
+                .cloned()
+                .collect(),
+        )
+    }
+}
+
+impl Handler<DeliveryReport> for WebhookRegistry {
+    type Result = ();
+
+    fn handle(&mut self, msg: DeliveryReport, _ctx: &mut Self::Context) {
+        if !msg.0.success {
+            eprintln!(
+                "Webhook ({}): Delivery {} Attempt {} failed: {}",
+                msg.0.webhook_id, msg.0.delivery_id, msg.0.attempt, msg.0.report
+            );
+        }
+
+        self.deliveries.push_back(msg.0);
+
+        while self.deliveries.len() > MAX_DELIVERY_LOG {
+            self.deliveries.pop_front();
+        }
+    }
+}
+
+//==============================================================================
+// Webhook Handlers
+
+/// Handler to register a new Webhook
+///
+/// Like all Webhook Endpoints it is only available to Administrators because the
+/// Webhooks are not bound to the Client which registered them.
+pub async fn register_webhook(
+    http_request: HttpRequest,
+    registry: web::Data<Addr<WebhookRegistry>>,
+    app_config: Option<web::Data<AppConfig>>,
+    request: web::Json<WebhookRequest>,
+) -> Result<HttpResponse, Error> {
+    check_admin(&http_request, app_config.as_ref().map(|c| c.get_ref()))?;
+
+    match registry.send(RegisterWebhook(request.into_inner())).await {
+        Ok(Ok(registration)) => Ok(HttpResponse::Created().json(registration)),
+        Ok(Err(e)) => Err(error::ErrorBadRequest(e.report)),
+        Err(e) => Err(error::ErrorInternalServerError(format!(
+            "Webhook Registry: Request failed! Message: {:?}",
+            e
+        ))),
+    }
+}
+
+/// Handler to list the registered Webhooks
+pub async fn list_webhooks(
+    request: HttpRequest,
+    registry: web::Data<Addr<WebhookRegistry>>,
+    app_config: Option<web::Data<AppConfig>>,
+) -> Result<HttpResponse, Error> {
+    check_admin(&request, app_config.as_ref().map(|c| c.get_ref()))?;
+
+    match registry.send(ListWebhooks).await {
+        Ok(webhooks) => Ok(HttpResponse::Ok().json(webhooks)),
+        Err(e) => Err(error::ErrorInternalServerError(format!(
+            "Webhook Registry: Request failed! Message: {:?}",
+            e
+        ))),
+    }
+}
+
+/// Handler to remove a Webhook
+pub async fn remove_webhook(
+    request: HttpRequest,
+    registry: web::Data<Addr<WebhookRegistry>>,
+    app_config: Option<web::Data<AppConfig>>,
+    path: web::Path<usize>,
+) -> Result<HttpResponse, Error> {
+    check_admin(&request, app_config.as_ref().map(|c| c.get_ref()))?;
+
+    match registry.send(RemoveWebhook { id: *path }).await {
+        Ok(true) => Ok(HttpResponse::NoContent().finish()),
+        Ok(false) => Err(error::ErrorNotFound(format!(
+            "Webhook ({}): Webhook does not exist",
+            *path
+        ))),
+        Err(e) => Err(error::ErrorInternalServerError(format!(
+            "Webhook Registry: Request failed! Message: {:?}",
+            e
+        ))),
+    }
+}
+
+/// Handler to list the Delivery Log of a Webhook
+pub async fn list_deliveries(
+    request: HttpRequest,
+    registry: web::Data<Addr<WebhookRegistry>>,
+    app_config: Option<web::Data<AppConfig>>,
+    path: web::Path<usize>,
+) -> Result<HttpResponse, Error> {
+    check_admin(&request, app_config.as_ref().map(|c| c.get_ref()))?;
+
+    match registry
+        .send(ListDeliveries {
+            webhook_id: Some(*path),
+        })
+        .await
+    {
+        Ok(deliveries) => Ok(HttpResponse::Ok().json(deliveries)),
+        Err(e) => Err(error::ErrorInternalServerError(format!(
+            "Webhook Registry: Request failed! Message: {:?}",
+            e
+        ))),
+    }
+}
+
+//==============================================================================
+// Auxiliary Functions
+
+/// Sign a Callback Payload with HMAC-SHA256.
+///
+/// # Returns:
+/// - The Signature as it is sent in the `X-Webhook-Signature` Header: `sha256=<hex digest>`
+///
+pub fn sign_payload(secret: &str, payload: &[u8]) -> String {
+    let mut mac =
+        HmacSha256::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any size");
+
+    mac.update(payload);
+
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+/// Check that a Callback URL does not target this Host or a private Network.
+///
+/// Host Names are resolved and all their Addresses must be public.
+pub fn check_target_url(url: &str) -> Result<(), WebhookError> {
+    let invalid = |report: String| WebhookError {
+        status: "invalid".to_owned(),
+        report,
+    };
+    let uri = url
+        .parse::<Uri>()
+        .map_err(|e| invalid(format!("Webhook: URL '{}' is invalid: {}", url, e)))?;
+    let host = uri
+        .host()
+        .map(|h| h.trim_start_matches('[').trim_end_matches(']'))
+        .ok_or_else(|| invalid(format!("Webhook: URL '{}' has no Host", url)))?;
+    let port = match (uri.port_u16(), uri.scheme_str()) {
+        (Some(p), _) => p,
+        (None, Some("https")) => 443,
+        (None, _) => 80,
+    };
+    let addresses: Vec<IpAddr> = match host.parse::<IpAddr>() {
+        Ok(a) => vec![a],
+        Err(_) => (host, port)
+            .to_socket_addrs()
+            .map_err(|e| {
+                invalid(format!(
+                    "Webhook: Host '{}' cannot be resolved: {}",
+                    host, e
+                ))
+            })?
+            .map(|a| a.ip())
+            .collect(),
+    };
+
+    if addresses.is_empty() || !addresses.iter().all(is_public_address) {
+        return Err(WebhookError {
+            status: "forbidden".to_owned(),
+            report: format!(
+                "Webhook: Host '{}' is not a public Address of another Host",
+                host
+            ),
+        });
+    }
+
+    Ok(())
+}
+
+/// Check whether an IP Address is reachable in the public Internet.
+fn is_public_address(address: &IpAddr) -> bool {
+    match address {
+        IpAddr::V4(a) => {
+            let octets = a.octets();
+
+            !(a.is_loopback()
+                || a.is_private()
+                || a.is_link_local()
+                || a.is_unspecified()
+                || a.is_broadcast()
+                || a.is_multicast()
+                || a.is_documentation()
+                || octets[0] == 0
+                // Shared Address Space 100.64.0.0/10
+                || (octets[0] == 100 && (octets[1] & 0xc0) == 64))
+        }
+        IpAddr::V6(a) => match a.to_ipv4_mapped() {
+            Some(v4) => is_public_address(&IpAddr::V4(v4)),
+            None => {
+                let segment = a.segments()[0];
+
+                !(a.is_loopback()
+                    || a.is_unspecified()
+                    || a.is_multicast()
+                    // Unique Local Addresses fc00::/7
+                    || (segment & 0xfe00) == 0xfc00
+                    // Link Local Addresses fe80::/10
+                    || (segment & 0xffc0) == 0xfe80)
+            }
+        },
+    }
+}
+
+fn matches_address<'a>(
+    webhook: &Webhook,
+    mut transactions: impl Iterator<Item = &'a Transaction>,
+) -> bool {
+    match webhook.address.as_ref() {
+        Some(a) => transactions.any(|t| &t.sender == a || &t.receiver == a),
+        None => true,
+    }
+}
+
+fn unix_timestamp() -> u64 {
+    match SystemTime::now().duration_since(SystemTime::UNIX_EPOCH) {
+        Ok(n) => n.as_secs(),
+        Err(_) => 0,
+    }
+}
+
+//==============================================================================
+// Unit Tests
+
+#[test]
+fn sign_payload_hmac_sha256() {
+    //-------------------------------------
+    // RFC 4231 Test Case 2
+
+    let signature = sign_payload("Jefe", b"what do ya want for nothing?");
+
+    assert_eq!(
+        signature,
+        "sha256=5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+    );
+}
+
+#[test]
+fn webhook_target_urls() {
+    assert!(check_target_url("https://93.184.215.14/paid").is_ok());
+    assert!(check_target_url("http://[2606:4700::1111]:8080/paid").is_ok());
+
+    for url in [
+        "http://127.0.0.1:3100/replace_chain",
+        "http://10.1.2.3/",
+        "http://172.16.0.1/",
+        "http://192.168.1.1/",
+        "http://169.254.169.254/latest/meta-data",
+        "http://100.64.0.1/",
+        "http://0.0.0.0/",
+        "http://[::1]/",
+        "http://[fd00::1]/",
+        "http://[::ffff:127.0.0.1]/",
+        "http://localhost:3100/",
+    ] {
+        assert_eq!(
+            check_target_url(url).map_err(|e| e.status),
+            Err(String::from("forbidden")),
+            "{}",
+            url
+        );
+    }
+}
@@ -1,17 +1,76 @@
 #[cfg(test)]
 mod tests {
     use actix::sync::SyncArbiter;
+    use actix::Actor;
     use actix_web::{http::header::ContentType, test, web, App};
 
     use std::sync::Mutex;
 
+    use blockchain_api::config::AppConfig;
+    use blockchain_api::consensus::poa::ValidatorVote;
+    use blockchain_api::consensus::seal::{self, DoubleSignEvidence};
+    use blockchain_api::consensus::{AuthorityParams, ConsensusMode};
+    use blockchain_api::events::sse::dispatch_mining_events;
+    use blockchain_api::events::websocket::dispatch_event_subscription;
+    use blockchain_api::events::EventHub;
     use blockchain_api::miner::{MinerLink, MiningResponse, MiningWorker};
-    use blockchain_api::model::blockchain::Blockchain;
-    use blockchain_api::model::transaction::{MutexTransactionList, Transaction};
+    use blockchain_api::model::address;
+    use blockchain_api::model::asset::{Asset, AssetDefinition};
+    use blockchain_api::model::blockchain::{Block, Blockchain, LedgerMode};
+    use blockchain_api::model::htlc::{self, Htlc};
+    use blockchain_api::model::stake::StakeAction;
+    use blockchain_api::model::transaction::{
+        MultisigPolicy, MutexTransactionList, PartialSignature, Transaction, COINBASE_SENDER,
+    };
+    use blockchain_api::model::utxo::{OutPoint, TxOutput};
+    use blockchain_api::model::wire;
+    use blockchain_api::multisig::{self, DraftData, MultisigAddressData, MultisigDrafts};
+    use blockchain_api::rpc::{dispatch_rpc_request, RpcResponse};
+    use blockchain_api::wallet::{KdfParams, Keystore};
+    use blockchain_api::webhooks::{
+        self, DeliveryRecord, WebhookRegistration, WebhookRegistry, SIGNATURE_HEADER,
+    };
     use blockchain_api::{
-        add_transaction, dispatch_home_page, dispatch_mining_request, ResponseData,
+        add_transaction, dispatch_address_outputs, dispatch_address_transactions, dispatch_asset,
+        dispatch_assets, dispatch_block, dispatch_chain, dispatch_home_page,
+        dispatch_mining_request, dispatch_staking, dispatch_transaction,
+        dispatch_transaction_status, dispatch_validators, propose_validator, replace_chain,
+        report_evidence, AssetData, ResponseData, StakingData, TransactionResponseData,
+        TransactionStatusData, UnspentOutputData, ValidatorData,
     };
 
+    /// Build a Chain whose Genesis Block grants the Amount to the Address
+    fn build_funded_chain(address: &str, amount: f64) -> Blockchain {
+        let transactions = web::Data::new(MutexTransactionList::new());
+        let mut chain = Blockchain::with_difficulty(1);
+
+        transactions
+            .add_transaction(chain.build_reward(address, amount))
+            .unwrap();
+        chain.seal_block(&transactions).unwrap();
+
+        chain
+    }
+
+    /// Build a signed Payment from the Address of the Key
+    fn build_payment(
+        signing_key: &ed25519_dalek::SigningKey,
+        receiver: &str,
+        amount: f64,
+        nonce: u64,
+    ) -> Transaction {
+        let mut transaction = Transaction::from_key(
+            &signing_key.verifying_key(),
+            receiver.to_owned(),
+            amount,
+            nonce,
+        );
+
+        transaction.sign(signing_key).unwrap();
+
+        transaction
+    }
+
     #[actix_rt::test]
     async fn test_home() {
         let mut app =
@@ -34,20 +93,46 @@ mod tests {
 
     #[actix_rt::test]
     async fn test_add_transaction() {
+        use ed25519_dalek::SigningKey;
+
+        let signing_key = SigningKey::from_bytes(&[9u8; 32]);
+        let sender = address::from_public_key(&signing_key.verifying_key());
+        let blockchain = web::Data::new(Mutex::new(build_funded_chain(&sender, 20.0)));
         let transactions = web::Data::new(MutexTransactionList::new());
 
         let mut app = test::init_service(
             App::new()
+                .app_data(blockchain.clone())
                 .app_data(transactions.clone())
                 .route("/add_transaction", web::post().to(add_transaction)),
         )
         .await;
 
-        let transaction = Transaction {
-            sender: String::from("sender1"),
-            receiver: String::from("receiver1"),
-            amount: 11.1317f64,
-        };
+        //-------------------------------------
+        // Unsigned Transactions and Mining Rewards are refused
+
+        for sender in ["sender1", COINBASE_SENDER, sender.as_str()] {
+            let req = test::TestRequest::post()
+                .uri("/add_transaction")
+                .set_json(&Transaction {
+                    sender: sender.to_owned(),
+                    receiver: String::from("16Jswqk47s9PUcyCc88MMVwzgvHPvtEpf"),
+                    amount: 11.1317f64,
+                    nonce: 1,
+                    ..Default::default()
+                })
+                .to_request();
+            let resp = test::call_service(&mut app, req).await;
+
+            assert_eq!(resp.status().as_u16(), 400);
+        }
+
+        let transaction = build_payment(
+            &signing_key,
+            "16Jswqk47s9PUcyCc88MMVwzgvHPvtEpf",
+            11.1317f64,
+            1,
+        );
 
         let req = test::TestRequest::post()
             .uri("/add_transaction")
@@ -66,46 +151,1946 @@ mod tests {
 
         assert_eq!(response.page.as_str(), "Add Transaction");
         assert_eq!(response.statuscode, 201);
+
+        //-------------------------------------
+        // Used Nonces and Overdrafts are refused
+
+        for (amount, nonce) in [(1.0, 1), (10.0, 2)] {
+            let req = test::TestRequest::post()
+                .uri("/add_transaction")
+                .set_json(&build_payment(
+                    &signing_key,
+                    "16Jswqk47s9PUcyCc88MMVwzgvHPvtEpf",
+                    amount,
+                    nonce,
+                ))
+                .to_request();
+            let resp = test::call_service(&mut app, req).await;
+
+            assert_eq!(resp.status().as_u16(), 400);
+        }
+
+        assert_eq!(transactions.get_count(), 1);
     }
 
     #[actix_rt::test]
-    async fn test_mining() {
-        let blockchain = web::Data::new(Mutex::new(Blockchain::new()));
+    async fn test_add_signed_transaction() {
+        //-------------------------------------
+        // Sign the Transaction with a Key from the Keystore as the Wallet does
+
+        let mut keystore = Keystore::with_kdf(KdfParams {
+            memory_cost: 64,
+            time_cost: 1,
+            parallelism: 1,
+        });
+        let address = keystore
+            .generate_key("main", "secret")
+            .unwrap()
+            .address
+            .clone();
+        let signing_key = keystore.unlock("main", "secret").unwrap();
+        let blockchain = web::Data::new(Mutex::new(build_funded_chain(&address, 10.0)));
         let transactions = web::Data::new(MutexTransactionList::new());
 
-        //Clone the Blockchain and the Transaction Vector for the Mining Worker
-        let worker_blockchain = blockchain.clone();
-        let worker_transactions = transactions.clone();
+        let mut app = test::init_service(
+            App::new()
+                .app_data(blockchain.clone())
+                .app_data(transactions.clone())
+                .route("/add_transaction", web::post().to(add_transaction)),
+        )
+        .await;
 
-        //Create 1 Mining Worker Instances
-        let miner = SyncArbiter::start(1, move || {
-            // Each Worker needs a copy of the reference to the Blockchain Data and
-            // the Transaction Vector
-            MiningWorker::with_data(worker_blockchain.clone(), worker_transactions.clone())
-        });
-        //Create 1 Mining Link Object
-        let link = MinerLink::new(miner);
+        let mut transaction = Transaction::from_key(
+            &signing_key.verifying_key(),
+            String::from("16Jswqk47s9PUcyCc88MMVwzgvHPvtEpf"),
+            3.5,
+            1,
+        );
+
+        transaction.sign(&signing_key).unwrap();
+
+        assert_eq!(transaction.sender, address);
+
+        let req = test::TestRequest::post()
+            .uri("/add_transaction")
+            .set_json(&transaction)
+            .to_request();
+
+        let resp = test::call_service(&mut app, req).await;
+
+        assert_eq!(resp.status().as_u16(), 201);
+
+        //-------------------------------------
+        // A changed Transaction does not match the Signature
+
+        transaction.amount = 35.0;
+
+        let req = test::TestRequest::post()
+            .uri("/add_transaction")
+            .set_json(&transaction)
+            .to_request();
+
+        let resp = test::call_service(&mut app, req).await;
+
+        assert_eq!(resp.status().as_u16(), 400);
+        assert_eq!(transactions.get_count(), 1);
+    }
+
+    #[actix_rt::test]
+    async fn test_add_utxo_transaction() {
+        use ed25519_dalek::SigningKey;
+
+        let signing_key = SigningKey::from_bytes(&[1u8; 32]);
+        let owner = address::from_public_key(&signing_key.verifying_key());
+        let mut chain = Blockchain::with_ledger_mode(LedgerMode::Utxo);
+        let mut config = AppConfig::new();
+
+        //-------------------------------------
+        // Mine the Reward which is spent afterwards
+
+        let pending = web::Data::new(MutexTransactionList::new());
+
+        chain.difficulty = 1;
+        chain.set_reward_address(&owner);
+        chain.seal_block(&pending).unwrap();
+        config.ledger_mode = LedgerMode::Utxo;
+
+        let reward = OutPoint {
+            txid: chain.chain[0].transactions[0].to_hash().unwrap(),
+            output: 0,
+        };
+        let blockchain = web::Data::new(Mutex::new(chain));
+        let transactions = web::Data::new(MutexTransactionList::new());
 
         let mut app = test::init_service(
             App::new()
                 .app_data(blockchain.clone())
                 .app_data(transactions.clone())
-                .app_data(web::Data::new(link.clone()))
-                .route("/mine_block", web::get().to(dispatch_mining_request)),
+                .app_data(web::Data::new(config))
+                .route("/add_transaction", web::post().to(add_transaction))
+                .route(
+                    "/addresses/{address}/outputs",
+                    web::get().to(dispatch_address_outputs),
+                ),
         )
         .await;
 
-        let req = test::TestRequest::get().uri("/mine_block").to_request();
+        let req = test::TestRequest::get()
+            .uri(&format!("/addresses/{}/outputs", owner))
+            .to_request();
+
         let resp = test::call_service(&mut app, req).await;
+        let outputs: Vec<UnspentOutputData> = test::read_body_json(resp).await;
 
-        println!("mining hdrs: '{:?}'", resp);
+        assert_eq!(outputs.len(), 1);
+        assert_eq!(outputs[0].outpoint, reward);
+        assert_eq!(outputs[0].output.amount, 10f64);
+
+        let mut payments: Vec<Transaction> = (1..=2u64)
+            .map(|nonce| {
+                Transaction::from_outputs(
+                    std::slice::from_ref(&reward),
+                    vec![TxOutput {
+                        address: String::from("16Jswqk47s9PUcyCc88MMVwzgvHPvtEpf"),
+                        amount: 10.0,
+                    }],
+                    nonce,
+                )
+            })
+            .collect();
+
+        payments
+            .iter_mut()
+            .for_each(|t| t.sign_inputs(&signing_key).unwrap());
+
+        let req = test::TestRequest::post()
+            .uri("/add_transaction")
+            .set_json(&payments[0])
+            .to_request();
+
+        let resp = test::call_service(&mut app, req).await;
+
+        assert_eq!(resp.status().as_u16(), 201);
+
+        //-------------------------------------
+        // The Double Spend and Account Transactions are rejected
+
+        let req = test::TestRequest::post()
+            .uri("/add_transaction")
+            .set_json(&payments[1])
+            .to_request();
+
+        let resp = test::call_service(&mut app, req).await;
+
+        assert_eq!(resp.status().as_u16(), 400);
+
+        let req = test::TestRequest::post()
+            .uri("/add_transaction")
+            .set_json(&Transaction::from_data(
+                String::from("sender1"),
+                String::from("16Jswqk47s9PUcyCc88MMVwzgvHPvtEpf"),
+                5.67,
+            ))
+            .to_request();
+
+        let resp = test::call_service(&mut app, req).await;
+
+        assert_eq!(resp.status().as_u16(), 400);
+        assert_eq!(transactions.get_count(), 1);
+    }
+
+    #[actix_rt::test]
+    async fn test_custom_assets() {
+        use ed25519_dalek::SigningKey;
+
+        let signing_key = SigningKey::from_bytes(&[3u8; 32]);
+        let issuer = address::from_public_key(&signing_key.verifying_key());
+        let receiver = String::from("16Jswqk47s9PUcyCc88MMVwzgvHPvtEpf");
+        let mut issuance = Transaction::from_issuance(
+            &signing_key.verifying_key(),
+            AssetDefinition {
+                symbol: String::from("LOYAL"),
+                decimals: 0,
+            },
+            500.0,
+            1,
+        );
+
+        issuance.sign(&signing_key).unwrap();
+
+        let asset_id = issuance.to_hash().unwrap();
+        let mut chain = Blockchain::new();
+        let pending = web::Data::new(MutexTransactionList::from_vec(vec![issuance]));
+
+        chain.difficulty = 1;
+        chain.seal_block(&pending).unwrap();
+
+        let blockchain = web::Data::new(Mutex::new(chain));
+        let transactions = web::Data::new(MutexTransactionList::new());
+
+        let mut app = test::init_service(
+            App::new()
+                .app_data(blockchain.clone())
+                .app_data(transactions.clone())
+                .route("/add_transaction", web::post().to(add_transaction))
+                .route("/assets", web::get().to(dispatch_assets))
+                .route("/assets/{id}", web::get().to(dispatch_asset)),
+        )
+        .await;
+
+        let req = test::TestRequest::get().uri("/assets").to_request();
+
+        let resp = test::call_service(&mut app, req).await;
+        let assets: Vec<Asset> = test::read_body_json(resp).await;
+
+        assert_eq!(assets.len(), 1);
+        assert_eq!(assets[0].id, asset_id);
+        assert_eq!(assets[0].issuer, issuer);
+
+        //-------------------------------------
+        // Transfers beyond the Balance are rejected
+
+        let transfers: Vec<Transaction> = [200.0, 400.0]
+            .iter()
+            .zip(2..)
+            .map(|(amount, nonce)| {
+                let mut t = Transaction::from_key(
+                    &signing_key.verifying_key(),
+                    receiver.clone(),
+                    *amount,
+                    nonce,
+                );
+
+                t.asset = Some(asset_id.clone());
+                t.sign(&signing_key).unwrap();
+
+                t
+            })
+            .collect();
+
+        let req = test::TestRequest::post()
+            .uri("/add_transaction")
+            .set_json(&transfers[0])
+            .to_request();
+
+        let resp = test::call_service(&mut app, req).await;
+
+        assert_eq!(resp.status().as_u16(), 201);
+
+        let req = test::TestRequest::post()
+            .uri("/add_transaction")
+            .set_json(&transfers[1])
+            .to_request();
+
+        let resp = test::call_service(&mut app, req).await;
+
+        assert_eq!(resp.status().as_u16(), 400);
+
+        blockchain
+            .lock()
+            .unwrap()
+            .seal_block(&transactions)
+            .unwrap();
+
+        let req = test::TestRequest::get()
+            .uri(&format!("/assets/{}", asset_id))
+            .to_request();
+
+        let resp = test::call_service(&mut app, req).await;
+        let asset: AssetData = test::read_body_json(resp).await;
+
+        assert_eq!(asset.asset.symbol, "LOYAL");
+        assert_eq!(asset.holders.len(), 2);
+        assert_eq!(asset.holders[0].address, issuer);
+        assert_eq!(asset.holders[0].balance, 300f64);
+        assert_eq!(asset.holders[1].balance, 200f64);
+
+        let req = test::TestRequest::get().uri("/assets/unknown").to_request();
+
+        let resp = test::call_service(&mut app, req).await;
+
+        assert_eq!(resp.status().as_u16(), 404);
+    }
+
+    #[actix_rt::test]
+    async fn test_htlc_swap() {
+        use ed25519_dalek::SigningKey;
+
+        let alice_key = SigningKey::from_bytes(&[11u8; 32]);
+        let bob_key = SigningKey::from_bytes(&[12u8; 32]);
+        let alice = address::from_public_key(&alice_key.verifying_key());
+        let bob = address::from_public_key(&bob_key.verifying_key());
+        let mut config = AppConfig::new();
+
+        config.ledger_mode = LedgerMode::Utxo;
+
+        //-------------------------------------
+        // Alice owns 10 on Chain A and Bob owns 10 on Chain B
+
+        let mut chains: Vec<Blockchain> = [&alice, &bob]
+            .iter()
+            .map(|owner| {
+                let mut chain = Blockchain::with_ledger_mode(LedgerMode::Utxo);
+                let pending = web::Data::new(MutexTransactionList::new());
+
+                chain.difficulty = 1;
+                chain.set_reward_address(owner);
+                chain.seal_block(&pending).unwrap();
+                chain.set_reward_address("1BcktgV7EjHmxEwQDFFhhztzNqZkd5gdm");
+
+                chain
+            })
+            .collect();
+        let reward_b = OutPoint {
+            txid: chains[1].chain[0].transactions[0].to_hash().unwrap(),
+            output: 0,
+        };
+        let reward_a = OutPoint {
+            txid: chains[0].chain[0].transactions[0].to_hash().unwrap(),
+            output: 0,
+        };
+        let chain_b = web::Data::new(Mutex::new(chains.pop().unwrap()));
+        let chain_a = web::Data::new(Mutex::new(chains.pop().unwrap()));
+        let pending_a = web::Data::new(MutexTransactionList::new());
+        let pending_b = web::Data::new(MutexTransactionList::new());
+
+        let mut app_a = test::init_service(
+            App::new()
+                .app_data(chain_a.clone())
+                .app_data(pending_a.clone())
+                .app_data(web::Data::new(config.clone()))
+                .route("/add_transaction", web::post().to(add_transaction)),
+        )
+        .await;
+        let mut app_b = test::init_service(
+            App::new()
+                .app_data(chain_b.clone())
+                .app_data(pending_b.clone())
+                .app_data(web::Data::new(config))
+                .route("/add_transaction", web::post().to(add_transaction)),
+        )
+        .await;
+
+        //-------------------------------------
+        // Alice locks 6 for Bob on Chain A. Bob locks 8 for Alice on Chain B
+        // with the same Hash Lock and an earlier Timeout.
+
+        let secret = b"atomic swap secret";
+        let htlc_a = Htlc::new(
+            &htlc::to_hash_lock(secret),
+            &bob_key.verifying_key(),
+            &alice_key.verifying_key(),
+            10,
+        );
+        let htlc_b = Htlc::new(
+            &htlc_a.hash_lock,
+            &alice_key.verifying_key(),
+            &bob_key.verifying_key(),
+            5,
+        );
+        let mut lock_a = Transaction::from_outputs(
+            &[reward_a],
+            vec![
+                TxOutput {
+                    address: htlc_a.to_address(),
+                    amount: 6.0,
+                },
+                TxOutput {
+                    address: alice.clone(),
+                    amount: 4.0,
+                },
+            ],
+            1,
+        );
+        let mut lock_b = Transaction::from_outputs(
+            &[reward_b],
+            vec![
+                TxOutput {
+                    address: htlc_b.to_address(),
+                    amount: 8.0,
+                },
+                TxOutput {
+                    address: bob.clone(),
+                    amount: 2.0,
+                },
+            ],
+            1,
+        );
+
+        lock_a.sign_inputs(&alice_key).unwrap();
+        lock_b.sign_inputs(&bob_key).unwrap();
+
+        let req = test::TestRequest::post()
+            .uri("/add_transaction")
+            .set_json(&lock_a)
+            .to_request();
+
+        assert_eq!(
+            test::call_service(&mut app_a, req).await.status().as_u16(),
+            201
+        );
+
+        let req = test::TestRequest::post()
+            .uri("/add_transaction")
+            .set_json(&lock_b)
+            .to_request();
+
+        assert_eq!(
+            test::call_service(&mut app_b, req).await.status().as_u16(),
+            201
+        );
+
+        chain_a.lock().unwrap().seal_block(&pending_a).unwrap();
+        chain_b.lock().unwrap().seal_block(&pending_b).unwrap();
+
+        let locked_a = OutPoint {
+            txid: lock_a.to_hash().unwrap(),
+            output: 0,
+        };
+        let locked_b = OutPoint {
+            txid: lock_b.to_hash().unwrap(),
+            output: 0,
+        };
+
+        //-------------------------------------
+        // Bob cannot take his Funds back before the Timeout
+
+        let mut refund_b = Transaction::from_htlc_output(
+            &htlc_b,
+            &locked_b,
+            vec![TxOutput {
+                address: bob.clone(),
+                amount: 8.0,
+            }],
+            2,
+        );
+
+        refund_b.refund_htlc(&bob_key).unwrap();
+
+        let req = test::TestRequest::post()
+            .uri("/add_transaction")
+            .set_json(&refund_b)
+            .to_request();
+
+        assert_eq!(
+            test::call_service(&mut app_b, req).await.status().as_u16(),
+            400
+        );
+
+        //-------------------------------------
+        // Alice claims on Chain B and reveals the Secret
+
+        let mut claim_b = Transaction::from_htlc_output(
+            &htlc_b,
+            &locked_b,
+            vec![TxOutput {
+                address: alice.clone(),
+                amount: 8.0,
+            }],
+            3,
+        );
+
+        claim_b.claim_htlc(secret, &alice_key).unwrap();
+
+        let req = test::TestRequest::post()
+            .uri("/add_transaction")
+            .set_json(&claim_b)
+            .to_request();
+
+        assert_eq!(
+            test::call_service(&mut app_b, req).await.status().as_u16(),
+            201
+        );
+
+        chain_b.lock().unwrap().seal_block(&pending_b).unwrap();
+
+        //-------------------------------------
+        // Bob learns the Secret from Chain B and claims on Chain A
+
+        let revealed = chain_b.lock().unwrap().find_htlc_secret(&htlc_b).unwrap();
+        let mut claim_a = Transaction::from_htlc_output(
+            &htlc_a,
+            &locked_a,
+            vec![TxOutput {
+                address: bob.clone(),
+                amount: 6.0,
+            }],
+            4,
+        );
+
+        assert!(chain_a.lock().unwrap().find_htlc_secret(&htlc_a).is_none());
+
+        claim_a.claim_htlc(&revealed, &bob_key).unwrap();
+
+        let req = test::TestRequest::post()
+            .uri("/add_transaction")
+            .set_json(&claim_a)
+            .to_request();
+
+        assert_eq!(
+            test::call_service(&mut app_a, req).await.status().as_u16(),
+            201
+        );
+
+        chain_a.lock().unwrap().seal_block(&pending_a).unwrap();
+
+        let chain_a = chain_a.lock().unwrap();
+        let chain_b = chain_b.lock().unwrap();
+
+        assert_eq!(chain_a.get_balance(&alice), 4f64);
+        assert_eq!(chain_a.get_balance(&bob), 6f64);
+        assert_eq!(chain_b.get_balance(&alice), 8f64);
+        assert_eq!(chain_b.get_balance(&bob), 2f64);
+        assert!(chain_a.get_unspent(&htlc_a.to_address()).is_empty());
+        assert!(chain_b.get_unspent(&htlc_b.to_address()).is_empty());
+    }
+
+    #[actix_rt::test]
+    async fn test_validator_votes() {
+        use ed25519_dalek::SigningKey;
+
+        let validator_key = SigningKey::from_bytes(&[5u8; 32]);
+        let validator = hex::encode(validator_key.verifying_key().as_bytes());
+        let candidate = hex::encode(
+            SigningKey::from_bytes(&[6u8; 32])
+                .verifying_key()
+                .as_bytes(),
+        );
+        let mut chain = Blockchain::new();
+
+        chain.consensus = ConsensusMode::ProofOfAuthority;
+        chain.authority = AuthorityParams {
+            validators: vec![validator.clone()],
+            block_interval: u32::MAX,
+        };
+        chain.set_validator_key(Some(validator_key));
+
+        let blockchain = web::Data::new(Mutex::new(chain));
+
+        let mut app = test::init_service(
+            App::new()
+                .app_data(blockchain.clone())
+                .route("/validators", web::get().to(dispatch_validators))
+                .route("/validators/proposals", web::post().to(propose_validator)),
+        )
+        .await;
+
+        let req = test::TestRequest::get().uri("/validators").to_request();
+        let resp = test::call_service(&mut app, req).await;
+        let data: ValidatorData = test::read_body_json(resp).await;
+
+        assert_eq!(data.validators, vec![validator.clone()]);
+        assert_eq!(data.block_interval, u32::MAX);
+        assert!(data.proposals.is_empty());
+
+        //-------------------------------------
+        // Only local Administrators can propose and only Public Keys
+
+        let req = test::TestRequest::post()
+            .uri("/validators/proposals")
+            .peer_addr("10.0.0.2:4000".parse().unwrap())
+            .set_json(&ValidatorVote {
+                validator: candidate.clone(),
+                add: true,
+            })
+            .to_request();
+        let resp = test::call_service(&mut app, req).await;
+
+        assert_eq!(resp.status().as_u16(), 403);
+
+        let req = test::TestRequest::post()
+            .uri("/validators/proposals")
+            .peer_addr("127.0.0.1:4000".parse().unwrap())
+            .set_json(&ValidatorVote {
+                validator: String::from("not a key"),
+                add: true,
+            })
+            .to_request();
+        let resp = test::call_service(&mut app, req).await;
+
+        assert_eq!(resp.status().as_u16(), 400);
+
+        let req = test::TestRequest::post()
+            .uri("/validators/proposals")
+            .peer_addr("127.0.0.1:4000".parse().unwrap())
+            .set_json(&ValidatorVote {
+                validator: candidate.clone(),
+                add: true,
+            })
+            .to_request();
+        let resp = test::call_service(&mut app, req).await;
 
         assert!(resp.status().is_success());
 
-        let response: MiningResponse = test::read_body_json(resp).await;
+        //-------------------------------------
+        // The only Validator passes the Vote with its next Block
 
-        println!("send bdy: '{:?}'", response);
+        blockchain
+            .lock()
+            .unwrap()
+            .seal_block(&web::Data::new(MutexTransactionList::new()))
+            .unwrap();
 
-        assert_eq!(response.status.as_str(), "success");
+        let req = test::TestRequest::get().uri("/validators").to_request();
+        let resp = test::call_service(&mut app, req).await;
+        let data: ValidatorData = test::read_body_json(resp).await;
+
+        assert_eq!(data.validators, vec![validator, candidate.clone()]);
+        assert!(data.tallies.is_empty());
+        assert_eq!(data.proposals.len(), 1);
+        assert_eq!(data.proposals[0].validator, candidate);
+    }
+
+    #[actix_rt::test]
+    async fn test_staking() {
+        use ed25519_dalek::SigningKey;
+
+        let validator_key = SigningKey::from_bytes(&[7u8; 32]);
+        let validator = hex::encode(validator_key.verifying_key().as_bytes());
+        let mut stake =
+            Transaction::from_stake(&validator_key.verifying_key(), StakeAction::Stake, 3.0, 1);
+
+        stake.sign(&validator_key).unwrap();
+
+        let funding =
+            Transaction::from_data(String::from(COINBASE_SENDER), stake.sender.clone(), 5.0);
+        let mut chain = Blockchain::new();
+
+        chain.consensus = ConsensusMode::ProofOfStake;
+        chain.authority = AuthorityParams {
+            validators: vec![validator.clone()],
+            block_interval: 10,
+        };
+        chain.set_validator_key(Some(validator_key.clone()));
+        chain
+            .seal_block(&web::Data::new(MutexTransactionList::from_vec(vec![
+                funding, stake,
+            ])))
+            .unwrap();
+
+        let mut conflicting = chain.chain[0].clone();
+
+        conflicting.transactions.clear();
+        seal::sign_block(&mut conflicting, &validator_key, None);
+
+        let evidence = DoubleSignEvidence::from_blocks(&chain.chain[0], &conflicting).unwrap();
+        let blockchain = web::Data::new(Mutex::new(chain));
+        let transactions = web::Data::new(MutexTransactionList::new());
+
+        let mut app = test::init_service(
+            App::new()
+                .app_data(blockchain.clone())
+                .app_data(transactions.clone())
+                .route("/add_transaction", web::post().to(add_transaction))
+                .route("/staking", web::get().to(dispatch_staking))
+                .route("/staking/evidence", web::post().to(report_evidence)),
+        )
+        .await;
+
+        let req = test::TestRequest::get().uri("/staking").to_request();
+        let resp = test::call_service(&mut app, req).await;
+        let data: StakingData = test::read_body_json(resp).await;
+
+        assert_eq!(data.stakes.len(), 1);
+        assert_eq!(data.stakes[0].public_key, validator);
+        assert_eq!(data.total_stake, 3.0);
+        assert!(data.evidence.is_empty());
+
+        //-------------------------------------
+        // Only staked Amounts can be released
+
+        let unstakes: Vec<Transaction> = [2.0, 2.0]
+            .iter()
+            .zip(2..)
+            .map(|(amount, nonce)| {
+                let mut t = Transaction::from_stake(
+                    &validator_key.verifying_key(),
+                    StakeAction::Unstake,
+                    *amount,
+                    nonce,
+                );
+
+                t.sign(&validator_key).unwrap();
+
+                t
+            })
+            .collect();
+
+        let req = test::TestRequest::post()
+            .uri("/add_transaction")
+            .set_json(&unstakes[0])
+            .to_request();
+        let resp = test::call_service(&mut app, req).await;
+
+        assert_eq!(resp.status().as_u16(), 201);
+
+        // The pending Unstake already claims most of the Stake
+        let req = test::TestRequest::post()
+            .uri("/add_transaction")
+            .set_json(&unstakes[1])
+            .to_request();
+        let resp = test::call_service(&mut app, req).await;
+
+        assert_eq!(resp.status().as_u16(), 400);
+
+        //-------------------------------------
+        // Double Signatures are reported in the next Block
+
+        let mut forged = evidence.clone();
+
+        forged.second = forged.first.clone();
+
+        // Only local Administrators can report Evidence
+        let req = test::TestRequest::post()
+            .uri("/staking/evidence")
+            .peer_addr("10.0.0.2:4000".parse().unwrap())
+            .set_json(&evidence)
+            .to_request();
+        let resp = test::call_service(&mut app, req).await;
+
+        assert_eq!(resp.status().as_u16(), 403);
+
+        let req = test::TestRequest::post()
+            .uri("/staking/evidence")
+            .peer_addr("127.0.0.1:4000".parse().unwrap())
+            .set_json(&forged)
+            .to_request();
+        let resp = test::call_service(&mut app, req).await;
+
+        assert_eq!(resp.status().as_u16(), 400);
+
+        // Headers with a forged Signature are no Evidence
+        let mut unsigned = evidence.clone();
+
+        unsigned.second.signature = unsigned.first.signature.clone();
+
+        let req = test::TestRequest::post()
+            .uri("/staking/evidence")
+            .peer_addr("127.0.0.1:4000".parse().unwrap())
+            .set_json(&unsigned)
+            .to_request();
+        let resp = test::call_service(&mut app, req).await;
+
+        assert_eq!(resp.status().as_u16(), 400);
+
+        let req = test::TestRequest::post()
+            .uri("/staking/evidence")
+            .peer_addr("127.0.0.1:4000".parse().unwrap())
+            .set_json(&evidence)
+            .to_request();
+        let resp = test::call_service(&mut app, req).await;
+
+        assert!(resp.status().is_success());
+
+        let req = test::TestRequest::get().uri("/staking").to_request();
+        let resp = test::call_service(&mut app, req).await;
+        let data: StakingData = test::read_body_json(resp).await;
+
+        assert_eq!(data.evidence, vec![evidence]);
+    }
+
+    #[actix_rt::test]
+    async fn test_multisig_draft() {
+        use ed25519_dalek::{Signer, SigningKey};
+
+        let keys: Vec<SigningKey> = (1..=3u8)
+            .map(|i| SigningKey::from_bytes(&[i; 32]))
+            .collect();
+        let policy = MultisigPolicy::new(
+            2,
+            &keys.iter().map(|k| k.verifying_key()).collect::<Vec<_>>(),
+        );
+        let blockchain = web::Data::new(Mutex::new(build_funded_chain(
+            &policy.to_address().unwrap(),
+            10.0,
+        )));
+        let transactions = web::Data::new(MutexTransactionList::new());
+        let drafts = web::Data::new(MultisigDrafts::new());
+
+        let mut app = test::init_service(
+            App::new()
+                .app_data(blockchain.clone())
+                .app_data(transactions.clone())
+                .app_data(drafts.clone())
+                .route(
+                    "/multisig/addresses",
+                    web::post().to(multisig::create_multisig_address),
+                )
+                .route("/multisig/drafts", web::post().to(multisig::create_draft))
+                .route("/multisig/drafts/{id}", web::get().to(multisig::get_draft))
+                .route(
+                    "/multisig/drafts/{id}/signatures",
+                    web::post().to(multisig::add_draft_signature),
+                ),
+        )
+        .await;
+
+        //-------------------------------------
+        // Derive the 2-of-3 Address
+
+        let req = test::TestRequest::post()
+            .uri("/multisig/addresses")
+            .set_json(&policy)
+            .to_request();
+
+        let resp = test::call_service(&mut app, req).await;
+
+        assert_eq!(resp.status().as_u16(), 200);
+
+        let multisig_address: MultisigAddressData = test::read_body_json(resp).await;
+
+        assert!(address::is_multisig_address(&multisig_address.address));
+
+        //-------------------------------------
+        // A Draft without Signatures cannot be submitted directly
+
+        let draft = Transaction::from_multisig(
+            policy.clone(),
+            String::from("16Jswqk47s9PUcyCc88MMVwzgvHPvtEpf"),
+            4.5,
+            1,
+        );
+
+        assert_eq!(draft.sender, multisig_address.address);
+        assert!(!draft.is_valid());
+
+        let req = test::TestRequest::post()
+            .uri("/multisig/drafts")
+            .set_json(&draft)
+            .to_request();
+
+        let resp = test::call_service(&mut app, req).await;
+
+        assert_eq!(resp.status().as_u16(), 201);
+
+        let created: DraftData = test::read_body_json(resp).await;
+
+        assert_eq!((created.signature_count, created.threshold), (0, 2));
+        assert!(!created.complete);
+
+        //-------------------------------------
+        // Each Signer signs the Signing Payload of the Draft
+
+        let payload = hex::decode(&created.signing_payload).unwrap();
+        let signatures: Vec<PartialSignature> = keys
+            .iter()
+            .map(|k| PartialSignature {
+                public_key: hex::encode(k.verifying_key().as_bytes()),
+                signature: hex::encode(k.sign(&payload).to_bytes()),
+            })
+            .collect();
+        let uri = format!("/multisig/drafts/{}/signatures", created.id);
+
+        let req = test::TestRequest::post()
+            .uri(&uri)
+            .set_json(&PartialSignature {
+                public_key: signatures[0].public_key.clone(),
+                signature: signatures[1].signature.clone(),
+            })
+            .to_request();
+
+        let resp = test::call_service(&mut app, req).await;
+
+        assert_eq!(resp.status().as_u16(), 400);
+
+        let req = test::TestRequest::post()
+            .uri(&uri)
+            .set_json(&signatures[0])
+            .to_request();
+
+        let resp = test::call_service(&mut app, req).await;
+
+        assert_eq!(resp.status().as_u16(), 200);
+
+        let req = test::TestRequest::get()
+            .uri(&format!("/multisig/drafts/{}", created.id))
+            .to_request();
+
+        let resp = test::call_service(&mut app, req).await;
+        let pending: DraftData = test::read_body_json(resp).await;
+
+        assert_eq!(pending.signature_count, 1);
+        assert_eq!(transactions.get_count(), 0);
+
+        //-------------------------------------
+        // Posting the Draft again keeps its Signatures
+
+        let req = test::TestRequest::post()
+            .uri("/multisig/drafts")
+            .set_json(&draft)
+            .to_request();
+
+        let resp = test::call_service(&mut app, req).await;
+        let reposted: DraftData = test::read_body_json(resp).await;
+
+        assert_eq!(reposted.id, created.id);
+        assert_eq!(reposted.signature_count, 1);
+
+        //-------------------------------------
+        // The second Signature completes the Draft and queues the Transaction
+
+        let req = test::TestRequest::post()
+            .uri(&uri)
+            .set_json(&signatures[2])
+            .to_request();
+
+        let resp = test::call_service(&mut app, req).await;
+
+        assert_eq!(resp.status().as_u16(), 201);
+
+        let completed: DraftData = test::read_body_json(resp).await;
+
+        assert!(completed.complete);
+        assert!(completed.transaction.is_valid());
+        assert_eq!(
+            completed.txid.as_deref(),
+            Some(completed.transaction.to_hash().unwrap().as_str())
+        );
+        assert_eq!(transactions.get_count(), 1);
+        assert_eq!(drafts.get_count(), 0);
+
+        let req = test::TestRequest::post()
+            .uri(&uri)
+            .set_json(&signatures[1])
+            .to_request();
+
+        let resp = test::call_service(&mut app, req).await;
+
+        assert_eq!(resp.status().as_u16(), 404);
+
+        //-------------------------------------
+        // A fully signed Draft is queued right away if the Ledger covers it
+
+        let mut signed = Transaction::from_multisig(
+            policy.clone(),
+            String::from("16Jswqk47s9PUcyCc88MMVwzgvHPvtEpf"),
+            5.0,
+            2,
+        );
+        let payload = signed.to_signing_payload().unwrap();
+
+        for k in keys.iter().take(2) {
+            signed.add_partial_signature(PartialSignature {
+                public_key: hex::encode(k.verifying_key().as_bytes()),
+                signature: hex::encode(k.sign(&payload).to_bytes()),
+            });
+        }
+
+        let req = test::TestRequest::post()
+            .uri("/multisig/drafts")
+            .set_json(&signed)
+            .to_request();
+
+        let resp = test::call_service(&mut app, req).await;
+
+        assert_eq!(resp.status().as_u16(), 201);
+
+        let queued: DraftData = test::read_body_json(resp).await;
+
+        assert!(queued.txid.is_some());
+        assert_eq!(transactions.get_count(), 2);
+        assert_eq!(drafts.get_count(), 0);
+
+        //-------------------------------------
+        // Drafts are checked against the Ledger like any other Transaction
+
+        let mut overdraft = Transaction::from_multisig(
+            policy,
+            String::from("16Jswqk47s9PUcyCc88MMVwzgvHPvtEpf"),
+            5.0,
+            3,
+        );
+        let payload = overdraft.to_signing_payload().unwrap();
+
+        for k in keys.iter().take(2) {
+            overdraft.add_partial_signature(PartialSignature {
+                public_key: hex::encode(k.verifying_key().as_bytes()),
+                signature: hex::encode(k.sign(&payload).to_bytes()),
+            });
+        }
+
+        let req = test::TestRequest::post()
+            .uri("/multisig/drafts")
+            .set_json(&overdraft)
+            .to_request();
+
+        let resp = test::call_service(&mut app, req).await;
+
+        assert_eq!(resp.status().as_u16(), 400);
+        assert_eq!(transactions.get_count(), 2);
+    }
+
+    #[actix_rt::test]
+    async fn test_mining() {
+        let blockchain = web::Data::new(Mutex::new(Blockchain::new()));
+        let transactions = web::Data::new(MutexTransactionList::new());
+
+        //Clone the Blockchain and the Transaction Vector for the Mining Worker
+        let worker_blockchain = blockchain.clone();
+        let worker_transactions = transactions.clone();
+
+        //Create 1 Mining Worker Instances
+        let miner = SyncArbiter::start(1, move || {
+            // Each Worker needs a copy of the reference to the Blockchain Data and
+            // the Transaction Vector
+            MiningWorker::with_data(worker_blockchain.clone(), worker_transactions.clone())
+        });
+        //Create 1 Mining Link Object
+        let link = MinerLink::new(miner);
+
+        let mut app = test::init_service(
+            App::new()
+                .app_data(blockchain.clone())
+                .app_data(transactions.clone())
+                .app_data(web::Data::new(link.clone()))
+                .route("/mine_block", web::get().to(dispatch_mining_request)),
+        )
+        .await;
+
+        let req = test::TestRequest::get().uri("/mine_block").to_request();
+        let resp = test::call_service(&mut app, req).await;
+
+        println!("mining hdrs: '{:?}'", resp);
+
+        assert!(resp.status().is_success());
+
+        let response: MiningResponse = test::read_body_json(resp).await;
+
+        println!("send bdy: '{:?}'", response);
+
+        assert_eq!(response.status.as_str(), "success");
+    }
+
+    #[actix_rt::test]
+    async fn test_rpc_mine_and_query() {
+        let blockchain = web::Data::new(Mutex::new(Blockchain::new()));
+        let transactions = web::Data::new(MutexTransactionList::new());
+
+        let worker_blockchain = blockchain.clone();
+        let worker_transactions = transactions.clone();
+
+        let miner = SyncArbiter::start(1, move || {
+            MiningWorker::with_data(worker_blockchain.clone(), worker_transactions.clone())
+        });
+        let link = MinerLink::new(miner);
+
+        let mut app = test::init_service(
+            App::new()
+                .app_data(blockchain.clone())
+                .app_data(transactions.clone())
+                .app_data(web::Data::new(link.clone()))
+                .route("/rpc", web::post().to(dispatch_rpc_request)),
+        )
+        .await;
+
+        let req = test::TestRequest::post()
+            .uri("/rpc")
+            .set_json(&serde_json::json!({"jsonrpc": "2.0", "method": "mine", "id": 1}))
+            .to_request();
+        let resp = test::call_service(&mut app, req).await;
+
+        assert!(resp.status().is_success());
+
+        let response: RpcResponse = test::read_body_json(resp).await;
+
+        println!("rpc mine bdy: '{:?}'", response);
+
+        assert!(response.error.is_none());
+        assert_eq!(response.id, serde_json::json!(1));
+
+        // Genesis Block and the requested Block
+        let req = test::TestRequest::post()
+            .uri("/rpc")
+            .set_json(&serde_json::json!([
+                {"jsonrpc": "2.0", "method": "getblockcount", "id": "count"},
+                {"jsonrpc": "2.0", "method": "getblockhash", "params": [2], "id": "hash"},
+                {"jsonrpc": "2.0", "method": "getblockchaininfo", "id": "info"},
+                {"jsonrpc": "2.0", "method": "getbalance", "params": [address::BURN_ADDRESS], "id": "balance"},
+                {"jsonrpc": "2.0", "method": "getmempoolinfo"},
+                {"jsonrpc": "2.0", "method": "unknown", "id": 9},
+                {"jsonrpc": "2.0", "method": "getblock", "params": {"index": 2}, "id": null}
+            ]))
+            .to_request();
+        let resp = test::call_service(&mut app, req).await;
+
+        assert!(resp.status().is_success());
+
+        let responses: Vec<RpcResponse> = test::read_body_json(resp).await;
+
+        println!("rpc batch bdy: '{:?}'", responses);
+
+        // The Notification is not answered
+        assert_eq!(responses.len(), 6);
+        assert_eq!(responses[0].result, Some(serde_json::json!(2)));
+
+        let block_hash = responses[1].result.as_ref().unwrap().as_str().unwrap();
+
+        assert!(block_hash.starts_with("0000"));
+        // Each Block at Difficulty 4 is worth 0x10001 Hashes
+        assert_eq!(
+            responses[2].result,
+            Some(serde_json::json!({
+                "blocks": 2,
+                "bestblockhash": block_hash,
+                "bits": "1f00ffff",
+                "chainwork": format!("{:064x}", 0x20002),
+            }))
+        );
+        // The Genesis Reward was mined into the 2nd Block
+        assert_eq!(responses[3].result, Some(serde_json::json!(10.0)));
+        assert_eq!(responses[4].error.as_ref().unwrap().code, -32601);
+        // Named Parameters and a `null` Id
+        assert_eq!(responses[5].id, serde_json::Value::Null);
+        assert_eq!(responses[5].result.as_ref().unwrap()["index"], 2);
+    }
+
+    #[actix_rt::test]
+    async fn test_rpc_send_transaction() {
+        use ed25519_dalek::SigningKey;
+
+        let signing_key = SigningKey::from_bytes(&[10u8; 32]);
+        let sender = address::from_public_key(&signing_key.verifying_key());
+        let blockchain = web::Data::new(Mutex::new(build_funded_chain(&sender, 10.0)));
+        let transactions = web::Data::new(MutexTransactionList::new());
+
+        let worker_blockchain = blockchain.clone();
+        let worker_transactions = transactions.clone();
+
+        let miner = SyncArbiter::start(1, move || {
+            MiningWorker::with_data(worker_blockchain.clone(), worker_transactions.clone())
+        });
+        let link = MinerLink::new(miner);
+
+        let mut app = test::init_service(
+            App::new()
+                .app_data(blockchain.clone())
+                .app_data(transactions.clone())
+                .app_data(web::Data::new(link.clone()))
+                .route("/rpc", web::post().to(dispatch_rpc_request)),
+        )
+        .await;
+
+        let req = test::TestRequest::post()
+            .uri("/rpc")
+            .set_json(&serde_json::json!({
+                "jsonrpc": "2.0",
+                "method": "sendtransaction",
+                "params": [build_payment(&signing_key, "16Jswqk47s9PUcyCc88MMVwzgvHPvtEpf", 5.67, 1)],
+                "id": 7
+            }))
+            .to_request();
+        let resp = test::call_service(&mut app, req).await;
+
+        assert!(resp.status().is_success());
+
+        let response: RpcResponse = test::read_body_json(resp).await;
+
+        println!("rpc send bdy: '{:?}'", response);
+
+        assert!(response.error.is_none());
+
+        // Unsigned Transactions are refused
+        let req = test::TestRequest::post()
+            .uri("/rpc")
+            .set_json(&serde_json::json!({
+                "jsonrpc": "2.0",
+                "method": "sendtransaction",
+                "params": [{"sender": "sender1", "receiver": "16Jswqk47s9PUcyCc88MMVwzgvHPvtEpf", "amount": 5.67}],
+                "id": 8
+            }))
+            .to_request();
+        let response: RpcResponse = test::read_response_json(&mut app, req).await;
+
+        assert_eq!(response.error.unwrap().code, -32602);
+
+        let req = test::TestRequest::post()
+            .uri("/rpc")
+            .set_payload("{\"jsonrpc\": \"2.0\", \"method\"")
+            .to_request();
+        let resp = test::call_service(&mut app, req).await;
+        let response: RpcResponse = test::read_body_json(resp).await;
+
+        assert_eq!(response.error.unwrap().code, -32700);
+    }
+
+    #[actix_rt::test]
+    async fn test_websocket_pending_transaction() {
+        use actix_web_actors::ws;
+        use futures_util::{SinkExt, StreamExt};
+
+        let signing_key = ed25519_dalek::SigningKey::from_bytes(&[13u8; 32]);
+        let sender = address::from_public_key(&signing_key.verifying_key());
+        let blockchain = web::Data::new(Mutex::new(build_funded_chain(&sender, 10.0)));
+        let event_hub = EventHub::new().start();
+
+        let mut srv = test::start(move || {
+            App::new()
+                .app_data(blockchain.clone())
+                .app_data(web::Data::new(MutexTransactionList::new()))
+                .app_data(web::Data::new(event_hub.clone()))
+                .route("/ws", web::get().to(dispatch_event_subscription))
+                .route("/add_transaction", web::post().to(add_transaction))
+        });
+
+        let mut framed = srv.ws_at("/ws").await.unwrap();
+
+        framed
+            .send(ws::Message::Text(
+                r#"{"action":"subscribe","topics":["newPendingTransaction"]}"#.into(),
+            ))
+            .await
+            .unwrap();
+
+        // Subscription Confirmation
+        let frame = framed.next().await.unwrap().unwrap();
+
+        println!("ws frame: '{:?}'", frame);
+
+        assert!(matches!(frame, ws::Frame::Text(_)));
+
+        let resp = srv
+            .post("/add_transaction")
+            .send_json(&build_payment(
+                &signing_key,
+                "16Jswqk47s9PUcyCc88MMVwzgvHPvtEpf",
+                3.5,
+                1,
+            ))
+            .await
+            .unwrap();
+
+        assert!(resp.status().is_success());
+
+        let frame = framed.next().await.unwrap().unwrap();
+
+        println!("ws frame: '{:?}'", frame);
+
+        match frame {
+            ws::Frame::Text(bytes) => {
+                let event: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+
+                assert_eq!(event["topic"], "newPendingTransaction");
+                assert_eq!(event["data"]["sender"], sender.as_str());
+            }
+            _ => panic!("Text Frame expected"),
+        }
+    }
+
+    #[actix_rt::test]
+    async fn test_mining_events() {
+        use futures_util::StreamExt;
+
+        let blockchain = web::Data::new(Mutex::new(Blockchain::new()));
+        let transactions = web::Data::new(MutexTransactionList::new());
+        let event_hub = EventHub::new().start();
+
+        let worker_blockchain = blockchain.clone();
+        let worker_transactions = transactions.clone();
+        let worker_hub = event_hub.clone();
+
+        let miner = SyncArbiter::start(1, move || {
+            let mut worker =
+                MiningWorker::with_data(worker_blockchain.clone(), worker_transactions.clone());
+
+            worker.set_event_hub(worker_hub.clone());
+
+            worker
+        });
+        let link = MinerLink::new(miner);
+
+        let srv = test::start(move || {
+            App::new()
+                .app_data(web::Data::new(event_hub.clone()))
+                .route("/mining/events", web::get().to(dispatch_mining_events))
+        });
+
+        let mut resp = srv.get("/mining/events").send().await.unwrap();
+
+        assert!(resp.status().is_success());
+        assert_eq!(
+            resp.headers().get("content-type").unwrap(),
+            "text/event-stream"
+        );
+
+        // Give the Client the time to subscribe at the Hub
+        actix_rt::time::delay_for(std::time::Duration::from_millis(200)).await;
+
+        let mining = link.mine_block();
+        let mut stream_text = String::new();
+
+        while !stream_text.contains("event: newBlock") {
+            let chunk = resp.next().await.unwrap().unwrap();
+
+            stream_text.push_str(std::str::from_utf8(&chunk).unwrap());
+        }
+
+        println!("sse stream: '{}'", stream_text);
+
+        // The Progress is reported at least once before the Block is complete
+        let progress_position = stream_text.find("event: miningProgress");
+
+        assert!(progress_position.is_some());
+        assert!(progress_position < stream_text.find("event: newBlock"));
+        assert!(stream_text.contains("\"attempts\""));
+        assert!(stream_text.contains("\"previous_hash\""));
+        assert!(mining.await.is_ok());
+    }
+
+    #[actix_rt::test]
+    async fn test_webhook_delivery() {
+        use actix_web::HttpRequest;
+        use blockchain_api::events::ChainEvent;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+        use std::time::Duration;
+
+        //-------------------------------------
+        // Local Stand-in Receiver which fails the first Callback
+
+        let received = Arc::new(Mutex::new(Vec::<(String, web::Bytes)>::new()));
+        let calls = Arc::new(AtomicUsize::new(0));
+        let receiver_received = received.clone();
+        let receiver_calls = calls.clone();
+
+        let receiver = test::start(move || {
+            let received = receiver_received.clone();
+            let calls = receiver_calls.clone();
+
+            App::new().route(
+                "/callback",
+                web::post().to(move |req: HttpRequest, body: web::Bytes| {
+                    let received = received.clone();
+                    let calls = calls.clone();
+
+                    async move {
+                        if calls.fetch_add(1, Ordering::SeqCst) == 0 {
+                            return Ok::<_, actix_web::Error>(
+                                actix_web::HttpResponse::InternalServerError().finish(),
+                            );
+                        }
+
+                        let signature = req
+                            .headers()
+                            .get(SIGNATURE_HEADER)
+                            .and_then(|h| h.to_str().ok())
+                            .unwrap_or_default()
+                            .to_owned();
+
+                        received.lock().unwrap().push((signature, body));
+
+                        Ok(actix_web::HttpResponse::Ok().finish())
+                    }
+                }),
+            )
+        });
+
+        // Callbacks to this Host are rejected by default
+        let rejected = WebhookRegistry::new(None)
+            .start()
+            .send(webhooks::RegisterWebhook(webhooks::WebhookRequest {
+                url: receiver.url("/callback"),
+                event: webhooks::WebhookEvent::BlockMined,
+                address: None,
+                confirmations: None,
+            }))
+            .await
+            .unwrap();
+
+        assert_eq!(rejected.unwrap_err().status, "forbidden");
+
+        let registry = WebhookRegistry::new(None)
+            .with_retry(3, Duration::from_millis(50))
+            .with_private_targets(true)
+            .start();
+
+        let mut app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(registry.clone()))
+                .route("/webhooks", web::post().to(webhooks::register_webhook))
+                .route("/webhooks/{id}", web::delete().to(webhooks::remove_webhook))
+                .route(
+                    "/webhooks/{id}/deliveries",
+                    web::get().to(webhooks::list_deliveries),
+                ),
+        )
+        .await;
+
+        let webhook_request = serde_json::json!({
+            "url": receiver.url("/callback"),
+            "event": "transactionConfirmed",
+            "address": "16Jswqk47s9PUcyCc88MMVwzgvHPvtEpf",
+            "confirmations": 2
+        });
+
+        // Only local Administrators can register Webhooks
+        let req = test::TestRequest::post()
+            .uri("/webhooks")
+            .peer_addr("10.0.0.2:4000".parse().unwrap())
+            .set_json(&webhook_request)
+            .to_request();
+        let resp = test::call_service(&mut app, req).await;
+
+        assert_eq!(resp.status().as_u16(), 403);
+
+        let req = test::TestRequest::post()
+            .uri("/webhooks")
+            .peer_addr("127.0.0.1:4000".parse().unwrap())
+            .set_json(&webhook_request)
+            .to_request();
+        let resp = test::call_service(&mut app, req).await;
+
+        assert_eq!(resp.status().as_u16(), 201);
+
+        let registration: WebhookRegistration = test::read_body_json(resp).await;
+
+        // The Payment to "16Jswqk47s9PUcyCc88MMVwzgvHPvtEpf" is confirmed with the 2nd Block
+        registry.do_send(ChainEvent::NewBlock(Block::build_block(
+            1,
+            0,
+            "0",
+            Some(vec![
+                Transaction::from_data(
+                    "sender1".to_owned(),
+                    "16Jswqk47s9PUcyCc88MMVwzgvHPvtEpf".to_owned(),
+                    4.5,
+                ),
+                Transaction::from_data(
+                    "sender2".to_owned(),
+                    "1BcktgV7EjHmxEwQDFFhhztzNqZkd5gdm".to_owned(),
+                    1.5,
+                ),
+            ]),
+        )));
+        registry.do_send(ChainEvent::NewBlock(Block::build_block(2, 0, "1", None)));
+
+        let mut waited = 0;
+
+        while received.lock().unwrap().is_empty() && waited < 50 {
+            actix_rt::time::delay_for(Duration::from_millis(100)).await;
+            waited += 1;
+        }
+
+        let callbacks = received.lock().unwrap().clone();
+
+        assert_eq!(callbacks.len(), 1);
+        assert_eq!(
+            callbacks[0].0,
+            webhooks::sign_payload(&registration.secret, &callbacks[0].1)
+        );
+
+        let callback: serde_json::Value = serde_json::from_slice(&callbacks[0].1).unwrap();
+
+        println!("webhook callback: '{}'", callback);
+
+        assert_eq!(callback["event"], "transactionConfirmed");
+        assert_eq!(callback["data"]["block_index"], 1);
+        assert_eq!(
+            callback["data"]["transaction"]["receiver"],
+            "16Jswqk47s9PUcyCc88MMVwzgvHPvtEpf"
+        );
+
+        // The Delivery Report arrives after the Response of the Receiver
+        actix_rt::time::delay_for(Duration::from_millis(200)).await;
+
+        let req = test::TestRequest::get()
+            .uri(&format!("/webhooks/{}/deliveries", registration.webhook.id))
+            .peer_addr("10.0.0.2:4000".parse().unwrap())
+            .to_request();
+        let resp = test::call_service(&mut app, req).await;
+
+        assert_eq!(resp.status().as_u16(), 403);
+
+        let req = test::TestRequest::get()
+            .uri(&format!("/webhooks/{}/deliveries", registration.webhook.id))
+            .peer_addr("127.0.0.1:4000".parse().unwrap())
+            .to_request();
+        let deliveries: Vec<DeliveryRecord> = test::read_response_json(&mut app, req).await;
+
+        println!("webhook deliveries: '{:?}'", deliveries);
+
+        assert_eq!(deliveries.len(), 2);
+        assert!(!deliveries[0].success);
+        assert_eq!(deliveries[0].status_code, Some(500));
+        assert!(deliveries[1].success);
+        assert_eq!(deliveries[1].attempt, 2);
+
+        //-------------------------------------
+        // A Reorganisation above the confirmed Block does not report it again
+
+        registry.do_send(ChainEvent::ChainReorg {
+            fork_index: 2,
+            previous_height: 2,
+            height: 3,
+            orphaned_blocks: vec![Block::build_block(2, 0, "1", None)],
+            adopted_blocks: vec![
+                Block::build_block(2, 1, "1", None),
+                Block::build_block(3, 0, "2", None),
+            ],
+        });
+
+        actix_rt::time::delay_for(Duration::from_millis(300)).await;
+
+        assert_eq!(received.lock().unwrap().len(), 1);
+
+        //-------------------------------------
+        // Only local Administrators can remove Webhooks
+
+        let uri = format!("/webhooks/{}", registration.webhook.id);
+        let req = test::TestRequest::delete()
+            .uri(&uri)
+            .peer_addr("10.0.0.2:4000".parse().unwrap())
+            .to_request();
+        let resp = test::call_service(&mut app, req).await;
+
+        assert_eq!(resp.status().as_u16(), 403);
+
+        let req = test::TestRequest::delete()
+            .uri(&uri)
+            .peer_addr("127.0.0.1:4000".parse().unwrap())
+            .to_request();
+        let resp = test::call_service(&mut app, req).await;
+
+        assert_eq!(resp.status().as_u16(), 204);
+    }
+
+    #[actix_rt::test]
+    async fn test_transaction_status() {
+        use blockchain_api::model::index::TransactionStatus;
+
+        let signing_key = ed25519_dalek::SigningKey::from_bytes(&[14u8; 32]);
+        let sender = address::from_public_key(&signing_key.verifying_key());
+        let blockchain = web::Data::new(Mutex::new(build_funded_chain(&sender, 10.0)));
+        let transactions = web::Data::new(MutexTransactionList::new());
+
+        let worker_blockchain = blockchain.clone();
+        let worker_transactions = transactions.clone();
+
+        let miner = SyncArbiter::start(1, move || {
+            MiningWorker::with_data(worker_blockchain.clone(), worker_transactions.clone())
+        });
+        let link = MinerLink::new(miner);
+
+        let mut app = test::init_service(
+            App::new()
+                .app_data(blockchain.clone())
+                .app_data(transactions.clone())
+                .app_data(web::Data::new(link.clone()))
+                .route("/add_transaction", web::post().to(add_transaction))
+                .route(
+                    "/transactions/{txid}/status",
+                    web::get().to(dispatch_transaction_status),
+                )
+                .route("/mine_block", web::get().to(dispatch_mining_request)),
+        )
+        .await;
+
+        let transaction = build_payment(&signing_key, "16Jswqk47s9PUcyCc88MMVwzgvHPvtEpf", 2.25, 1);
+        let txid = transaction.to_hash().unwrap();
+
+        let req = test::TestRequest::get()
+            .uri(&format!("/transactions/{}/status", txid))
+            .to_request();
+        let response: TransactionStatusData = test::read_response_json(&mut app, req).await;
+
+        assert_eq!(response.status, TransactionStatus::Unknown);
+
+        let req = test::TestRequest::post()
+            .uri("/add_transaction")
+            .set_json(&transaction)
+            .to_request();
+        let response: TransactionResponseData = test::read_response_json(&mut app, req).await;
+
+        assert_eq!(response.txid, txid);
+        assert!(response.response.description.contains(&txid));
+
+        // The same Transaction is only queued once
+        let req = test::TestRequest::post()
+            .uri("/add_transaction")
+            .set_json(&transaction)
+            .to_request();
+        let resp = test::call_service(&mut app, req).await;
+
+        assert_eq!(resp.status().as_u16(), 400);
+
+        let req = test::TestRequest::get()
+            .uri(&format!("/transactions/{}/status", txid))
+            .to_request();
+        let response: TransactionStatusData = test::read_response_json(&mut app, req).await;
+
+        println!("tx status bdy: '{:?}'", response);
+
+        assert_eq!(response.status, TransactionStatus::Pending);
+
+        let req = test::TestRequest::get().uri("/mine_block").to_request();
+        let resp = test::call_service(&mut app, req).await;
+
+        assert!(resp.status().is_success());
+
+        let req = test::TestRequest::get()
+            .uri(&format!("/transactions/{}/status", txid))
+            .to_request();
+        let response: TransactionStatusData = test::read_response_json(&mut app, req).await;
+
+        println!("tx status bdy: '{:?}'", response);
+
+        // The Transaction is mined with the Block after the Genesis Block
+        match response.status {
+            TransactionStatus::Confirmed {
+                block_index,
+                confirmations,
+            } => {
+                assert_eq!(block_index, 2);
+                assert_eq!(confirmations, 1);
+            }
+            _ => panic!("Transaction is not confirmed"),
+        }
+    }
+
+    #[actix_rt::test]
+    async fn test_replace_chain() {
+        use ed25519_dalek::SigningKey;
+
+        let signing_key = SigningKey::from_bytes(&[8u8; 32]);
+        let sender = address::from_public_key(&signing_key.verifying_key());
+        let pending = web::Data::new(MutexTransactionList::new());
+        let mut local = Blockchain::with_difficulty(1);
+
+        pending
+            .add_transaction(local.build_reward(&sender, 10.0))
+            .unwrap();
+        local.seal_block(&pending).unwrap();
+
+        let mut remote = Blockchain::from_chain(local.chain.clone()).unwrap();
+        let mut payment = Transaction::from_key(
+            &signing_key.verifying_key(),
+            String::from("16Jswqk47s9PUcyCc88MMVwzgvHPvtEpf"),
+            2.5,
+            1,
+        );
+
+        payment.sign(&signing_key).unwrap();
+        pending.add_transaction(payment.clone()).unwrap();
+        local.seal_block(&pending).unwrap();
+
+        remote.difficulty = 1;
+        remote.seal_block(&pending).unwrap();
+        remote.seal_block(&pending).unwrap();
+
+        let blockchain = web::Data::new(Mutex::new(local));
+        let transactions = web::Data::new(MutexTransactionList::new());
+
+        let mut app = test::init_service(
+            App::new()
+                .app_data(blockchain.clone())
+                .app_data(transactions.clone())
+                .route("/replace_chain", web::post().to(replace_chain)),
+        )
+        .await;
+
+        //-------------------------------------
+        // Remote Clients cannot push a Chain
+
+        let req = test::TestRequest::post()
+            .uri("/replace_chain")
+            .peer_addr("10.0.0.2:4000".parse().unwrap())
+            .set_json(&remote.chain)
+            .to_request();
+        let resp = test::call_service(&mut app, req).await;
+
+        assert_eq!(resp.status().as_u16(), 403);
+        assert_eq!(blockchain.lock().unwrap().get_last_block_index(), 2);
+
+        //-------------------------------------
+        // The Payment of the orphaned Block is pending again
+
+        let req = test::TestRequest::post()
+            .uri("/replace_chain")
+            .peer_addr("127.0.0.1:4000".parse().unwrap())
+            .set_json(&remote.chain)
+            .to_request();
+        let response: ResponseData = test::read_response_json(&mut app, req).await;
+
+        println!("replace bdy: '{:?}'", response);
+
+        assert!(response
+            .description
+            .contains("1 Transactions are pending again"));
+        assert_eq!(blockchain.lock().unwrap().get_last_block_index(), 3);
+        assert!(transactions.contains(&payment.to_hash().unwrap()));
+
+        //-------------------------------------
+        // With an Admin Token even local Clients must send it
+
+        let mut config = AppConfig::new();
+
+        config.admin_token = String::from("admin-secret");
+
+        let mut app = test::init_service(
+            App::new()
+                .app_data(blockchain.clone())
+                .app_data(transactions.clone())
+                .app_data(web::Data::new(config))
+                .route("/replace_chain", web::post().to(replace_chain)),
+        )
+        .await;
+
+        let req = test::TestRequest::post()
+            .uri("/replace_chain")
+            .peer_addr("127.0.0.1:4000".parse().unwrap())
+            .set_json(&remote.chain)
+            .to_request();
+        let resp = test::call_service(&mut app, req).await;
+
+        assert_eq!(resp.status().as_u16(), 401);
+
+        let req = test::TestRequest::post()
+            .uri("/replace_chain")
+            .header("Authorization", "Bearer admin-secret")
+            .set_json(&remote.chain)
+            .to_request();
+        let resp = test::call_service(&mut app, req).await;
+
+        assert!(resp.status().is_success());
+    }
+
+    #[actix_rt::test]
+    async fn test_wire_format() {
+        let signing_key = ed25519_dalek::SigningKey::from_bytes(&[15u8; 32]);
+        let sender = address::from_public_key(&signing_key.verifying_key());
+        let blockchain = web::Data::new(Mutex::new(build_funded_chain(&sender, 10.0)));
+        let transactions = web::Data::new(MutexTransactionList::new());
+
+        let worker_blockchain = blockchain.clone();
+        let worker_transactions = transactions.clone();
+
+        let miner = SyncArbiter::start(1, move || {
+            MiningWorker::with_data(worker_blockchain.clone(), worker_transactions.clone())
+        });
+        let link = MinerLink::new(miner);
+
+        let mut app = test::init_service(
+            App::new()
+                .app_data(blockchain.clone())
+                .app_data(transactions.clone())
+                .app_data(web::Data::new(link.clone()))
+                .route("/add_transaction", web::post().to(add_transaction))
+                .route("/mine_block", web::get().to(dispatch_mining_request))
+                .route("/chain", web::get().to(dispatch_chain))
+                .route("/blocks/{id}", web::get().to(dispatch_block))
+                .route("/transactions/{txid}", web::get().to(dispatch_transaction)),
+        )
+        .await;
+
+        let transaction = build_payment(&signing_key, "16Jswqk47s9PUcyCc88MMVwzgvHPvtEpf", 0.3, 1);
+        let txid = transaction.to_hash().unwrap();
+
+        let req = test::TestRequest::post()
+            .uri("/add_transaction")
+            .set_json(&transaction)
+            .to_request();
+        let resp = test::call_service(&mut app, req).await;
+
+        assert!(resp.status().is_success());
+
+        let req = test::TestRequest::get().uri("/mine_block").to_request();
+        let resp = test::call_service(&mut app, req).await;
+
+        assert!(resp.status().is_success());
+
+        //-------------------------------------
+        // The Chain in all Formats
+
+        let req = test::TestRequest::get().uri("/chain").to_request();
+        let chain: Vec<Block> = test::read_response_json(&mut app, req).await;
+        let chain_json = serde_json::to_string(&chain).unwrap();
+
+        assert!(chain.len() >= 2);
+
+        let req = test::TestRequest::get()
+            .uri("/chain")
+            .header("Accept", "application/octet-stream")
+            .to_request();
+        let resp = test::call_service(&mut app, req).await;
+
+        assert_eq!(
+            resp.headers().get("content-type").unwrap(),
+            "application/octet-stream"
+        );
+        assert_eq!(resp.headers().get("vary").unwrap(), "Accept");
+
+        let body = test::read_body(resp).await;
+        let decoded: Vec<Block> = wire::decode(&body).unwrap();
+
+        assert!(body.len() < chain_json.len());
+        assert_eq!(serde_json::to_string(&decoded).unwrap(), chain_json);
+
+        let req = test::TestRequest::get()
+            .uri("/chain")
+            .header("Accept", "application/cbor")
+            .to_request();
+        let resp = test::call_service(&mut app, req).await;
+
+        assert_eq!(
+            resp.headers().get("content-type").unwrap(),
+            "application/cbor"
+        );
+
+        let body = test::read_body(resp).await;
+        let decoded: Vec<Block> = wire::from_cbor(&body).unwrap();
+
+        assert_eq!(serde_json::to_string(&decoded).unwrap(), chain_json);
+
+        // Formats with q=0 are not acceptable
+        let req = test::TestRequest::get()
+            .uri("/chain")
+            .header("Accept", "application/octet-stream;q=0, application/json")
+            .to_request();
+        let resp = test::call_service(&mut app, req).await;
+
+        assert_eq!(
+            resp.headers().get("content-type").unwrap(),
+            "application/json"
+        );
+        assert_eq!(resp.headers().get("vary").unwrap(), "Accept");
+
+        let req = test::TestRequest::get()
+            .uri("/chain")
+            .header("Accept", "*/*;q=0")
+            .to_request();
+        let resp = test::call_service(&mut app, req).await;
+
+        assert_eq!(resp.status().as_u16(), 406);
+
+        //-------------------------------------
+        // Blocks by Index and Hash, Transactions by Id
+
+        let block_hash = chain[1].to_hash().unwrap();
+
+        for uri in ["/blocks/2".to_owned(), format!("/blocks/{}", block_hash)] {
+            let req = test::TestRequest::get()
+                .uri(&uri)
+                .header("Accept", "application/octet-stream")
+                .to_request();
+            let body = test::read_body(test::call_service(&mut app, req).await).await;
+            let block: Block = wire::decode(&body).unwrap();
+
+            assert_eq!(block.to_hash().unwrap(), block_hash);
+        }
+
+        let req = test::TestRequest::get()
+            .uri(&format!("/transactions/{}", txid))
+            .header("Accept", "application/octet-stream")
+            .to_request();
+        let body = test::read_body(test::call_service(&mut app, req).await).await;
+        let confirmed: Transaction = wire::decode(&body).unwrap();
+
+        assert_eq!(confirmed.to_hash().unwrap(), txid);
+
+        let req = test::TestRequest::get()
+            .uri(&format!("/transactions/{}", txid))
+            .to_request();
+        let confirmed: Transaction = test::read_response_json(&mut app, req).await;
+
+        assert_eq!(confirmed.to_json().unwrap(), transaction.to_json().unwrap());
+
+        for uri in ["/blocks/99", "/transactions/unknown"] {
+            let req = test::TestRequest::get().uri(uri).to_request();
+            let resp = test::call_service(&mut app, req).await;
+
+            assert_eq!(resp.status(), 404);
+        }
+    }
+
+    #[actix_rt::test]
+    async fn test_address_transactions() {
+        use blockchain_api::model::index::AddressHistory;
+
+        let transactions = web::Data::new(MutexTransactionList::new());
+        let mut chain = Blockchain::new();
+
+        // The Senders are funded first
+        for sender in ["sender1", "sender2"] {
+            let _ = transactions.add_transaction(chain.build_reward(sender, 5.0));
+        }
+
+        let _ = transactions.add_transaction_from_data(
+            "sender1",
+            "16Jswqk47s9PUcyCc88MMVwzgvHPvtEpf",
+            1.5,
+        );
+        let _ = transactions.add_transaction(Transaction {
+            sender: String::from("16Jswqk47s9PUcyCc88MMVwzgvHPvtEpf"),
+            receiver: String::from("sender1"),
+            amount: 0.5,
+            nonce: 1,
+            ..Default::default()
+        });
+        let _ = transactions.add_transaction_from_data(
+            "sender2",
+            "16Jswqk47s9PUcyCc88MMVwzgvHPvtEpf",
+            2.0,
+        );
+        chain.build_block(0, "0", transactions.clone()).unwrap();
+
+        let blockchain = web::Data::new(Mutex::new(chain));
+
+        let mut app = test::init_service(App::new().app_data(blockchain.clone()).route(
+            "/addresses/{address}/transactions",
+            web::get().to(dispatch_address_transactions),
+        ))
+        .await;
+
+        let req = test::TestRequest::get()
+            .uri("/addresses/16Jswqk47s9PUcyCc88MMVwzgvHPvtEpf/transactions?direction=received&limit=1")
+            .to_request();
+        let history: AddressHistory = test::read_response_json(&mut app, req).await;
+
+        println!("address history bdy: '{:?}'", history);
+
+        assert_eq!(history.total_count, 2);
+        assert_eq!(history.total_received, 3.5);
+        assert_eq!(history.total_sent, 0.5);
+        assert_eq!(history.transactions.len(), 1);
+        assert_eq!(history.transactions[0].transaction.sender, "sender2");
     }
 }
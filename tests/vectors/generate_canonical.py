@@ -0,0 +1,255 @@
+#!/usr/bin/env python3
+#
+# @author Bodo (Hugo) Barwich
+# @version 2026-10-18
+# @package Blockchain Exercise
+# @subpackage Canonical Encoding Vectors
+#
+# This Script generates the Golden Vectors of the canonical Encoding in
+# "canonical.json" without the Rust Implementation of the Node
+#
+# ---------------------------------
+# Requirements:
+# - The Python Package "cryptography" must be installed
+#
+# Usage:
+#   python3 tests/vectors/generate_canonical.py > tests/vectors/canonical.json
+
+import hashlib
+import json
+import struct
+import sys
+
+from cryptography.hazmat.primitives.asymmetric.ed25519 import Ed25519PrivateKey
+
+AMOUNT_UNITS = 100_000_000
+MAX_AMOUNT_UNITS = 1 << 53
+BASE58_ALPHABET = "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz"
+
+
+# ==============================================================================
+# Canonical Encoding
+
+
+def to_amount_units(amount):
+    units = round(amount * AMOUNT_UNITS)
+
+    if abs(units) > MAX_AMOUNT_UNITS or units / AMOUNT_UNITS != amount:
+        raise ValueError("Amount %r is no Multiple of 10^-8" % amount)
+
+    return units
+
+
+def canonical(value, is_amount=False):
+    if is_amount and isinstance(value, (int, float)) and not isinstance(value, bool):
+        return str(to_amount_units(value))
+
+    if value is None:
+        return "null"
+    if value is True:
+        return "true"
+    if value is False:
+        return "false"
+    if isinstance(value, int):
+        return str(value)
+    if isinstance(value, float):
+        raise ValueError("Only Amounts are Floating Point Numbers: %r" % value)
+    if isinstance(value, str):
+        return json.dumps(value, ensure_ascii=False)
+    if isinstance(value, list):
+        return "[" + ",".join(canonical(v) for v in value) + "]"
+
+    fields = sorted(value.items(), key=lambda f: f[0].encode("utf-8"))
+
+    return (
+        "{"
+        + ",".join(
+            json.dumps(k, ensure_ascii=False) + ":" + canonical(v, k == "amount")
+            for k, v in fields
+        )
+        + "}"
+    )
+
+
+def sha256_hex(data):
+    return hashlib.sha256(data).hexdigest()
+
+
+# ==============================================================================
+# Keys and Addresses
+
+
+def base58check(payload):
+    data = payload + hashlib.sha256(hashlib.sha256(payload).digest()).digest()[:4]
+    number = int.from_bytes(data, "big")
+    encoded = ""
+
+    while number > 0:
+        number, rest = divmod(number, 58)
+        encoded = BASE58_ALPHABET[rest] + encoded
+
+    return "1" * (len(data) - len(data.lstrip(b"\0"))) + encoded
+
+
+def make_key(name):
+    key = Ed25519PrivateKey.from_private_bytes(hashlib.sha256(name.encode()).digest())
+    public_key = key.public_key().public_bytes_raw()
+    address = base58check(b"\0" + hashlib.sha256(public_key).digest()[:20])
+
+    return key, public_key.hex(), address
+
+
+# ==============================================================================
+# Transactions
+
+
+def transaction_canonical(transaction):
+    fields = {k: v for k, v in transaction.items() if k != "witness"}
+
+    if "inputs" in fields:
+        fields["inputs"] = [
+            {k: v for k, v in i.items() if k != "witness"} for i in fields["inputs"]
+        ]
+
+    return canonical(fields)
+
+
+def signing_payload(transaction):
+    payload = {
+        "sender": transaction.get("sender", ""),
+        "receiver": transaction.get("receiver", ""),
+        "amount": transaction["amount"],
+        "nonce": transaction.get("nonce", 0),
+        "public_key": transaction.get("public_key", ""),
+    }
+
+    if transaction.get("inputs"):
+        payload["inputs"] = [
+            {"txid": i["txid"], "output": i["output"]} for i in transaction["inputs"]
+        ]
+    if transaction.get("outputs"):
+        payload["outputs"] = transaction["outputs"]
+
+    return canonical(payload)
+
+
+def transaction_vector(name, transaction, signed=False):
+    vector = {"name": name, "transaction": transaction}
+
+    vector["canonical"] = transaction_canonical(transaction)
+
+    if signed:
+        vector["signing_payload"] = signing_payload(transaction)
+
+    vector["txid"] = sha256_hex(vector["canonical"].encode())
+
+    return vector
+
+
+# ==============================================================================
+# Blocks
+
+
+def block_vector(name, block):
+    body = canonical([block["transactions"], [], None]).encode()
+    body_hash = hashlib.sha256(body).digest()
+    previous_hash = (
+        bytes(32) if block["previous_hash"] == "0" else bytes.fromhex(block["previous_hash"])
+    )
+    header = (
+        struct.pack("<QIIQ", block["index"], block["timestamp"], block["bits"], block["proof"])
+        + previous_hash
+        + body_hash
+    )
+
+    return {
+        "name": name,
+        "block": block,
+        "body_hash": body_hash.hex(),
+        "header": header.hex(),
+        "hash": sha256_hex(header),
+    }
+
+
+# ==============================================================================
+# Main Script
+
+sender_key, sender_public_key, sender_address = make_key("canonical vector sender")
+_, _, receiver_address = make_key("canonical vector receiver")
+
+coinbase = {
+    "sender": "blockchain",
+    "amount": 0.0,
+    "nonce": 1,
+    "outputs": [{"address": sender_address, "amount": 0.1}],
+}
+
+transfer = {
+    "sender": sender_address,
+    "receiver": receiver_address,
+    "amount": 12.5,
+    "nonce": 3,
+    "public_key": sender_public_key,
+}
+transfer["signature"] = sender_key.sign(signing_payload(transfer).encode()).hex()
+
+coinbase_id = sha256_hex(transaction_canonical(coinbase).encode())
+spend = {
+    "amount": 0.0,
+    "nonce": 4,
+    "inputs": [{"txid": coinbase_id, "output": 0, "public_key": "", "signature": ""}],
+    "outputs": [
+        {"address": receiver_address, "amount": 0.04},
+        {"address": sender_address, "amount": 0.05999999},
+    ],
+}
+spend_signature = sender_key.sign(signing_payload(spend).encode()).hex()
+spend["inputs"][0]["public_key"] = sender_public_key
+spend["inputs"][0]["signature"] = spend_signature
+
+first_block = block_vector(
+    "transfer_block",
+    {
+        "index": 1,
+        "timestamp": 1790000000,
+        "bits": 0x1F00FFFF,
+        "proof": 42,
+        "previous_hash": "0",
+        "transactions": [coinbase, transfer],
+    },
+)
+second_block = block_vector(
+    "spend_block",
+    {
+        "index": 2,
+        "timestamp": 1790000600,
+        "bits": 0x1F00FFFF,
+        "proof": 7,
+        "previous_hash": first_block["hash"],
+        "transactions": [spend],
+    },
+)
+
+vectors = {
+    "description": "Golden Vectors of the canonical Encoding generated by "
+    "generate_canonical.py. Amount Fields are Integers of 10^-8 Coins, "
+    "Object Keys are sorted and there is no Whitespace.",
+    "transactions": [
+        transaction_vector("coinbase", coinbase),
+        transaction_vector("signed_transfer", transfer, signed=True),
+        transaction_vector("utxo_spend", spend, signed=True),
+    ],
+    "blocks": [first_block, second_block],
+    "invalid_amounts": [
+        {"sender": "blockchain", "receiver": receiver_address, "amount": 0.123456789},
+        {"sender": "blockchain", "receiver": receiver_address, "amount": 100000000.0},
+        {
+            "sender": "blockchain",
+            "nonce": 5,
+            "outputs": [{"address": receiver_address, "amount": 0.1 + 0.2}],
+        },
+    ],
+}
+
+json.dump(vectors, sys.stdout, indent=2, ensure_ascii=False)
+sys.stdout.write("\n")